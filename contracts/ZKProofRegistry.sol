@@ -6,6 +6,7 @@ import "@openzeppelin/contracts/security/Pausable.sol";
 import "@openzeppelin/contracts/token/ERC20/IERC20.sol";
 import "./ACMEToken.sol";
 import "./SubwalletRegistry.sol";
+import "./interfaces/IGroth16Verifier.sol";
 
 /**
  * @dev Registro de provas ZK com controle de acesso e capacidade de pausa
@@ -33,10 +34,14 @@ contract ZKProofRegistry is Ownable, Pausable {
     mapping(bytes32 => Proof) private _proofs;
     mapping(address => bytes32[]) public userProofs;
 
+    // Verificador on-chain de cada circuito (ex.: keccak256("solvency-v1"))
+    mapping(bytes32 => address) public circuitVerifiers;
+
     event ProofSubmitted(bytes32 indexed proofId, address indexed submitter);
     event ProofVerified(bytes32 indexed proofId, address indexed verifier);
     event RegistrationCostUpdated(uint256 oldCost, uint256 newCost);
     event EmergencyRecovery(address token, uint256 amount);
+    event CircuitVerifierUpdated(bytes32 indexed circuitId, address verifier);
 
     /**
      * @dev Inicializa o registro com um proprietário inicial
@@ -112,6 +117,38 @@ contract ZKProofRegistry is Ownable, Pausable {
         emit ProofVerified(proofId, msg.sender);
     }
 
+    /**
+     * @dev Define o verificador Groth16 de um circuito; address(0) desativa o circuito
+     */
+    function setCircuitVerifier(bytes32 circuitId, address verifier) external onlyOwner {
+        circuitVerifiers[circuitId] = verifier;
+        emit CircuitVerifierUpdated(circuitId, verifier);
+    }
+
+    /**
+     * @dev Verifica uma prova registrada com o verificador do circuito, sem
+     * depender do proprietário. O proofId deve ser keccak256(abi.encode(a, b, c, input)).
+     */
+    function verifyProofOnChain(
+        bytes32 proofId,
+        bytes32 circuitId,
+        uint256[2] calldata a,
+        uint256[2][2] calldata b,
+        uint256[2] calldata c,
+        uint256[] calldata input
+    ) external whenNotPaused {
+        require(_proofs[proofId].hash != bytes32(0), "Proof does not exist");
+        require(!_proofs[proofId].verified, "Proof already verified");
+        require(keccak256(abi.encode(a, b, c, input)) == proofId, "Prova nao corresponde ao registro");
+
+        address verifier = circuitVerifiers[circuitId];
+        require(verifier != address(0), "Circuito sem verificador");
+        require(IGroth16Verifier(verifier).verifyProof(a, b, c, input), "Prova invalida");
+
+        _proofs[proofId].verified = true;
+        emit ProofVerified(proofId, msg.sender);
+    }
+
     /**
      * @dev Retorna os detalhes de uma prova
      */
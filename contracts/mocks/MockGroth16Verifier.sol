@@ -0,0 +1,18 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+import "../interfaces/IGroth16Verifier.sol";
+
+/**
+ * @dev Verificador de teste: aceita apenas provas cuja primeira entrada pública é 1
+ */
+contract MockGroth16Verifier is IGroth16Verifier {
+    function verifyProof(
+        uint256[2] calldata,
+        uint256[2][2] calldata,
+        uint256[2] calldata,
+        uint256[] calldata input
+    ) external pure override returns (bool) {
+        return input.length > 0 && input[0] == 1;
+    }
+}
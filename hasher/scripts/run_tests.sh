@@ -13,7 +13,9 @@ PGPASSWORD=ibeleve psql -U ibeleve -h localhost -c "CREATE DATABASE ibeleve_test
 
 # Executar migrações no banco de teste
 echo "🔄 Executando migrações..."
-PGPASSWORD=ibeleve psql -U ibeleve -h localhost -d ibeleve_test -f services/common/src/db/migrations/001_initial_schema.sql
+for migration in services/common/src/db/migrations/*.sql; do
+    PGPASSWORD=ibeleve psql -U ibeleve -h localhost -d ibeleve_test -f "$migration"
+done
 
 # Função para executar testes de um serviço
 run_service_tests() {
@@ -59,7 +59,9 @@ fi
 
 # Executar migrações
 echo "🔄 Executando migrações..."
-PGPASSWORD=ibeleve psql -U ibeleve -h localhost -d ibeleve -f services/common/src/db/migrations/001_initial_schema.sql
-PGPASSWORD=ibeleve psql -U ibeleve -h localhost -d ibeleve_test -f services/common/src/db/migrations/001_initial_schema.sql
+for migration in services/common/src/db/migrations/*.sql; do
+    PGPASSWORD=ibeleve psql -U ibeleve -h localhost -d ibeleve -f "$migration"
+    PGPASSWORD=ibeleve psql -U ibeleve -h localhost -d ibeleve_test -f "$migration"
+done
 
 echo -e "${GREEN}✅ Ambiente configurado com sucesso!${NC}" 
\ No newline at end of file
@@ -16,6 +16,7 @@ pub struct Config {
     pub elasticsearch_url: String,
     pub ollama_url: String,
     pub ollama_model: String,
+    pub zkp_service_url: String,
     pub jaeger_endpoint: String,
 }
 
@@ -35,6 +36,8 @@ impl Config {
                 .unwrap_or_else(|_| "http://localhost:11434".to_string()),
             ollama_model: env::var("OLLAMA_MODEL")
                 .unwrap_or_else(|_| "gemma:2b".to_string()),
+            zkp_service_url: env::var("ZKP_SERVICE_URL")
+                .unwrap_or_else(|_| "http://localhost:3003".to_string()),
             jaeger_endpoint: env::var("JAEGER_ENDPOINT")
                 .unwrap_or_else(|_| "http://localhost:4317".to_string()),
         })
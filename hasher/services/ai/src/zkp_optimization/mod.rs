@@ -9,7 +9,10 @@ use utoipa::ToSchema;
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ZkpOptimizationRequest {
+    /// Nome do circuito registrado no zkp-service (ex.: `solvency`)
     pub circuit: String,
+    /// Versão do circuito; sem ela, a ativa mais recente
+    pub circuit_version: Option<String>,
     pub constraints: Vec<Constraint>,
     pub optimization_target: OptimizationTarget,
 }
@@ -28,12 +31,17 @@ pub struct Constraint {
     pub weight: f32,
 }
 
+/// Métricas medidas pelo zkp-service (`/circuits/{name}/analysis`); tempos em segundos
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct PerformanceMetrics {
+    pub setup_time: f64,
     pub proof_generation_time: f64,
     pub verification_time: f64,
+    /// Variáveis do sistema R1CS: entradas públicas mais variáveis privadas
     pub circuit_size: usize,
     pub constraint_count: usize,
+    /// Termos por restrição nas combinações lineares, em média
+    pub lc_density: f64,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -3,6 +3,7 @@ use actix_web::web;
 use elasticsearch::Elasticsearch;
 use redis::Client as RedisClient;
 use reqwest::Client;
+use serde::Deserialize;
 use std::sync::Arc;
 use thiserror::Error;
 
@@ -16,12 +17,36 @@ pub enum ZkpOptimizationError {
     RedisError(#[from] redis::RedisError),
     #[error("Erro no Elasticsearch: {0}")]
     ElasticsearchError(String),
+    #[error("Erro no zkp-service: {0}")]
+    ZkpServiceError(String),
+}
+
+/// Campos usados da análise de circuito do zkp-service
+#[derive(Debug, Deserialize)]
+struct CircuitAnalysis {
+    constraints: ConstraintProfile,
+    timings: Option<CircuitTimings>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConstraintProfile {
+    constraints: usize,
+    public_inputs: usize,
+    private_variables: usize,
+    lc_density: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct CircuitTimings {
+    setup_seconds: f64,
+    proving_seconds: f64,
+    verification_seconds: f64,
 }
 
 impl actix_web::error::ResponseError for ZkpOptimizationError {}
 
 pub struct ZkpOptimizationService {
-    ollama_client: Client,
+    http_client: Client,
     redis_client: RedisClient,
     elasticsearch_client: Arc<Elasticsearch>,
     config: crate::config::Config,
@@ -29,13 +54,13 @@ pub struct ZkpOptimizationService {
 
 impl ZkpOptimizationService {
     pub fn new(config: crate::config::Config) -> Self {
-        let ollama_client = Client::new();
+        let http_client = Client::new();
         let redis_client = RedisClient::open(config.redis_url.clone())
             .expect("Falha ao conectar ao Redis");
         let elasticsearch_client = Arc::new(Elasticsearch::default());
 
         Self {
-            ollama_client,
+            http_client,
             redis_client,
             elasticsearch_client,
             config,
@@ -44,8 +69,9 @@ impl ZkpOptimizationService {
 
     pub async fn optimize_circuit(&self, request: ZkpOptimizationRequest) -> Result<ZkpOptimizationResponse, ZkpOptimizationError> {
         // Verificar cache Redis
-        let cache_key = format!("zkp:{}:{}",
+        let cache_key = format!("zkp:{}:{}:{}",
             request.circuit,
+            request.circuit_version.as_deref().unwrap_or("latest"),
             serde_json::to_string(&request.optimization_target).unwrap()
         );
         
@@ -60,17 +86,21 @@ impl ZkpOptimizationService {
                 .map_err(|e| ZkpOptimizationError::OptimizationError(e.to_string()))?);
         }
 
+        // Medir o circuito antes, para a IA partir de números reais
+        let metrics = self.measure_performance(&request).await?;
+
         // Buscar otimizações similares no Elasticsearch
         let similar_optimizations = self.search_similar_optimizations(&request).await?;
 
         // Otimizar com IA
-        let ollama_response = self.ollama_client
+        let ollama_response = self.http_client
             .post(&format!("{}/api/generate", self.config.ollama_url))
             .json(&serde_json::json!({
                 "model": self.config.ollama_model,
                 "prompt": format!(
-                    "Otimize o seguinte circuito ZKP:\n{}\n\nRestrições:\n{}\n\nAlvo de otimização: {:?}\n\nOtimizações similares:\n{}", 
+                    "Otimize o seguinte circuito ZKP:\n{}\n\nMétricas medidas:\n{}\n\nRestrições:\n{}\n\nAlvo de otimização: {:?}\n\nOtimizações similares:\n{}", 
                     request.circuit,
+                    serde_json::to_string_pretty(&metrics).unwrap(),
                     serde_json::to_string_pretty(&request.constraints).unwrap(),
                     request.optimization_target,
                     similar_optimizations.join("\n")
@@ -86,9 +116,6 @@ impl ZkpOptimizationService {
             .ok_or_else(|| ZkpOptimizationError::OptimizationError("Resposta inválida do Ollama".to_string()))?
             .to_string();
 
-        // Calcular métricas de performance
-        let metrics = self.calculate_performance_metrics(&optimized_circuit)?;
-
         let response = ZkpOptimizationResponse {
             optimized_circuit,
             performance_metrics: metrics,
@@ -146,13 +173,37 @@ impl ZkpOptimizationService {
             .collect())
     }
 
-    fn calculate_performance_metrics(&self, circuit: &str) -> Result<PerformanceMetrics, ZkpOptimizationError> {
-        // TODO: Implementar cálculo real de métricas
+    /// Métricas medidas pelo zkp-service: restrições e variáveis do circuito
+    /// registrado e tempos de setup, prova e verificação
+    async fn measure_performance(&self, request: &ZkpOptimizationRequest) -> Result<PerformanceMetrics, ZkpOptimizationError> {
+        let mut query = vec![("measure", "true")];
+        if let Some(version) = &request.circuit_version {
+            query.push(("version", version.as_str()));
+        }
+
+        let response = self.http_client
+            .get(&format!("{}/api/v1/zkp/circuits/{}/analysis", self.config.zkp_service_url, request.circuit))
+            .query(&query)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(ZkpOptimizationError::ZkpServiceError(format!("{}: {}", status, body)));
+        }
+
+        let analysis = response.json::<CircuitAnalysis>().await?;
+        let timings = analysis.timings
+            .ok_or_else(|| ZkpOptimizationError::ZkpServiceError("análise sem tempos medidos".to_string()))?;
+
         Ok(PerformanceMetrics {
-            proof_generation_time: 0.5,
-            verification_time: 0.1,
-            circuit_size: circuit.len(),
-            constraint_count: circuit.matches("constraint").count(),
+            setup_time: timings.setup_seconds,
+            proof_generation_time: timings.proving_seconds,
+            verification_time: timings.verification_seconds,
+            circuit_size: analysis.constraints.public_inputs + analysis.constraints.private_variables,
+            constraint_count: analysis.constraints.constraints,
+            lc_density: analysis.constraints.lc_density,
         })
     }
 } 
\ No newline at end of file
@@ -1,75 +1,129 @@
-use actix_web::{get, post, web, HttpResponse, Responder};
-use chrono::{DateTime, Utc};
+use actix_web::{get, post, web, HttpRequest, HttpResponse, Responder};
+use ethers::types::{Address, Bytes, U256};
 use log::{error, info};
-use serde::{Deserialize, Serialize};
+use serde::Serialize;
 use uuid::Uuid;
 use utoipa::OpenApi;
 use sqlx::PgPool;
 
-use crate::models::{
-    BlockchainVerification, ErrorResponse, VerifyAssetsRequest, VerifyAssetsResponse,
-    VerifyTransactionRequest, VerifyTransactionResponse,
+use crate::services::{
+    indexer_checkpoints, query_events, submitted_transaction, AssetError, AssetVerifier, BalanceRequest,
+    BlockchainService, EventQuery, SignerError, SubmitAccess, SubmitRequest, TransactionError, TransactionSubmitter,
 };
-use crate::services::BlockchainService;
-use common::models::{BlockchainVerification, ErrorResponse, User};
+use common::models::{ErrorResponse, SignerBroadcast, SignerTransaction};
 
 #[derive(OpenApi)]
 #[openapi(
     paths(
         verify_assets,
+        get_asset_verification,
         verify_transaction,
+        list_chains,
+        list_events,
+        get_indexer_status,
+        get_signer,
+        submit_transaction,
+        get_submission,
         get_verification
     ),
-    components(
-        schemas(BlockchainVerification)
-    ),
     tags(
         (name = "blockchain", description = "API de verificação blockchain")
     )
 )]
 pub struct BlockchainApi;
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct VerifyAssetsRequest {
-    pub verified: bool,
-    pub timestamp: DateTime<Utc>,
-    pub transaction_hash: Option<String>,
+/// Serviço no ar (usado pelo healthcheck do docker-compose)
+#[get("/health")]
+pub async fn health() -> impl Responder {
+    HttpResponse::Ok().json(serde_json::json!({
+        "status": "up",
+        "timestamp": chrono::Utc::now()
+    }))
 }
 
-/// Verifica ativos na blockchain
+/// Verifica o saldo de um token ERC-20 (padrão: ACMEToken) num bloco por
+/// `eth_getProof`: as provas de conta e de storage são checadas contra a raiz
+/// de estado do bloco, cujo cabeçalho tem o hash recalculado e conferido entre
+/// os RPCs da rede. O saldo gravado é o provado, não o informado pelo nó ou
+/// pelo cliente.
 #[utoipa::path(
     post,
     path = "/verify-assets",
-    request_body = VerifyAssetsRequest,
+    request_body = BalanceRequest,
     responses(
-        (status = 200, description = "Ativos verificados com sucesso", body = VerifyAssetsResponse),
-        (status = 400, description = "Requisição inválida", body = ErrorResponse),
+        (status = 201, description = "Saldo verificado", body = AssetVerification),
+        (status = 400, description = "Rede não suportada ou token não informado", body = ErrorResponse),
+        (status = 404, description = "Bloco ou contrato do token não encontrado", body = ErrorResponse),
+        (status = 502, description = "RPCs divergentes, cabeçalho ou prova inválidos", body = ErrorResponse),
+        (status = 503, description = "RPCs insuficientes para confirmar o bloco", body = ErrorResponse),
         (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
     ),
     tag = "blockchain"
 )]
 #[post("/verify-assets")]
 pub async fn verify_assets(
-    request: web::Json<VerifyAssetsRequest>,
-    service: web::Data<BlockchainService>,
+    request: web::Json<BalanceRequest>,
+    verifier: web::Data<AssetVerifier>,
 ) -> impl Responder {
-    info!("Recebida requisição para verificar ativos: {:?}", request);
-
-    let verification = BlockchainVerification {
-        id: Uuid::new_v4(),
-        verified: request.verified,
-        timestamp: request.timestamp,
-        transaction_hash: request.transaction_hash.clone(),
-        created_at: Utc::now(),
-    };
+    info!(
+        "Recebida requisição para verificar o saldo de {:?} na rede {}",
+        request.holder, request.chain_id
+    );
 
-    match service.verify_assets(verification).await {
-        Ok(result) => HttpResponse::Ok().json(result),
+    match verifier.verify_balance(&request).await {
+        Ok(verification) => HttpResponse::Created().json(verification),
         Err(e) => {
-            error!("Erro ao verificar ativos: {}", e);
+            let body = ErrorResponse {
+                error: e.code().to_string(),
+                message: e.to_string(),
+            };
+            match e {
+                AssetError::UnknownChain(_) | AssetError::TokenRequired => HttpResponse::BadRequest().json(body),
+                AssetError::BlockNotFound(_) | AssetError::NotAContract(..) => HttpResponse::NotFound().json(body),
+                AssetError::Disagreement(_) | AssetError::InvalidHeader(_) | AssetError::InvalidProof(_) => {
+                    error!("Verificação de saldo recusada: {}", e);
+                    HttpResponse::BadGateway().json(body)
+                }
+                AssetError::InsufficientCorroboration { .. } => HttpResponse::ServiceUnavailable().json(body),
+                _ => {
+                    error!("Erro ao verificar saldo: {}", e);
+                    HttpResponse::InternalServerError().json(body)
+                }
+            }
+        }
+    }
+}
+
+/// Obtém uma verificação de saldo
+#[utoipa::path(
+    get,
+    path = "/verify-assets/{verification_id}",
+    params(
+        ("verification_id" = Uuid, Path, description = "ID da verificação de saldo")
+    ),
+    responses(
+        (status = 200, description = "Verificação encontrada com sucesso", body = AssetVerification),
+        (status = 404, description = "Verificação não encontrada", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
+    ),
+    tag = "blockchain"
+)]
+#[get("/verify-assets/{verification_id}")]
+pub async fn get_asset_verification(
+    verification_id: web::Path<Uuid>,
+    verifier: web::Data<AssetVerifier>,
+) -> impl Responder {
+    match verifier.get_verification(*verification_id).await {
+        Ok(Some(verification)) => HttpResponse::Ok().json(verification),
+        Ok(None) => HttpResponse::NotFound().json(ErrorResponse {
+            error: "not_found".to_string(),
+            message: "Verificação não encontrada".to_string(),
+        }),
+        Err(e) => {
+            error!("Erro ao buscar verificação de saldo: {}", e);
             HttpResponse::InternalServerError().json(ErrorResponse {
-                error: "internal_server_error".to_string(),
-                message: format!("Erro ao verificar ativos: {}", e),
+                error: e.code().to_string(),
+                message: format!("Erro ao buscar verificação de saldo: {}", e),
             })
         }
     }
@@ -78,16 +132,66 @@ pub async fn verify_assets(
 #[derive(Debug, serde::Deserialize)]
 pub struct VerifyTransactionRequest {
     pub user_id: Uuid,
+    /// Chain id (EIP-155) da rede em que a transação foi enviada
+    pub chain_id: u64,
     pub transaction_hash: String,
 }
 
-/// Verifica uma transação na blockchain
+/// Rede atendida pelo serviço
+#[derive(Debug, Serialize)]
+pub struct ChainInfo {
+    pub chain_id: u64,
+    pub name: String,
+    pub finality_depth: u64,
+    pub endpoints: usize,
+    pub healthy_endpoints: usize,
+}
+
+/// Contas do keystore com que o serviço assina transações
+#[derive(Debug, Serialize)]
+pub struct SignerInfo {
+    pub enabled: bool,
+    pub accounts: Vec<String>,
+}
+
+/// Transação a assinar com uma conta do keystore e enviar
+#[derive(Debug, serde::Deserialize)]
+pub struct SubmitTransactionRequest {
+    pub chain_id: u64,
+    /// Conta de envio; pode faltar quando o keystore tem uma só
+    pub from: Option<Address>,
+    /// Ausente na criação de contratos
+    pub to: Option<Address>,
+    #[serde(default)]
+    pub value: U256,
+    /// Calldata em hexadecimal
+    #[serde(default)]
+    pub data: Bytes,
+    /// Sem ele, `eth_estimateGas` com folga
+    pub gas_limit: Option<U256>,
+    /// Origem da transação (ex.: `anchor`), registrada junto do serviço chamador
+    pub purpose: String,
+}
+
+/// Transação enviada pelo serviço e as versões assinadas dela (a original e
+/// as substituições com taxa maior), da mais antiga à mais recente
+#[derive(Debug, Serialize)]
+pub struct SubmissionDetails {
+    #[serde(flatten)]
+    pub transaction: SignerTransaction,
+    pub broadcasts: Vec<SignerBroadcast>,
+}
+
+/// Verifica uma transação na blockchain pelo recibo: execução, bloco e
+/// confirmações. Até a profundidade de finalidade ela fica `pending` e é
+/// reconsultada em segundo plano (`confirmed`, `failed` ou `reorged`).
 #[utoipa::path(
     post,
     path = "/verify",
     request_body = VerifyTransactionRequest,
     responses(
         (status = 201, description = "Verificação criada com sucesso", body = BlockchainVerification),
+        (status = 400, description = "Hash de transação inválido ou rede não suportada", body = ErrorResponse),
         (status = 404, description = "Usuário não encontrado", body = ErrorResponse),
         (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
     ),
@@ -96,56 +200,256 @@ pub struct VerifyTransactionRequest {
 #[post("/verify")]
 pub async fn verify_transaction(
     request: web::Json<VerifyTransactionRequest>,
-    pool: web::Data<PgPool>,
+    service: web::Data<BlockchainService>,
 ) -> impl Responder {
-    info!("Recebida requisição para verificar transação: {}", request.transaction_hash);
+    info!(
+        "Recebida requisição para verificar transação {} na rede {}",
+        request.transaction_hash, request.chain_id
+    );
 
-    // Buscar usuário
-    let user = match sqlx::query_as!(
-        User,
-        "SELECT * FROM users WHERE id = $1",
-        request.user_id
-    )
-    .fetch_optional(pool.get_ref())
-    .await {
-        Ok(Some(user)) => user,
-        Ok(None) => {
-            return HttpResponse::NotFound().json(ErrorResponse {
-                error: "not_found".to_string(),
-                message: "Usuário não encontrado".to_string(),
+    // Verificar transação pelo recibo
+    match service.verify_transaction(request.user_id, request.chain_id, &request.transaction_hash).await {
+        Ok(verification) => HttpResponse::Created().json(verification),
+        Err(e @ (TransactionError::InvalidHash(_) | TransactionError::UnknownChain(_))) => HttpResponse::BadRequest().json(ErrorResponse {
+            error: e.code().to_string(),
+            message: e.to_string(),
+        }),
+        Err(TransactionError::UnknownUser(_)) => HttpResponse::NotFound().json(ErrorResponse {
+            error: "not_found".to_string(),
+            message: "Usuário não encontrado".to_string(),
+        }),
+        Err(e) => {
+            error!("Erro ao verificar transação: {}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: e.code().to_string(),
+                message: format!("Erro ao verificar transação: {}", e),
             })
         }
+    }
+}
+
+/// Lista as redes configuradas e quantos RPCs de cada uma estão disponíveis
+#[utoipa::path(
+    get,
+    path = "/chains",
+    responses(
+        (status = 200, description = "Redes configuradas", body = [ChainInfo])
+    ),
+    tag = "blockchain"
+)]
+#[get("/chains")]
+pub async fn list_chains(service: web::Data<BlockchainService>) -> impl Responder {
+    let chains = service
+        .chains()
+        .into_iter()
+        .map(|chain| ChainInfo {
+            chain_id: chain.chain_id,
+            finality_depth: service.finality_for(&chain).depth,
+            name: chain.name,
+            endpoints: chain.endpoints,
+            healthy_endpoints: chain.healthy_endpoints,
+        })
+        .collect::<Vec<_>>();
+
+    HttpResponse::Ok().json(chains)
+}
+
+/// Lista os eventos indexados do ZKProofRegistry e do SubwalletRegistry,
+/// do mais antigo ao mais recente
+#[utoipa::path(
+    get,
+    path = "/events",
+    params(
+        ("chain_id" = Option<i64>, Query, description = "Rede"),
+        ("event" = Option<String>, Query, description = "ProofSubmitted, ProofVerified, SubwalletRegistered ou SubwalletStatusChanged"),
+        ("subject" = Option<String>, Query, description = "proofId ou carteira"),
+        ("from_block" = Option<i64>, Query, description = "Primeiro bloco"),
+        ("limit" = Option<i64>, Query, description = "Máximo de eventos (padrão 100, até 1000)")
+    ),
+    responses(
+        (status = 200, description = "Eventos indexados", body = [ChainEvent]),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
+    ),
+    tag = "blockchain"
+)]
+#[get("/events")]
+pub async fn list_events(query: web::Query<EventQuery>, pool: web::Data<PgPool>) -> impl Responder {
+    match query_events(pool.get_ref(), &query).await {
+        Ok(events) => HttpResponse::Ok().json(events),
         Err(e) => {
-            error!("Erro ao buscar usuário: {}", e);
-            return HttpResponse::InternalServerError().json(ErrorResponse {
+            error!("Erro ao buscar eventos: {}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
                 error: "internal_server_error".to_string(),
-                message: format!("Erro ao buscar usuário: {}", e),
-            });
+                message: format!("Erro ao buscar eventos: {}", e),
+            })
         }
-    };
+    }
+}
 
-    // Criar serviço de blockchain
-    let rpc_url = std::env::var("ETHEREUM_RPC_URL")
-        .expect("ETHEREUM_RPC_URL deve estar definida");
-    let service = match BlockchainService::new(pool.get_ref(), &rpc_url) {
-        Ok(service) => service,
+/// Último bloco indexado de cada rede
+#[utoipa::path(
+    get,
+    path = "/indexer",
+    responses(
+        (status = 200, description = "Checkpoints do indexador", body = [IndexerCheckpoint]),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
+    ),
+    tag = "blockchain"
+)]
+#[get("/indexer")]
+pub async fn get_indexer_status(pool: web::Data<PgPool>) -> impl Responder {
+    match indexer_checkpoints(pool.get_ref()).await {
+        Ok(checkpoints) => HttpResponse::Ok().json(checkpoints),
         Err(e) => {
-            error!("Erro ao criar serviço de blockchain: {}", e);
-            return HttpResponse::InternalServerError().json(ErrorResponse {
+            error!("Erro ao buscar checkpoints do indexador: {}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
                 error: "internal_server_error".to_string(),
-                message: format!("Erro ao criar serviço de blockchain: {}", e),
-            });
+                message: format!("Erro ao buscar checkpoints do indexador: {}", e),
+            })
+        }
+    }
+}
+
+/// Mostra se o envio de transações está ativo e com quais contas
+#[utoipa::path(
+    get,
+    path = "/signer",
+    responses(
+        (status = 200, description = "Contas do keystore", body = SignerInfo)
+    ),
+    tag = "blockchain"
+)]
+#[get("/signer")]
+pub async fn get_signer(signer: web::Data<Option<TransactionSubmitter>>) -> impl Responder {
+    let accounts = signer
+        .get_ref()
+        .as_ref()
+        .map(|signer| signer.accounts().iter().map(|account| format!("{:?}", account)).collect())
+        .unwrap_or_default();
+
+    HttpResponse::Ok().json(SignerInfo {
+        enabled: signer.is_some(),
+        accounts,
+    })
+}
+
+/// Assina e envia uma transação com o nonce e as taxas geridos pelo signer,
+/// que a acompanha até a finalidade (e a substitui se ficar presa). Restrito
+/// aos serviços de `SIGNER_ALLOWED_SERVICES`, com um token JWT em
+/// `Authorization: Bearer`.
+#[utoipa::path(
+    post,
+    path = "/submissions",
+    request_body = SubmitTransactionRequest,
+    responses(
+        (status = 201, description = "Transação enviada", body = SignerTransaction),
+        (status = 400, description = "Rede, conta ou mercado de taxas não suportados", body = ErrorResponse),
+        (status = 401, description = "Token ausente, inválido ou de serviço não autorizado", body = ErrorResponse),
+        (status = 422, description = "O nó recusou a transação", body = ErrorResponse),
+        (status = 502, description = "Erro no nó Ethereum", body = ErrorResponse),
+        (status = 503, description = "Signer desligado", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
+    ),
+    tag = "blockchain"
+)]
+#[post("/submissions")]
+pub async fn submit_transaction(
+    http: HttpRequest,
+    request: web::Json<SubmitTransactionRequest>,
+    signer: web::Data<Option<TransactionSubmitter>>,
+    access: web::Data<Option<SubmitAccess>>,
+) -> impl Responder {
+    let (Some(signer), Some(access)) = (signer.get_ref(), access.get_ref()) else {
+        return HttpResponse::ServiceUnavailable().json(ErrorResponse {
+            error: "signer_disabled".to_string(),
+            message: "O envio de transações não está configurado".to_string(),
+        });
+    };
+
+    let header = http
+        .headers()
+        .get("Authorization")
+        .and_then(|header| header.to_str().ok());
+    let result = match access.authorize(header) {
+        Ok(claims) => {
+            let request = request.into_inner();
+            info!(
+                "Recebido pedido de envio de {} na rede {} ({})",
+                claims.service, request.chain_id, request.purpose
+            );
+            signer
+                .submit(&SubmitRequest {
+                    chain_id: request.chain_id,
+                    from: request.from,
+                    to: request.to,
+                    value: request.value,
+                    data: request.data,
+                    gas_limit: request.gas_limit,
+                    purpose: format!("{}:{}", claims.service, request.purpose),
+                })
+                .await
         }
+        Err(e) => Err(e),
     };
 
-    // Verificar transação
-    match service.verify_transaction(&user, &request.transaction_hash).await {
-        Ok(verification) => HttpResponse::Created().json(verification),
+    match result {
+        Ok(transaction) => HttpResponse::Created().json(transaction),
         Err(e) => {
-            error!("Erro ao verificar transação: {}", e);
+            let body = ErrorResponse {
+                error: e.code().to_string(),
+                message: e.to_string(),
+            };
+            match e {
+                SignerError::Unauthorized(_) => HttpResponse::Unauthorized().json(body),
+                SignerError::UnknownChain(_)
+                | SignerError::UnknownAccount(_)
+                | SignerError::AccountRequired
+                | SignerError::NoFeeMarket(_) => HttpResponse::BadRequest().json(body),
+                SignerError::Rejected(_) => HttpResponse::UnprocessableEntity().json(body),
+                SignerError::Provider(_) => {
+                    error!("Erro no nó ao enviar transação: {}", e);
+                    HttpResponse::BadGateway().json(body)
+                }
+                _ => {
+                    error!("Erro ao enviar transação: {}", e);
+                    HttpResponse::InternalServerError().json(body)
+                }
+            }
+        }
+    }
+}
+
+/// Obtém uma transação enviada pelo serviço: estado, nonce, taxas e todas as
+/// versões transmitidas
+#[utoipa::path(
+    get,
+    path = "/submissions/{submission_id}",
+    params(
+        ("submission_id" = Uuid, Path, description = "ID da transação enviada")
+    ),
+    responses(
+        (status = 200, description = "Transação encontrada com sucesso", body = SubmissionDetails),
+        (status = 404, description = "Transação não encontrada", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
+    ),
+    tag = "blockchain"
+)]
+#[get("/submissions/{submission_id}")]
+pub async fn get_submission(submission_id: web::Path<Uuid>, pool: web::Data<PgPool>) -> impl Responder {
+    match submitted_transaction(pool.get_ref(), *submission_id).await {
+        Ok(Some((transaction, broadcasts))) => HttpResponse::Ok().json(SubmissionDetails {
+            transaction,
+            broadcasts,
+        }),
+        Ok(None) => HttpResponse::NotFound().json(ErrorResponse {
+            error: "not_found".to_string(),
+            message: "Transação não encontrada".to_string(),
+        }),
+        Err(e) => {
+            error!("Erro ao buscar transação enviada: {}", e);
             HttpResponse::InternalServerError().json(ErrorResponse {
-                error: "internal_server_error".to_string(),
-                message: format!("Erro ao verificar transação: {}", e),
+                error: e.code().to_string(),
+                message: format!("Erro ao buscar transação enviada: {}", e),
             })
         }
     }
@@ -168,23 +472,10 @@ pub async fn verify_transaction(
 #[get("/{verification_id}")]
 pub async fn get_verification(
     verification_id: web::Path<Uuid>,
-    pool: web::Data<PgPool>,
+    service: web::Data<BlockchainService>,
 ) -> impl Responder {
     info!("Recebida requisição para buscar verificação: {}", verification_id);
 
-    let rpc_url = std::env::var("ETHEREUM_RPC_URL")
-        .expect("ETHEREUM_RPC_URL deve estar definida");
-    let service = match BlockchainService::new(pool.get_ref(), &rpc_url) {
-        Ok(service) => service,
-        Err(e) => {
-            error!("Erro ao criar serviço de blockchain: {}", e);
-            return HttpResponse::InternalServerError().json(ErrorResponse {
-                error: "internal_server_error".to_string(),
-                message: format!("Erro ao criar serviço de blockchain: {}", e),
-            });
-        }
-    };
-
     match service.get_verification(*verification_id).await {
         Ok(Some(verification)) => HttpResponse::Ok().json(verification),
         Ok(None) => {
@@ -196,7 +487,7 @@ pub async fn get_verification(
         Err(e) => {
             error!("Erro ao buscar verificação: {}", e);
             HttpResponse::InternalServerError().json(ErrorResponse {
-                error: "internal_server_error".to_string(),
+                error: e.code().to_string(),
                 message: format!("Erro ao buscar verificação: {}", e),
             })
         }
@@ -206,43 +497,203 @@ pub async fn get_verification(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::services::{
+        AbiRegistry, ChainStatus, FinalityConfig, MemoryVerificationRepository, MockChainClient,
+    };
     use actix_web::test;
+    use common::models::{BlockchainVerification, VerificationStatus};
+    use ethers::types::{Transaction, TransactionReceipt, H256, U64};
+    use std::sync::Arc;
 
     #[actix_rt::test]
-    async fn test_verify_assets() {
+    async fn test_verify_assets_rejects_client_assertion() {
         let app = test::init_service(
             actix_web::App::new().service(verify_assets),
         )
         .await;
 
+        // O resultado não é mais informado pelo cliente: sem rede e titular
+        // a requisição é inválida
         let req = test::TestRequest::post()
             .uri("/verify-assets")
-            .set_json(&VerifyAssetsRequest {
-                verified: true,
-                timestamp: Utc::now(),
-                transaction_hash: Some("0x123...".to_string()),
-            })
+            .set_json(serde_json::json!({
+                "verified": true,
+                "timestamp": chrono::Utc::now(),
+                "transaction_hash": "0x123...",
+            }))
             .to_request();
 
         let resp = test::call_service(&app, req).await;
-        assert!(resp.status().is_success());
+        assert_eq!(resp.status(), actix_web::http::StatusCode::BAD_REQUEST);
+    }
+
+    /// Serviço sem banco nem RPC: repositório em memória com um usuário e
+    /// uma rede em que toda transação já está final no bloco 100
+    fn test_service(user_id: Uuid) -> BlockchainService {
+        let repository = Arc::new(MemoryVerificationRepository::new());
+        repository.add_user(user_id);
+
+        let mut client = MockChainClient::new();
+        client.expect_supported_chains().returning(|| {
+            vec![ChainStatus {
+                chain_id: 1,
+                name: "ethereum".to_string(),
+                finality_depth: Some(1),
+                endpoints: 1,
+                healthy_endpoints: 1,
+            }]
+        });
+        client.expect_get_transaction_receipt().returning(|_, hash| {
+            Ok(Some(TransactionReceipt {
+                transaction_hash: hash,
+                block_number: Some(U64::from(100)),
+                block_hash: Some(H256::repeat_byte(7)),
+                status: Some(U64::one()),
+                ..Default::default()
+            }))
+        });
+        client.expect_get_block_number().returning(|_| Ok(100));
+        client
+            .expect_get_transaction()
+            .returning(|_, hash| Ok(Some(Transaction { hash, ..Default::default() })));
+
+        BlockchainService::new(
+            repository,
+            Arc::new(client),
+            FinalityConfig::default(),
+            Arc::new(AbiRegistry::builtin()),
+        )
     }
 
     #[actix_rt::test]
     async fn test_verify_transaction() {
+        let user_id = Uuid::new_v4();
         let app = test::init_service(
-            actix_web::App::new().service(verify_transaction),
+            actix_web::App::new()
+                .app_data(web::Data::new(test_service(user_id)))
+                .service(verify_transaction)
+                .service(get_verification),
         )
         .await;
 
         let req = test::TestRequest::post()
             .uri("/verify")
-            .set_json(&VerifyTransactionRequest {
-                tx_hash: "0x123...".to_string(),
-            })
+            .set_json(serde_json::json!({
+                "user_id": user_id,
+                "chain_id": 1,
+                "transaction_hash": format!("{:?}", H256::repeat_byte(1)),
+            }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::CREATED);
+        let verification: BlockchainVerification = test::read_body_json(resp).await;
+        assert_eq!(verification.status, VerificationStatus::Confirmed);
+
+        let req = test::TestRequest::get().uri(&format!("/{}", verification.id)).to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::OK);
+
+        // Usuário desconhecido e rede fora da configuração
+        let req = test::TestRequest::post()
+            .uri("/verify")
+            .set_json(serde_json::json!({
+                "user_id": Uuid::new_v4(),
+                "chain_id": 1,
+                "transaction_hash": format!("{:?}", H256::repeat_byte(1)),
+            }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::NOT_FOUND);
+
+        let req = test::TestRequest::post()
+            .uri("/verify")
+            .set_json(serde_json::json!({
+                "user_id": user_id,
+                "chain_id": 137,
+                "transaction_hash": format!("{:?}", H256::repeat_byte(1)),
+            }))
             .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_rt::test]
+    async fn test_submit_transaction_requires_service_token() {
+        use crate::services::{ChainConfig, ChainRegistry, Keystore, SignerConfig};
+        use ethers::core::rand::thread_rng;
+        use ethers::signers::LocalWallet;
+        use std::time::Duration;
+
+        // Nada chega ao banco nem ao RPC: a autorização vem antes do envio
+        let pool = PgPool::connect_lazy("postgres://postgres@localhost/unused").unwrap();
+        let chains = ChainRegistry::new(&[ChainConfig {
+            chain_id: 31337,
+            name: "dev".to_string(),
+            rpc_urls: vec!["http://localhost:1".to_string()],
+            finality_depth: Some(1),
+        }])
+        .unwrap();
+        let config = SignerConfig {
+            keystore_dir: Default::default(),
+            password: String::new(),
+            stuck_after: Duration::from_secs(180),
+            fee_bump_percent: 15,
+            max_fee_per_gas: None,
+            poll_interval: Duration::from_secs(15),
+        };
+        let submitter = TransactionSubmitter::new(
+            &pool,
+            Arc::new(chains),
+            Keystore::from_wallets([LocalWallet::new(&mut thread_rng())]),
+            config,
+            1,
+        );
+        let app = test::init_service(
+            actix_web::App::new()
+                .app_data(web::Data::new(Some(submitter)))
+                .app_data(web::Data::new(Some(SubmitAccess::new(b"segredo", ["zkp".to_string()]))))
+                .service(submit_transaction),
+        )
+        .await;
+        let body = serde_json::json!({
+            "chain_id": 31337,
+            "to": format!("{:?}", Address::repeat_byte(0x42)),
+            "data": "0x1234",
+            "purpose": "anchor",
+        });
+        let token = |secret: &[u8], service: &str| {
+            common::Auth::new(secret).generate_token(Uuid::nil(), service).unwrap()
+        };
 
+        let req = test::TestRequest::post().uri("/submissions").set_json(&body).to_request();
         let resp = test::call_service(&app, req).await;
-        assert!(resp.status().is_success());
+        assert_eq!(resp.status(), actix_web::http::StatusCode::UNAUTHORIZED);
+
+        // Token de outro segredo ou de um serviço fora da lista
+        for token in [token(b"outro", "zkp"), token(b"segredo", "user-service")] {
+            let req = test::TestRequest::post()
+                .uri("/submissions")
+                .insert_header(("Authorization", format!("Bearer {}", token)))
+                .set_json(&body)
+                .to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), actix_web::http::StatusCode::UNAUTHORIZED);
+        }
+
+        // Sem signer configurado o envio fica indisponível
+        let app = test::init_service(
+            actix_web::App::new()
+                .app_data(web::Data::new(None::<TransactionSubmitter>))
+                .app_data(web::Data::new(None::<SubmitAccess>))
+                .service(submit_transaction),
+        )
+        .await;
+        let req = test::TestRequest::post()
+            .uri("/submissions")
+            .insert_header(("Authorization", format!("Bearer {}", token(b"segredo", "zkp"))))
+            .set_json(&body)
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::SERVICE_UNAVAILABLE);
     }
-} 
\ No newline at end of file
+}
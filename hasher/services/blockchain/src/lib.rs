@@ -1,21 +1,76 @@
 pub mod api;
-pub mod models;
 pub mod services;
 
 use actix_web::{web, App, HttpServer};
-use sqlx::PgPool;
 use std::error::Error;
+use std::sync::Arc;
+
+use crate::services::{
+    AbiRegistry, AssetVerifier, BlockchainService, ChainConfig, ChainRegistry, EventIndexer, FinalityConfig, IndexerConfig,
+    Keystore, PostgresVerificationRepository, SignerConfig, SubmitAccess, TransactionSubmitter,
+};
 
 pub async fn run_server(database_url: &str, port: u16) -> Result<(), Box<dyn Error>> {
     let pool = common::db::setup_database(database_url).await?;
+
+    // Providers de todas as redes, compartilhados pelas requisições
+    let configs = ChainConfig::from_env()?;
+    let chains = Arc::new(ChainRegistry::connect(&configs).await?);
+    chains.start_health_checks();
+
+    let finality = FinalityConfig::from_env();
+
+    // Indexador de eventos dos registries (desligado sem os endereços)
+    if let Some(config) = IndexerConfig::from_env(configs[0].chain_id)? {
+        EventIndexer::new(&pool, chains.clone(), config, finality.depth)?.start();
+    }
+
+    // Saldos de tokens provados contra a raiz de estado
+    let assets = web::Data::new(AssetVerifier::from_env(&pool, chains.clone(), finality.depth)?);
+
+    // Envio de transações com as contas do keystore (desligado sem SIGNER_KEYSTORE_DIR),
+    // pedido pela API só pelos serviços autorizados
+    let (signer, access) = match SignerConfig::from_env()? {
+        Some(config) => {
+            let access = SubmitAccess::from_env()?;
+            let keystore = Keystore::load_dir(&config.keystore_dir, &config.password)?;
+            let submitter = TransactionSubmitter::new(&pool, chains.clone(), keystore, config, finality.depth);
+            submitter.start();
+            (Some(submitter), Some(access))
+        }
+        None => (None, None),
+    };
+    let signer = web::Data::new(signer);
+    let access = web::Data::new(access);
+
+    // Acompanhamento das transações até a finalidade, decodificadas pelas ABIs conhecidas
+    let abis = Arc::new(AbiRegistry::from_env()?);
+    let repository = Arc::new(PostgresVerificationRepository::new(&pool));
+    let service = BlockchainService::new(repository, chains, finality, abis);
+    service.start();
+
     let pool = web::Data::new(pool);
+    let service = web::Data::new(service);
 
     HttpServer::new(move || {
         App::new()
             .app_data(pool.clone())
+            .app_data(service.clone())
+            .app_data(assets.clone())
+            .app_data(signer.clone())
+            .app_data(access.clone())
+            .service(api::health)
             .service(
                 web::scope("/api/v1/blockchain")
+                    .service(api::verify_assets)
+                    .service(api::get_asset_verification)
                     .service(api::verify_transaction)
+                    .service(api::list_chains)
+                    .service(api::list_events)
+                    .service(api::get_indexer_status)
+                    .service(api::get_signer)
+                    .service(api::submit_transaction)
+                    .service(api::get_submission)
                     .service(api::get_verification)
             )
     })
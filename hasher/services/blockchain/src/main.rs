@@ -28,18 +28,15 @@ async fn main() -> std::io::Result<()> {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-    use actix_web::test;
+    use actix_web::{test, App};
+    use blockchain_service::api;
 
     #[actix_rt::test]
     async fn test_health_check() {
-        let app = test::init_service(
-            App::new().service(web::scope("/api/blockchain")),
-        )
-        .await;
+        let app = test::init_service(App::new().service(api::health)).await;
 
-        let req = test::TestRequest::get().uri("/api/blockchain/health").to_request();
+        let req = test::TestRequest::get().uri("/health").to_request();
         let resp = test::call_service(&app, req).await;
         assert!(resp.status().is_success());
     }
-} 
\ No newline at end of file
+}
@@ -0,0 +1,421 @@
+use common::models::AssetVerification;
+use ethers::providers::{Middleware, ProviderError};
+use ethers::types::{Address, Block, BlockId, H256, U256};
+use ethers::utils::keccak256;
+use ethers::utils::rlp::RlpStream;
+use serde::Deserialize;
+use sqlx::{PgExecutor, PgPool};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::services::{mapping_slot, verify_account, verify_storage, Chain, ChainRegistry, ProofError};
+
+/// Slot do mapping `_balances` no ERC20 do OpenZeppelin (primeira variável de
+/// estado), usado pelo ACMEToken
+pub const DEFAULT_BALANCES_SLOT: u64 = 0;
+
+/// RPCs independentes que precisam concordar com o cabeçalho do bloco quando
+/// `ASSET_MIN_AGREEING_ENDPOINTS` não está definida
+pub const DEFAULT_MIN_AGREEING_ENDPOINTS: usize = 2;
+
+#[derive(Debug, thiserror::Error)]
+pub enum AssetError {
+    #[error("Configuração inválida: {0}")]
+    Config(String),
+    #[error("Rede {0} não suportada")]
+    UnknownChain(u64),
+    #[error("Informe o token ou defina ACME_TOKEN_ADDRESS")]
+    TokenRequired,
+    #[error("Bloco {0} não encontrado")]
+    BlockNotFound(u64),
+    #[error("Não há contrato em {0:?} no bloco {1}")]
+    NotAContract(Address, u64),
+    #[error("Os RPCs divergem sobre o bloco {0}")]
+    Disagreement(u64),
+    #[error("O cabeçalho do bloco {0} não corresponde ao hash informado pelo RPC")]
+    InvalidHeader(u64),
+    #[error("Só {agreeing} RPC(s) confirmaram o bloco {number}; são necessários {required}")]
+    InsufficientCorroboration { number: u64, agreeing: usize, required: usize },
+    #[error("Prova de storage inválida: {0}")]
+    InvalidProof(#[from] ProofError),
+    #[error("Erro no nó Ethereum: {0}")]
+    Provider(#[from] ProviderError),
+    #[error("Erro de banco de dados: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl AssetError {
+    /// Código de erro exposto na API
+    pub fn code(&self) -> &'static str {
+        match self {
+            AssetError::UnknownChain(_) => "unsupported_chain",
+            AssetError::TokenRequired => "token_required",
+            AssetError::BlockNotFound(_) => "block_not_found",
+            AssetError::NotAContract(..) => "token_not_found",
+            AssetError::Disagreement(_) => "rpc_disagreement",
+            AssetError::InvalidHeader(_) => "invalid_block_header",
+            AssetError::InsufficientCorroboration { .. } => "insufficient_corroboration",
+            AssetError::InvalidProof(_) => "invalid_storage_proof",
+            AssetError::Config(_) | AssetError::Provider(_) | AssetError::Database(_) => "internal_server_error",
+        }
+    }
+}
+
+/// Saldo a verificar
+#[derive(Debug, Deserialize)]
+pub struct BalanceRequest {
+    pub chain_id: u64,
+    pub holder: Address,
+    /// Padrão: `ACME_TOKEN_ADDRESS`
+    pub token: Option<Address>,
+    /// Padrão: o bloco mais recente na profundidade de finalidade da rede
+    pub block_number: Option<u64>,
+    /// Slot do mapping de saldos no layout do token (padrão 0)
+    pub balance_slot: Option<u64>,
+}
+
+/// Verifica saldos de ERC-20 por provas de storage: o saldo vem da prova
+/// checada contra a raiz de estado do bloco, não do valor informado pelo nó.
+/// A raiz de estado, por sua vez, vem do cabeçalho, cujo hash é recalculado
+/// localmente e precisa ser o mesmo em pelo menos `min_endpoints` RPCs da
+/// rede. Um RPC sozinho não forja o saldo, mas RPCs em conluio (ou uma única
+/// fonte por trás de vários endereços) ainda poderiam.
+/// Saldos do ACMEToken na rede `solvency_chain`, em blocos finalizados,
+/// viram o saldo verificado do dono da carteira para as provas de solvência.
+#[derive(Clone)]
+pub struct AssetVerifier {
+    pool: PgPool,
+    chains: Arc<ChainRegistry>,
+    default_depth: u64,
+    acme_token: Option<Address>,
+    solvency_chain: Option<u64>,
+    min_endpoints: usize,
+}
+
+impl AssetVerifier {
+    pub fn new(
+        pool: &PgPool,
+        chains: Arc<ChainRegistry>,
+        default_depth: u64,
+        acme_token: Option<Address>,
+        solvency_chain: Option<u64>,
+        min_endpoints: usize,
+    ) -> Self {
+        Self {
+            pool: pool.clone(),
+            chains,
+            default_depth,
+            acme_token,
+            solvency_chain,
+            min_endpoints: min_endpoints.max(1),
+        }
+    }
+
+    /// Token padrão em `ACME_TOKEN_ADDRESS`, rede dos saldos de solvência em
+    /// `SOLVENCY_CHAIN_ID` (vazias valem como ausentes) e RPCs que precisam
+    /// concordar com o cabeçalho em `ASSET_MIN_AGREEING_ENDPOINTS`
+    pub fn from_env(pool: &PgPool, chains: Arc<ChainRegistry>, default_depth: u64) -> Result<Self, AssetError> {
+        let acme_token = match std::env::var("ACME_TOKEN_ADDRESS").ok().filter(|value| !value.trim().is_empty()) {
+            Some(value) => Some(
+                value
+                    .trim()
+                    .parse()
+                    .map_err(|_| AssetError::Config(format!("ACME_TOKEN_ADDRESS não é um endereço: {}", value)))?,
+            ),
+            None => None,
+        };
+        let solvency_chain = match std::env::var("SOLVENCY_CHAIN_ID").ok().filter(|value| !value.trim().is_empty()) {
+            Some(value) => Some(
+                value
+                    .trim()
+                    .parse()
+                    .map_err(|_| AssetError::Config(format!("SOLVENCY_CHAIN_ID não é um número: {}", value)))?,
+            ),
+            None => None,
+        };
+        let min_endpoints = match std::env::var("ASSET_MIN_AGREEING_ENDPOINTS") {
+            Ok(value) => value.trim().parse().map_err(|_| {
+                AssetError::Config(format!("ASSET_MIN_AGREEING_ENDPOINTS não é um número: {}", value))
+            })?,
+            Err(_) => DEFAULT_MIN_AGREEING_ENDPOINTS,
+        };
+
+        Ok(Self::new(pool, chains, default_depth, acme_token, solvency_chain, min_endpoints))
+    }
+
+    pub async fn verify_balance(&self, request: &BalanceRequest) -> Result<AssetVerification, AssetError> {
+        let chain = self.chains.get(request.chain_id).ok_or(AssetError::UnknownChain(request.chain_id))?;
+        let token = request.token.or(self.acme_token).ok_or(AssetError::TokenRequired)?;
+        let balance_slot = mapping_slot(request.holder, U256::from(request.balance_slot.unwrap_or(DEFAULT_BALANCES_SLOT)));
+
+        let latest = chain
+            .call(|provider| async move { provider.get_block_number().await })
+            .await?
+            .as_u64();
+        // O último bloco conta como a primeira confirmação
+        let depth = chain.finality_depth.unwrap_or(self.default_depth);
+        let finalized = latest.saturating_sub(depth.saturating_sub(1));
+        let number = request.block_number.unwrap_or(finalized);
+        let header = agreed_header(chain, number, self.min_endpoints).await?;
+
+        // A prova é pedida pelo hash, no mesmo bloco cujo cabeçalho foi conferido
+        let proof = chain
+            .call(|provider| async move {
+                provider
+                    .get_proof(token, vec![balance_slot], Some(BlockId::Hash(header.block_hash)))
+                    .await
+            })
+            .await?;
+
+        let account = verify_account(header.state_root, token, &proof.account_proof)?;
+        if account.code_hash == H256(keccak256([])) {
+            return Err(AssetError::NotAContract(token, number));
+        }
+        let storage_proof = proof
+            .storage_proof
+            .iter()
+            .find(|storage| slot_key(storage.key) == balance_slot)
+            .ok_or(ProofError::Incomplete)?;
+        let balance = verify_storage(account.storage_root, balance_slot, &storage_proof.proof)?;
+
+        let mut tx = self.pool.begin().await?;
+        let verification = sqlx::query_as!(
+            AssetVerification,
+            "INSERT INTO asset_verifications (id, chain_id, token_address, holder, balance, block_number, block_hash, \
+            state_root, storage_root, balance_slot, corroborating_endpoints) \
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11) \
+            RETURNING id, chain_id, token_address, holder, balance, block_number, block_hash, state_root, \
+            storage_root, balance_slot, corroborating_endpoints, created_at",
+            Uuid::new_v4(),
+            request.chain_id as i64,
+            format!("{:?}", token),
+            format!("{:?}", request.holder),
+            balance.to_string(),
+            number as i64,
+            format!("{:?}", header.block_hash),
+            format!("{:?}", header.state_root),
+            format!("{:?}", account.storage_root),
+            format!("{:?}", balance_slot),
+            header.corroborating_endpoints as i32
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        // Um bloco ainda não finalizado pode ser desfeito numa reorganização
+        if Some(token) == self.acme_token && self.solvency_chain == Some(request.chain_id) && number <= finalized {
+            record_verified_balance(&mut *tx, &verification).await?;
+        }
+        tx.commit().await?;
+
+        Ok(verification)
+    }
+
+    pub async fn get_verification(&self, id: Uuid) -> Result<Option<AssetVerification>, AssetError> {
+        let verification = sqlx::query_as!(
+            AssetVerification,
+            "SELECT id, chain_id, token_address, holder, balance, block_number, block_hash, state_root, \
+            storage_root, balance_slot, corroborating_endpoints, created_at FROM asset_verifications WHERE id = $1",
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(verification)
+    }
+}
+
+/// Grava o saldo provado como o saldo verificado (`verified_balances`) do
+/// usuário dono da carteira, usado nas provas de solvência do serviço de ZKP.
+/// Uma verificação de um bloco anterior não substitui a de um bloco mais
+/// recente, para que um saldo antigo maior não volte a valer. Saldos acima de
+/// 128 bits, o limite do circuito, ficam no máximo: a prova de `saldo >=
+/// limite` continua verdadeira. Devolve se algum usuário foi atualizado.
+pub async fn record_verified_balance<'e, E>(executor: E, verification: &AssetVerification) -> Result<bool, AssetError>
+where
+    E: PgExecutor<'e>,
+{
+    let updated = sqlx::query!(
+        "INSERT INTO verified_balances (user_id, balance, source, verification_id, block_number, verified_at, updated_at) \
+        SELECT id, LEAST($2::TEXT::NUMERIC, 340282366920938463463374607431768211455), 'storage_proof', $3, $4, NOW(), NOW() \
+        FROM users WHERE lower(wallet_address) = lower($1) \
+        ON CONFLICT (user_id) DO UPDATE SET balance = EXCLUDED.balance, source = EXCLUDED.source, \
+        verification_id = EXCLUDED.verification_id, block_number = EXCLUDED.block_number, \
+        verified_at = EXCLUDED.verified_at, updated_at = EXCLUDED.updated_at \
+        WHERE verified_balances.block_number IS NULL OR verified_balances.block_number <= EXCLUDED.block_number",
+        verification.holder,
+        verification.balance,
+        verification.id,
+        verification.block_number
+    )
+    .execute(executor)
+    .await?
+    .rows_affected();
+
+    Ok(updated > 0)
+}
+
+/// Hash e raiz de estado de um bloco, iguais nos RPCs que o conhecem
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct AgreedHeader {
+    block_hash: H256,
+    state_root: H256,
+    corroborating_endpoints: usize,
+}
+
+async fn agreed_header(chain: &Chain, number: u64, min_endpoints: usize) -> Result<AgreedHeader, AssetError> {
+    let responses = chain
+        .call_each(|provider| async move { provider.get_block(number).await })
+        .await;
+
+    let mut headers = Vec::new();
+    let mut last_error = None;
+    for response in responses {
+        match response {
+            // O hash é recalculado do cabeçalho: a raiz de estado fica presa a ele
+            Ok(Some(block)) => match (block.hash, header_hash(&block)) {
+                (Some(hash), Some(computed)) if hash == computed => headers.push((hash, block.state_root)),
+                // Blocos pendentes não têm hash
+                (None, _) => return Err(AssetError::BlockNotFound(number)),
+                _ => return Err(AssetError::InvalidHeader(number)),
+            },
+            // Um RPC atrasado ainda não tem o bloco
+            Ok(None) => {}
+            Err(e) => last_error = Some(e),
+        }
+    }
+    if headers.is_empty() {
+        return Err(last_error.map_or(AssetError::BlockNotFound(number), AssetError::Provider));
+    }
+
+    agree(number, &headers, min_endpoints)
+}
+
+fn agree(number: u64, headers: &[(H256, H256)], min_endpoints: usize) -> Result<AgreedHeader, AssetError> {
+    let (block_hash, state_root) = headers[0];
+    if headers.iter().any(|header| *header != (block_hash, state_root)) {
+        return Err(AssetError::Disagreement(number));
+    }
+    if headers.len() < min_endpoints {
+        return Err(AssetError::InsufficientCorroboration {
+            number,
+            agreeing: headers.len(),
+            required: min_endpoints,
+        });
+    }
+
+    Ok(AgreedHeader {
+        block_hash,
+        state_root,
+        corroborating_endpoints: headers.len(),
+    })
+}
+
+/// `keccak256(rlp(cabeçalho))`: os campos do Yellow Paper seguidos dos
+/// acrescentados em cada hard fork (London, Shanghai, Cancun, Prague), quando
+/// o bloco os tem. `None` se faltar um campo obrigatório.
+fn header_hash(block: &Block<H256>) -> Option<H256> {
+    let requests_hash = block.other.get_deserialized::<H256>("requestsHash").and_then(Result::ok);
+
+    let mut rlp = RlpStream::new();
+    rlp.begin_unbounded_list();
+    rlp.append(&block.parent_hash);
+    rlp.append(&block.uncles_hash);
+    rlp.append(&block.author?);
+    rlp.append(&block.state_root);
+    rlp.append(&block.transactions_root);
+    rlp.append(&block.receipts_root);
+    rlp.append(&block.logs_bloom?.as_bytes());
+    rlp.append(&block.difficulty);
+    rlp.append(&block.number?);
+    rlp.append(&block.gas_limit);
+    rlp.append(&block.gas_used);
+    rlp.append(&block.timestamp);
+    rlp.append(&block.extra_data.as_ref());
+    rlp.append(&block.mix_hash?);
+    rlp.append(&block.nonce?.as_bytes());
+    // Cada campo opcional só existe se os anteriores existem
+    let optional = [
+        block.base_fee_per_gas.map(|fee| rlp_item(&fee)),
+        block.withdrawals_root.map(|root| rlp_item(&root)),
+        block.blob_gas_used.map(|gas| rlp_item(&gas)),
+        block.excess_blob_gas.map(|gas| rlp_item(&gas)),
+        block.parent_beacon_block_root.map(|root| rlp_item(&root)),
+        requests_hash.map(|hash| rlp_item(&hash)),
+    ];
+    for item in optional.into_iter().map_while(|item| item) {
+        rlp.append_raw(&item, 1);
+    }
+    rlp.finalize_unbounded_list();
+
+    Some(H256(keccak256(rlp.out())))
+}
+
+fn rlp_item<T: ethers::utils::rlp::Encodable>(value: &T) -> Vec<u8> {
+    ethers::utils::rlp::encode(value).to_vec()
+}
+
+fn slot_key(key: U256) -> H256 {
+    let mut bytes = [0u8; 32];
+    key.to_big_endian(&mut bytes);
+    H256(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_headers_must_agree() {
+        let (hash, root) = (H256::repeat_byte(1), H256::repeat_byte(2));
+
+        let agreed = agree(7, &[(hash, root), (hash, root)], 2).unwrap();
+        assert_eq!(agreed.block_hash, H256::repeat_byte(1));
+        assert_eq!(agreed.corroborating_endpoints, 2);
+
+        // Um RPC com outra raiz de estado invalida a verificação
+        let forged = agree(7, &[(hash, root), (hash, H256::repeat_byte(3))], 1);
+        assert!(matches!(forged, Err(AssetError::Disagreement(7))));
+        // Um RPC sozinho não basta
+        assert!(matches!(
+            agree(7, &[(hash, root)], 2),
+            Err(AssetError::InsufficientCorroboration { agreeing: 1, required: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_header_hash_of_mainnet_genesis() {
+        let hex = |value: &str| value.parse::<H256>().unwrap();
+        let mut block = Block::<H256> {
+            hash: Some(hex("0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3")),
+            uncles_hash: hex("0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347"),
+            author: Some(Address::zero()),
+            state_root: hex("0xd7f8974fb5ac78d9ac099b9ad5018bedc2ce0a72dad1827a1709da30580f0544"),
+            transactions_root: hex("0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"),
+            receipts_root: hex("0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"),
+            logs_bloom: Some(Default::default()),
+            difficulty: U256::from(0x400000000u64),
+            number: Some(0u64.into()),
+            gas_limit: U256::from(5000),
+            extra_data: hex("0x11bbe8db4e347b4e8c937c1c8370e4b5ed33adb3db69cbdb7a38e1e50b1b82fa")
+                .as_bytes()
+                .to_vec()
+                .into(),
+            mix_hash: Some(H256::zero()),
+            nonce: Some(ethers::types::H64::from_low_u64_be(0x42)),
+            ..Default::default()
+        };
+        assert_eq!(header_hash(&block), block.hash);
+
+        // Uma raiz de estado trocada muda o hash
+        block.state_root = H256::repeat_byte(9);
+        assert_ne!(header_hash(&block), block.hash);
+        block.author = None;
+        assert_eq!(header_hash(&block), None);
+    }
+
+    #[test]
+    fn test_slot_key_round_trip() {
+        let slot = mapping_slot(Address::repeat_byte(0xab), U256::zero());
+        assert_eq!(slot_key(U256::from_big_endian(slot.as_bytes())), slot);
+    }
+}
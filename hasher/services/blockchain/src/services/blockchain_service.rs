@@ -1,88 +1,379 @@
-use common::models::{BlockchainVerification, User, VerificationStatus};
-use ethers::providers::{Http, Provider};
-use sqlx::PgPool;
-use std::error::Error;
-use uuid::Uuid;
 use chrono::Utc;
+use common::models::{BlockchainVerification, VerificationStatus};
+use ethers::providers::ProviderError;
+use ethers::types::{TransactionReceipt, H256};
+use log::{error, info, warn};
+use std::collections::HashSet;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::services::{
+    next_state, AbiRegistry, ChainClient, ChainStatus, FinalityConfig, Observation, Transition, VerificationRepository,
+};
+
+/// Verificações reconsultadas por passada da tarefa de acompanhamento
+const RECHECK_BATCH_SIZE: i64 = 100;
+
+#[derive(Debug, thiserror::Error)]
+pub enum TransactionError {
+    #[error("Hash de transação inválido: {0}")]
+    InvalidHash(String),
+    #[error("Rede {0} não suportada")]
+    UnknownChain(u64),
+    #[error("Usuário {0} não encontrado")]
+    UnknownUser(Uuid),
+    #[error("Erro no nó Ethereum: {0}")]
+    Provider(#[from] ProviderError),
+    #[error("Erro de banco de dados: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl TransactionError {
+    /// Código de erro exposto na API
+    pub fn code(&self) -> &'static str {
+        match self {
+            TransactionError::InvalidHash(_) => "invalid_transaction_hash",
+            TransactionError::UnknownChain(_) => "unsupported_chain",
+            TransactionError::UnknownUser(_) => "not_found",
+            TransactionError::Provider(_) | TransactionError::Database(_) => "internal_server_error",
+        }
+    }
+}
 
+/// Verificação de transações. O armazenamento e o acesso às redes são
+/// injetados na inicialização: Postgres e `ChainRegistry` no servidor,
+/// implementações em memória nos testes.
+#[derive(Clone)]
 pub struct BlockchainService {
-    pool: PgPool,
-    provider: Provider<Http>,
+    repository: Arc<dyn VerificationRepository>,
+    client: Arc<dyn ChainClient>,
+    finality: FinalityConfig,
+    abis: Arc<AbiRegistry>,
 }
 
 impl BlockchainService {
-    pub fn new(pool: &PgPool, rpc_url: &str) -> Result<Self, Box<dyn Error>> {
-        let provider = Provider::<Http>::try_from(rpc_url)?;
-        
-        Ok(Self {
-            pool: pool.clone(),
-            provider,
-        })
+    pub fn new(
+        repository: Arc<dyn VerificationRepository>,
+        client: Arc<dyn ChainClient>,
+        finality: FinalityConfig,
+        abis: Arc<AbiRegistry>,
+    ) -> Self {
+        Self {
+            repository,
+            client,
+            finality,
+            abis,
+        }
+    }
+
+    pub fn chains(&self) -> Vec<ChainStatus> {
+        self.client.supported_chains()
     }
 
-    pub async fn verify_transaction(&self, user: &User, tx_hash: &str) -> Result<BlockchainVerification, Box<dyn Error>> {
-        // Verificar se a transação existe
-        let tx = self.provider.get_transaction(tx_hash.parse()?).await?;
+    /// Registra a verificação de uma transação a partir do recibo: execução,
+    /// bloco e confirmações. Até a finalidade ela fica em aberto e é
+    /// reconsultada pela tarefa iniciada em `start`.
+    pub async fn verify_transaction(
+        &self,
+        user_id: Uuid,
+        chain_id: u64,
+        tx_hash: &str,
+    ) -> Result<BlockchainVerification, TransactionError> {
+        if !self.repository.user_exists(user_id).await? {
+            return Err(TransactionError::UnknownUser(user_id));
+        }
+        let chain = self
+            .chains()
+            .into_iter()
+            .find(|chain| chain.chain_id == chain_id)
+            .ok_or(TransactionError::UnknownChain(chain_id))?;
+        let hash = parse_hash(tx_hash)?;
+        let now = Utc::now();
 
-        // Criar verificação
-        let verification = BlockchainVerification {
+        let pending = BlockchainVerification {
             id: Uuid::new_v4(),
-            user_id: user.id,
-            transaction_hash: tx_hash.to_string(),
-            status: match tx {
-                Some(_) => VerificationStatus::Verified,
-                None => VerificationStatus::Invalid,
-            },
-            verified_at: Utc::now(),
-            created_at: Utc::now(),
-            updated_at: Utc::now(),
+            user_id,
+            chain_id: chain_id as i64,
+            transaction_hash: format!("{:?}", hash),
+            status: VerificationStatus::Pending,
+            block_number: None,
+            block_hash: None,
+            confirmations: 0,
+            failure_reason: None,
+            last_seen_at: None,
+            decoded: None,
+            verified_at: now,
+            created_at: now,
+            updated_at: now,
         };
+        let (observation, receipt) = self.observe(chain_id, hash).await?;
+        let transition = next_state(&pending, &observation, now, &self.finality_for(&chain));
+        let decoded = self.decode(chain_id, &pending, &transition, receipt).await?;
 
-        // Salvar verificação no banco
-        sqlx::query!(
-            "INSERT INTO blockchain_verifications (id, user_id, transaction_hash, status, verified_at, created_at, updated_at) \
-            VALUES ($1, $2, $3, $4, $5, $6, $7)",
-            verification.id,
-            verification.user_id,
-            verification.transaction_hash,
-            verification.status as _,
-            verification.verified_at,
-            verification.created_at,
-            verification.updated_at
-        )
-        .execute(&self.pool)
-        .await?;
+        let verification = BlockchainVerification {
+            status: transition.status,
+            block_number: transition.block_number,
+            block_hash: transition.block_hash,
+            confirmations: transition.confirmations,
+            failure_reason: transition.failure_reason.map(str::to_string),
+            last_seen_at: transition.seen.then_some(now),
+            decoded,
+            ..pending
+        };
+        self.repository.insert(&verification).await?;
 
         Ok(verification)
     }
 
-    pub async fn get_verification(&self, verification_id: Uuid) -> Result<Option<BlockchainVerification>, Box<dyn Error>> {
-        let verification = sqlx::query_as!(
-            BlockchainVerification,
-            "SELECT * FROM blockchain_verifications WHERE id = $1",
-            verification_id
-        )
-        .fetch_optional(&self.pool)
-        .await?;
+    pub async fn get_verification(
+        &self,
+        verification_id: Uuid,
+    ) -> Result<Option<BlockchainVerification>, TransactionError> {
+        Ok(self.repository.get(verification_id).await?)
+    }
 
-        Ok(verification)
+    /// Inicia a tarefa que reconsulta as verificações em aberto
+    pub fn start(&self) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            loop {
+                if let Err(e) = service.recheck_open().await {
+                    error!("Falha ao reconsultar verificações: {}", e);
+                }
+                tokio::time::sleep(service.finality.recheck_interval).await;
+            }
+        });
+
+        for chain in self.chains() {
+            info!(
+                "Acompanhamento de transações na rede {} ({}): finalidade de {} confirmações",
+                chain.chain_id,
+                chain.name,
+                self.finality_for(&chain).depth
+            );
+        }
+    }
+
+    /// Finalidade de uma rede: a profundidade própria dela ou a padrão
+    pub fn finality_for(&self, chain: &ChainStatus) -> FinalityConfig {
+        FinalityConfig {
+            depth: chain.finality_depth.unwrap_or(self.finality.depth),
+            ..self.finality
+        }
+    }
+
+    /// Reconsulta as verificações em aberto: pendentes, reorganizadas e
+    /// revertidas ainda antes da finalidade da rede (uma reorganização pode
+    /// incluí-las de novo). Devolve quantas foram atualizadas.
+    pub async fn recheck_open(&self) -> Result<usize, TransactionError> {
+        let chains = self.chains();
+        let deepest = chains
+            .iter()
+            .map(|chain| self.finality_for(chain).depth)
+            .max()
+            .unwrap_or(0);
+        let open = self.repository.list_open(deepest, RECHECK_BATCH_SIZE).await?;
+
+        let mut updated = 0;
+        let mut unavailable = HashSet::new();
+        for verification in open {
+            if unavailable.contains(&verification.chain_id) {
+                continue;
+            }
+            let Some(chain) = chains
+                .iter()
+                .find(|chain| chain.chain_id as i64 == verification.chain_id)
+            else {
+                // Rede removida da configuração: fica como está
+                continue;
+            };
+
+            match self.refresh(chain, &verification).await {
+                Ok(status) => {
+                    if status != verification.status {
+                        info!(
+                            "Transação {}: {:?} -> {:?}",
+                            verification.transaction_hash, verification.status, status
+                        );
+                    }
+                    updated += 1;
+                }
+                // Sem RPC que responda, o resto da rede fica para a próxima passada
+                Err(TransactionError::Provider(e)) => {
+                    warn!("Rede {} indisponível: {}", verification.chain_id, e);
+                    unavailable.insert(verification.chain_id);
+                }
+                Err(e) => warn!(
+                    "Falha ao atualizar a transação {}: {}",
+                    verification.transaction_hash, e
+                ),
+            }
+        }
+
+        Ok(updated)
+    }
+
+    /// Consulta a rede e grava o novo estado de uma verificação
+    async fn refresh(
+        &self,
+        chain: &ChainStatus,
+        verification: &BlockchainVerification,
+    ) -> Result<VerificationStatus, TransactionError> {
+        let hash = parse_hash(&verification.transaction_hash)?;
+        let now = Utc::now();
+        let (observation, receipt) = self.observe(chain.chain_id, hash).await?;
+        let transition = next_state(verification, &observation, now, &self.finality_for(chain));
+        let decoded = self.decode(chain.chain_id, verification, &transition, receipt).await?;
+        self.repository
+            .update(verification.id, &transition, decoded, now)
+            .await?;
+
+        Ok(transition.status)
+    }
+
+    /// Recibo, último bloco e, sem recibo, se a transação está na mempool
+    async fn observe(
+        &self,
+        chain_id: u64,
+        hash: H256,
+    ) -> Result<(Observation, Option<TransactionReceipt>), ProviderError> {
+        let receipt = self.client.get_transaction_receipt(chain_id, hash).await?;
+        let latest_block = self.client.get_block_number(chain_id).await?;
+
+        if let Some(receipt) = receipt {
+            if let Some(observation) = Observation::from_receipt(&receipt, latest_block) {
+                return Ok((observation, Some(receipt)));
+            }
+        }
+
+        let observation = match self.client.get_transaction(chain_id, hash).await? {
+            Some(_) => Observation::Mempool,
+            None => Observation::Unknown,
+        };
+        Ok((observation, None))
+    }
+
+    /// Forma decodificada da transação no bloco da transição: refeita quando
+    /// ela entra num bloco ou muda de bloco (os logs podem mudar), descartada
+    /// quando sai dele
+    async fn decode(
+        &self,
+        chain_id: u64,
+        current: &BlockchainVerification,
+        transition: &Transition,
+        receipt: Option<TransactionReceipt>,
+    ) -> Result<Option<serde_json::Value>, TransactionError> {
+        let (Some(block_hash), Some(receipt)) = (&transition.block_hash, receipt) else {
+            return Ok(None);
+        };
+        if current.block_hash.as_ref() == Some(block_hash) && current.decoded.is_some() {
+            return Ok(current.decoded.clone());
+        }
+
+        let transaction = self.client.get_transaction(chain_id, receipt.transaction_hash).await?;
+
+        Ok(transaction.map(|transaction| {
+            let decoded = self.abis.decode_transaction(&transaction, &receipt);
+            serde_json::to_value(decoded).expect("DecodedTransaction é serializável")
+        }))
     }
 }
 
+fn parse_hash(tx_hash: &str) -> Result<H256, TransactionError> {
+    tx_hash
+        .parse()
+        .map_err(|_| TransactionError::InvalidHash(tx_hash.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use mockall::predicate::*;
-    use mockall::*;
+    use crate::services::{MemoryVerificationRepository, MockChainClient};
+    use ethers::types::{Transaction, U64};
+    use std::sync::atomic::{AtomicU64, Ordering};
 
-    mock! {
-        Provider {
-            fn get_transaction(&self, hash: String) -> Result<Option<Transaction>, Box<dyn Error>>;
-        }
+    const CHAIN_ID: u64 = 1;
+
+    /// Rede com finalidade de 3 blocos em que toda transação foi minerada no
+    /// bloco 100; o último bloco é o valor de `latest`
+    fn mined_chain(latest: Arc<AtomicU64>) -> MockChainClient {
+        let mut client = MockChainClient::new();
+        client.expect_supported_chains().returning(|| {
+            vec![ChainStatus {
+                chain_id: CHAIN_ID,
+                name: "ethereum".to_string(),
+                finality_depth: Some(3),
+                endpoints: 1,
+                healthy_endpoints: 1,
+            }]
+        });
+        client.expect_get_transaction_receipt().returning(|_, hash| {
+            Ok(Some(TransactionReceipt {
+                transaction_hash: hash,
+                block_number: Some(U64::from(100)),
+                block_hash: Some(H256::repeat_byte(7)),
+                status: Some(U64::one()),
+                ..Default::default()
+            }))
+        });
+        client
+            .expect_get_block_number()
+            .returning(move |_| Ok(latest.load(Ordering::SeqCst)));
+        client.expect_get_transaction().returning(|_, hash| {
+            Ok(Some(Transaction {
+                hash,
+                ..Default::default()
+            }))
+        });
+        client
+    }
+
+    fn service(repository: Arc<MemoryVerificationRepository>, client: MockChainClient) -> BlockchainService {
+        BlockchainService::new(
+            repository,
+            Arc::new(client),
+            FinalityConfig::default(),
+            Arc::new(AbiRegistry::builtin()),
+        )
     }
 
     #[tokio::test]
-    async fn test_verify_transaction() {
-        // TODO: Implementar testes
+    async fn test_verify_transaction_until_finality() {
+        let user_id = Uuid::new_v4();
+        let repository = Arc::new(MemoryVerificationRepository::new());
+        repository.add_user(user_id);
+        let latest = Arc::new(AtomicU64::new(100));
+        let service = service(repository.clone(), mined_chain(latest.clone()));
+
+        let hash = format!("{:?}", H256::repeat_byte(1));
+        let verification = service.verify_transaction(user_id, CHAIN_ID, &hash).await.unwrap();
+        assert_eq!(verification.status, VerificationStatus::Pending);
+        assert_eq!(verification.confirmations, 1);
+        assert!(verification.decoded.is_some());
+
+        // Na terceira confirmação a tarefa de acompanhamento a confirma
+        latest.store(102, Ordering::SeqCst);
+        assert_eq!(service.recheck_open().await.unwrap(), 1);
+        let confirmed = service.get_verification(verification.id).await.unwrap().unwrap();
+        assert_eq!(confirmed.status, VerificationStatus::Confirmed);
+        assert_eq!(confirmed.confirmations, 3);
+        assert!(repository.list_open(3, 10).await.unwrap().is_empty());
     }
-} 
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_verify_transaction_rejects_invalid_requests() {
+        let user_id = Uuid::new_v4();
+        let repository = Arc::new(MemoryVerificationRepository::new());
+        let service = service(repository.clone(), mined_chain(Arc::new(AtomicU64::new(100))));
+        let hash = format!("{:?}", H256::repeat_byte(1));
+
+        let result = service.verify_transaction(user_id, CHAIN_ID, &hash).await;
+        assert!(matches!(result, Err(TransactionError::UnknownUser(id)) if id == user_id));
+
+        repository.add_user(user_id);
+        let result = service.verify_transaction(user_id, 137, &hash).await;
+        assert!(matches!(result, Err(TransactionError::UnknownChain(137))));
+        let result = service.verify_transaction(user_id, CHAIN_ID, "0x123...").await;
+        assert!(matches!(result, Err(TransactionError::InvalidHash(_))));
+    }
+}
@@ -0,0 +1,84 @@
+use async_trait::async_trait;
+use ethers::providers::{Middleware, ProviderError};
+use ethers::types::{Transaction, TransactionReceipt, H256};
+
+use crate::services::{Chain, ChainRegistry};
+
+/// Rede atendida, como vista pelo acompanhamento de transações
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainStatus {
+    pub chain_id: u64,
+    pub name: String,
+    /// Profundidade própria da rede; sem ela vale a padrão
+    pub finality_depth: Option<u64>,
+    pub endpoints: usize,
+    pub healthy_endpoints: usize,
+}
+
+impl From<&Chain> for ChainStatus {
+    fn from(chain: &Chain) -> Self {
+        Self {
+            chain_id: chain.chain_id,
+            name: chain.name.clone(),
+            finality_depth: chain.finality_depth,
+            endpoints: chain.endpoint_count(),
+            healthy_endpoints: chain.healthy_endpoints(),
+        }
+    }
+}
+
+/// Consultas ao nó usadas pela verificação de transações. Em produção é o
+/// `ChainRegistry` (ethers, com failover entre os RPCs); nos testes, um mock.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait ChainClient: Send + Sync {
+    fn supported_chains(&self) -> Vec<ChainStatus>;
+
+    async fn get_transaction_receipt(
+        &self,
+        chain_id: u64,
+        hash: H256,
+    ) -> Result<Option<TransactionReceipt>, ProviderError>;
+
+    async fn get_transaction(&self, chain_id: u64, hash: H256) -> Result<Option<Transaction>, ProviderError>;
+
+    async fn get_block_number(&self, chain_id: u64) -> Result<u64, ProviderError>;
+}
+
+impl ChainRegistry {
+    fn require(&self, chain_id: u64) -> Result<&Chain, ProviderError> {
+        self.get(chain_id)
+            .ok_or_else(|| ProviderError::CustomError(format!("rede {} não configurada", chain_id)))
+    }
+}
+
+#[async_trait]
+impl ChainClient for ChainRegistry {
+    fn supported_chains(&self) -> Vec<ChainStatus> {
+        self.chains().map(ChainStatus::from).collect()
+    }
+
+    async fn get_transaction_receipt(
+        &self,
+        chain_id: u64,
+        hash: H256,
+    ) -> Result<Option<TransactionReceipt>, ProviderError> {
+        self.require(chain_id)?
+            .call(|provider| async move { provider.get_transaction_receipt(hash).await })
+            .await
+    }
+
+    async fn get_transaction(&self, chain_id: u64, hash: H256) -> Result<Option<Transaction>, ProviderError> {
+        self.require(chain_id)?
+            .call(|provider| async move { provider.get_transaction(hash).await })
+            .await
+    }
+
+    async fn get_block_number(&self, chain_id: u64) -> Result<u64, ProviderError> {
+        let number = self
+            .require(chain_id)?
+            .call(|provider| async move { provider.get_block_number().await })
+            .await?;
+        Ok(number.as_u64())
+    }
+}
@@ -0,0 +1,404 @@
+use ethers::providers::{Http, Middleware, Provider, ProviderError, RpcError};
+use log::{info, warn};
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Espera de um endpoint depois da primeira falha; dobra a cada falha seguida
+const BASE_BACKOFF: Duration = Duration::from_secs(5);
+const MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+/// Intervalo entre as sondagens ativas dos endpoints
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Debug, thiserror::Error)]
+pub enum ChainError {
+    #[error("Configuração de redes inválida: {0}")]
+    Config(String),
+    #[error("O RPC {url} configurado para a rede {expected} pertence à rede {actual}")]
+    WrongChain { url: String, expected: u64, actual: u64 },
+}
+
+/// Uma rede EVM e seus endpoints RPC, em ordem de preferência
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainConfig {
+    pub chain_id: u64,
+    pub name: String,
+    pub rpc_urls: Vec<String>,
+    /// Confirmações exigidas nesta rede; sem ela, `BLOCKCHAIN_FINALITY_DEPTH`
+    pub finality_depth: Option<u64>,
+}
+
+impl ChainConfig {
+    /// Redes em `BLOCKCHAIN_CHAINS` (ids separados por vírgula), cada uma com
+    /// `CHAIN_<id>_RPC_URLS` (separados por vírgula) e, opcionais,
+    /// `CHAIN_<id>_NAME` e `CHAIN_<id>_FINALITY_DEPTH`. Sem `BLOCKCHAIN_CHAINS`,
+    /// uma única rede com `ETHEREUM_CHAIN_ID` (padrão 1) e `ETHEREUM_RPC_URL`.
+    pub fn from_env() -> Result<Vec<Self>, ChainError> {
+        Self::from_vars(|name| std::env::var(name).ok())
+    }
+
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Result<Vec<Self>, ChainError> {
+        // Variáveis vazias (ex.: repassadas pelo docker-compose) valem como ausentes
+        let var = |name: &str| var(name).map(|value| value.trim().to_string()).filter(|value| !value.is_empty());
+        let chain_id = |value: &str| {
+            value
+                .trim()
+                .parse::<u64>()
+                .map_err(|_| ChainError::Config(format!("chain id inválido: {}", value)))
+        };
+        let urls = |value: String| {
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|url| !url.is_empty())
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        };
+
+        let Some(chains) = var("BLOCKCHAIN_CHAINS") else {
+            let Some(rpc_url) = var("ETHEREUM_RPC_URL") else {
+                return Err(ChainError::Config("defina BLOCKCHAIN_CHAINS ou ETHEREUM_RPC_URL".to_string()));
+            };
+            let chain_id = var("ETHEREUM_CHAIN_ID").map_or(Ok(1), |value| chain_id(&value))?;
+            return Ok(vec![Self {
+                chain_id,
+                name: format!("chain-{}", chain_id),
+                rpc_urls: urls(rpc_url),
+                finality_depth: None,
+            }]);
+        };
+
+        let mut configs = Vec::new();
+        for id in chains.split(',').filter(|id| !id.trim().is_empty()) {
+            let chain_id = chain_id(id)?;
+            if configs.iter().any(|config: &Self| config.chain_id == chain_id) {
+                return Err(ChainError::Config(format!("rede {} repetida em BLOCKCHAIN_CHAINS", chain_id)));
+            }
+
+            let rpc_urls = var(&format!("CHAIN_{}_RPC_URLS", chain_id)).map(urls).unwrap_or_default();
+            if rpc_urls.is_empty() {
+                return Err(ChainError::Config(format!("CHAIN_{}_RPC_URLS deve estar definida", chain_id)));
+            }
+            let finality_depth = match var(&format!("CHAIN_{}_FINALITY_DEPTH", chain_id)) {
+                Some(value) => Some(value.parse::<u64>().ok().filter(|depth| *depth > 0).ok_or_else(|| {
+                    ChainError::Config(format!("CHAIN_{}_FINALITY_DEPTH deve ser um número positivo", chain_id))
+                })?),
+                None => None,
+            };
+
+            configs.push(Self {
+                chain_id,
+                name: var(&format!("CHAIN_{}_NAME", chain_id)).unwrap_or_else(|| format!("chain-{}", chain_id)),
+                rpc_urls,
+                finality_depth,
+            });
+        }
+
+        if configs.is_empty() {
+            return Err(ChainError::Config("BLOCKCHAIN_CHAINS não lista nenhuma rede".to_string()));
+        }
+        Ok(configs)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Health {
+    consecutive_failures: u32,
+    down_until: Option<Instant>,
+}
+
+struct Endpoint {
+    url: String,
+    provider: Provider<Http>,
+    health: Mutex<Health>,
+}
+
+impl Endpoint {
+    fn down_until(&self) -> Option<Instant> {
+        self.health.lock().unwrap().down_until
+    }
+
+    fn is_healthy(&self, now: Instant) -> bool {
+        self.down_until().map_or(true, |until| until <= now)
+    }
+
+    fn record_success(&self) {
+        let mut health = self.health.lock().unwrap();
+        if health.consecutive_failures > 0 {
+            info!("RPC {} voltou a responder", self.url);
+        }
+        *health = Health::default();
+    }
+
+    fn record_failure(&self, now: Instant, error: &ProviderError) {
+        let mut health = self.health.lock().unwrap();
+        health.consecutive_failures += 1;
+        let backoff = BASE_BACKOFF
+            .saturating_mul(1 << (health.consecutive_failures - 1).min(16))
+            .min(MAX_BACKOFF);
+        health.down_until = Some(now + backoff);
+        warn!(
+            "RPC {} falhou ({} seguidas), fora de uso por {:?}: {}",
+            self.url, health.consecutive_failures, backoff, error
+        );
+    }
+}
+
+/// Erros do próprio endpoint (transporte, HTTP, resposta ilegível), e não
+/// respostas de erro JSON-RPC, que outro endpoint repetiria
+fn is_endpoint_failure(error: &ProviderError) -> bool {
+    !error.is_error_response()
+}
+
+/// Rede configurada, com os providers criados na inicialização
+pub struct Chain {
+    pub chain_id: u64,
+    pub name: String,
+    pub finality_depth: Option<u64>,
+    endpoints: Vec<Endpoint>,
+}
+
+impl Chain {
+    fn new(config: &ChainConfig) -> Result<Self, ChainError> {
+        let endpoints = config
+            .rpc_urls
+            .iter()
+            .map(|url| {
+                let provider = Provider::<Http>::try_from(url.as_str())
+                    .map_err(|e| ChainError::Config(format!("RPC inválido para a rede {}: {}", config.chain_id, e)))?;
+                Ok(Endpoint {
+                    url: url.clone(),
+                    provider,
+                    health: Mutex::new(Health::default()),
+                })
+            })
+            .collect::<Result<Vec<_>, ChainError>>()?;
+
+        Ok(Self {
+            chain_id: config.chain_id,
+            name: config.name.clone(),
+            finality_depth: config.finality_depth,
+            endpoints,
+        })
+    }
+
+    pub fn endpoint_count(&self) -> usize {
+        self.endpoints.len()
+    }
+
+    pub fn healthy_endpoints(&self) -> usize {
+        let now = Instant::now();
+        self.endpoints.iter().filter(|endpoint| endpoint.is_healthy(now)).count()
+    }
+
+    /// Ordem de tentativa: os saudáveis na ordem configurada, depois os fora
+    /// de uso, do que volta primeiro ao que volta por último
+    fn ordered(&self) -> Vec<&Endpoint> {
+        let now = Instant::now();
+        let (mut ordered, mut down): (Vec<_>, Vec<_>) =
+            self.endpoints.iter().partition(|endpoint| endpoint.is_healthy(now));
+        down.sort_by_key(|endpoint| endpoint.down_until());
+        ordered.extend(down);
+        ordered
+    }
+
+    /// Executa uma chamada RPC, passando ao próximo endpoint quando o atual
+    /// falha; devolve o erro do último quando todos falham
+    pub async fn call<T, F, Fut>(&self, request: F) -> Result<T, ProviderError>
+    where
+        F: Fn(Provider<Http>) -> Fut,
+        Fut: Future<Output = Result<T, ProviderError>>,
+    {
+        let mut last_error = None;
+        for endpoint in self.ordered() {
+            match request(endpoint.provider.clone()).await {
+                Ok(value) => {
+                    endpoint.record_success();
+                    return Ok(value);
+                }
+                Err(e) if is_endpoint_failure(&e) => {
+                    endpoint.record_failure(Instant::now(), &e);
+                    last_error = Some(e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| ProviderError::CustomError(format!("rede {} sem RPC", self.chain_id))))
+    }
+
+    /// Executa a chamada em cada endpoint disponível, para comparar as
+    /// respostas de RPCs independentes
+    pub async fn call_each<T, F, Fut>(&self, request: F) -> Vec<Result<T, ProviderError>>
+    where
+        F: Fn(Provider<Http>) -> Fut,
+        Fut: Future<Output = Result<T, ProviderError>>,
+    {
+        let now = Instant::now();
+        let mut results = Vec::new();
+        for endpoint in self.endpoints.iter().filter(|endpoint| endpoint.is_healthy(now)) {
+            let result = request(endpoint.provider.clone()).await;
+            match &result {
+                Ok(_) => endpoint.record_success(),
+                Err(e) if is_endpoint_failure(e) => endpoint.record_failure(Instant::now(), e),
+                Err(_) => {}
+            }
+            results.push(result);
+        }
+        results
+    }
+
+    /// Sonda cada endpoint com `eth_blockNumber` e atualiza a saúde
+    async fn check_health(&self) {
+        for endpoint in &self.endpoints {
+            match endpoint.provider.get_block_number().await {
+                Ok(_) => endpoint.record_success(),
+                Err(e) => endpoint.record_failure(Instant::now(), &e),
+            }
+        }
+    }
+}
+
+/// Redes atendidas pelo serviço, por chain id
+pub struct ChainRegistry {
+    chains: BTreeMap<u64, Chain>,
+}
+
+impl ChainRegistry {
+    pub fn new(configs: &[ChainConfig]) -> Result<Self, ChainError> {
+        let chains = configs
+            .iter()
+            .map(|config| Ok((config.chain_id, Chain::new(config)?)))
+            .collect::<Result<BTreeMap<_, _>, ChainError>>()?;
+
+        Ok(Self { chains })
+    }
+
+    /// Cria os providers e confere que cada RPC responde pela rede
+    /// configurada; um RPC fora do ar não impede a inicialização
+    pub async fn connect(configs: &[ChainConfig]) -> Result<Self, ChainError> {
+        let registry = Self::new(configs)?;
+
+        for chain in registry.chains.values() {
+            for endpoint in &chain.endpoints {
+                match endpoint.provider.get_chainid().await {
+                    Ok(actual) if actual.as_u64() != chain.chain_id => {
+                        return Err(ChainError::WrongChain {
+                            url: endpoint.url.clone(),
+                            expected: chain.chain_id,
+                            actual: actual.as_u64(),
+                        });
+                    }
+                    Ok(_) => endpoint.record_success(),
+                    Err(e) => endpoint.record_failure(Instant::now(), &e),
+                }
+            }
+            info!(
+                "Rede {} ({}): {} de {} RPCs disponíveis",
+                chain.chain_id,
+                chain.name,
+                chain.healthy_endpoints(),
+                chain.endpoint_count()
+            );
+        }
+
+        Ok(registry)
+    }
+
+    pub fn get(&self, chain_id: u64) -> Option<&Chain> {
+        self.chains.get(&chain_id)
+    }
+
+    pub fn chains(&self) -> impl Iterator<Item = &Chain> {
+        self.chains.values()
+    }
+
+    /// Inicia a sondagem periódica dos endpoints de todas as redes
+    pub fn start_health_checks(self: &Arc<Self>) {
+        let registry = Arc::clone(self);
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(HEALTH_CHECK_INTERVAL).await;
+                for chain in registry.chains.values() {
+                    chain.check_health().await;
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn vars(pairs: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars = pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect::<HashMap<_, _>>();
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn test_chain_config_from_vars() {
+        let configs = ChainConfig::from_vars(vars(&[
+            ("BLOCKCHAIN_CHAINS", "1, 137"),
+            ("CHAIN_1_RPC_URLS", "https://eth-a.example, https://eth-b.example"),
+            ("CHAIN_1_NAME", "ethereum"),
+            ("CHAIN_137_RPC_URLS", "https://polygon.example"),
+            ("CHAIN_137_FINALITY_DEPTH", "128"),
+        ]))
+        .unwrap();
+
+        assert_eq!(configs.len(), 2);
+        assert_eq!(configs[0].name, "ethereum");
+        assert_eq!(configs[0].rpc_urls, vec!["https://eth-a.example", "https://eth-b.example"]);
+        assert_eq!(configs[0].finality_depth, None);
+        assert_eq!(configs[1].name, "chain-137");
+        assert_eq!(configs[1].finality_depth, Some(128));
+
+        // Sem BLOCKCHAIN_CHAINS, a configuração de uma rede só
+        let legacy = ChainConfig::from_vars(vars(&[("ETHEREUM_RPC_URL", "http://localhost:8545")])).unwrap();
+        assert_eq!(legacy[0].chain_id, 1);
+        assert_eq!(legacy[0].rpc_urls, vec!["http://localhost:8545"]);
+
+        assert!(ChainConfig::from_vars(vars(&[])).is_err());
+        assert!(ChainConfig::from_vars(vars(&[("BLOCKCHAIN_CHAINS", "1")])).is_err());
+        assert!(ChainConfig::from_vars(vars(&[("BLOCKCHAIN_CHAINS", "mainnet")])).is_err());
+        assert!(ChainConfig::from_vars(vars(&[
+            ("BLOCKCHAIN_CHAINS", "1,1"),
+            ("CHAIN_1_RPC_URLS", "http://localhost:8545"),
+        ]))
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_failover_skips_failed_endpoint() {
+        // Portas sem nada escutando: falha de conexão imediata
+        let config = ChainConfig {
+            chain_id: 1,
+            name: "ethereum".to_string(),
+            rpc_urls: vec!["http://127.0.0.1:1".to_string(), "http://127.0.0.1:2".to_string()],
+            finality_depth: None,
+        };
+        let registry = ChainRegistry::new(&[config]).unwrap();
+        let chain = registry.get(1).unwrap();
+        assert!(registry.get(137).is_none());
+        assert_eq!(chain.healthy_endpoints(), 2);
+
+        let result = chain.call(|provider| async move { provider.get_block_number().await }).await;
+        assert!(result.is_err());
+        assert_eq!(chain.healthy_endpoints(), 0);
+
+        // O primeiro a falhar é o primeiro a voltar
+        let ordered = chain.ordered().iter().map(|endpoint| endpoint.url.clone()).collect::<Vec<_>>();
+        assert_eq!(ordered, vec!["http://127.0.0.1:1", "http://127.0.0.1:2"]);
+
+        chain.endpoints[1].record_success();
+        assert_eq!(chain.healthy_endpoints(), 1);
+        assert_eq!(chain.ordered()[0].url, "http://127.0.0.1:2");
+    }
+}
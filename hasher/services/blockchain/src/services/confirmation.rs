@@ -0,0 +1,298 @@
+use chrono::{DateTime, Duration, Utc};
+use common::models::{BlockchainVerification, VerificationStatus};
+use ethers::types::{TransactionReceipt, H256};
+
+/// Confirmações exigidas quando `BLOCKCHAIN_FINALITY_DEPTH` não está definida
+pub const DEFAULT_FINALITY_DEPTH: u64 = 12;
+
+/// Tempo que uma transação pode ficar desconhecida pelo nó antes de falhar
+pub const DEFAULT_NOT_FOUND_GRACE_SECS: i64 = 600;
+
+/// Intervalo entre as passadas da tarefa de acompanhamento
+pub const DEFAULT_RECHECK_INTERVAL_SECS: u64 = 15;
+
+#[derive(Debug, Clone, Copy)]
+pub struct FinalityConfig {
+    /// Confirmações (o bloco da transação conta como a primeira) a partir das
+    /// quais uma reorganização não é mais esperada
+    pub depth: u64,
+    pub not_found_grace: Duration,
+    pub recheck_interval: std::time::Duration,
+}
+
+impl Default for FinalityConfig {
+    fn default() -> Self {
+        Self {
+            depth: DEFAULT_FINALITY_DEPTH,
+            not_found_grace: Duration::seconds(DEFAULT_NOT_FOUND_GRACE_SECS),
+            recheck_interval: std::time::Duration::from_secs(DEFAULT_RECHECK_INTERVAL_SECS),
+        }
+    }
+}
+
+impl FinalityConfig {
+    /// Lê `BLOCKCHAIN_FINALITY_DEPTH`, `BLOCKCHAIN_NOT_FOUND_GRACE_SECS` e
+    /// `BLOCKCHAIN_RECHECK_INTERVAL_SECS`; ausentes ou inválidas ficam no padrão
+    pub fn from_env() -> Self {
+        let number = |name: &str| std::env::var(name).ok().and_then(|value| value.parse::<u64>().ok());
+        let default = Self::default();
+
+        Self {
+            depth: number("BLOCKCHAIN_FINALITY_DEPTH").unwrap_or(default.depth).max(1),
+            not_found_grace: number("BLOCKCHAIN_NOT_FOUND_GRACE_SECS")
+                .map_or(default.not_found_grace, |secs| Duration::seconds(secs.min(i64::MAX as u64) as i64)),
+            recheck_interval: number("BLOCKCHAIN_RECHECK_INTERVAL_SECS")
+                .map_or(default.recheck_interval, |secs| std::time::Duration::from_secs(secs.max(1))),
+        }
+    }
+}
+
+/// O que o nó informa sobre uma transação
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Observation {
+    /// O nó não conhece a transação
+    Unknown,
+    /// Na mempool, sem recibo
+    Mempool,
+    Mined {
+        block_number: u64,
+        block_hash: H256,
+        success: bool,
+        latest_block: u64,
+    },
+}
+
+impl Observation {
+    /// Recibo de uma transação minerada; recibos sem bloco (pendentes, em
+    /// alguns nós) não contam como mineração
+    pub fn from_receipt(receipt: &TransactionReceipt, latest_block: u64) -> Option<Self> {
+        Some(Observation::Mined {
+            block_number: receipt.block_number?.as_u64(),
+            block_hash: receipt.block_hash?,
+            // Recibos anteriores ao Byzantium não têm status
+            success: receipt.status.map_or(true, |status| !status.is_zero()),
+            latest_block,
+        })
+    }
+}
+
+/// Novo estado de uma verificação depois de consultar o nó
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transition {
+    pub status: VerificationStatus,
+    pub block_number: Option<i64>,
+    pub block_hash: Option<String>,
+    pub confirmations: i64,
+    pub failure_reason: Option<&'static str>,
+    /// O nó conhecia a transação nesta consulta
+    pub seen: bool,
+}
+
+/// Aplica uma observação ao estado salvo:
+///
+/// - minerada com sucesso: `confirmed` na profundidade de finalidade, senão
+///   `pending`; se estava em outro bloco, `reorged` até a próxima consulta
+/// - revertida: `failed` (`reverted`), reconsultada até a finalidade
+/// - de volta à mempool ou desconhecida depois de minerada: `reorged`
+/// - desconhecida por mais que `not_found_grace`: `failed` (`not_found`, ou
+///   `dropped` se o nó já a conheceu)
+pub fn next_state(
+    current: &BlockchainVerification,
+    observation: &Observation,
+    now: DateTime<Utc>,
+    config: &FinalityConfig,
+) -> Transition {
+    let was_mined = current.block_hash.is_some();
+
+    match *observation {
+        Observation::Mined {
+            block_number,
+            block_hash,
+            success,
+            latest_block,
+        } => {
+            let block_hash = format!("{:?}", block_hash);
+            let confirmations = (latest_block + 1).saturating_sub(block_number);
+            let moved = current.block_hash.as_ref().is_some_and(|previous| *previous != block_hash);
+
+            let status = if !success {
+                VerificationStatus::Failed
+            } else if moved {
+                VerificationStatus::Reorged
+            } else if confirmations >= config.depth {
+                VerificationStatus::Confirmed
+            } else {
+                VerificationStatus::Pending
+            };
+
+            Transition {
+                status,
+                block_number: Some(block_number as i64),
+                block_hash: Some(block_hash),
+                confirmations: confirmations as i64,
+                failure_reason: (!success).then_some("reverted"),
+                seen: true,
+            }
+        }
+        Observation::Mempool => Transition {
+            status: if was_mined {
+                VerificationStatus::Reorged
+            } else {
+                VerificationStatus::Pending
+            },
+            block_number: None,
+            block_hash: None,
+            confirmations: 0,
+            failure_reason: None,
+            seen: true,
+        },
+        Observation::Unknown => {
+            let since = current.last_seen_at.unwrap_or(current.created_at);
+            let (status, failure_reason) = if was_mined {
+                (VerificationStatus::Reorged, None)
+            } else if now - since > config.not_found_grace {
+                let reason = if current.last_seen_at.is_some() { "dropped" } else { "not_found" };
+                (VerificationStatus::Failed, Some(reason))
+            } else if current.status == VerificationStatus::Reorged {
+                (VerificationStatus::Reorged, None)
+            } else {
+                (VerificationStatus::Pending, None)
+            };
+
+            Transition {
+                status,
+                block_number: None,
+                block_hash: None,
+                confirmations: 0,
+                failure_reason,
+                seen: false,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::U64;
+    use uuid::Uuid;
+
+    fn verification(block_hash: Option<H256>, last_seen_at: Option<DateTime<Utc>>) -> BlockchainVerification {
+        let created_at = Utc::now() - Duration::minutes(30);
+        BlockchainVerification {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            chain_id: 1,
+            transaction_hash: format!("{:?}", H256::repeat_byte(1)),
+            status: VerificationStatus::Pending,
+            block_number: block_hash.map(|_| 100),
+            block_hash: block_hash.map(|hash| format!("{:?}", hash)),
+            confirmations: 0,
+            failure_reason: None,
+            last_seen_at,
+            decoded: None,
+            verified_at: created_at,
+            created_at,
+            updated_at: created_at,
+        }
+    }
+
+    fn mined(block_hash: H256, success: bool, latest_block: u64) -> Observation {
+        Observation::Mined {
+            block_number: 100,
+            block_hash,
+            success,
+            latest_block,
+        }
+    }
+
+    #[test]
+    fn test_confirmations_reach_finality() {
+        let config = FinalityConfig::default();
+        let current = verification(None, None);
+        let block = H256::repeat_byte(0xaa);
+
+        let pending = next_state(&current, &mined(block, true, 110), Utc::now(), &config);
+        assert_eq!(pending.status, VerificationStatus::Pending);
+        assert_eq!(pending.confirmations, 11);
+        assert_eq!(pending.block_number, Some(100));
+
+        let confirmed = next_state(&current, &mined(block, true, 111), Utc::now(), &config);
+        assert_eq!(confirmed.status, VerificationStatus::Confirmed);
+        assert_eq!(confirmed.confirmations, 12);
+
+        // O nó pode estar atrás do bloco do recibo
+        assert_eq!(next_state(&current, &mined(block, true, 99), Utc::now(), &config).confirmations, 0);
+    }
+
+    #[test]
+    fn test_reverted_transaction_fails() {
+        let transition = next_state(
+            &verification(None, None),
+            &mined(H256::repeat_byte(0xaa), false, 200),
+            Utc::now(),
+            &FinalityConfig::default(),
+        );
+        assert_eq!(transition.status, VerificationStatus::Failed);
+        assert_eq!(transition.failure_reason, Some("reverted"));
+    }
+
+    #[test]
+    fn test_reorg_is_detected() {
+        let config = FinalityConfig::default();
+        let current = verification(Some(H256::repeat_byte(0xaa)), Some(Utc::now()));
+
+        // Mesma transação em outro bloco
+        let moved = next_state(&current, &mined(H256::repeat_byte(0xbb), true, 120), Utc::now(), &config);
+        assert_eq!(moved.status, VerificationStatus::Reorged);
+        assert_eq!(moved.block_hash, Some(format!("{:?}", H256::repeat_byte(0xbb))));
+
+        // De volta à mempool, ou sumiu do nó
+        assert_eq!(next_state(&current, &Observation::Mempool, Utc::now(), &config).status, VerificationStatus::Reorged);
+        let gone = next_state(&current, &Observation::Unknown, Utc::now(), &config);
+        assert_eq!(gone.status, VerificationStatus::Reorged);
+        assert_eq!(gone.block_hash, None);
+    }
+
+    #[test]
+    fn test_unknown_transaction_fails_after_grace() {
+        let config = FinalityConfig::default();
+        let current = verification(None, None);
+
+        let early = next_state(&current, &Observation::Unknown, current.created_at + Duration::minutes(5), &config);
+        assert_eq!(early.status, VerificationStatus::Pending);
+
+        let late = next_state(&current, &Observation::Unknown, Utc::now(), &config);
+        assert_eq!(late.status, VerificationStatus::Failed);
+        assert_eq!(late.failure_reason, Some("not_found"));
+
+        // Vista na mempool e depois descartada
+        let seen = verification(None, Some(Utc::now() - Duration::minutes(20)));
+        assert_eq!(next_state(&seen, &Observation::Unknown, Utc::now(), &config).failure_reason, Some("dropped"));
+    }
+
+    #[test]
+    fn test_observation_from_receipt() {
+        let mut receipt = TransactionReceipt {
+            status: Some(U64::from(1)),
+            ..Default::default()
+        };
+        assert_eq!(Observation::from_receipt(&receipt, 10), None);
+
+        receipt.block_number = Some(U64::from(7));
+        receipt.block_hash = Some(H256::repeat_byte(0xaa));
+        assert_eq!(Observation::from_receipt(&receipt, 10), Some(mined_at(7, true)));
+
+        receipt.status = Some(U64::zero());
+        assert_eq!(Observation::from_receipt(&receipt, 10), Some(mined_at(7, false)));
+    }
+
+    fn mined_at(block_number: u64, success: bool) -> Observation {
+        Observation::Mined {
+            block_number,
+            block_hash: H256::repeat_byte(0xaa),
+            success,
+            latest_block: 10,
+        }
+    }
+}
@@ -0,0 +1,522 @@
+use ethers::abi::{parse_abi, Abi, Function, RawLog, Token};
+use ethers::types::{Address, Log, Transaction, TransactionReceipt, U256};
+use ethers::utils::format_ether;
+use log::{info, warn};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Contratos de `contracts/` (os eventos de `Ownable`/`Pausable` incluídos)
+const PROJECT_ABIS: &[(&str, &[&str])] = &[
+    (
+        "ZKProofRegistry",
+        &[
+            "function submitProof(bytes32 proofHash)",
+            "function verifyProof(bytes32 proofId)",
+            "function verifyProofOnChain(bytes32 proofId, bytes32 circuitId, uint256[2] a, uint256[2][2] b, uint256[2] c, uint256[] input)",
+            "function setCircuitVerifier(bytes32 circuitId, address verifier)",
+            "function updateRegistrationCost(uint256 newCost)",
+            "function emergencyTokenRecovery(address token, uint256 amount)",
+            "function pause()",
+            "function unpause()",
+            "event ProofSubmitted(bytes32 indexed proofId, address indexed submitter)",
+            "event ProofVerified(bytes32 indexed proofId, address indexed verifier)",
+            "event RegistrationCostUpdated(uint256 oldCost, uint256 newCost)",
+            "event EmergencyRecovery(address token, uint256 amount)",
+            "event CircuitVerifierUpdated(bytes32 indexed circuitId, address verifier)",
+            "event Paused(address account)",
+            "event Unpaused(address account)",
+            "event OwnershipTransferred(address indexed previousOwner, address indexed newOwner)",
+        ],
+    ),
+    (
+        "SubwalletRegistry",
+        &[
+            "function registerSubwallet(address wallet, string name)",
+            "function removeSubwallet(address wallet)",
+            "function updateSubwalletName(address wallet, string newName)",
+            "function setSubwalletStatus(address wallet, bool isActive)",
+            "function updateLastActivity(address wallet)",
+            "function pause()",
+            "function unpause()",
+            "event SubwalletRegistered(address indexed wallet, string name)",
+            "event SubwalletRemoved(address indexed wallet)",
+            "event SubwalletUpdated(address indexed wallet, string newName)",
+            "event SubwalletStatusChanged(address indexed wallet, bool isActive)",
+            "event Paused(address account)",
+            "event Unpaused(address account)",
+            "event OwnershipTransferred(address indexed previousOwner, address indexed newOwner)",
+        ],
+    ),
+    (
+        "ACMEToken",
+        &[
+            "function transfer(address to, uint256 amount) returns (bool)",
+            "function approve(address spender, uint256 amount) returns (bool)",
+            "function transferFrom(address from, address to, uint256 amount) returns (bool)",
+            "function setZKProofRegistry(address _zkProofRegistry)",
+            "function mint(address to, uint256 amount)",
+            "function burn(uint256 amount)",
+            "function pause()",
+            "function unpause()",
+            "event Transfer(address indexed from, address indexed to, uint256 value)",
+            "event Approval(address indexed owner, address indexed spender, uint256 value)",
+            "event TokensMinted(address indexed to, uint256 amount)",
+            "event TokensBurned(address indexed from, uint256 amount)",
+            "event Paused(address account)",
+            "event Unpaused(address account)",
+            "event OwnershipTransferred(address indexed previousOwner, address indexed newOwner)",
+        ],
+    ),
+];
+
+const ERC20_ABI: &[&str] = &[
+    "function transfer(address to, uint256 amount) returns (bool)",
+    "function approve(address spender, uint256 amount) returns (bool)",
+    "function transferFrom(address from, address to, uint256 amount) returns (bool)",
+    "event Transfer(address indexed from, address indexed to, uint256 value)",
+    "event Approval(address indexed owner, address indexed spender, uint256 value)",
+];
+
+const ERC721_ABI: &[&str] = &[
+    "function transferFrom(address from, address to, uint256 tokenId)",
+    "function safeTransferFrom(address from, address to, uint256 tokenId)",
+    "function safeTransferFrom(address from, address to, uint256 tokenId, bytes data)",
+    "function approve(address to, uint256 tokenId)",
+    "function setApprovalForAll(address operator, bool approved)",
+    "event Transfer(address indexed from, address indexed to, uint256 indexed tokenId)",
+    "event Approval(address indexed owner, address indexed approved, uint256 indexed tokenId)",
+    "event ApprovalForAll(address indexed owner, address indexed operator, bool approved)",
+];
+
+#[derive(Debug, thiserror::Error)]
+pub enum AbiError {
+    #[error("ABI {0} inválida: {1}")]
+    Invalid(String, String),
+    #[error("Erro ao ler {0}: {1}")]
+    Io(String, std::io::Error),
+}
+
+/// O que uma transação fez, segundo as ABIs conhecidas
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DecodedTransaction {
+    pub to: Option<String>,
+    /// Contrato criado pela transação
+    pub created_contract: Option<String>,
+    /// ABI que decodificou a chamada
+    pub contract: Option<String>,
+    pub value_wei: String,
+    /// Valor em unidades nativas (ether)
+    pub value: String,
+    pub method: Option<DecodedCall>,
+    pub events: Vec<DecodedEvent>,
+    pub token_transfers: Vec<TokenTransfer>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DecodedCall {
+    pub name: String,
+    pub signature: String,
+    pub args: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DecodedEvent {
+    pub log_index: Option<u64>,
+    pub address: String,
+    pub contract: String,
+    pub name: String,
+    pub args: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TokenTransfer {
+    /// `erc20` ou `erc721`
+    pub standard: &'static str,
+    pub token: String,
+    pub from: String,
+    pub to: String,
+    pub amount: Option<String>,
+    pub token_id: Option<String>,
+}
+
+/// ABIs conhecidas pelo serviço. Um endereço vinculado a uma ABI é decodificado
+/// só com ela; os demais, pela primeira ABI (na ordem de registro: ERC-20,
+/// ERC-721, contratos do projeto, carregadas) que tenha o seletor da chamada
+/// ou a assinatura do evento.
+#[derive(Debug, Clone)]
+pub struct AbiRegistry {
+    abis: Vec<(String, Abi)>,
+    bindings: HashMap<Address, usize>,
+}
+
+impl AbiRegistry {
+    /// ERC-20, ERC-721 e contratos do projeto
+    pub fn builtin() -> Self {
+        let mut registry = Self {
+            abis: Vec::new(),
+            bindings: HashMap::new(),
+        };
+        let standard = [("ERC20", ERC20_ABI), ("ERC721", ERC721_ABI)];
+        // Os padrões vêm antes: um Transfer qualquer não é atribuído ao ACMEToken
+        for (name, signatures) in standard.iter().chain(PROJECT_ABIS) {
+            let abi = parse_abi(signatures).expect("ABIs embutidas são válidas");
+            registry.abis.push((name.to_string(), abi));
+        }
+        registry
+    }
+
+    /// ABIs embutidas, mais as de `BLOCKCHAIN_ABI_DIR`, com os endereços de
+    /// `ACME_TOKEN_ADDRESS`, `INDEXER_PROOF_REGISTRY` e
+    /// `INDEXER_SUBWALLET_REGISTRY` vinculados aos contratos do projeto
+    pub fn from_env() -> Result<Self, AbiError> {
+        let mut registry = Self::builtin();
+        let var = |name: &str| std::env::var(name).ok().filter(|value| !value.trim().is_empty());
+
+        if let Some(dir) = var("BLOCKCHAIN_ABI_DIR") {
+            let loaded = registry.load_dir(Path::new(&dir))?;
+            info!("{} ABIs carregadas de {}", loaded, dir);
+        }
+        for (name, contract) in [
+            ("ACME_TOKEN_ADDRESS", "ACMEToken"),
+            ("INDEXER_PROOF_REGISTRY", "ZKProofRegistry"),
+            ("INDEXER_SUBWALLET_REGISTRY", "SubwalletRegistry"),
+        ] {
+            match var(name).map(|value| value.trim().parse::<Address>()) {
+                Some(Ok(address)) => registry.bind(address, contract),
+                Some(Err(_)) => warn!("{} não é um endereço; {} fica sem vínculo", name, contract),
+                None => {}
+            }
+        }
+
+        Ok(registry)
+    }
+
+    /// Lê os `*.json` de um diretório: uma ABI (lista) ou um artefato com
+    /// `abi` e, opcionalmente, `address`. O nome do arquivo é o do contrato;
+    /// um nome já registrado é substituído.
+    pub fn load_dir(&mut self, dir: &Path) -> Result<usize, AbiError> {
+        let entries = std::fs::read_dir(dir).map_err(|e| AbiError::Io(dir.display().to_string(), e))?;
+        let mut paths = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+            .collect::<Vec<_>>();
+        paths.sort();
+
+        for path in &paths {
+            let name = path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+            let content = std::fs::read_to_string(path).map_err(|e| AbiError::Io(path.display().to_string(), e))?;
+            let (abi, address) = parse_artifact(&content).map_err(|e| AbiError::Invalid(name.clone(), e))?;
+            self.register(&name, abi);
+            if let Some(address) = address {
+                self.bind(address, &name);
+            }
+        }
+
+        Ok(paths.len())
+    }
+
+    pub fn register(&mut self, name: &str, abi: Abi) {
+        match self.abis.iter().position(|(registered, _)| registered == name) {
+            Some(index) => self.abis[index].1 = abi,
+            None => self.abis.push((name.to_string(), abi)),
+        }
+    }
+
+    /// Decodifica as chamadas e os eventos de `address` só com a ABI `name`
+    pub fn bind(&mut self, address: Address, name: &str) {
+        match self.abis.iter().position(|(registered, _)| registered == name) {
+            Some(index) => {
+                self.bindings.insert(address, index);
+            }
+            None => warn!("ABI {} não registrada; {:?} fica sem vínculo", name, address),
+        }
+    }
+
+    /// ABIs candidatas para um endereço: a vinculada, ou todas
+    fn candidates(&self, address: Option<Address>) -> Vec<&(String, Abi)> {
+        match address.and_then(|address| self.bindings.get(&address)) {
+            Some(&index) => vec![&self.abis[index]],
+            None => self.abis.iter().collect(),
+        }
+    }
+
+    pub fn decode_call(&self, to: Option<Address>, input: &[u8]) -> Option<(String, DecodedCall)> {
+        let (selector, arguments) = (input.get(..4)?, &input[4..]);
+
+        self.candidates(to).into_iter().find_map(|(contract, abi)| {
+            abi.functions()
+                .filter(|function| function.short_signature() == selector)
+                .find_map(|function| {
+                    let tokens = function.decode_input(arguments).ok()?;
+                    Some((contract.clone(), call(function, tokens)))
+                })
+        })
+    }
+
+    pub fn decode_log(&self, log: &Log) -> Option<DecodedEvent> {
+        let topic = *log.topics.first()?;
+
+        self.candidates(Some(log.address)).into_iter().find_map(|(contract, abi)| {
+            abi.events()
+                .filter(|event| !event.anonymous && event.signature() == topic)
+                .find_map(|event| {
+                    let raw = RawLog {
+                        topics: log.topics.clone(),
+                        data: log.data.to_vec(),
+                    };
+                    let decoded = event.parse_log_whole(raw).ok()?;
+                    Some(DecodedEvent {
+                        log_index: log.log_index.map(|index| index.as_u64()),
+                        address: format!("{:?}", log.address),
+                        contract: contract.clone(),
+                        name: event.name.clone(),
+                        args: decoded.params.into_iter().map(|param| (param.name, token_json(&param.value))).collect(),
+                    })
+                })
+        })
+    }
+
+    /// Decodifica a chamada, os logs do recibo e as transferências de tokens
+    pub fn decode_transaction(&self, transaction: &Transaction, receipt: &TransactionReceipt) -> DecodedTransaction {
+        let call = self.decode_call(transaction.to, &transaction.input);
+        let events = receipt.logs.iter().filter_map(|log| self.decode_log(log)).collect::<Vec<_>>();
+        let token_transfers = events.iter().filter_map(transfer).collect();
+        let (contract, method) = call.map_or((None, None), |(contract, method)| (Some(contract), Some(method)));
+
+        DecodedTransaction {
+            to: transaction.to.map(|to| format!("{:?}", to)),
+            created_contract: receipt.contract_address.map(|address| format!("{:?}", address)),
+            contract,
+            value_wei: transaction.value.to_string(),
+            value: format_ether(transaction.value),
+            method,
+            events,
+            token_transfers,
+        }
+    }
+}
+
+fn parse_artifact(content: &str) -> Result<(Abi, Option<Address>), String> {
+    let value = serde_json::from_str::<Value>(content).map_err(|e| e.to_string())?;
+    let (abi, address) = match value {
+        Value::Object(mut artifact) => (
+            artifact.remove("abi").ok_or("artefato sem \"abi\"")?,
+            artifact.remove("address"),
+        ),
+        abi => (abi, None),
+    };
+
+    let abi = serde_json::from_value::<Abi>(abi).map_err(|e| e.to_string())?;
+    let address = address
+        .map(|address| serde_json::from_value::<Address>(address).map_err(|e| format!("address inválido: {}", e)))
+        .transpose()?;
+    Ok((abi, address))
+}
+
+fn call(function: &Function, tokens: Vec<Token>) -> DecodedCall {
+    DecodedCall {
+        name: function.name.clone(),
+        signature: function.signature().split(':').next().unwrap_or_default().to_string(),
+        args: function
+            .inputs
+            .iter()
+            .zip(&tokens)
+            .enumerate()
+            .map(|(index, (param, token))| {
+                // Parâmetros sem nome na ABI ficam pela posição
+                let name = if param.name.is_empty() { index.to_string() } else { param.name.clone() };
+                (name, token_json(token))
+            })
+            .collect(),
+    }
+}
+
+/// Transferência de tokens num evento `Transfer` de ERC-20 (valor nos dados)
+/// ou ERC-721 (tokenId indexado)
+fn transfer(event: &DecodedEvent) -> Option<TokenTransfer> {
+    if event.name != "Transfer" {
+        return None;
+    }
+    let arg = |name: &str| event.args.get(name).and_then(Value::as_str).map(str::to_string);
+    let (standard, amount, token_id) = match (arg("value").or_else(|| arg("amount")), arg("tokenId")) {
+        (Some(amount), None) => ("erc20", Some(amount), None),
+        (None, Some(token_id)) => ("erc721", None, Some(token_id)),
+        _ => return None,
+    };
+
+    Some(TokenTransfer {
+        standard,
+        token: event.address.clone(),
+        from: arg("from")?,
+        to: arg("to")?,
+        amount,
+        token_id,
+    })
+}
+
+/// Inteiros em decimal (uint256 não cabe em número JSON), bytes em hex
+fn token_json(token: &Token) -> Value {
+    match token {
+        Token::Address(address) => json!(format!("{:?}", address)),
+        Token::Uint(value) => json!(value.to_string()),
+        Token::Int(value) => json!(int_string(*value)),
+        Token::Bool(value) => json!(value),
+        Token::String(value) => json!(value),
+        Token::Bytes(bytes) | Token::FixedBytes(bytes) => json!(format!("0x{}", hex(bytes))),
+        Token::Array(tokens) | Token::FixedArray(tokens) | Token::Tuple(tokens) => {
+            Value::Array(tokens.iter().map(token_json).collect())
+        }
+    }
+}
+
+/// int256 em complemento de dois
+fn int_string(value: U256) -> String {
+    if value.bit(255) {
+        format!("-{}", (!value).overflowing_add(U256::one()).0)
+    } else {
+        value.to_string()
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::abi::{encode, AbiEncode};
+    use ethers::types::{Bytes, H256};
+
+    fn address_topic(address: Address) -> H256 {
+        H256::from(address)
+    }
+
+    fn erc20_transfer_log(token: Address, from: Address, to: Address, amount: u64) -> Log {
+        Log {
+            address: token,
+            topics: vec![
+                ethers::utils::keccak256("Transfer(address,address,uint256)").into(),
+                address_topic(from),
+                address_topic(to),
+            ],
+            data: encode(&[Token::Uint(amount.into())]).into(),
+            log_index: Some(0.into()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_decodes_project_call() {
+        let registry = AbiRegistry::builtin();
+        let wallet = Address::repeat_byte(0x44);
+        let mut input = ethers::utils::id("registerSubwallet(address,string)").to_vec();
+        input.extend(encode(&[Token::Address(wallet), Token::String("tesouraria".to_string())]));
+
+        let (contract, call) = registry.decode_call(Some(Address::repeat_byte(0x22)), &input).unwrap();
+        assert_eq!(contract, "SubwalletRegistry");
+        assert_eq!(call.name, "registerSubwallet");
+        assert_eq!(call.signature, "registerSubwallet(address,string)");
+        assert_eq!(call.args["wallet"], json!(format!("{:?}", wallet)));
+        assert_eq!(call.args["name"], json!("tesouraria"));
+
+        // Seletor desconhecido ou calldata curta
+        assert_eq!(registry.decode_call(None, &[0xde, 0xad, 0xbe, 0xef]), None);
+        assert_eq!(registry.decode_call(None, &[0x01]), None);
+    }
+
+    #[test]
+    fn test_decodes_erc20_and_erc721_transfers() {
+        let registry = AbiRegistry::builtin();
+        let (token, from, to) = (Address::repeat_byte(0xac), Address::repeat_byte(1), Address::repeat_byte(2));
+
+        let erc20 = registry.decode_log(&erc20_transfer_log(token, from, to, 1500)).unwrap();
+        assert_eq!(erc20.contract, "ERC20");
+        assert_eq!(
+            transfer(&erc20),
+            Some(TokenTransfer {
+                standard: "erc20",
+                token: format!("{:?}", token),
+                from: format!("{:?}", from),
+                to: format!("{:?}", to),
+                amount: Some("1500".to_string()),
+                token_id: None,
+            })
+        );
+
+        // Mesmo tópico, mas com o tokenId indexado
+        let mut nft = erc20_transfer_log(token, from, to, 0);
+        nft.topics.push(H256::from_low_u64_be(7));
+        nft.data = Bytes::new();
+        let erc721 = registry.decode_log(&nft).unwrap();
+        assert_eq!(erc721.contract, "ERC721");
+        let nft_transfer = transfer(&erc721).unwrap();
+        assert_eq!((nft_transfer.standard, nft_transfer.token_id), ("erc721", Some("7".to_string())));
+    }
+
+    #[test]
+    fn test_decode_transaction() {
+        let registry = AbiRegistry::builtin();
+        let (token, holder, to) = (Address::repeat_byte(0xac), Address::repeat_byte(1), Address::repeat_byte(2));
+
+        let mut input = ethers::utils::id("transfer(address,uint256)").to_vec();
+        input.extend(encode(&[Token::Address(to), Token::Uint(1500.into())]));
+        let transaction = Transaction {
+            from: holder,
+            to: Some(token),
+            value: U256::exp10(17),
+            input: input.into(),
+            ..Default::default()
+        };
+        let receipt = TransactionReceipt {
+            logs: vec![erc20_transfer_log(token, holder, to, 1500)],
+            ..Default::default()
+        };
+
+        let decoded = registry.decode_transaction(&transaction, &receipt);
+        assert_eq!(decoded.contract.as_deref(), Some("ERC20"));
+        assert_eq!(decoded.method.as_ref().unwrap().name, "transfer");
+        assert_eq!(decoded.value_wei, "100000000000000000");
+        assert_eq!(decoded.value, "0.100000000000000000");
+        assert_eq!(decoded.token_transfers.len(), 1);
+        assert_eq!(serde_json::to_value(&decoded).unwrap()["events"][0]["name"], json!("Transfer"));
+    }
+
+    #[test]
+    fn test_bound_address_uses_its_abi() {
+        let mut registry = AbiRegistry::builtin();
+        let token = Address::repeat_byte(0xac);
+        let artifact = json!({
+            "address": format!("{:?}", token),
+            "abi": [{
+                "type": "function",
+                "name": "transfer",
+                "inputs": [{ "name": "recipient", "type": "address" }, { "name": "", "type": "uint256" }],
+                "outputs": [],
+                "stateMutability": "nonpayable"
+            }]
+        });
+        let (abi, address) = parse_artifact(&artifact.to_string()).unwrap();
+        registry.register("Custom", abi);
+        registry.bind(address.unwrap(), "Custom");
+
+        let input = [
+            ethers::utils::id("transfer(address,uint256)").to_vec(),
+            (Address::repeat_byte(2), U256::from(5)).encode(),
+        ]
+        .concat();
+        let (contract, call) = registry.decode_call(Some(token), &input).unwrap();
+        assert_eq!(contract, "Custom");
+        assert_eq!(call.args["recipient"], json!(format!("{:?}", Address::repeat_byte(2))));
+        assert_eq!(call.args["1"], json!("5"));
+
+        assert_eq!(int_string(!U256::zero()), "-1");
+
+        // O ACMEToken vinculado decodifica também as funções de ERC-20
+        let acme = Address::repeat_byte(0xad);
+        registry.bind(acme, "ACMEToken");
+        assert_eq!(registry.decode_call(Some(acme), &input).unwrap().0, "ACMEToken");
+    }
+}
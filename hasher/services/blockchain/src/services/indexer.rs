@@ -0,0 +1,692 @@
+use common::models::{ChainEvent, IndexerCheckpoint};
+use ethers::contract::{abigen, EthEvent, EthLogDecode};
+use ethers::providers::{Middleware, ProviderError};
+use ethers::types::{Address, Filter, Log, H256};
+use log::{error, info, warn};
+use serde::Deserialize;
+use serde_json::json;
+use sqlx::PgPool;
+use std::collections::{btree_map::Entry, BTreeMap};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::services::{Chain, ChainRegistry};
+
+/// Blocos por consulta `eth_getLogs` quando `INDEXER_BATCH_BLOCKS` não está definida
+pub const DEFAULT_BATCH_BLOCKS: u64 = 1000;
+
+/// Espera entre passadas, já em dia com a rede, sem `INDEXER_POLL_INTERVAL_SECS`
+pub const DEFAULT_POLL_INTERVAL_SECS: u64 = 5;
+
+/// Eventos devolvidos por consulta quando `limit` não é informado
+const DEFAULT_QUERY_LIMIT: i64 = 100;
+const MAX_QUERY_LIMIT: i64 = 1000;
+
+abigen!(
+    ProofRegistry,
+    r#"[
+        event ProofSubmitted(bytes32 indexed proofId, address indexed submitter)
+        event ProofVerified(bytes32 indexed proofId, address indexed verifier)
+    ]"#;
+
+    SubwalletRegistry,
+    r#"[
+        event SubwalletRegistered(address indexed wallet, string name)
+        event SubwalletStatusChanged(address indexed wallet, bool isActive)
+    ]"#
+);
+
+#[derive(Debug, thiserror::Error)]
+pub enum IndexerError {
+    #[error("Configuração do indexador inválida: {0}")]
+    Config(String),
+    #[error("Erro no nó Ethereum: {0}")]
+    Provider(#[from] ProviderError),
+    #[error("Erro de banco de dados: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+#[derive(Debug, Clone)]
+pub struct IndexerConfig {
+    pub chain_id: u64,
+    pub proof_registry: Option<Address>,
+    pub subwallet_registry: Option<Address>,
+    /// Primeiro bloco indexado (bloco de implantação dos contratos)
+    pub from_block: u64,
+    pub batch_blocks: u64,
+    pub poll_interval: Duration,
+}
+
+impl IndexerConfig {
+    /// Lê `INDEXER_PROOF_REGISTRY`, `INDEXER_SUBWALLET_REGISTRY`,
+    /// `INDEXER_CHAIN_ID` (padrão `default_chain`), `INDEXER_FROM_BLOCK`,
+    /// `INDEXER_BATCH_BLOCKS` e `INDEXER_POLL_INTERVAL_SECS`. Sem nenhum dos
+    /// dois contratos o indexador fica desligado (`None`).
+    pub fn from_env(default_chain: u64) -> Result<Option<Self>, IndexerError> {
+        // Variáveis vazias (ex.: repassadas pelo docker-compose) valem como ausentes
+        let var = |name: &str| std::env::var(name).ok().map(|value| value.trim().to_string()).filter(|value| !value.is_empty());
+        let address = |name: &str| {
+            var(name)
+                .map(|value| value.parse::<Address>().map_err(|_| IndexerError::Config(format!("{} não é um endereço: {}", name, value))))
+                .transpose()
+        };
+        let number = |name: &str, default: u64| {
+            var(name).map_or(Ok(default), |value| {
+                value
+                    .parse::<u64>()
+                    .map_err(|_| IndexerError::Config(format!("{} deve ser um número: {}", name, value)))
+            })
+        };
+
+        let proof_registry = address("INDEXER_PROOF_REGISTRY")?;
+        let subwallet_registry = address("INDEXER_SUBWALLET_REGISTRY")?;
+        if proof_registry.is_none() && subwallet_registry.is_none() {
+            return Ok(None);
+        }
+
+        Ok(Some(Self {
+            chain_id: number("INDEXER_CHAIN_ID", default_chain)?,
+            proof_registry,
+            subwallet_registry,
+            from_block: number("INDEXER_FROM_BLOCK", 0)?,
+            batch_blocks: number("INDEXER_BATCH_BLOCKS", DEFAULT_BATCH_BLOCKS)?.max(1),
+            poll_interval: Duration::from_secs(number("INDEXER_POLL_INTERVAL_SECS", DEFAULT_POLL_INTERVAL_SECS)?.max(1)),
+        }))
+    }
+
+    /// Filtro `eth_getLogs` dos eventos indexados num intervalo de blocos
+    pub fn filter(&self, from: u64, to: u64) -> Filter {
+        let addresses = self.proof_registry.into_iter().chain(self.subwallet_registry).collect::<Vec<_>>();
+        let topics = vec![
+            ProofSubmittedFilter::signature(),
+            ProofVerifiedFilter::signature(),
+            SubwalletRegisteredFilter::signature(),
+            SubwalletStatusChangedFilter::signature(),
+        ];
+
+        Filter::new().address(addresses).from_block(from).to_block(to).topic0(topics)
+    }
+
+    /// Decodifica um log dos contratos configurados; logs pendentes, de
+    /// outros contratos ou de outros eventos ficam de fora
+    pub fn decode(&self, log: &Log) -> Option<RegistryEvent> {
+        let (event_name, subject, data) = if Some(log.address) == self.proof_registry {
+            match ProofRegistryEvents::decode_log(&log.clone().into()).ok()? {
+                ProofRegistryEvents::ProofSubmittedFilter(event) => (
+                    "ProofSubmitted",
+                    format!("{:?}", H256::from(event.proof_id)),
+                    json!({ "submitter": format!("{:?}", event.submitter) }),
+                ),
+                ProofRegistryEvents::ProofVerifiedFilter(event) => (
+                    "ProofVerified",
+                    format!("{:?}", H256::from(event.proof_id)),
+                    json!({ "verifier": format!("{:?}", event.verifier) }),
+                ),
+            }
+        } else if Some(log.address) == self.subwallet_registry {
+            match SubwalletRegistryEvents::decode_log(&log.clone().into()).ok()? {
+                SubwalletRegistryEvents::SubwalletRegisteredFilter(event) => (
+                    "SubwalletRegistered",
+                    format!("{:?}", event.wallet),
+                    json!({ "name": event.name }),
+                ),
+                SubwalletRegistryEvents::SubwalletStatusChangedFilter(event) => (
+                    "SubwalletStatusChanged",
+                    format!("{:?}", event.wallet),
+                    json!({ "is_active": event.is_active }),
+                ),
+            }
+        } else {
+            return None;
+        };
+
+        Some(RegistryEvent {
+            contract_address: log.address,
+            event_name,
+            subject,
+            data,
+            block_number: log.block_number?.as_u64(),
+            block_hash: log.block_hash?,
+            transaction_hash: log.transaction_hash?,
+            log_index: log.log_index?.as_u64(),
+        })
+    }
+}
+
+/// Evento decodificado de um log, ainda não gravado
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegistryEvent {
+    pub contract_address: Address,
+    pub event_name: &'static str,
+    pub subject: String,
+    pub data: serde_json::Value,
+    pub block_number: u64,
+    pub block_hash: H256,
+    pub transaction_hash: H256,
+    pub log_index: u64,
+}
+
+/// Filtros de `GET /events`
+#[derive(Debug, Default, Deserialize)]
+pub struct EventQuery {
+    pub chain_id: Option<i64>,
+    pub event: Option<String>,
+    /// proofId ou carteira
+    pub subject: Option<String>,
+    pub from_block: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+/// Segue os blocos finalizados de uma rede e grava os eventos dos
+/// registries, desfazendo o que foi indexado depois do ponto de divergência
+/// numa reorganização
+#[derive(Clone)]
+pub struct EventIndexer {
+    pool: PgPool,
+    chains: Arc<ChainRegistry>,
+    config: IndexerConfig,
+    /// Confirmações exigidas nas redes sem `CHAIN_<id>_FINALITY_DEPTH`
+    default_depth: u64,
+}
+
+impl EventIndexer {
+    pub fn new(
+        pool: &PgPool,
+        chains: Arc<ChainRegistry>,
+        config: IndexerConfig,
+        default_depth: u64,
+    ) -> Result<Self, IndexerError> {
+        if chains.get(config.chain_id).is_none() {
+            return Err(IndexerError::Config(format!("rede {} não está em BLOCKCHAIN_CHAINS", config.chain_id)));
+        }
+
+        Ok(Self {
+            pool: pool.clone(),
+            chains,
+            config,
+            default_depth,
+        })
+    }
+
+    fn chain(&self) -> &Chain {
+        self.chains.get(self.config.chain_id).expect("rede conferida em EventIndexer::new")
+    }
+
+    /// Inicia a tarefa de indexação; atrasada em relação à rede, ela segue
+    /// lote a lote sem esperar
+    pub fn start(&self) {
+        let indexer = self.clone();
+        tokio::spawn(async move {
+            loop {
+                let remaining = match indexer.index_next_batch().await {
+                    Ok(remaining) => remaining,
+                    Err(e) => {
+                        error!("Falha ao indexar eventos da rede {}: {}", indexer.config.chain_id, e);
+                        0
+                    }
+                };
+                if remaining == 0 {
+                    tokio::time::sleep(indexer.config.poll_interval).await;
+                }
+            }
+        });
+
+        info!(
+            "Indexador de eventos iniciado na rede {} a partir do bloco {}",
+            self.config.chain_id, self.config.from_block
+        );
+    }
+
+    /// Confere o checkpoint contra a rede (desfazendo uma reorganização, se
+    /// houver) e indexa o próximo lote de blocos já finalizados. Devolve
+    /// quantos blocos faltam.
+    pub async fn index_next_batch(&self) -> Result<u64, IndexerError> {
+        let chain = self.chain();
+        let chain_id = self.config.chain_id as i64;
+        let latest = chain
+            .call(|provider| async move { provider.get_block_number().await })
+            .await?
+            .as_u64();
+        // O último bloco conta como a primeira confirmação
+        let depth = self.chain().finality_depth.unwrap_or(self.default_depth);
+        let finalized = latest.saturating_sub(depth.saturating_sub(1));
+
+        let checkpoint = sqlx::query_as!(
+            IndexerCheckpoint,
+            "SELECT chain_id, block_number, block_hash, updated_at FROM indexer_checkpoints WHERE chain_id = $1",
+            chain_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let next = match checkpoint {
+            Some(checkpoint) => match block_hash(chain, checkpoint.block_number as u64).await? {
+                Some(hash) if format!("{:?}", hash) == checkpoint.block_hash => checkpoint.block_number as u64 + 1,
+                // O nó ainda não chegou ao checkpoint (ex.: failover para um RPC atrasado)
+                None => return Ok(0),
+                Some(_) => {
+                    self.roll_back().await?;
+                    return Ok(1);
+                }
+            },
+            None => self.config.from_block,
+        };
+        if next > finalized {
+            return Ok(0);
+        }
+
+        let to = finalized.min(next + self.config.batch_blocks - 1);
+        let events = fetch_events(chain, &self.config, next, to).await?;
+
+        // Os hashes canônicos são lidos depois dos logs: um evento de um bloco
+        // trocado entre as duas consultas não é gravado, e o lote é refeito
+        let mut canonical = BTreeMap::new();
+        for number in events.iter().map(|event| event.block_number).chain([to]) {
+            if let Entry::Vacant(entry) = canonical.entry(number) {
+                match block_hash(chain, number).await? {
+                    Some(hash) => entry.insert(hash),
+                    None => return Ok(0),
+                };
+            }
+        }
+        if let Some(event) = off_canonical(&events, &canonical) {
+            warn!(
+                "Rede {}: evento no bloco {} fora da cadeia canônica ({:?}); lote refeito na próxima passada",
+                chain_id, event.block_number, event.block_hash
+            );
+            return Ok(0);
+        }
+        self.store(&events, to, canonical[&to]).await?;
+
+        if !events.is_empty() {
+            info!("Rede {}: {} eventos indexados até o bloco {}", chain_id, events.len(), to);
+        }
+        Ok(finalized - to)
+    }
+
+    /// Desfaz tudo o que foi indexado depois do último bloco salvo que ainda
+    /// está na cadeia canônica; sem nenhum, recomeça de `from_block`
+    async fn roll_back(&self) -> Result<(), IndexerError> {
+        let chain_id = self.config.chain_id as i64;
+        let stored = sqlx::query!(
+            "SELECT block_number, block_hash FROM indexed_blocks WHERE chain_id = $1 ORDER BY block_number DESC",
+            chain_id
+        )
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(|row| (row.block_number as u64, row.block_hash))
+        .collect::<Vec<_>>();
+
+        let fork = find_fork_point(self.chain(), &stored).await?;
+        let keep_through = fork.as_ref().map_or(-1, |(number, _)| *number as i64);
+
+        let mut tx = self.pool.begin().await?;
+        let removed = sqlx::query!(
+            "DELETE FROM chain_events WHERE chain_id = $1 AND block_number > $2",
+            chain_id,
+            keep_through
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        sqlx::query!(
+            "DELETE FROM indexed_blocks WHERE chain_id = $1 AND block_number > $2",
+            chain_id,
+            keep_through
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        match &fork {
+            Some((number, hash)) => {
+                sqlx::query!(
+                    "UPDATE indexer_checkpoints SET block_number = $2, block_hash = $3, updated_at = NOW() WHERE chain_id = $1",
+                    chain_id,
+                    *number as i64,
+                    hash
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
+            None => {
+                sqlx::query!("DELETE FROM indexer_checkpoints WHERE chain_id = $1", chain_id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+        tx.commit().await?;
+
+        warn!(
+            "Reorganização na rede {}: {} eventos depois do bloco {} desfeitos",
+            chain_id, removed, keep_through
+        );
+        Ok(())
+    }
+
+    /// Grava os eventos do lote, os hashes dos blocos vistos e o novo
+    /// checkpoint numa única transação
+    async fn store(&self, events: &[RegistryEvent], to: u64, to_hash: H256) -> Result<(), IndexerError> {
+        let chain_id = self.config.chain_id as i64;
+        let mut blocks = events
+            .iter()
+            .map(|event| (event.block_number, event.block_hash))
+            .collect::<BTreeMap<_, _>>();
+        blocks.insert(to, to_hash);
+
+        let mut tx = self.pool.begin().await?;
+        for event in events {
+            sqlx::query!(
+                "INSERT INTO chain_events (chain_id, contract_address, event_name, subject, data, block_number, \
+                block_hash, transaction_hash, log_index) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) \
+                ON CONFLICT (chain_id, transaction_hash, log_index) DO NOTHING",
+                chain_id,
+                format!("{:?}", event.contract_address),
+                event.event_name,
+                event.subject,
+                event.data,
+                event.block_number as i64,
+                format!("{:?}", event.block_hash),
+                format!("{:?}", event.transaction_hash),
+                event.log_index as i64
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        for (number, hash) in &blocks {
+            sqlx::query!(
+                "INSERT INTO indexed_blocks (chain_id, block_number, block_hash) VALUES ($1, $2, $3) \
+                ON CONFLICT (chain_id, block_number) DO UPDATE SET block_hash = EXCLUDED.block_hash",
+                chain_id,
+                *number as i64,
+                format!("{:?}", hash)
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        sqlx::query!(
+            "INSERT INTO indexer_checkpoints (chain_id, block_number, block_hash) VALUES ($1, $2, $3) \
+            ON CONFLICT (chain_id) DO UPDATE SET block_number = EXCLUDED.block_number, \
+            block_hash = EXCLUDED.block_hash, updated_at = NOW()",
+            chain_id,
+            to as i64,
+            format!("{:?}", to_hash)
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+}
+
+/// Eventos indexados, do mais antigo ao mais recente
+pub async fn query_events(pool: &PgPool, query: &EventQuery) -> Result<Vec<ChainEvent>, sqlx::Error> {
+    // Endereços e proofIds são gravados em hexadecimal minúsculo
+    let subject = query.subject.as_ref().map(|subject| subject.to_lowercase());
+    let limit = query.limit.unwrap_or(DEFAULT_QUERY_LIMIT).clamp(1, MAX_QUERY_LIMIT);
+
+    sqlx::query_as!(
+        ChainEvent,
+        "SELECT id, chain_id, contract_address, event_name, subject, data, block_number, block_hash, \
+        transaction_hash, log_index, indexed_at FROM chain_events \
+        WHERE ($1::BIGINT IS NULL OR chain_id = $1) AND ($2::TEXT IS NULL OR event_name = $2) \
+        AND ($3::TEXT IS NULL OR subject = $3) AND ($4::BIGINT IS NULL OR block_number >= $4) \
+        ORDER BY block_number, log_index LIMIT $5",
+        query.chain_id,
+        query.event,
+        subject,
+        query.from_block,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Checkpoints de todas as redes indexadas
+pub async fn indexer_checkpoints(pool: &PgPool) -> Result<Vec<IndexerCheckpoint>, sqlx::Error> {
+    sqlx::query_as!(
+        IndexerCheckpoint,
+        "SELECT chain_id, block_number, block_hash, updated_at FROM indexer_checkpoints ORDER BY chain_id"
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Logs dos registries num intervalo de blocos, já decodificados
+pub async fn fetch_events(
+    chain: &Chain,
+    config: &IndexerConfig,
+    from: u64,
+    to: u64,
+) -> Result<Vec<RegistryEvent>, ProviderError> {
+    let filter = config.filter(from, to);
+    let logs = chain
+        .call(|provider| {
+            let filter = filter.clone();
+            async move { provider.get_logs(&filter).await }
+        })
+        .await?;
+
+    // Logs marcados como removidos vêm de uma reorganização em curso; o
+    // checkpoint divergente é tratado na próxima passada
+    Ok(logs
+        .iter()
+        .filter(|log| log.removed != Some(true))
+        .filter_map(|log| config.decode(log))
+        .collect())
+}
+
+/// Primeiro evento cujo bloco não tem o hash canônico da sua altura
+pub fn off_canonical<'a>(events: &'a [RegistryEvent], canonical: &BTreeMap<u64, H256>) -> Option<&'a RegistryEvent> {
+    events
+        .iter()
+        .find(|event| canonical.get(&event.block_number) != Some(&event.block_hash))
+}
+
+/// Último bloco salvo (em ordem decrescente) cujo hash ainda é o da cadeia
+/// canônica: o ponto de divergência de uma reorganização
+pub async fn find_fork_point(
+    chain: &Chain,
+    stored: &[(u64, String)],
+) -> Result<Option<(u64, String)>, ProviderError> {
+    for (number, hash) in stored {
+        if let Some(canonical) = block_hash(chain, *number).await? {
+            if format!("{:?}", canonical) == *hash {
+                return Ok(Some((*number, hash.clone())));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+async fn block_hash(chain: &Chain, number: u64) -> Result<Option<H256>, ProviderError> {
+    let block = chain
+        .call(|provider| async move { provider.get_block(number).await })
+        .await?;
+
+    Ok(block.and_then(|block| block.hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::ChainConfig;
+    use ethers::abi::{encode, Token};
+    use ethers::middleware::SignerMiddleware;
+    use ethers::providers::{Http, Provider};
+    use ethers::signers::{LocalWallet, Signer};
+    use ethers::types::{U256, U64};
+
+    fn config() -> IndexerConfig {
+        IndexerConfig {
+            chain_id: 31337,
+            proof_registry: Some(Address::repeat_byte(0x11)),
+            subwallet_registry: Some(Address::repeat_byte(0x22)),
+            from_block: 0,
+            batch_blocks: DEFAULT_BATCH_BLOCKS,
+            poll_interval: Duration::from_secs(DEFAULT_POLL_INTERVAL_SECS),
+        }
+    }
+
+    fn log(address: Address, topics: Vec<H256>, data: Vec<u8>) -> Log {
+        Log {
+            address,
+            topics,
+            data: data.into(),
+            block_number: Some(U64::from(42)),
+            block_hash: Some(H256::repeat_byte(0xbb)),
+            transaction_hash: Some(H256::repeat_byte(0xcc)),
+            log_index: Some(3.into()),
+            ..Default::default()
+        }
+    }
+
+    fn address_topic(address: Address) -> H256 {
+        H256::from(address)
+    }
+
+    #[test]
+    fn test_decodes_proof_submitted() {
+        let config = config();
+        let submitter = Address::repeat_byte(0x33);
+        let log = log(
+            Address::repeat_byte(0x11),
+            vec![ProofSubmittedFilter::signature(), H256::repeat_byte(0xaa), address_topic(submitter)],
+            Vec::new(),
+        );
+
+        let event = config.decode(&log).unwrap();
+        assert_eq!(event.event_name, "ProofSubmitted");
+        assert_eq!(event.subject, format!("{:?}", H256::repeat_byte(0xaa)));
+        assert_eq!(event.data, json!({ "submitter": format!("{:?}", submitter) }));
+        assert_eq!(event.block_number, 42);
+        assert_eq!(event.log_index, 3);
+    }
+
+    #[test]
+    fn test_decodes_subwallet_events() {
+        let config = config();
+        let wallet = Address::repeat_byte(0x44);
+
+        let registered = log(
+            Address::repeat_byte(0x22),
+            vec![SubwalletRegisteredFilter::signature(), address_topic(wallet)],
+            encode(&[Token::String("tesouraria".to_string())]),
+        );
+        let event = config.decode(&registered).unwrap();
+        assert_eq!(event.event_name, "SubwalletRegistered");
+        assert_eq!(event.subject, format!("{:?}", wallet));
+        assert_eq!(event.data, json!({ "name": "tesouraria" }));
+
+        let status = log(
+            Address::repeat_byte(0x22),
+            vec![SubwalletStatusChangedFilter::signature(), address_topic(wallet)],
+            encode(&[Token::Bool(false)]),
+        );
+        assert_eq!(config.decode(&status).unwrap().data, json!({ "is_active": false }));
+    }
+
+    #[test]
+    fn test_ignores_other_contracts_and_pending_logs() {
+        let config = config();
+        let topics = vec![ProofVerifiedFilter::signature(), H256::repeat_byte(0xaa), address_topic(Address::zero())];
+
+        // Mesmo evento emitido por outro contrato
+        assert_eq!(config.decode(&log(Address::repeat_byte(0x99), topics.clone(), Vec::new())), None);
+        // Evento de prova no endereço do SubwalletRegistry
+        assert_eq!(config.decode(&log(Address::repeat_byte(0x22), topics.clone(), Vec::new())), None);
+
+        let mut pending = log(Address::repeat_byte(0x11), topics, Vec::new());
+        pending.block_hash = None;
+        assert_eq!(config.decode(&pending), None);
+    }
+
+    #[test]
+    fn test_finds_events_off_the_canonical_chain() {
+        let config = config();
+        let topics = vec![ProofVerifiedFilter::signature(), H256::repeat_byte(0xaa), address_topic(Address::zero())];
+        let event = config.decode(&log(Address::repeat_byte(0x11), topics, Vec::new())).unwrap();
+        let mut orphan = event.clone();
+        orphan.block_hash = H256::repeat_byte(0xdd);
+
+        let canonical = BTreeMap::from([(42, H256::repeat_byte(0xbb))]);
+        assert_eq!(off_canonical(std::slice::from_ref(&event), &canonical), None);
+        // Log de um bloco trocado numa reorganização entre as consultas
+        assert_eq!(off_canonical(&[event.clone(), orphan.clone()], &canonical), Some(&orphan));
+        // Bloco sem hash canônico conhecido
+        assert_eq!(off_canonical(&[event], &BTreeMap::new()).map(|event| event.block_number), Some(42));
+    }
+
+    abigen!(
+        DevSubwalletRegistry,
+        r#"[
+            function registerSubwallet(address wallet, string name)
+        ]"#
+    );
+
+    /// Primeira conta padrão do anvil e do `hardhat node`
+    const DEV_PRIVATE_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    const DEV_CHAIN_ID: u64 = 31337;
+
+    /// Contra um nó local (`anvil` ou `npx hardhat node`) com os contratos
+    /// implantados por `npx hardhat run scripts/deploy.ts --network localhost`:
+    ///
+    /// `INDEXER_TEST_SUBWALLET_REGISTRY=0x... cargo test -p blockchain-service indexer -- --ignored`
+    #[tokio::test]
+    #[ignore]
+    async fn test_indexes_and_detects_reorg_on_dev_chain() {
+        let rpc_url = std::env::var("INDEXER_TEST_RPC_URL").unwrap_or_else(|_| "http://localhost:8545".to_string());
+        let registry_address: Address = std::env::var("INDEXER_TEST_SUBWALLET_REGISTRY")
+            .expect("INDEXER_TEST_SUBWALLET_REGISTRY deve ter o endereço do SubwalletRegistry")
+            .parse()
+            .unwrap();
+
+        let chains = ChainRegistry::connect(&[ChainConfig {
+            chain_id: DEV_CHAIN_ID,
+            name: "dev".to_string(),
+            rpc_urls: vec![rpc_url.clone()],
+            finality_depth: None,
+        }])
+        .await
+        .unwrap();
+        let chain = chains.get(DEV_CHAIN_ID).unwrap();
+        let config = IndexerConfig {
+            chain_id: DEV_CHAIN_ID,
+            proof_registry: None,
+            subwallet_registry: Some(registry_address),
+            ..config()
+        };
+
+        let provider = Provider::<Http>::try_from(rpc_url.as_str()).unwrap();
+        let signer = DEV_PRIVATE_KEY.parse::<LocalWallet>().unwrap().with_chain_id(DEV_CHAIN_ID);
+        let registry = DevSubwalletRegistry::new(registry_address, Arc::new(SignerMiddleware::new(provider.clone(), signer)));
+
+        // O snapshot desfaz o registro no fim, então o teste pode ser repetido
+        let snapshot: U256 = provider.request("evm_snapshot", ()).await.unwrap();
+        let wallet = Address::random();
+        let call = registry.register_subwallet(wallet, "indexer".to_string());
+        call.send().await.unwrap().await.unwrap();
+        let block = provider.get_block_number().await.unwrap().as_u64();
+
+        let events = fetch_events(chain, &config, block, block).await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_name, "SubwalletRegistered");
+        assert_eq!(events[0].subject, format!("{:?}", wallet));
+        assert_eq!(events[0].data, json!({ "name": "indexer" }));
+
+        let mut stored = Vec::new();
+        for number in [block, block - 1] {
+            stored.push((number, format!("{:?}", block_hash(chain, number).await.unwrap().unwrap())));
+        }
+        assert_eq!(find_fork_point(chain, &stored).await.unwrap(), Some(stored[0].clone()));
+
+        // Reorganização: o bloco do registro é trocado por um vazio na mesma altura
+        let _: bool = provider.request("evm_revert", [snapshot]).await.unwrap();
+        let _: serde_json::Value = provider.request("evm_mine", ()).await.unwrap();
+
+        assert_eq!(find_fork_point(chain, &stored).await.unwrap(), Some(stored[1].clone()));
+        assert!(fetch_events(chain, &config, block, block).await.unwrap().is_empty());
+    }
+}
@@ -1,3 +1,21 @@
+mod assets;
 mod blockchain_service;
+mod chain_client;
+mod chains;
+mod confirmation;
+mod decoder;
+mod indexer;
+mod mpt;
+mod repository;
+mod signer;
 
-pub use blockchain_service::*; 
\ No newline at end of file
+pub use assets::*;
+pub use blockchain_service::*;
+pub use chain_client::*;
+pub use chains::*;
+pub use confirmation::*;
+pub use decoder::*;
+pub use indexer::*;
+pub use mpt::*;
+pub use repository::*;
+pub use signer::*;
\ No newline at end of file
@@ -0,0 +1,327 @@
+//! Verificação de provas de Merkle-Patricia (`eth_getProof`, EIP-1186) contra
+//! uma raiz de estado, sem confiar nos valores informados pelo nó.
+
+use ethers::types::{Address, Bytes, H256, U256};
+use ethers::utils::keccak256;
+use ethers::utils::rlp::{DecoderError, Rlp};
+
+/// Raiz de uma trie vazia: `keccak256(rlp(""))`
+pub const EMPTY_TRIE_ROOT: H256 = H256([
+    0x56, 0xe8, 0x1f, 0x17, 0x1b, 0xcc, 0x55, 0xa6, 0xff, 0x83, 0x45, 0xe6, 0x92, 0xc0, 0xf8, 0x6e, 0x5b, 0x48, 0xe0,
+    0x1b, 0x99, 0x6c, 0xad, 0xc0, 0x01, 0x62, 0x2f, 0xb5, 0xe3, 0x63, 0xb4, 0x21,
+]);
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum ProofError {
+    #[error("Nó {0} da prova não confere com o hash esperado")]
+    HashMismatch(usize),
+    #[error("Nó {0} da prova não é RLP válido: {1}")]
+    Rlp(usize, DecoderError),
+    #[error("Nó {0} da prova não é um nó de trie")]
+    InvalidNode(usize),
+    #[error("Prova incompleta")]
+    Incomplete,
+    #[error("Prova com nós além do caminho da chave")]
+    TrailingNodes,
+    #[error("Conta inválida na prova: {0}")]
+    InvalidAccount(String),
+}
+
+/// Referência ao próximo nó: hash de um nó da prova ou nó embutido (< 32 bytes)
+enum Next<'a> {
+    Hash(H256),
+    Inline(Rlp<'a>),
+}
+
+/// Percorre a prova pelo caminho `keccak256(key)` (trie segura) e devolve o
+/// valor da folha, ou `None` se a prova mostra que a chave não existe
+pub fn verify_proof(root: H256, key: &[u8], proof: &[Bytes]) -> Result<Option<Vec<u8>>, ProofError> {
+    let path = nibbles(&keccak256(key));
+    let mut path = path.as_slice();
+
+    if root == EMPTY_TRIE_ROOT && proof.is_empty() {
+        return Ok(None);
+    }
+
+    let mut index = 0;
+    let mut next = Next::Hash(root);
+    loop {
+        let node = match next {
+            Next::Hash(hash) => {
+                let encoded = proof.get(index).ok_or(ProofError::Incomplete)?;
+                if H256(keccak256(encoded)) != hash {
+                    return Err(ProofError::HashMismatch(index));
+                }
+                index += 1;
+                Rlp::new(encoded)
+            }
+            Next::Inline(node) => node,
+        };
+        let at = index.saturating_sub(1);
+        let rlp_error = |e| ProofError::Rlp(at, e);
+
+        let child = match node.item_count().map_err(rlp_error)? {
+            17 => match path.split_first() {
+                Some((&nibble, rest)) => {
+                    path = rest;
+                    node.at(nibble as usize).map_err(rlp_error)?
+                }
+                // Chaves da trie segura têm sempre 64 nibbles
+                None => return Err(ProofError::InvalidNode(at)),
+            },
+            2 => {
+                let (is_leaf, partial) = decode_compact(node.at(0).map_err(rlp_error)?.data().map_err(rlp_error)?)
+                    .ok_or(ProofError::InvalidNode(at))?;
+                if is_leaf {
+                    if partial != path {
+                        return finish(proof, index, None);
+                    }
+                    let value = node.at(1).and_then(|value| value.data().map(<[u8]>::to_vec)).map_err(rlp_error)?;
+                    return finish(proof, index, Some(value));
+                }
+                if !path.starts_with(&partial) {
+                    return finish(proof, index, None);
+                }
+                path = &path[partial.len()..];
+                node.at(1).map_err(rlp_error)?
+            }
+            _ => return Err(ProofError::InvalidNode(at)),
+        };
+
+        next = if child.is_list() {
+            Next::Inline(child)
+        } else {
+            match child.data().map_err(rlp_error)? {
+                [] => return finish(proof, index, None),
+                hash if hash.len() == 32 => Next::Hash(H256::from_slice(hash)),
+                _ => return Err(ProofError::InvalidNode(at)),
+            }
+        };
+    }
+}
+
+/// Aceita o resultado só se a prova terminou no último nó percorrido
+fn finish<T>(proof: &[Bytes], used: usize, value: T) -> Result<T, ProofError> {
+    if used < proof.len() {
+        return Err(ProofError::TrailingNodes);
+    }
+    Ok(value)
+}
+
+fn nibbles(bytes: &[u8]) -> Vec<u8> {
+    bytes.iter().flat_map(|byte| [byte >> 4, byte & 0x0f]).collect()
+}
+
+/// Caminho em hex-prefix: `(é folha, nibbles)`
+fn decode_compact(encoded: &[u8]) -> Option<(bool, Vec<u8>)> {
+    let (&first, rest) = encoded.split_first()?;
+    let flag = first >> 4;
+    if flag > 3 {
+        return None;
+    }
+
+    let mut path = Vec::with_capacity(rest.len() * 2 + 1);
+    // Comprimento ímpar: o primeiro nibble vem junto com a flag
+    if flag & 1 == 1 {
+        path.push(first & 0x0f);
+    } else if first & 0x0f != 0 {
+        return None;
+    }
+    path.extend(nibbles(rest));
+
+    Some((flag & 2 == 2, path))
+}
+
+/// Conta provada na trie de estado; uma conta inexistente tem tudo zerado
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProvenAccount {
+    pub nonce: U256,
+    pub balance: U256,
+    pub storage_root: H256,
+    pub code_hash: H256,
+}
+
+impl ProvenAccount {
+    fn empty() -> Self {
+        Self {
+            nonce: U256::zero(),
+            balance: U256::zero(),
+            storage_root: EMPTY_TRIE_ROOT,
+            code_hash: H256(keccak256([])),
+        }
+    }
+}
+
+/// Verifica a prova de conta contra a raiz de estado do bloco
+pub fn verify_account(state_root: H256, address: Address, proof: &[Bytes]) -> Result<ProvenAccount, ProofError> {
+    let Some(encoded) = verify_proof(state_root, address.as_bytes(), proof)? else {
+        return Ok(ProvenAccount::empty());
+    };
+
+    let invalid = |e: DecoderError| ProofError::InvalidAccount(e.to_string());
+    let account = Rlp::new(&encoded);
+    if account.item_count().map_err(invalid)? != 4 {
+        return Err(ProofError::InvalidAccount("esperados 4 campos".to_string()));
+    }
+    let hash = |index: usize| -> Result<H256, ProofError> {
+        let bytes = account.at(index).and_then(|item| item.data().map(<[u8]>::to_vec)).map_err(invalid)?;
+        (bytes.len() == 32)
+            .then(|| H256::from_slice(&bytes))
+            .ok_or_else(|| ProofError::InvalidAccount("hash com tamanho inválido".to_string()))
+    };
+
+    Ok(ProvenAccount {
+        nonce: account.val_at(0).map_err(invalid)?,
+        balance: account.val_at(1).map_err(invalid)?,
+        storage_root: hash(2)?,
+        code_hash: hash(3)?,
+    })
+}
+
+/// Verifica a prova de um slot contra a raiz de storage da conta; um slot
+/// ausente vale zero
+pub fn verify_storage(storage_root: H256, slot: H256, proof: &[Bytes]) -> Result<U256, ProofError> {
+    match verify_proof(storage_root, slot.as_bytes(), proof)? {
+        Some(encoded) => Rlp::new(&encoded).as_val().map_err(|e| ProofError::Rlp(proof.len() - 1, e)),
+        None => Ok(U256::zero()),
+    }
+}
+
+/// Slot de `mapping(address => uint256)[key]` para o mapping declarado em `slot` (layout do Solidity)
+pub fn mapping_slot(key: Address, slot: U256) -> H256 {
+    let mut preimage = [0u8; 64];
+    preimage[12..32].copy_from_slice(key.as_bytes());
+    slot.to_big_endian(&mut preimage[32..]);
+    H256(keccak256(preimage))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::utils::rlp::RlpStream;
+
+    fn compact(path: &[u8], leaf: bool) -> Vec<u8> {
+        let flag = if leaf { 2 } else { 0 } + (path.len() % 2) as u8;
+        let (first, rest) = if path.len() % 2 == 1 {
+            (flag << 4 | path[0], &path[1..])
+        } else {
+            (flag << 4, path)
+        };
+        std::iter::once(first)
+            .chain(rest.chunks(2).map(|pair| pair[0] << 4 | pair[1]))
+            .collect()
+    }
+
+    fn leaf(path: &[u8], value: &[u8]) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(2);
+        stream.append(&compact(path, true)).append(&value.to_vec());
+        stream.out().to_vec()
+    }
+
+    fn branch(children: &[(u8, H256)]) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(17);
+        for nibble in 0..16u8 {
+            match children.iter().find(|(at, _)| *at == nibble) {
+                Some((_, hash)) => stream.append(&hash.as_bytes().to_vec()),
+                None => stream.append_empty_data(),
+            };
+        }
+        stream.append_empty_data();
+        stream.out().to_vec()
+    }
+
+    fn hash(node: &[u8]) -> H256 {
+        H256(keccak256(node))
+    }
+
+    /// Trie com duas chaves de primeiro nibble diferente: um ramo e duas folhas
+    fn two_leaf_trie(a: &[u8], b: &[u8]) -> (H256, Vec<Bytes>, Vec<Bytes>) {
+        let (path_a, path_b) = (nibbles(&keccak256(a)), nibbles(&keccak256(b)));
+        assert_ne!(path_a[0], path_b[0]);
+
+        let leaf_a = leaf(&path_a[1..], b"valor a com mais de trinta e dois bytes");
+        let leaf_b = leaf(&path_b[1..], b"valor b com mais de trinta e dois bytes");
+        let root = branch(&[(path_a[0], hash(&leaf_a)), (path_b[0], hash(&leaf_b))]);
+
+        (
+            hash(&root),
+            vec![root.clone().into(), leaf_a.into()],
+            vec![root.into(), leaf_b.into()],
+        )
+    }
+
+    #[test]
+    fn test_single_leaf_trie() {
+        let node = leaf(&nibbles(&keccak256(b"chave")), b"valor");
+        let root = hash(&node);
+
+        assert_eq!(verify_proof(root, b"chave", &[node.clone().into()]), Ok(Some(b"valor".to_vec())));
+        // A mesma folha prova a ausência de outra chave
+        assert_eq!(verify_proof(root, b"outra", &[node.into()]), Ok(None));
+        assert_eq!(verify_proof(EMPTY_TRIE_ROOT, b"chave", &[]), Ok(None));
+    }
+
+    #[test]
+    fn test_branch_proofs() {
+        let (a, b) = (b"a".as_slice(), b"b".as_slice());
+        let (root, proof_a, proof_b) = two_leaf_trie(a, b);
+
+        assert_eq!(verify_proof(root, a, &proof_a).unwrap().unwrap(), b"valor a com mais de trinta e dois bytes");
+        assert_eq!(verify_proof(root, b, &proof_b).unwrap().unwrap(), b"valor b com mais de trinta e dois bytes");
+
+        // Prova de `b` apresentada para `a`: a folha não corresponde ao caminho
+        assert_eq!(verify_proof(root, a, &proof_b), Err(ProofError::HashMismatch(1)));
+        // Sem a folha
+        assert_eq!(verify_proof(root, a, &proof_a[..1]), Err(ProofError::Incomplete));
+    }
+
+    #[test]
+    fn test_rejects_tampered_proof() {
+        let node = leaf(&nibbles(&keccak256(b"chave")), b"valor");
+        let root = hash(&node);
+        let forged = leaf(&nibbles(&keccak256(b"chave")), b"outro valor");
+
+        assert_eq!(verify_proof(root, b"chave", &[forged.into()]), Err(ProofError::HashMismatch(0)));
+        assert_eq!(
+            verify_proof(root, b"chave", &[node.clone().into(), node.into()]),
+            Err(ProofError::TrailingNodes)
+        );
+    }
+
+    #[test]
+    fn test_account_and_storage() {
+        let token = Address::repeat_byte(0xac);
+        let holder = Address::repeat_byte(0x01);
+        let slot = mapping_slot(holder, U256::zero());
+
+        let storage_leaf = leaf(&nibbles(&keccak256(slot)), &ethers::utils::rlp::encode(&U256::from(1500u64)));
+        let storage_root = hash(&storage_leaf);
+
+        let mut account = RlpStream::new_list(4);
+        account
+            .append(&U256::one())
+            .append(&U256::zero())
+            .append(&storage_root.as_bytes().to_vec())
+            .append(&H256::repeat_byte(0xcd).as_bytes().to_vec());
+        let account_leaf = leaf(&nibbles(&keccak256(token)), &account.out());
+        let state_root = hash(&account_leaf);
+
+        let proven = verify_account(state_root, token, &[account_leaf.into()]).unwrap();
+        assert_eq!(proven.storage_root, storage_root);
+        assert_eq!(proven.nonce, U256::one());
+        assert_eq!(verify_storage(storage_root, slot, &[storage_leaf.clone().into()]), Ok(U256::from(1500u64)));
+
+        // Outro titular: a mesma folha prova que o slot dele está vazio
+        let other = mapping_slot(Address::repeat_byte(0x02), U256::zero());
+        assert_eq!(verify_storage(storage_root, other, &[storage_leaf.into()]), Ok(U256::zero()));
+        assert_eq!(verify_account(EMPTY_TRIE_ROOT, token, &[]).unwrap(), ProvenAccount::empty());
+    }
+
+    #[test]
+    fn test_mapping_slot() {
+        // keccak256(abi.encode(address(1), uint256(0)))
+        let expected: H256 = "0xada5013122d395ba3c54772283fb069b10426056ef8ca54750cb9bb552a59e7d".parse().unwrap();
+        assert_eq!(mapping_slot(Address::from_low_u64_be(1), U256::zero()), expected);
+        assert_eq!(EMPTY_TRIE_ROOT, H256(keccak256(ethers::utils::rlp::NULL_RLP)));
+    }
+}
@@ -0,0 +1,277 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use common::models::{BlockchainVerification, VerificationStatus};
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use uuid::Uuid;
+
+use crate::services::Transition;
+
+/// Armazenamento das verificações de transações
+#[async_trait]
+pub trait VerificationRepository: Send + Sync {
+    async fn user_exists(&self, user_id: Uuid) -> Result<bool, sqlx::Error>;
+
+    async fn insert(&self, verification: &BlockchainVerification) -> Result<(), sqlx::Error>;
+
+    async fn get(&self, id: Uuid) -> Result<Option<BlockchainVerification>, sqlx::Error>;
+
+    /// Verificações em aberto, das consultadas há mais tempo às mais
+    /// recentes: pendentes, reorganizadas e revertidas com menos de
+    /// `max_depth` confirmações
+    async fn list_open(&self, max_depth: u64, limit: i64) -> Result<Vec<BlockchainVerification>, sqlx::Error>;
+
+    /// Grava o novo estado de uma verificação consultada em `now`
+    async fn update(
+        &self,
+        id: Uuid,
+        transition: &Transition,
+        decoded: Option<serde_json::Value>,
+        now: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error>;
+}
+
+/// Verificações na tabela `blockchain_verifications`
+pub struct PostgresVerificationRepository {
+    pool: PgPool,
+}
+
+impl PostgresVerificationRepository {
+    pub fn new(pool: &PgPool) -> Self {
+        Self { pool: pool.clone() }
+    }
+}
+
+#[async_trait]
+impl VerificationRepository for PostgresVerificationRepository {
+    async fn user_exists(&self, user_id: Uuid) -> Result<bool, sqlx::Error> {
+        let exists = sqlx::query_scalar!(
+            r#"SELECT EXISTS(SELECT 1 FROM users WHERE id = $1) as "exists!""#,
+            user_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(exists)
+    }
+
+    async fn insert(&self, verification: &BlockchainVerification) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "INSERT INTO blockchain_verifications (id, user_id, chain_id, transaction_hash, status, block_number, \
+            block_hash, confirmations, failure_reason, last_seen_at, decoded, verified_at, created_at, updated_at) \
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)",
+            verification.id,
+            verification.user_id,
+            verification.chain_id,
+            verification.transaction_hash,
+            verification.status as _,
+            verification.block_number,
+            verification.block_hash,
+            verification.confirmations,
+            verification.failure_reason,
+            verification.last_seen_at,
+            verification.decoded,
+            verification.verified_at,
+            verification.created_at,
+            verification.updated_at
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn get(&self, id: Uuid) -> Result<Option<BlockchainVerification>, sqlx::Error> {
+        sqlx::query_as!(
+            BlockchainVerification,
+            r#"SELECT id, user_id, chain_id, transaction_hash, status as "status: _", block_number, block_hash, confirmations,
+            failure_reason, last_seen_at, decoded, verified_at, created_at, updated_at
+            FROM blockchain_verifications WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    async fn list_open(&self, max_depth: u64, limit: i64) -> Result<Vec<BlockchainVerification>, sqlx::Error> {
+        sqlx::query_as!(
+            BlockchainVerification,
+            r#"SELECT id, user_id, chain_id, transaction_hash, status as "status: _", block_number, block_hash, confirmations,
+            failure_reason, last_seen_at, decoded, verified_at, created_at, updated_at
+            FROM blockchain_verifications
+            WHERE status IN ('pending', 'reorged')
+                OR (status = 'failed' AND failure_reason = 'reverted' AND confirmations < $1)
+            ORDER BY verified_at LIMIT $2"#,
+            max_depth as i64,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    async fn update(
+        &self,
+        id: Uuid,
+        transition: &Transition,
+        decoded: Option<serde_json::Value>,
+        now: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE blockchain_verifications SET status = $2, block_number = $3, block_hash = $4, confirmations = $5, \
+            failure_reason = $6, last_seen_at = CASE WHEN $7 THEN $8 ELSE last_seen_at END, decoded = $9, \
+            verified_at = $8, updated_at = $8 WHERE id = $1",
+            id,
+            transition.status as _,
+            transition.block_number,
+            transition.block_hash,
+            transition.confirmations,
+            transition.failure_reason,
+            transition.seen,
+            now,
+            decoded
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+/// Verificações em memória, para testes e execuções sem banco. Os usuários
+/// são registrados com `add_user`.
+#[derive(Default)]
+pub struct MemoryVerificationRepository {
+    users: Mutex<HashSet<Uuid>>,
+    verifications: Mutex<HashMap<Uuid, BlockchainVerification>>,
+}
+
+impl MemoryVerificationRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_user(&self, user_id: Uuid) {
+        self.users.lock().unwrap().insert(user_id);
+    }
+}
+
+#[async_trait]
+impl VerificationRepository for MemoryVerificationRepository {
+    async fn user_exists(&self, user_id: Uuid) -> Result<bool, sqlx::Error> {
+        Ok(self.users.lock().unwrap().contains(&user_id))
+    }
+
+    async fn insert(&self, verification: &BlockchainVerification) -> Result<(), sqlx::Error> {
+        self.verifications
+            .lock()
+            .unwrap()
+            .insert(verification.id, verification.clone());
+        Ok(())
+    }
+
+    async fn get(&self, id: Uuid) -> Result<Option<BlockchainVerification>, sqlx::Error> {
+        Ok(self.verifications.lock().unwrap().get(&id).cloned())
+    }
+
+    async fn list_open(&self, max_depth: u64, limit: i64) -> Result<Vec<BlockchainVerification>, sqlx::Error> {
+        let mut open = self
+            .verifications
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|verification| match verification.status {
+                VerificationStatus::Pending | VerificationStatus::Reorged => true,
+                VerificationStatus::Failed => {
+                    verification.failure_reason.as_deref() == Some("reverted")
+                        && verification.confirmations < max_depth as i64
+                }
+                VerificationStatus::Confirmed => false,
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        open.sort_by_key(|verification| verification.verified_at);
+        open.truncate(limit.max(0) as usize);
+
+        Ok(open)
+    }
+
+    async fn update(
+        &self,
+        id: Uuid,
+        transition: &Transition,
+        decoded: Option<serde_json::Value>,
+        now: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        let mut verifications = self.verifications.lock().unwrap();
+        if let Some(verification) = verifications.get_mut(&id) {
+            verification.status = transition.status;
+            verification.block_number = transition.block_number;
+            verification.block_hash = transition.block_hash.clone();
+            verification.confirmations = transition.confirmations;
+            verification.failure_reason = transition.failure_reason.map(str::to_string);
+            if transition.seen {
+                verification.last_seen_at = Some(now);
+            }
+            verification.decoded = decoded;
+            verification.verified_at = now;
+            verification.updated_at = now;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn verification(
+        status: VerificationStatus,
+        failure_reason: Option<&str>,
+        confirmations: i64,
+    ) -> BlockchainVerification {
+        let now = Utc::now();
+        BlockchainVerification {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            chain_id: 1,
+            transaction_hash: format!("{:?}", ethers::types::H256::random()),
+            status,
+            block_number: None,
+            block_hash: None,
+            confirmations,
+            failure_reason: failure_reason.map(str::to_string),
+            last_seen_at: None,
+            decoded: None,
+            verified_at: now,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_memory_repository_lists_open_verifications() {
+        let repository = MemoryVerificationRepository::new();
+        let pending = verification(VerificationStatus::Pending, None, 0);
+        let mut reverted = verification(VerificationStatus::Failed, Some("reverted"), 2);
+        reverted.verified_at = pending.verified_at + chrono::Duration::seconds(1);
+        for stored in [
+            &pending,
+            &reverted,
+            &verification(VerificationStatus::Confirmed, None, 12),
+            &verification(VerificationStatus::Failed, Some("dropped"), 0),
+        ] {
+            repository.insert(stored).await.unwrap();
+        }
+
+        // Como na consulta do Postgres: revertidas só até a finalidade
+        let open = repository.list_open(12, 10).await.unwrap();
+        assert_eq!(
+            open.iter().map(|v| v.id).collect::<Vec<_>>(),
+            vec![pending.id, reverted.id]
+        );
+        let open = repository.list_open(2, 10).await.unwrap();
+        assert_eq!(open.iter().map(|v| v.id).collect::<Vec<_>>(), vec![pending.id]);
+    }
+}
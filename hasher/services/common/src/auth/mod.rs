@@ -11,6 +11,7 @@ pub struct Claims {
     pub service: String,
 }
 
+#[derive(Clone)]
 pub struct Auth {
     encoding_key: EncodingKey,
     decoding_key: DecodingKey,
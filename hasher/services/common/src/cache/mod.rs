@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum CacheError {
+    #[error("Cache cheio: {0} entradas")]
+    Full(usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct CacheConfig {
+    pub ttl: Duration,
+    pub max_size: usize,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            ttl: Duration::from_secs(300),
+            max_size: 1000,
+        }
+    }
+}
+
+struct CacheEntry<V> {
+    value: V,
+    expires_at: Instant,
+}
+
+/// Cache em memória com expiração por entrada
+pub struct MemoryCache<K, V> {
+    config: CacheConfig,
+    entries: RwLock<HashMap<K, CacheEntry<V>>>,
+}
+
+impl<K, V> MemoryCache<K, V>
+where
+    K: Eq + Hash,
+    V: Clone,
+{
+    pub fn new(config: CacheConfig) -> Self {
+        Self {
+            config,
+            entries: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub async fn get(&self, key: K) -> Result<Option<V>, CacheError> {
+        let entries = self.entries.read().await;
+        Ok(entries
+            .get(&key)
+            .filter(|entry| entry.expires_at > Instant::now())
+            .map(|entry| entry.value.clone()))
+    }
+
+    pub async fn set(&self, key: K, value: V) -> Result<(), CacheError> {
+        let mut entries = self.entries.write().await;
+        let now = Instant::now();
+
+        // Antes de recusar, abre espaço descartando as expiradas
+        if entries.len() >= self.config.max_size && !entries.contains_key(&key) {
+            entries.retain(|_, entry| entry.expires_at > now);
+            if entries.len() >= self.config.max_size {
+                return Err(CacheError::Full(self.config.max_size));
+            }
+        }
+
+        entries.insert(key, CacheEntry {
+            value,
+            expires_at: now + self.config.ttl,
+        });
+        Ok(())
+    }
+
+    pub async fn remove(&self, key: &K) {
+        self.entries.write().await.remove(key);
+    }
+}
@@ -1,8 +1,7 @@
-use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
-use tokio::time::sleep;
 
 #[derive(Debug)]
 pub enum CircuitBreakerState {
@@ -14,6 +13,7 @@ pub enum CircuitBreakerState {
 pub struct CircuitBreaker {
     failure_threshold: u64,
     reset_timeout: Duration,
+    #[allow(dead_code)]
     half_open_timeout: Duration,
     failure_count: AtomicU64,
     last_failure_time: RwLock<Option<Instant>>,
@@ -47,12 +47,15 @@ impl CircuitBreaker {
     }
 
     pub async fn is_closed(&self) -> bool {
-        match *self.state.read().await {
+        // Trava de escrita desde o início: a leitura seguida de escrita no
+        // mesmo estado travaria a task
+        let mut state = self.state.write().await;
+        match *state {
             CircuitBreakerState::Closed => true,
             CircuitBreakerState::Open => {
                 if let Some(last_failure) = *self.last_failure_time.read().await {
                     if last_failure.elapsed() >= self.reset_timeout {
-                        *self.state.write().await = CircuitBreakerState::HalfOpen;
+                        *state = CircuitBreakerState::HalfOpen;
                         true
                     } else {
                         false
@@ -69,12 +72,10 @@ impl CircuitBreaker {
     where
         F: FnOnce() -> Fut,
         Fut: std::future::Future<Output = Result<T, E>>,
+        E: From<std::io::Error>,
     {
         if !self.is_closed().await {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                "Circuit breaker is open",
-            )
+            return Err(std::io::Error::other("Circuit breaker is open")
             .into());
         }
 
@@ -95,6 +96,7 @@ impl CircuitBreaker {
 mod tests {
     use super::*;
     use std::error::Error;
+    use tokio::time::sleep;
 
     #[tokio::test]
     async fn test_circuit_breaker() -> Result<(), Box<dyn Error>> {
@@ -108,7 +110,7 @@ mod tests {
         // Teste falhas
         for _ in 0..3 {
             let result = cb
-                .call(|| async { Err::<i32, _>("erro".into()) })
+                .call(|| async { Err::<i32, Box<dyn Error>>("erro".into()) })
                 .await;
             assert!(result.is_err());
         }
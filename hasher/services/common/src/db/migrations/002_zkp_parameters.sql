@@ -0,0 +1,15 @@
+-- Parâmetros Groth16 persistidos por versão de circuito
+CREATE TABLE IF NOT EXISTS zkp_parameters (
+    circuit_version VARCHAR(64) PRIMARY KEY,
+    fingerprint VARCHAR(64) NOT NULL UNIQUE,
+    parameters BYTEA NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Cada prova referencia a chave com que foi gerada (NULL para provas legadas)
+ALTER TABLE zkp_proofs
+    ADD COLUMN IF NOT EXISTS key_fingerprint VARCHAR(64) REFERENCES zkp_parameters(fingerprint);
+
+CREATE INDEX IF NOT EXISTS idx_zkp_proofs_key_fingerprint ON zkp_proofs(key_fingerprint);
+
+COMMENT ON TABLE zkp_parameters IS 'Chaves de prova e verificação Groth16 por versão de circuito';
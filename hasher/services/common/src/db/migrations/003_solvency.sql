@@ -0,0 +1,17 @@
+-- Saldos verificados por usuário, na unidade mínima do ativo (até 128 bits)
+CREATE TABLE IF NOT EXISTS verified_balances (
+    user_id UUID PRIMARY KEY REFERENCES users(id),
+    balance NUMERIC(39, 0) NOT NULL CHECK (balance >= 0 AND balance < 340282366920938463463374607431768211456),
+    blinding BYTEA,
+    source VARCHAR(64) NOT NULL,
+    verified_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Entradas públicas de cada prova, necessárias para verificá-la
+ALTER TABLE zkp_proofs
+    ADD COLUMN IF NOT EXISTS public_inputs TEXT[] NOT NULL DEFAULT '{}';
+
+COMMENT ON TABLE verified_balances IS 'Saldos verificados usados nas provas de solvência';
+COMMENT ON COLUMN verified_balances.blinding IS 'Fator de blinding do compromisso com o saldo';
@@ -0,0 +1,19 @@
+-- Carteiras aprovadas (ex.: subwallets ativas do SubwalletRegistry), em minúsculas
+CREATE TABLE IF NOT EXISTS whitelisted_wallets (
+    wallet_address VARCHAR(42) PRIMARY KEY,
+    source VARCHAR(64) NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Raízes publicadas da árvore de Merkle, com as carteiras na ordem das folhas
+CREATE TABLE IF NOT EXISTS whitelist_roots (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    root VARCHAR(66) NOT NULL,
+    wallets TEXT[] NOT NULL,
+    published_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_whitelist_roots_published_at ON whitelist_roots(published_at DESC);
+
+COMMENT ON TABLE whitelisted_wallets IS 'Carteiras aprovadas para provas de pertinência';
+COMMENT ON TABLE whitelist_roots IS 'Raízes publicadas da árvore de carteiras aprovadas';
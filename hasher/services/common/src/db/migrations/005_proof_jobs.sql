@@ -0,0 +1,23 @@
+-- Fila de geração de provas; sobrevive a reinícios do serviço
+CREATE TABLE IF NOT EXISTS zkp_jobs (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id),
+    circuit_version VARCHAR(64) NOT NULL,
+    arguments JSONB NOT NULL DEFAULT '{}',
+    status VARCHAR(16) NOT NULL DEFAULT 'queued'
+        CHECK (status IN ('queued', 'running', 'succeeded', 'failed', 'cancelled')),
+    proof_id UUID REFERENCES zkp_proofs(id),
+    error_code VARCHAR(64),
+    error_message TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    started_at TIMESTAMP WITH TIME ZONE,
+    finished_at TIMESTAMP WITH TIME ZONE,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Os workers retiram da fila em ordem de chegada
+CREATE INDEX IF NOT EXISTS idx_zkp_jobs_queued ON zkp_jobs(created_at) WHERE status = 'queued';
+CREATE INDEX IF NOT EXISTS idx_zkp_jobs_user_id ON zkp_jobs(user_id);
+
+COMMENT ON TABLE zkp_jobs IS 'Jobs assíncronos de geração de provas';
+COMMENT ON COLUMN zkp_jobs.arguments IS 'Argumentos do circuito (ex.: limite da prova de solvência)';
@@ -0,0 +1,9 @@
+-- Parâmetros Groth16 do backend BN254 (verificável na EVM), por versão de circuito
+CREATE TABLE IF NOT EXISTS zkp_evm_parameters (
+    circuit_version VARCHAR(64) PRIMARY KEY,
+    fingerprint VARCHAR(64) NOT NULL UNIQUE,
+    parameters BYTEA NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+COMMENT ON TABLE zkp_evm_parameters IS 'Chaves de prova e verificação Groth16 no BN254 por versão de circuito';
@@ -0,0 +1,9 @@
+-- Provas antigas estão no formato comprimido do bellman (A‖B‖C, 192 bytes,
+-- sempre com o bit de compressão no primeiro byte). A codificação canônica é
+-- o mesmo corpo precedido de versão (0x01) e curva (0x01 = BLS12-381); provas
+-- canônicas começam com 0x01, então a atualização pode ser repetida sem efeito.
+UPDATE zkp_proofs
+SET proof_data = '\x0101'::bytea || proof_data
+WHERE length(proof_data) = 192 AND get_byte(proof_data, 0) >= 128;
+
+COMMENT ON COLUMN zkp_proofs.proof_data IS 'Prova na codificação canônica (versão, curva e pontos comprimidos)';
@@ -0,0 +1,20 @@
+-- Circuito (nome e versão semântica) de cada prova, conforme o registro de circuitos do zkp-service
+ALTER TABLE zkp_proofs
+    ADD COLUMN IF NOT EXISTS circuit_name VARCHAR(64),
+    ADD COLUMN IF NOT EXISTS circuit_version VARCHAR(32);
+
+CREATE INDEX IF NOT EXISTS idx_zkp_proofs_circuit ON zkp_proofs(circuit_name, circuit_version);
+
+-- Provas anteriores ao registro: o circuito sai da chave com que foram geradas
+UPDATE zkp_proofs p
+SET circuit_name = c.name, circuit_version = c.version
+FROM zkp_parameters k
+JOIN (VALUES
+    ('simple-v1', 'simple', '1.0.0'),
+    ('solvency-v1', 'solvency', '1.0.0'),
+    ('membership-v1', 'membership', '1.0.0')
+) AS c(parameters_key, name, version) ON c.parameters_key = k.circuit_version
+WHERE p.key_fingerprint = k.fingerprint AND p.circuit_name IS NULL;
+
+COMMENT ON COLUMN zkp_proofs.circuit_name IS 'Nome do circuito no registro (NULL para provas legadas sem chave)';
+COMMENT ON COLUMN zkp_proofs.circuit_version IS 'Versão semântica do circuito';
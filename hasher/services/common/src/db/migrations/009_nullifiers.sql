@@ -0,0 +1,22 @@
+-- Segredo de cada usuário do qual saem os nullifiers (ver circuits::Nullified no zkp-service)
+CREATE TABLE IF NOT EXISTS nullifier_secrets (
+    user_id UUID PRIMARY KEY REFERENCES users(id),
+    secret BYTEA NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Nullifiers por contexto de aplicação: gravados junto com a prova e
+-- consumidos uma única vez na verificação
+CREATE TABLE IF NOT EXISTS nullifiers (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    context VARCHAR(128) NOT NULL,
+    nullifier VARCHAR(66) NOT NULL,
+    proof_id UUID NOT NULL UNIQUE REFERENCES zkp_proofs(id),
+    consumed_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT nullifiers_context_nullifier_key UNIQUE (context, nullifier)
+);
+
+COMMENT ON TABLE nullifier_secrets IS 'Segredos dos usuários para derivar nullifiers; nunca saem do serviço';
+COMMENT ON TABLE nullifiers IS 'Nullifiers usados por contexto, para impedir a reutilização de provas';
+COMMENT ON COLUMN nullifiers.consumed_at IS 'Quando a prova foi aceita em uma verificação com o contexto (NULL enquanto não usada)';
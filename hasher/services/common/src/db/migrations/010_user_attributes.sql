@@ -0,0 +1,17 @@
+-- Atributos atestados de cada usuário, usados nas provas de predicados
+-- (ver circuits::AttributeCircuit no zkp-service)
+CREATE TABLE IF NOT EXISTS user_attributes (
+    user_id UUID PRIMARY KEY REFERENCES users(id),
+    birth_date DATE NOT NULL,
+    country CHAR(2) NOT NULL CHECK (country ~ '^[A-Z]{2}$'),
+    kyc_tier SMALLINT NOT NULL CHECK (kyc_tier BETWEEN 0 AND 255),
+    issuer VARCHAR(64) NOT NULL,
+    blinding BYTEA,
+    attested_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+COMMENT ON TABLE user_attributes IS 'Atributos atestados (nascimento, país, nível de KYC) provados sem revelá-los';
+COMMENT ON COLUMN user_attributes.country IS 'País de residência, ISO 3166-1 alfa-2';
+COMMENT ON COLUMN user_attributes.blinding IS 'Fator de blinding do compromisso; volta a NULL quando os atributos mudam';
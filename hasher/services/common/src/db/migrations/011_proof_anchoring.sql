@@ -0,0 +1,23 @@
+-- Ancoragem das provas no ZKProofRegistry: o hash de cada prova salva é
+-- submetido on-chain por um worker do zkp-service
+ALTER TABLE zkp_proofs
+    ADD COLUMN IF NOT EXISTS anchor_hash VARCHAR(66),
+    ADD COLUMN IF NOT EXISTS anchor_status VARCHAR(16) NOT NULL DEFAULT 'pending'
+        CHECK (anchor_status IN ('pending', 'submitted', 'confirmed', 'failed')),
+    ADD COLUMN IF NOT EXISTS anchor_tx_hash VARCHAR(66),
+    ADD COLUMN IF NOT EXISTS anchor_block BIGINT,
+    ADD COLUMN IF NOT EXISTS anchor_attempts INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS anchor_error TEXT,
+    ADD COLUMN IF NOT EXISTS anchor_submitted_at TIMESTAMP WITH TIME ZONE,
+    ADD COLUMN IF NOT EXISTS anchor_retry_at TIMESTAMP WITH TIME ZONE,
+    ADD COLUMN IF NOT EXISTS anchored_at TIMESTAMP WITH TIME ZONE;
+
+-- O worker só percorre as provas ainda não confirmadas
+CREATE INDEX IF NOT EXISTS idx_zkp_proofs_anchor_open ON zkp_proofs(anchor_status, created_at)
+    WHERE anchor_status <> 'confirmed';
+
+COMMENT ON COLUMN zkp_proofs.anchor_hash IS 'keccak256(abi.encode(proof_data, public_inputs)) submetido ao ZKProofRegistry';
+COMMENT ON COLUMN zkp_proofs.anchor_status IS 'Estado da ancoragem: pending, submitted, confirmed ou failed';
+COMMENT ON COLUMN zkp_proofs.anchor_tx_hash IS 'Transação submitProof que registrou a prova';
+COMMENT ON COLUMN zkp_proofs.anchor_block IS 'Bloco da transação de ancoragem';
+COMMENT ON COLUMN zkp_proofs.anchor_retry_at IS 'Próxima tentativa de uma ancoragem que falhou';
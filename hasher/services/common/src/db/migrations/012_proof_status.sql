@@ -0,0 +1,29 @@
+-- Validade e revogação das provas emitidas. `status_index` é a posição da
+-- prova na lista de status publicada em /api/v1/zkp/status-list
+ALTER TABLE zkp_proofs
+    ADD COLUMN IF NOT EXISTS valid_from TIMESTAMP WITH TIME ZONE,
+    ADD COLUMN IF NOT EXISTS valid_until TIMESTAMP WITH TIME ZONE,
+    ADD COLUMN IF NOT EXISTS status_index BIGSERIAL,
+    ADD COLUMN IF NOT EXISTS revoked_at TIMESTAMP WITH TIME ZONE,
+    ADD COLUMN IF NOT EXISTS revocation_reason VARCHAR(32)
+        CHECK (revocation_reason IN ('superseded', 'balance_changed', 'attributes_changed', 'key_compromise', 'user_request', 'fraud')),
+    ADD COLUMN IF NOT EXISTS revocation_comment TEXT;
+
+-- Provas anteriores à validade: 90 dias a partir da emissão, o padrão do serviço
+UPDATE zkp_proofs
+SET valid_from = created_at, valid_until = created_at + INTERVAL '90 days'
+WHERE valid_from IS NULL OR valid_until IS NULL;
+
+ALTER TABLE zkp_proofs
+    ALTER COLUMN valid_from SET DEFAULT CURRENT_TIMESTAMP,
+    ALTER COLUMN valid_from SET NOT NULL,
+    ALTER COLUMN valid_until SET NOT NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_zkp_proofs_status_index ON zkp_proofs(status_index);
+
+-- A verificação avulsa localiza a prova emitida pelos seus bytes
+CREATE INDEX IF NOT EXISTS idx_zkp_proofs_proof_data ON zkp_proofs USING hash (proof_data);
+
+COMMENT ON COLUMN zkp_proofs.valid_until IS 'Fim da validade: depois dele a prova é recusada na verificação';
+COMMENT ON COLUMN zkp_proofs.status_index IS 'Posição da prova na lista de status (bit 1 = revogada ou expirada)';
+COMMENT ON COLUMN zkp_proofs.revocation_reason IS 'Motivo da revogação (NULL enquanto não revogada)';
@@ -0,0 +1,27 @@
+-- Ciclo de vida das verificações de transação: o recibo é consultado e a
+-- transação acompanhada até a profundidade de finalidade
+ALTER TABLE blockchain_verifications
+    ADD COLUMN IF NOT EXISTS block_number BIGINT,
+    ADD COLUMN IF NOT EXISTS block_hash VARCHAR(66),
+    ADD COLUMN IF NOT EXISTS confirmations BIGINT NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS failure_reason VARCHAR(16),
+    ADD COLUMN IF NOT EXISTS last_seen_at TIMESTAMP WITH TIME ZONE;
+
+-- Verificações antigas: "verified" só dizia que o nó conhecia a transação,
+-- então voltam a pending para serem conferidas pelo recibo
+UPDATE blockchain_verifications SET status = 'pending' WHERE status = 'verified';
+UPDATE blockchain_verifications SET status = 'failed', failure_reason = 'not_found' WHERE status = 'invalid';
+
+ALTER TABLE blockchain_verifications DROP CONSTRAINT IF EXISTS blockchain_verifications_status_check;
+ALTER TABLE blockchain_verifications ADD CONSTRAINT blockchain_verifications_status_check
+    CHECK (status IN ('pending', 'confirmed', 'failed', 'reorged'));
+
+-- A tarefa de acompanhamento só percorre as verificações em aberto
+CREATE INDEX IF NOT EXISTS idx_blockchain_verifications_open ON blockchain_verifications(status, verified_at)
+    WHERE status <> 'confirmed';
+
+COMMENT ON COLUMN blockchain_verifications.status IS 'pending, confirmed (na profundidade de finalidade), failed ou reorged';
+COMMENT ON COLUMN blockchain_verifications.verified_at IS 'Última consulta ao nó';
+COMMENT ON COLUMN blockchain_verifications.block_hash IS 'Bloco em que a transação foi vista pela última vez';
+COMMENT ON COLUMN blockchain_verifications.failure_reason IS 'reverted, not_found ou dropped (NULL se não falhou)';
+COMMENT ON COLUMN blockchain_verifications.last_seen_at IS 'Última vez que o nó conhecia a transação (mempool ou bloco)';
@@ -0,0 +1,13 @@
+-- Verificações em várias redes: cada transação pertence a um chain id
+ALTER TABLE blockchain_verifications ADD COLUMN IF NOT EXISTS chain_id BIGINT;
+
+-- Verificações anteriores eram todas da mainnet
+UPDATE blockchain_verifications SET chain_id = 1 WHERE chain_id IS NULL;
+ALTER TABLE blockchain_verifications ALTER COLUMN chain_id SET NOT NULL;
+
+-- O mesmo hash pode existir em redes diferentes
+ALTER TABLE blockchain_verifications DROP CONSTRAINT IF EXISTS blockchain_verifications_transaction_hash_key;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_blockchain_verifications_chain_tx_hash
+    ON blockchain_verifications(chain_id, transaction_hash);
+
+COMMENT ON COLUMN blockchain_verifications.chain_id IS 'Chain id (EIP-155) da rede da transação';
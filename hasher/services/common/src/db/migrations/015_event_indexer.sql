@@ -0,0 +1,42 @@
+-- Indexador de eventos do ZKProofRegistry e do SubwalletRegistry
+
+-- Último bloco indexado de cada rede
+CREATE TABLE IF NOT EXISTS indexer_checkpoints (
+    chain_id BIGINT PRIMARY KEY,
+    block_number BIGINT NOT NULL,
+    block_hash VARCHAR(66) NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Hashes dos blocos já indexados (com eventos ou fim de lote), usados para
+-- achar o ponto de divergência numa reorganização
+CREATE TABLE IF NOT EXISTS indexed_blocks (
+    chain_id BIGINT NOT NULL,
+    block_number BIGINT NOT NULL,
+    block_hash VARCHAR(66) NOT NULL,
+    PRIMARY KEY (chain_id, block_number)
+);
+
+CREATE TABLE IF NOT EXISTS chain_events (
+    id BIGSERIAL PRIMARY KEY,
+    chain_id BIGINT NOT NULL,
+    contract_address VARCHAR(42) NOT NULL,
+    event_name VARCHAR(32) NOT NULL,
+    -- proofId dos eventos de prova, carteira dos de subwallet
+    subject VARCHAR(66) NOT NULL,
+    -- Campos decodificados que não estão em subject
+    data JSONB NOT NULL DEFAULT '{}',
+    block_number BIGINT NOT NULL,
+    block_hash VARCHAR(66) NOT NULL,
+    transaction_hash VARCHAR(66) NOT NULL,
+    log_index BIGINT NOT NULL,
+    indexed_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (chain_id, transaction_hash, log_index)
+);
+
+CREATE INDEX IF NOT EXISTS idx_chain_events_subject ON chain_events(chain_id, subject, block_number);
+CREATE INDEX IF NOT EXISTS idx_chain_events_block ON chain_events(chain_id, block_number);
+
+COMMENT ON TABLE indexer_checkpoints IS 'Último bloco indexado por rede';
+COMMENT ON TABLE indexed_blocks IS 'Hashes de blocos indexados, para detectar reorganizações';
+COMMENT ON TABLE chain_events IS 'Eventos ProofSubmitted, ProofVerified, SubwalletRegistered e SubwalletStatusChanged';
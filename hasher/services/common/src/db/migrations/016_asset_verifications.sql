@@ -0,0 +1,23 @@
+-- Saldos de ERC-20 verificados por provas de storage (eth_getProof) contra a
+-- raiz de estado do bloco, no lugar do "verified" informado pelo cliente
+CREATE TABLE IF NOT EXISTS asset_verifications (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    chain_id BIGINT NOT NULL,
+    token_address VARCHAR(42) NOT NULL,
+    holder VARCHAR(42) NOT NULL,
+    -- uint256 em decimal
+    balance VARCHAR(78) NOT NULL,
+    block_number BIGINT NOT NULL,
+    block_hash VARCHAR(66) NOT NULL,
+    state_root VARCHAR(66) NOT NULL,
+    storage_root VARCHAR(66) NOT NULL,
+    balance_slot VARCHAR(66) NOT NULL,
+    corroborating_endpoints INTEGER NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_asset_verifications_holder ON asset_verifications(chain_id, token_address, holder);
+
+COMMENT ON TABLE asset_verifications IS 'Saldos de tokens provados contra a raiz de estado de um bloco';
+COMMENT ON COLUMN asset_verifications.balance_slot IS 'Slot de storage do saldo (mapping de saldos do token)';
+COMMENT ON COLUMN asset_verifications.corroborating_endpoints IS 'RPCs que concordaram com o hash e a raiz de estado do bloco';
@@ -0,0 +1,9 @@
+-- Forma decodificada da transação (método, argumentos, eventos e
+-- transferências de tokens) segundo as ABIs conhecidas pelo serviço
+ALTER TABLE blockchain_verifications ADD COLUMN IF NOT EXISTS decoded JSONB;
+
+-- Consultas por método, evento ou token (ex.: decoded @> '{"contract": "ACMEToken"}')
+CREATE INDEX IF NOT EXISTS idx_blockchain_verifications_decoded
+    ON blockchain_verifications USING GIN (decoded jsonb_path_ops);
+
+COMMENT ON COLUMN blockchain_verifications.decoded IS 'Chamada, eventos e transferências decodificados do bloco em que a transação foi vista (NULL fora de um bloco)';
@@ -0,0 +1,13 @@
+-- Nonces do Sign-In with Ethereum (EIP-4361): cada um vale por pouco tempo e
+-- é consumido no primeiro login que o usa
+CREATE TABLE IF NOT EXISTS siwe_nonces (
+    nonce VARCHAR(64) PRIMARY KEY,
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    consumed_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_siwe_nonces_expires_at ON siwe_nonces(expires_at);
+
+COMMENT ON TABLE siwe_nonces IS 'Nonces emitidos para mensagens SIWE, de uso único';
+COMMENT ON COLUMN siwe_nonces.consumed_at IS 'Momento do login que usou o nonce; NULL enquanto disponível';
@@ -0,0 +1,57 @@
+-- Próximo nonce de cada conta de assinatura por rede. A linha é travada
+-- (SELECT ... FOR UPDATE) durante o envio, o que serializa as submissões
+-- concorrentes da mesma conta, inclusive entre instâncias do serviço
+CREATE TABLE IF NOT EXISTS signer_nonces (
+    chain_id BIGINT NOT NULL,
+    address VARCHAR(42) NOT NULL,
+    next_nonce BIGINT NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (chain_id, address)
+);
+
+-- Transações enviadas pelo serviço, uma por nonce
+CREATE TABLE IF NOT EXISTS signer_transactions (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    chain_id BIGINT NOT NULL,
+    from_address VARCHAR(42) NOT NULL,
+    nonce BIGINT NOT NULL,
+    to_address VARCHAR(42),
+    -- uint256 em decimal
+    value VARCHAR(78) NOT NULL,
+    data BYTEA NOT NULL,
+    gas_limit BIGINT NOT NULL,
+    max_fee_per_gas VARCHAR(78) NOT NULL,
+    max_priority_fee_per_gas VARCHAR(78) NOT NULL,
+    purpose VARCHAR(64) NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    tx_hash VARCHAR(66) NOT NULL,
+    replacements INTEGER NOT NULL DEFAULT 0,
+    block_number BIGINT,
+    failure_reason VARCHAR(32),
+    last_broadcast_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (chain_id, from_address, nonce)
+);
+
+CREATE INDEX IF NOT EXISTS idx_signer_transactions_pending ON signer_transactions(status) WHERE status = 'pending';
+
+-- Cada versão assinada de uma transação (a original e as substituições),
+-- já que qualquer uma delas pode ser a minerada
+CREATE TABLE IF NOT EXISTS signer_broadcasts (
+    id BIGSERIAL PRIMARY KEY,
+    transaction_id UUID NOT NULL REFERENCES signer_transactions(id),
+    tx_hash VARCHAR(66) NOT NULL UNIQUE,
+    raw_transaction BYTEA NOT NULL,
+    max_fee_per_gas VARCHAR(78) NOT NULL,
+    max_priority_fee_per_gas VARCHAR(78) NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_signer_broadcasts_transaction ON signer_broadcasts(transaction_id);
+
+COMMENT ON TABLE signer_transactions IS 'Transações assinadas e enviadas com as contas do keystore';
+COMMENT ON COLUMN signer_transactions.purpose IS 'Origem da transação (ex.: anchor, admin), para auditoria';
+COMMENT ON COLUMN signer_transactions.tx_hash IS 'Hash da última versão enviada ou da minerada';
+COMMENT ON COLUMN signer_transactions.failure_reason IS 'nonce_used quando outra transação ocupou o nonce';
+COMMENT ON TABLE signer_broadcasts IS 'Versões assinadas de cada transação, com as taxas EIP-1559 usadas';
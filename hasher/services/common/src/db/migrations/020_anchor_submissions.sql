@@ -0,0 +1,6 @@
+-- Ancoragem pelo signer do blockchain-service: o envio é acompanhado pelo id
+-- da transação lá, cujo hash muda quando ela é substituída por taxas maiores
+ALTER TABLE zkp_proofs
+    ADD COLUMN IF NOT EXISTS anchor_submission_id UUID;
+
+COMMENT ON COLUMN zkp_proofs.anchor_submission_id IS 'Transação no signer do blockchain-service (signer_transactions.id), quando o envio passou por ele';
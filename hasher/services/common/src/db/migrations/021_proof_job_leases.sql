@@ -0,0 +1,11 @@
+-- Lease dos jobs em execução: o worker que pegou o job renova heartbeat_at
+-- enquanto prova, e só jobs com o lease vencido voltam para a fila. Assim
+-- várias instâncias podem consumir a fila sem retomar jobs umas das outras.
+ALTER TABLE zkp_jobs
+    ADD COLUMN IF NOT EXISTS locked_by VARCHAR(128),
+    ADD COLUMN IF NOT EXISTS heartbeat_at TIMESTAMP WITH TIME ZONE;
+
+CREATE INDEX IF NOT EXISTS idx_zkp_jobs_running ON zkp_jobs(heartbeat_at) WHERE status = 'running';
+
+COMMENT ON COLUMN zkp_jobs.locked_by IS 'Worker (instância) que executa o job';
+COMMENT ON COLUMN zkp_jobs.heartbeat_at IS 'Última renovação do lease pelo worker';
@@ -0,0 +1,16 @@
+-- Enunciado das provas: chave de verificação e entradas públicas. Provas
+-- Groth16 podem ser re-randomizadas por qualquer um, sem a testemunha, então
+-- a validade e a revogação de uma prova emitida são buscadas pelo enunciado
+-- e não pelos bytes da prova
+ALTER TABLE zkp_proofs ADD COLUMN IF NOT EXISTS statement_hash BYTEA;
+
+-- Mesma fórmula de `statement_hash` no zkp-service; provas legadas, sem a
+-- impressão digital da chave, ficam de fora
+UPDATE zkp_proofs
+SET statement_hash = sha256(convert_to(key_fingerprint || ':' || array_to_string(public_inputs, ','), 'UTF8'))
+WHERE statement_hash IS NULL AND key_fingerprint IS NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_zkp_proofs_statement_hash ON zkp_proofs(statement_hash, status_index);
+DROP INDEX IF EXISTS idx_zkp_proofs_proof_data;
+
+COMMENT ON COLUMN zkp_proofs.statement_hash IS 'SHA-256 de "<key_fingerprint>:<entradas públicas separadas por vírgula>"';
@@ -0,0 +1,9 @@
+-- Saldos verificados derivados de provas de storage (asset_verifications) do
+-- token de solvência: a verificação de origem e o bloco, para que um saldo de
+-- um bloco anterior não substitua o de um bloco mais recente
+ALTER TABLE verified_balances
+    ADD COLUMN IF NOT EXISTS verification_id UUID REFERENCES asset_verifications(id),
+    ADD COLUMN IF NOT EXISTS block_number BIGINT;
+
+COMMENT ON COLUMN verified_balances.verification_id IS 'Prova de storage de onde veio o saldo';
+COMMENT ON COLUMN verified_balances.block_number IS 'Bloco da prova de storage de onde veio o saldo';
@@ -1,15 +1,34 @@
-use sqlx::PgPool;
+use sqlx::{Executor, PgPool};
 use std::error::Error;
 
 pub async fn setup_database(database_url: &str) -> Result<PgPool, Box<dyn Error>> {
     let pool = PgPool::connect(database_url).await?;
 
-    // Executar migrações
-    sqlx::query!(
-        include_str!("migrations/001_initial_schema.sql")
-    )
-    .execute(&pool)
-    .await?;
+    // Executar migrações (cada arquivo tem vários comandos, então vai pelo
+    // protocolo simples, sem preparar)
+    (&pool).execute(include_str!("migrations/001_initial_schema.sql")).await?;
+    (&pool).execute(include_str!("migrations/002_zkp_parameters.sql")).await?;
+    (&pool).execute(include_str!("migrations/003_solvency.sql")).await?;
+    (&pool).execute(include_str!("migrations/004_whitelist.sql")).await?;
+    (&pool).execute(include_str!("migrations/005_proof_jobs.sql")).await?;
+    (&pool).execute(include_str!("migrations/006_evm_parameters.sql")).await?;
+    (&pool).execute(include_str!("migrations/007_canonical_proofs.sql")).await?;
+    (&pool).execute(include_str!("migrations/008_circuit_registry.sql")).await?;
+    (&pool).execute(include_str!("migrations/009_nullifiers.sql")).await?;
+    (&pool).execute(include_str!("migrations/010_user_attributes.sql")).await?;
+    (&pool).execute(include_str!("migrations/011_proof_anchoring.sql")).await?;
+    (&pool).execute(include_str!("migrations/012_proof_status.sql")).await?;
+    (&pool).execute(include_str!("migrations/013_transaction_lifecycle.sql")).await?;
+    (&pool).execute(include_str!("migrations/014_multichain.sql")).await?;
+    (&pool).execute(include_str!("migrations/015_event_indexer.sql")).await?;
+    (&pool).execute(include_str!("migrations/016_asset_verifications.sql")).await?;
+    (&pool).execute(include_str!("migrations/017_decoded_transactions.sql")).await?;
+    (&pool).execute(include_str!("migrations/018_siwe_nonces.sql")).await?;
+    (&pool).execute(include_str!("migrations/019_signer.sql")).await?;
+    (&pool).execute(include_str!("migrations/020_anchor_submissions.sql")).await?;
+    (&pool).execute(include_str!("migrations/021_proof_job_leases.sql")).await?;
+    (&pool).execute(include_str!("migrations/022_proof_statements.sql")).await?;
+    (&pool).execute(include_str!("migrations/023_verified_balance_sources.sql")).await?;
 
     Ok(pool)
 }
@@ -1,4 +1,3 @@
-use std::any::Any;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::broadcast;
@@ -49,9 +48,12 @@ pub trait EventHandler: Send + Sync {
     async fn handle(&self, event: Event) -> Result<(), EventError>;
 }
 
+type Handlers = Arc<RwLock<HashMap<String, Vec<Box<dyn EventHandler>>>>>;
+
 pub struct MemoryEventBus {
+    #[allow(dead_code)]
     config: EventConfig,
-    handlers: Arc<RwLock<HashMap<String, Vec<Box<dyn EventHandler>>>>>,
+    handlers: Handlers,
     tx: broadcast::Sender<Event>,
 }
 
@@ -66,10 +68,9 @@ impl MemoryEventBus {
     }
 
     pub async fn publish(&self, event: Event) -> Result<(), EventError> {
-        // Enviar evento para todos os subscribers
-        if let Err(e) = self.tx.send(event.clone()) {
-            return Err(EventError::PublishError(e.to_string()));
-        }
+        // Enviar evento para todos os subscribers. Sem nenhum inscrito no
+        // canal o envio falha, mas os handlers registrados ainda recebem
+        let _ = self.tx.send(event.clone());
 
         // Processar com handlers registrados
         let handlers = self.handlers.read().await;
@@ -1,9 +1,8 @@
 use lazy_static::lazy_static;
 use prometheus::{
-    register_histogram_vec, register_int_counter_vec, Histogram, HistogramVec, IntCounter,
-    IntCounterVec, IntGauge, Opts, Registry, HistogramOpts,
+    register_histogram_vec, register_int_counter_vec, Histogram, HistogramVec,
+    IntCounterVec, IntGauge, Registry, HistogramOpts,
 };
-use std::time::Instant;
 
 lazy_static! {
     pub static ref REGISTRY: Registry = Registry::new();
@@ -27,12 +26,21 @@ lazy_static! {
     // Métricas de ZKP
     pub static ref ZKP_GENERATION_TIME: HistogramVec = register_histogram_vec!(
         "zkp_generation_seconds",
-        "Tempo de geração de provas ZKP",
-        &["proof_type"],
+        "Tempo das etapas de prova ZKP (setup, prove, verify) por circuito",
+        &["circuit", "stage"],
         vec![0.1, 0.5, 1.0, 2.0, 5.0, 10.0]
     )
     .unwrap();
 
+    // Medições do perfilador de circuitos, separadas das provas emitidas
+    pub static ref ZKP_PROFILE_TIME: HistogramVec = register_histogram_vec!(
+        "zkp_profile_seconds",
+        "Tempo das etapas (setup, prove, verify) medidas pelo perfilador de circuitos",
+        &["circuit", "stage"],
+        vec![0.1, 0.5, 1.0, 2.0, 5.0, 10.0, 30.0]
+    )
+    .unwrap();
+
     pub static ref ZKP_VERIFICATION_TIME: Histogram = Histogram::with_opts(
         HistogramOpts::new(
             "zkp_verification_seconds",
@@ -177,6 +185,7 @@ pub fn register_metrics() {
     lazy_static::initialize(&HTTP_REQUESTS_TOTAL);
     lazy_static::initialize(&HTTP_REQUEST_DURATION_SECONDS);
     lazy_static::initialize(&ZKP_GENERATION_TIME);
+    lazy_static::initialize(&ZKP_PROFILE_TIME);
     lazy_static::initialize(&ZKP_VERIFICATION_TIME);
     lazy_static::initialize(&BLOCKCHAIN_VERIFICATION_TIME);
     lazy_static::initialize(&AI_ANALYSIS_TIME);
@@ -203,6 +212,7 @@ pub fn register_metrics() {
     REGISTRY.register(Box::new(HTTP_REQUESTS_TOTAL.clone())).unwrap();
     REGISTRY.register(Box::new(HTTP_REQUEST_DURATION_SECONDS.clone())).unwrap();
     REGISTRY.register(Box::new(ZKP_GENERATION_TIME.clone())).unwrap();
+    REGISTRY.register(Box::new(ZKP_PROFILE_TIME.clone())).unwrap();
     REGISTRY.register(Box::new(ZKP_VERIFICATION_TIME.clone())).unwrap();
     REGISTRY.register(Box::new(BLOCKCHAIN_VERIFICATION_TIME.clone())).unwrap();
     REGISTRY.register(Box::new(AI_ANALYSIS_TIME.clone())).unwrap();
@@ -266,7 +276,7 @@ mod tests {
 
         // Testar timer ZKP
         {
-            let _timer = Timer::new(&ZKP_GENERATION_TIME, vec!["zkp"]);
+            let _timer = Timer::new(&ZKP_GENERATION_TIME, vec!["zkp", "prove"]);
             thread::sleep(Duration::from_millis(100));
         }
 
@@ -3,9 +3,6 @@ use actix_web::{
     Error, HttpMessage,
 };
 use futures::future::{ready, LocalBoxFuture, Ready};
-use std::future::Future;
-use std::pin::Pin;
-use std::task::{Context, Poll};
 
 use crate::auth::Auth;
 use crate::metrics::HTTP_REQUESTS_TOTAL;
@@ -72,8 +69,7 @@ where
         // Verificar token de autenticação
         if let Some(auth_header) = req.headers().get("Authorization") {
             if let Ok(auth_str) = auth_header.to_str() {
-                if auth_str.starts_with("Bearer ") {
-                    let token = &auth_str[7..];
+                if let Some(token) = auth_str.strip_prefix("Bearer ") {
                     match self.auth.verify_token(token) {
                         Ok(claims) => {
                             req.extensions_mut().insert(claims);
@@ -140,7 +136,7 @@ mod tests {
 
         let req = test::TestRequest::get()
             .uri("/test")
-            .header("Authorization", format!("Bearer {}", token))
+            .insert_header(("Authorization", format!("Bearer {}", token)))
             .to_request();
 
         let resp = test::call_service(&app, req).await;
@@ -1,8 +1,10 @@
 use actix_web::{
+    body::EitherBody,
     dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
     Error, HttpResponse,
 };
 use futures::future::{ready, LocalBoxFuture, Ready};
+use std::rc::Rc;
 use std::sync::Arc;
 
 use crate::circuit_breaker::CircuitBreaker;
@@ -25,11 +27,11 @@ impl ResilienceMiddleware {
 
 impl<S, B> Transform<S, ServiceRequest> for ResilienceMiddleware
 where
-    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
     S::Future: 'static,
     B: 'static,
 {
-    type Response = ServiceResponse<B>;
+    type Response = ServiceResponse<EitherBody<B>>;
     type Error = Error;
     type InitError = ();
     type Transform = ResilienceMiddlewareService<S>;
@@ -37,7 +39,7 @@ where
 
     fn new_transform(&self, service: S) -> Self::Future {
         ready(Ok(ResilienceMiddlewareService {
-            service,
+            service: Rc::new(service),
             rate_limiter: self.rate_limiter.clone(),
             circuit_breaker: self.circuit_breaker.clone(),
         }))
@@ -45,18 +47,18 @@ where
 }
 
 pub struct ResilienceMiddlewareService<S> {
-    service: S,
+    service: Rc<S>,
     rate_limiter: Arc<RateLimiter>,
     circuit_breaker: Arc<CircuitBreaker>,
 }
 
 impl<S, B> Service<ServiceRequest> for ResilienceMiddlewareService<S>
 where
-    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
     S::Future: 'static,
     B: 'static,
 {
-    type Response = ServiceResponse<B>;
+    type Response = ServiceResponse<EitherBody<B>>;
     type Error = Error;
     type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
 
@@ -66,59 +68,41 @@ where
         let rate_limiter = self.rate_limiter.clone();
         let circuit_breaker = self.circuit_breaker.clone();
         let service = self.service.clone();
+        let method = req.method().to_string();
         let path = req.path().to_string();
 
         Box::pin(async move {
             // Verificar rate limit
             if !rate_limiter.is_allowed(&path).await {
                 HTTP_REQUESTS_TOTAL
-                    .with_label_values(&[
-                        req.method().as_str(),
-                        &path,
-                        "429",
-                    ])
+                    .with_label_values(&[&method, &path, "429"])
                     .inc();
 
                 return Ok(req.into_response(
                     HttpResponse::TooManyRequests()
                         .body("Taxa de requisições excedida")
-                        .into_body(),
+                        .map_into_right_body(),
                 ));
             }
 
-            // Usar circuit breaker
-            match circuit_breaker
-                .call(|| async {
-                    let fut = service.call(req);
-                    fut.await
-                })
-                .await
-            {
-                Ok(response) => {
-                    HTTP_REQUESTS_TOTAL
-                        .with_label_values(&[
-                            response.request().method().as_str(),
-                            &path,
-                            response.status().as_str(),
-                        ])
-                        .inc();
-                    Ok(response)
-                }
-                Err(_) => {
-                    HTTP_REQUESTS_TOTAL
-                        .with_label_values(&[
-                            req.method().as_str(),
-                            &path,
-                            "503",
-                        ])
-                        .inc();
-                    Ok(req.into_response(
-                        HttpResponse::ServiceUnavailable()
-                            .body("Serviço temporariamente indisponível")
-                            .into_body(),
-                    ))
-                }
+            // Com o circuito aberto a requisição nem chega ao serviço
+            if !circuit_breaker.is_closed().await {
+                HTTP_REQUESTS_TOTAL
+                    .with_label_values(&[&method, &path, "503"])
+                    .inc();
+
+                return Ok(req.into_response(
+                    HttpResponse::ServiceUnavailable()
+                        .body("Serviço temporariamente indisponível")
+                        .map_into_right_body(),
+                ));
             }
+
+            let response = circuit_breaker.call(|| service.call(req)).await?;
+            HTTP_REQUESTS_TOTAL
+                .with_label_values(&[&method, &path, response.status().as_str()])
+                .inc();
+            Ok(response.map_into_left_body())
         })
     }
 }
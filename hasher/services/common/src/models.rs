@@ -0,0 +1,178 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct User {
+    pub id: Uuid,
+    pub wallet_address: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "VARCHAR", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationStatus {
+    /// Sem recibo ainda, ou com menos confirmações que a profundidade de finalidade
+    Pending,
+    /// Executada com sucesso e na profundidade de finalidade
+    Confirmed,
+    /// Revertida, ou desconhecida pelo nó por tempo demais
+    Failed,
+    /// Saiu do bloco em que tinha sido vista
+    Reorged,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct BlockchainVerification {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    /// Chain id (EIP-155) da rede da transação
+    pub chain_id: i64,
+    pub transaction_hash: String,
+    pub status: VerificationStatus,
+    pub block_number: Option<i64>,
+    pub block_hash: Option<String>,
+    pub confirmations: i64,
+    /// `reverted`, `not_found` ou `dropped` quando `status` é `failed`
+    pub failure_reason: Option<String>,
+    /// Última vez que o nó conhecia a transação (mempool ou bloco)
+    pub last_seen_at: Option<DateTime<Utc>>,
+    /// Chamada, eventos e transferências de tokens decodificados (None fora de um bloco)
+    pub decoded: Option<serde_json::Value>,
+    /// Última consulta ao nó
+    pub verified_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Saldo de token provado por `eth_getProof` contra a raiz de estado do bloco
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AssetVerification {
+    pub id: Uuid,
+    pub chain_id: i64,
+    pub token_address: String,
+    pub holder: String,
+    /// uint256 em decimal
+    pub balance: String,
+    pub block_number: i64,
+    pub block_hash: String,
+    pub state_root: String,
+    pub storage_root: String,
+    pub balance_slot: String,
+    /// RPCs que concordaram com o hash e a raiz de estado do bloco
+    pub corroborating_endpoints: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Evento indexado do ZKProofRegistry ou do SubwalletRegistry
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ChainEvent {
+    pub id: i64,
+    pub chain_id: i64,
+    pub contract_address: String,
+    /// `ProofSubmitted`, `ProofVerified`, `SubwalletRegistered` ou `SubwalletStatusChanged`
+    pub event_name: String,
+    /// proofId dos eventos de prova, carteira dos de subwallet
+    pub subject: String,
+    /// Demais campos decodificados (`submitter`, `verifier`, `name`, `is_active`)
+    pub data: serde_json::Value,
+    pub block_number: i64,
+    pub block_hash: String,
+    pub transaction_hash: String,
+    pub log_index: i64,
+    pub indexed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct IndexerCheckpoint {
+    pub chain_id: i64,
+    pub block_number: i64,
+    pub block_hash: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "VARCHAR", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum SubmissionStatus {
+    /// Enviada, fora de um bloco ou com menos confirmações que a finalidade
+    Pending,
+    /// Executada com sucesso e na profundidade de finalidade
+    Confirmed,
+    /// Minerada e revertida, na profundidade de finalidade
+    Reverted,
+    /// O nonce foi ocupado por uma transação que não é nossa
+    Failed,
+}
+
+/// Transação assinada com uma conta do keystore e enviada pelo serviço
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SignerTransaction {
+    pub id: Uuid,
+    pub chain_id: i64,
+    pub from_address: String,
+    pub nonce: i64,
+    /// None na criação de contratos
+    pub to_address: Option<String>,
+    /// Wei, em decimal
+    pub value: String,
+    pub data: Vec<u8>,
+    pub gas_limit: i64,
+    pub max_fee_per_gas: String,
+    pub max_priority_fee_per_gas: String,
+    /// Origem da transação (ex.: `anchor`, `admin`)
+    pub purpose: String,
+    pub status: SubmissionStatus,
+    /// Última versão enviada, ou a minerada
+    pub tx_hash: String,
+    /// Quantas vezes a transação foi substituída por taxas maiores
+    pub replacements: i32,
+    pub block_number: Option<i64>,
+    pub failure_reason: Option<String>,
+    pub last_broadcast_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Uma versão assinada de uma transação: a original ou uma substituição
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SignerBroadcast {
+    pub tx_hash: String,
+    pub max_fee_per_gas: String,
+    pub max_priority_fee_per_gas: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ZkpProof {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub proof_data: Vec<u8>,
+    pub public_inputs: Vec<String>,
+    pub verification_result: bool,
+    /// Fingerprint da chave de verificação usada na prova (None para provas legadas)
+    pub key_fingerprint: Option<String>,
+    /// Circuito e versão semântica no registro do zkp-service (None para provas legadas)
+    pub circuit_name: Option<String>,
+    pub circuit_version: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AiAnalysis {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub risk_score: f64,
+    pub analysis_data: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ErrorResponse {
+    pub error: String,
+    pub message: String,
+}
@@ -72,11 +72,12 @@ impl RateLimiter {
 
     pub async fn try_acquire(&self, key: &str, tokens: u64) -> Result<(), RateLimitError> {
         let mut buckets = self.buckets.write().await;
-        let bucket = buckets.entry(key.to_string()).or_insert_with(|| {
+        buckets.entry(key.to_string()).or_insert_with(|| {
             TokenBucket::new(self.default_capacity, self.default_refill_rate)
         });
 
         RATE_LIMIT_BUCKETS.set(buckets.len() as i64);
+        let bucket = buckets.get_mut(key).expect("bucket inserido acima");
 
         if bucket.try_acquire(tokens) {
             RATE_LIMIT_OPERATIONS
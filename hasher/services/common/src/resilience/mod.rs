@@ -19,9 +19,11 @@ pub struct CircuitBreakerMetrics {
 }
 
 pub struct CircuitBreaker {
+    #[allow(dead_code)]
     name: String,
     failure_threshold: u64,
     reset_timeout: Duration,
+    #[allow(dead_code)]
     half_open_timeout: Duration,
     is_open: AtomicBool,
     metrics: Arc<CircuitBreakerMetrics>,
@@ -48,9 +50,10 @@ impl CircuitBreaker {
         }
     }
 
-    pub async fn execute<F, T, E>(&self, operation: F) -> Result<T, CircuitBreakerError>
+    pub async fn execute<F, Fut, T, E>(&self, operation: F) -> Result<T, CircuitBreakerError>
     where
-        F: FnOnce() -> Result<T, E>,
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Result<T, E>>,
         E: std::fmt::Display,
     {
         if self.is_open.load(Ordering::Relaxed) {
@@ -65,7 +68,7 @@ impl CircuitBreaker {
             }
         }
 
-        match operation() {
+        match operation().await {
             Ok(result) => {
                 self.metrics.success_count.fetch_add(1, Ordering::Relaxed);
                 if self.metrics.failure_count.load(Ordering::Relaxed) > 0 {
@@ -105,7 +108,7 @@ mod tests {
             Duration::from_secs(1),
         );
 
-        let result = cb.execute(|| Ok::<_, String>("success")).await;
+        let result = cb.execute(|| async { Ok::<_, String>("success") }).await;
         assert!(result.is_ok());
         assert_eq!(cb.metrics.success_count.load(Ordering::Relaxed), 1);
     }
@@ -120,7 +123,7 @@ mod tests {
         );
 
         for _ in 0..3 {
-            let result = cb.execute(|| Err::<String, _>("error")).await;
+            let result = cb.execute(|| async { Err::<String, _>("error") }).await;
             assert!(result.is_err());
         }
 
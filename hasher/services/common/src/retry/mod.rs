@@ -61,6 +61,7 @@ where
 mod tests {
     use super::*;
     use std::sync::atomic::{AtomicU32, Ordering};
+    use std::io;
     use std::sync::Arc;
 
     #[tokio::test]
@@ -68,12 +69,15 @@ mod tests {
         let attempts = Arc::new(AtomicU32::new(0));
         let attempts_clone = attempts.clone();
 
-        let operation = || async move {
-            let current = attempts_clone.fetch_add(1, Ordering::SeqCst);
-            if current < 2 {
-                Err("erro temporário".into())
-            } else {
-                Ok(42)
+        let operation = || {
+            let attempts = attempts_clone.clone();
+            async move {
+                let current = attempts.fetch_add(1, Ordering::SeqCst);
+                if current < 2 {
+                    Err(io::Error::other("erro temporário"))
+                } else {
+                    Ok(42)
+                }
             }
         };
 
@@ -95,9 +99,12 @@ mod tests {
         let attempts = Arc::new(AtomicU32::new(0));
         let attempts_clone = attempts.clone();
 
-        let operation = || async move {
-            attempts_clone.fetch_add(1, Ordering::SeqCst);
-            Err("erro permanente".into())
+        let operation = || {
+            let attempts = attempts_clone.clone();
+            async move {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                Err(io::Error::other("erro permanente"))
+            }
         };
 
         let config = RetryConfig {
@@ -107,7 +114,7 @@ mod tests {
             multiplier: 2.0,
         };
 
-        let result: Result<i32, &str> = retry(operation, config, |_| true).await;
+        let result: Result<i32, io::Error> = retry(operation, config, |_| true).await;
         assert!(result.is_err());
         assert_eq!(attempts.load(Ordering::SeqCst), 3);
     }
@@ -117,9 +124,12 @@ mod tests {
         let attempts = Arc::new(AtomicU32::new(0));
         let attempts_clone = attempts.clone();
 
-        let operation = || async move {
-            attempts_clone.fetch_add(1, Ordering::SeqCst);
-            Err("erro não retratável".into())
+        let operation = || {
+            let attempts = attempts_clone.clone();
+            async move {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                Err(io::Error::other("erro não retratável"))
+            }
         };
 
         let config = RetryConfig {
@@ -129,7 +139,7 @@ mod tests {
             multiplier: 2.0,
         };
 
-        let result: Result<i32, &str> = retry(operation, config, |e| e == "erro temporário").await;
+        let result: Result<i32, io::Error> = retry(operation, config, |e| e.to_string() == "erro temporário").await;
         assert!(result.is_err());
         assert_eq!(attempts.load(Ordering::SeqCst), 1);
     }
@@ -7,13 +7,13 @@ use std::{
     future::{ready, Ready},
     rc::Rc,
 };
-use tracing::{Span, info_span};
+use tracing::info_span;
 
 pub struct TracingMiddleware;
 
 impl<S, B> Transform<S, ServiceRequest> for TracingMiddleware
 where
-    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
     S::Future: 'static,
     B: 'static,
 {
@@ -91,12 +91,14 @@ mod tests {
 
     #[actix_web::test]
     async fn test_tracing_middleware() {
-        // Configurar subscriber de teste
+        // Configurar subscriber de teste (só nesta thread, o global fica
+        // para o teste de init_tracing)
         let subscriber = tracing_subscriber::fmt()
             .with_max_level(Level::INFO)
             .with_span_events(FmtSpan::CLOSE)
             .with_test_writer()
-            .init();
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
 
         // Criar app de teste
         let app = test::init_service(
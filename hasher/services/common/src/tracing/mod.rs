@@ -19,7 +19,6 @@ use tracing_subscriber::{
     fmt,
     Registry,
 };
-use tracing_opentelemetry::OpenTelemetryLayer;
 
 #[derive(Debug, Error)]
 pub enum TracingError {
@@ -101,7 +100,9 @@ mod tests {
     use super::*;
     use tracing::{info, info_span};
 
-    #[tokio::test]
+    // O exportador em lote roda em outra task; com um só worker o shutdown
+    // esperaria por ela para sempre
+    #[tokio::test(flavor = "multi_thread")]
     async fn test_tracing_init() {
         let config = TracingConfig {
             service_name: "test-service".to_string(),
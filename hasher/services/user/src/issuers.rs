@@ -0,0 +1,81 @@
+use common::Auth;
+use std::collections::BTreeSet;
+
+/// Serviço dos tokens emitidos no login SIWE: o próprio usuário, que nunca
+/// pode atestar os seus atributos
+const USER_SERVICE: &str = "user-service";
+
+/// Tamanho máximo do nome do emissor (coluna `user_attributes.issuer`)
+const MAX_ISSUER_LEN: usize = 64;
+
+#[derive(Debug, thiserror::Error)]
+pub enum IssuerError {
+    #[error("Token de emissor ausente ou inválido")]
+    Unauthenticated,
+    #[error("{0} não é um emissor de atributos registrado")]
+    NotAnIssuer(String),
+    #[error("O emissor informado ({declared}) não é o do token ({authenticated})")]
+    Mismatch { declared: String, authenticated: String },
+}
+
+impl IssuerError {
+    /// Código de erro exposto na API
+    pub fn code(&self) -> &'static str {
+        match self {
+            IssuerError::Unauthenticated => "unauthorized",
+            IssuerError::NotAnIssuer(_) => "forbidden_issuer",
+            IssuerError::Mismatch { .. } => "issuer_mismatch",
+        }
+    }
+}
+
+/// Emissores que podem atestar atributos: serviços (ex.: o provedor de KYC)
+/// com token JWT assinado com `JWT_SECRET`, cujo `service` está em
+/// `ATTRIBUTE_ISSUERS` (separados por vírgula). O emissor gravado é o do
+/// token, nunca um nome declarado no corpo da requisição.
+pub struct AttributeIssuers {
+    issuers: BTreeSet<String>,
+}
+
+impl AttributeIssuers {
+    pub fn new(issuers: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            issuers: issuers
+                .into_iter()
+                .map(|issuer| issuer.trim().to_string())
+                .filter(|issuer| !issuer.is_empty() && issuer.len() <= MAX_ISSUER_LEN && issuer != USER_SERVICE)
+                .collect(),
+        }
+    }
+
+    /// Sem `ATTRIBUTE_ISSUERS` ninguém grava atributos
+    pub fn from_env() -> Self {
+        let issuers = std::env::var("ATTRIBUTE_ISSUERS").unwrap_or_default();
+        Self::new(issuers.split(',').map(str::to_string))
+    }
+
+    /// Valida o cabeçalho `Authorization: Bearer <token>` e devolve o emissor
+    /// autenticado; `declared`, quando informado, deve ser o mesmo
+    pub fn authenticate(
+        &self,
+        auth: &Auth,
+        header: Option<&str>,
+        declared: Option<&str>,
+    ) -> Result<String, IssuerError> {
+        let claims = header
+            .and_then(|header| header.strip_prefix("Bearer "))
+            .and_then(|token| auth.verify_token(token).ok())
+            .ok_or(IssuerError::Unauthenticated)?;
+        if !self.issuers.contains(&claims.service) {
+            return Err(IssuerError::NotAnIssuer(claims.service));
+        }
+        if let Some(declared) = declared.filter(|declared| *declared != claims.service) {
+            return Err(IssuerError::Mismatch {
+                declared: declared.to_string(),
+                authenticated: claims.service,
+            });
+        }
+
+        Ok(claims.service)
+    }
+}
@@ -1,15 +1,16 @@
 use actix_web::{web, App, HttpServer};
+use common::Auth;
 use dotenv::dotenv;
 use log::info;
-use sqlx::PgPool;
 use std::env;
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
 mod api;
+mod issuers;
 mod models;
 mod services;
-mod database;
+mod siwe;
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
@@ -27,15 +28,20 @@ async fn main() -> std::io::Result<()> {
     let database_url = env::var("DATABASE_URL")
         .expect("DATABASE_URL deve ser definida");
     
-    let pool = PgPool::connect(&database_url)
+    // Conexão e migrações compartilhadas com os outros serviços
+    let pool = common::db::setup_database(&database_url)
         .await
-        .expect("Falha ao conectar ao banco de dados");
+        .expect("Falha ao preparar o banco de dados");
 
-    // Executar migrações
-    sqlx::migrate!("./migrations")
-        .run(&pool)
-        .await
-        .expect("Falha ao executar migrações");
+    // Login com a carteira (SIWE) e emissão de JWT
+    let siwe = web::Data::new(siwe::SiweVerifier::from_env().expect("Configuração SIWE inválida"));
+    // Sem segredo próprio qualquer um forjaria os tokens
+    let secret = env::var("JWT_SECRET")
+        .expect("JWT_SECRET deve ser definida")
+        .into_bytes();
+    let auth = web::Data::new(Auth::new(&secret));
+    // Serviços que atestam atributos (ex.: o provedor de KYC)
+    let issuers = web::Data::new(issuers::AttributeIssuers::from_env());
 
     info!("Iniciando serviço de usuários na porta {}", port);
 
@@ -43,14 +49,20 @@ async fn main() -> std::io::Result<()> {
     HttpServer::new(move || {
         App::new()
             .app_data(web::Data::new(pool.clone()))
+            .app_data(siwe.clone())
+            .app_data(auth.clone())
+            .app_data(issuers.clone())
             .service(
                 SwaggerUi::new("/swagger-ui/{_:.*}")
                     .url("/api-docs/openapi.json", api::UserApi::openapi()),
             )
             .service(web::scope("/api/users")
+                .service(api::health)
+                .service(api::siwe_nonce)
+                .service(api::siwe_verify)
                 .service(api::get_user)
-                .service(api::create_user)
-                .service(api::update_user))
+                .service(api::get_user_attributes)
+                .service(api::set_user_attributes))
     })
     .bind(("0.0.0.0", port))?
     .run()
@@ -65,7 +77,7 @@ mod tests {
     #[actix_rt::test]
     async fn test_health_check() {
         let app = test::init_service(
-            App::new().service(web::scope("/api/users")),
+            App::new().service(web::scope("/api/users").service(api::health)),
         )
         .await;
 
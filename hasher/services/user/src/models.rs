@@ -1,4 +1,4 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use utoipa::ToSchema;
@@ -13,24 +13,44 @@ pub struct User {
     pub updated_at: DateTime<Utc>,
 }
 
+/// Atributos atestados do usuário, provados pelo zkp-service sem revelá-los.
+/// O blinding do compromisso não sai do banco.
+#[derive(Debug, Serialize, Deserialize, ToSchema, sqlx::FromRow)]
+pub struct UserAttributes {
+    pub user_id: Uuid,
+    #[schema(value_type = String, example = "1990-05-17")]
+    pub birth_date: NaiveDate,
+    /// País de residência, ISO 3166-1 alfa-2
+    #[schema(example = "BR")]
+    pub country: String,
+    #[schema(example = 2)]
+    pub kyc_tier: i16,
+    /// Quem atestou os atributos (ex.: provedor de KYC)
+    pub issuer: String,
+    pub attested_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Nonce para a mensagem Sign-In with Ethereum, de uso único
+#[derive(Debug, Serialize, Deserialize, ToSchema, sqlx::FromRow)]
+pub struct SiweNonce {
+    #[schema(example = "3f6c1a2b9d8e4f7a8b1c2d3e4f5a6b7c")]
+    pub nonce: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Sessão emitida depois de um login SIWE
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SiweSession {
+    /// JWT assinado com `JWT_SECRET`, válido por uma hora
+    pub token: String,
+    #[schema(example = "Bearer")]
+    pub token_type: String,
+    pub user: User,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ErrorResponse {
     pub error: String,
     pub message: String,
 }
-
-impl User {
-    pub fn new(wallet_address: String) -> Self {
-        Self {
-            id: Uuid::new_v4(),
-            wallet_address,
-            created_at: Utc::now(),
-            updated_at: Utc::now(),
-        }
-    }
-
-    pub fn update_wallet_address(&mut self, wallet_address: String) {
-        self.wallet_address = wallet_address;
-        self.updated_at = Utc::now();
-    }
-} 
\ No newline at end of file
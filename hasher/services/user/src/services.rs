@@ -0,0 +1,127 @@
+use chrono::{Duration, NaiveDate, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::{SiweNonce, User, UserAttributes};
+
+pub struct UserService {
+    pool: PgPool,
+}
+
+impl UserService {
+    pub fn new(pool: &PgPool) -> Self {
+        Self { pool: pool.clone() }
+    }
+
+    pub async fn get_user(&self, user_id: Uuid) -> Result<Option<User>, sqlx::Error> {
+        sqlx::query_as!(
+            User,
+            "SELECT id, wallet_address, created_at, updated_at FROM users WHERE id = $1",
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Usuário da carteira, criado no primeiro login. Endereços já cadastrados
+    /// são encontrados sem diferenciar maiúsculas (checksum EIP-55).
+    pub async fn upsert_wallet_user(&self, wallet_address: &str) -> Result<User, sqlx::Error> {
+        let wallet_address = wallet_address.to_lowercase();
+        let now = Utc::now();
+
+        let existing = sqlx::query_as!(
+            User,
+            "UPDATE users SET updated_at = $2 WHERE lower(wallet_address) = $1 \
+            RETURNING id, wallet_address, created_at, updated_at",
+            wallet_address,
+            now
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+        if let Some(user) = existing {
+            return Ok(user);
+        }
+
+        // Outro login da mesma carteira pode ter inserido o usuário no meio tempo
+        sqlx::query_as!(
+            User,
+            "INSERT INTO users (id, wallet_address, created_at, updated_at) VALUES ($1, $2, $3, $3) \
+            ON CONFLICT (wallet_address) DO UPDATE SET updated_at = EXCLUDED.updated_at \
+            RETURNING id, wallet_address, created_at, updated_at",
+            Uuid::new_v4(),
+            wallet_address,
+            now
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Emite um nonce SIWE aleatório e apaga os já vencidos
+    pub async fn create_nonce(&self, ttl: Duration) -> Result<SiweNonce, sqlx::Error> {
+        sqlx::query!("DELETE FROM siwe_nonces WHERE expires_at <= CURRENT_TIMESTAMP")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query_as!(
+            SiweNonce,
+            "INSERT INTO siwe_nonces (nonce, expires_at) VALUES ($1, $2) RETURNING nonce, expires_at",
+            Uuid::new_v4().simple().to_string(),
+            Utc::now() + ttl
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Consome o nonce numa única instrução: `false` se ele não existe, venceu
+    /// ou já foi usado (inclusive por um login concorrente)
+    pub async fn consume_nonce(&self, nonce: &str) -> Result<bool, sqlx::Error> {
+        let consumed = sqlx::query!(
+            "UPDATE siwe_nonces SET consumed_at = CURRENT_TIMESTAMP \
+            WHERE nonce = $1 AND consumed_at IS NULL AND expires_at > CURRENT_TIMESTAMP RETURNING nonce",
+            nonce
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(consumed.is_some())
+    }
+
+    pub async fn get_attributes(&self, user_id: Uuid) -> Result<Option<UserAttributes>, sqlx::Error> {
+        sqlx::query_as!(
+            UserAttributes,
+            "SELECT user_id, birth_date, country, kyc_tier, issuer, attested_at, updated_at \
+            FROM user_attributes WHERE user_id = $1",
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Grava os atributos atestados do usuário. O blinding volta a NULL: o
+    /// compromisso usado nas provas muda junto com os atributos.
+    pub async fn set_attributes(
+        &self,
+        user_id: Uuid,
+        birth_date: NaiveDate,
+        country: &str,
+        kyc_tier: i16,
+        issuer: &str,
+    ) -> Result<UserAttributes, sqlx::Error> {
+        sqlx::query_as!(
+            UserAttributes,
+            "INSERT INTO user_attributes (user_id, birth_date, country, kyc_tier, issuer) \
+            VALUES ($1, $2, $3, $4, $5) \
+            ON CONFLICT (user_id) DO UPDATE SET birth_date = EXCLUDED.birth_date, country = EXCLUDED.country, \
+            kyc_tier = EXCLUDED.kyc_tier, issuer = EXCLUDED.issuer, blinding = NULL, \
+            attested_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP \
+            RETURNING user_id, birth_date, country, kyc_tier, issuer, attested_at, updated_at",
+            user_id,
+            birth_date,
+            country,
+            kyc_tier,
+            issuer
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+}
@@ -0,0 +1,451 @@
+use chrono::{DateTime, Duration, FixedOffset, Utc};
+use ethers::contract::{abigen, ContractError};
+use ethers::providers::{Http, Middleware, Provider, ProviderError};
+use ethers::types::{Address, Bytes, Signature};
+use ethers::utils::{hash_message, to_checksum};
+use std::collections::BTreeMap;
+use std::iter::Peekable;
+use std::str::{FromStr, Split};
+use std::sync::Arc;
+
+abigen!(
+    Erc1271Wallet,
+    r#"[
+        function isValidSignature(bytes32 hash, bytes signature) external view returns (bytes4)
+    ]"#
+);
+
+/// Valor de retorno de `isValidSignature` quando a carteira aceita a assinatura
+const EIP1271_MAGIC_VALUE: [u8; 4] = [0x16, 0x26, 0xba, 0x7e];
+
+const PREAMBLE: &str = " wants you to sign in with your Ethereum account:";
+
+/// Tolerância para relógios adiantados na carteira (`Issued At`)
+const CLOCK_SKEW_SECS: i64 = 60;
+
+const DEFAULT_NONCE_TTL_SECS: u64 = 300;
+
+#[derive(Debug, thiserror::Error)]
+pub enum SiweError {
+    #[error("Configuração SIWE inválida: {0}")]
+    Config(String),
+    #[error("Mensagem SIWE malformada: {0}")]
+    Malformed(String),
+    #[error("A mensagem foi emitida para o domínio {0}")]
+    DomainMismatch(String),
+    #[error("A mensagem expirou")]
+    Expired,
+    #[error("A mensagem ainda não é válida")]
+    NotYetValid,
+    #[error("A assinatura não corresponde ao endereço da mensagem")]
+    InvalidSignature,
+    #[error("A assinatura não corresponde ao endereço e não há RPC da rede {0} para consultar carteiras de contrato")]
+    UnsupportedChain(u64),
+    #[error("Nonce inválido, expirado ou já usado")]
+    InvalidNonce,
+    #[error("Erro no nó Ethereum: {0}")]
+    Provider(#[from] ProviderError),
+}
+
+impl SiweError {
+    /// Código de erro exposto na API
+    pub fn code(&self) -> &'static str {
+        match self {
+            SiweError::Malformed(_) => "invalid_message",
+            SiweError::DomainMismatch(_) => "domain_mismatch",
+            SiweError::Expired => "message_expired",
+            SiweError::NotYetValid => "message_not_yet_valid",
+            SiweError::InvalidSignature => "invalid_signature",
+            SiweError::UnsupportedChain(_) => "unsupported_chain",
+            SiweError::InvalidNonce => "invalid_nonce",
+            SiweError::Config(_) | SiweError::Provider(_) => "internal_server_error",
+        }
+    }
+}
+
+/// Mensagem Sign-In with Ethereum (EIP-4361)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SiweMessage {
+    pub scheme: Option<String>,
+    pub domain: String,
+    pub address: Address,
+    pub statement: Option<String>,
+    pub uri: String,
+    pub version: String,
+    pub chain_id: u64,
+    pub nonce: String,
+    pub issued_at: DateTime<FixedOffset>,
+    pub expiration_time: Option<DateTime<FixedOffset>>,
+    pub not_before: Option<DateTime<FixedOffset>>,
+    pub request_id: Option<String>,
+    pub resources: Vec<String>,
+}
+
+impl SiweMessage {
+    /// Confere o domínio e a janela de validade da mensagem
+    pub fn validate(&self, domain: &str, now: DateTime<Utc>) -> Result<(), SiweError> {
+        if self.domain != domain {
+            return Err(SiweError::DomainMismatch(self.domain.clone()));
+        }
+        if self.expiration_time.is_some_and(|expiration| expiration <= now) {
+            return Err(SiweError::Expired);
+        }
+        if self.not_before.is_some_and(|not_before| not_before > now)
+            || self.issued_at > now + Duration::seconds(CLOCK_SKEW_SECS)
+        {
+            return Err(SiweError::NotYetValid);
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for SiweMessage {
+    type Err = SiweError;
+
+    /// Segue a ABNF da EIP-4361: campos em ordem fixa, separados por `\n`
+    fn from_str(message: &str) -> Result<Self, SiweError> {
+        let malformed = |reason: &str| SiweError::Malformed(reason.to_string());
+        let mut lines = message.split('\n').peekable();
+
+        let header = lines
+            .next()
+            .and_then(|line| line.strip_suffix(PREAMBLE))
+            .ok_or_else(|| malformed("cabeçalho ausente"))?;
+        let (scheme, domain) = match header.split_once("://") {
+            Some((scheme, domain)) => (Some(scheme.to_string()), domain),
+            None => (None, header),
+        };
+        if domain.is_empty() || domain.contains(char::is_whitespace) {
+            return Err(malformed("domínio inválido"));
+        }
+
+        let address_line = lines.next().ok_or_else(|| malformed("endereço ausente"))?;
+        let address: Address = address_line.parse().map_err(|_| malformed("endereço inválido"))?;
+        if to_checksum(&address, None) != address_line {
+            return Err(malformed("o endereço deve estar no formato EIP-55"));
+        }
+
+        if lines.next() != Some("") {
+            return Err(malformed("falta a linha em branco depois do endereço"));
+        }
+        let statement = match lines.next() {
+            Some("") => None,
+            Some(statement) => {
+                if lines.next() != Some("") {
+                    return Err(malformed("falta a linha em branco depois do statement"));
+                }
+                Some(statement.to_string())
+            }
+            None => return Err(malformed("mensagem incompleta")),
+        };
+
+        let uri = required_field(&mut lines, "URI")?.to_string();
+        let version = required_field(&mut lines, "Version")?;
+        if version != "1" {
+            return Err(malformed("versão não suportada"));
+        }
+        let chain_id = required_field(&mut lines, "Chain ID")?
+            .parse()
+            .map_err(|_| malformed("Chain ID inválido"))?;
+        let nonce = required_field(&mut lines, "Nonce")?;
+        if nonce.len() < 8 || !nonce.bytes().all(|b| b.is_ascii_alphanumeric()) {
+            return Err(malformed("o nonce deve ter ao menos 8 caracteres alfanuméricos"));
+        }
+        let issued_at = timestamp(required_field(&mut lines, "Issued At")?)?;
+        let expiration_time = optional_field(&mut lines, "Expiration Time").map(timestamp).transpose()?;
+        let not_before = optional_field(&mut lines, "Not Before").map(timestamp).transpose()?;
+        let request_id = optional_field(&mut lines, "Request ID").map(str::to_string);
+
+        let mut resources = Vec::new();
+        if lines.next_if_eq(&"Resources:").is_some() {
+            while let Some(resource) = lines.next_if(|line| line.starts_with("- ")) {
+                resources.push(resource[2..].to_string());
+            }
+        }
+        if lines.next().is_some() {
+            return Err(malformed("conteúdo inesperado depois dos campos"));
+        }
+
+        Ok(Self {
+            scheme,
+            domain: domain.to_string(),
+            address,
+            statement,
+            uri,
+            version: version.to_string(),
+            chain_id,
+            nonce: nonce.to_string(),
+            issued_at,
+            expiration_time,
+            not_before,
+            request_id,
+            resources,
+        })
+    }
+}
+
+fn optional_field<'a>(lines: &mut Peekable<Split<'a, char>>, name: &str) -> Option<&'a str> {
+    let value = lines.peek()?.strip_prefix(name)?.strip_prefix(": ")?;
+    lines.next();
+    Some(value)
+}
+
+fn required_field<'a>(lines: &mut Peekable<Split<'a, char>>, name: &str) -> Result<&'a str, SiweError> {
+    optional_field(lines, name).ok_or_else(|| SiweError::Malformed(format!("campo {} ausente", name)))
+}
+
+fn timestamp(value: &str) -> Result<DateTime<FixedOffset>, SiweError> {
+    DateTime::parse_from_rfc3339(value).map_err(|_| SiweError::Malformed(format!("data inválida: {}", value)))
+}
+
+/// Verifica mensagens SIWE assinadas: assinaturas EIP-191 de contas comuns e,
+/// quando o endereço é um contrato, `isValidSignature` (EIP-1271)
+pub struct SiweVerifier {
+    domain: String,
+    nonce_ttl: Duration,
+    providers: BTreeMap<u64, Arc<Provider<Http>>>,
+}
+
+impl SiweVerifier {
+    pub fn new(domain: String, nonce_ttl: Duration, rpc_urls: BTreeMap<u64, String>) -> Result<Self, SiweError> {
+        let mut providers = BTreeMap::new();
+        for (chain_id, url) in rpc_urls {
+            let provider = Provider::<Http>::try_from(url.as_str())
+                .map_err(|e| SiweError::Config(format!("RPC inválido para a rede {}: {}", chain_id, e)))?;
+            providers.insert(chain_id, Arc::new(provider));
+        }
+
+        Ok(Self {
+            domain,
+            nonce_ttl,
+            providers,
+        })
+    }
+
+    /// Domínio em `SIWE_DOMAIN` e validade dos nonces em `SIWE_NONCE_TTL_SECS`
+    /// (padrão 300). Carteiras de contrato são consultadas no primeiro RPC de
+    /// cada rede, configurada como no serviço de blockchain (`BLOCKCHAIN_CHAINS`
+    /// e `CHAIN_<id>_RPC_URLS`, ou `ETHEREUM_RPC_URL` e `ETHEREUM_CHAIN_ID`)
+    pub fn from_env() -> Result<Self, SiweError> {
+        let (domain, nonce_ttl, rpc_urls) = Self::from_vars(|name| std::env::var(name).ok())?;
+        Self::new(domain, nonce_ttl, rpc_urls)
+    }
+
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Result<(String, Duration, BTreeMap<u64, String>), SiweError> {
+        // Variáveis vazias valem como ausentes
+        let var = |name: &str| var(name).map(|value| value.trim().to_string()).filter(|value| !value.is_empty());
+        let first_url = |value: String| value.split(',').map(str::trim).find(|url| !url.is_empty()).map(str::to_string);
+
+        let domain = var("SIWE_DOMAIN").ok_or_else(|| SiweError::Config("SIWE_DOMAIN deve estar definida".to_string()))?;
+        let nonce_ttl = match var("SIWE_NONCE_TTL_SECS") {
+            Some(value) => value
+                .parse::<u64>()
+                .ok()
+                .filter(|secs| *secs > 0)
+                .ok_or_else(|| SiweError::Config("SIWE_NONCE_TTL_SECS deve ser um número positivo".to_string()))?,
+            None => DEFAULT_NONCE_TTL_SECS,
+        };
+
+        let mut rpc_urls = BTreeMap::new();
+        if let Some(chains) = var("BLOCKCHAIN_CHAINS") {
+            for id in chains.split(',').map(str::trim).filter(|id| !id.is_empty()) {
+                let chain_id = id
+                    .parse::<u64>()
+                    .map_err(|_| SiweError::Config(format!("chain id inválido: {}", id)))?;
+                if let Some(url) = var(&format!("CHAIN_{}_RPC_URLS", chain_id)).and_then(first_url) {
+                    rpc_urls.insert(chain_id, url);
+                }
+            }
+        } else if let Some(url) = var("ETHEREUM_RPC_URL").and_then(first_url) {
+            let chain_id = match var("ETHEREUM_CHAIN_ID") {
+                Some(value) => value
+                    .parse::<u64>()
+                    .map_err(|_| SiweError::Config(format!("chain id inválido: {}", value)))?,
+                None => 1,
+            };
+            rpc_urls.insert(chain_id, url);
+        }
+
+        Ok((domain, Duration::seconds(nonce_ttl as i64), rpc_urls))
+    }
+
+    pub fn nonce_ttl(&self) -> Duration {
+        self.nonce_ttl
+    }
+
+    /// Valida a mensagem e a assinatura. O nonce não é consumido aqui: isso
+    /// cabe a quem o emitiu, depois da verificação.
+    pub async fn verify(&self, message: &str, signature: &Bytes, now: DateTime<Utc>) -> Result<SiweMessage, SiweError> {
+        let parsed: SiweMessage = message.parse()?;
+        parsed.validate(&self.domain, now)?;
+
+        if recover_signer(message, signature) == Some(parsed.address) {
+            return Ok(parsed);
+        }
+        self.verify_contract_wallet(&parsed, message, signature).await?;
+
+        Ok(parsed)
+    }
+
+    async fn verify_contract_wallet(&self, parsed: &SiweMessage, message: &str, signature: &Bytes) -> Result<(), SiweError> {
+        let provider = self
+            .providers
+            .get(&parsed.chain_id)
+            .ok_or(SiweError::UnsupportedChain(parsed.chain_id))?;
+
+        // Sem código no endereço, é uma conta comum e a assinatura já falhou
+        if provider.get_code(parsed.address, None).await?.is_empty() {
+            return Err(SiweError::InvalidSignature);
+        }
+
+        let wallet = Erc1271Wallet::new(parsed.address, provider.clone());
+        match wallet
+            .is_valid_signature(hash_message(message).0, signature.clone())
+            .call()
+            .await
+        {
+            Ok(magic) if magic == EIP1271_MAGIC_VALUE => Ok(()),
+            Err(ContractError::MiddlewareError { e }) | Err(ContractError::ProviderError { e }) => Err(SiweError::Provider(e)),
+            // Outro valor, revert ou retorno fora da ABI: a carteira recusa
+            _ => Err(SiweError::InvalidSignature),
+        }
+    }
+}
+
+/// Conta que assinou a mensagem com `personal_sign` (EIP-191), se a assinatura
+/// for ECDSA
+fn recover_signer(message: &str, signature: &Bytes) -> Option<Address> {
+    let signature = Signature::try_from(signature.as_ref()).ok()?;
+    signature.recover(message).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::signers::{LocalWallet, Signer};
+
+    const KEY: &str = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+    fn message(address: Address, expiration: &str) -> String {
+        format!(
+            "app.ibelieve.finance wants you to sign in with your Ethereum account:\n\
+            {}\n\n\
+            Entrar no Ibelieve Finance\n\n\
+            URI: https://app.ibelieve.finance/login\n\
+            Version: 1\n\
+            Chain ID: 1\n\
+            Nonce: 32891756abcdef01\n\
+            Issued At: 2026-10-18T12:00:00Z\n\
+            Expiration Time: {}\n\
+            Resources:\n\
+            - ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq/\n\
+            - https://example.com/my-web2-claim.json",
+            to_checksum(&address, None),
+            expiration
+        )
+    }
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-10-18T12:01:00Z").unwrap().with_timezone(&Utc)
+    }
+
+    fn verifier() -> SiweVerifier {
+        SiweVerifier::new("app.ibelieve.finance".to_string(), Duration::seconds(300), BTreeMap::new()).unwrap()
+    }
+
+    #[test]
+    fn test_parses_eip4361_message() {
+        let wallet: LocalWallet = KEY.parse().unwrap();
+        let parsed: SiweMessage = message(wallet.address(), "2026-10-18T12:05:00Z").parse().unwrap();
+
+        assert_eq!(parsed.domain, "app.ibelieve.finance");
+        assert_eq!(parsed.address, wallet.address());
+        assert_eq!(parsed.statement.as_deref(), Some("Entrar no Ibelieve Finance"));
+        assert_eq!(parsed.chain_id, 1);
+        assert_eq!(parsed.nonce, "32891756abcdef01");
+        assert_eq!(parsed.resources.len(), 2);
+        assert!(parsed.validate("app.ibelieve.finance", now()).is_ok());
+
+        // Sem statement, a ABNF mantém as duas linhas em branco
+        let minimal = format!(
+            "https://localhost:8085 wants you to sign in with your Ethereum account:\n{}\n\n\n\
+            URI: https://localhost:8085\nVersion: 1\nChain ID: 137\nNonce: abcdefgh12\nIssued At: 2026-10-18T12:00:00.000+03:00",
+            to_checksum(&wallet.address(), None)
+        );
+        let parsed: SiweMessage = minimal.parse().unwrap();
+        assert_eq!(parsed.scheme.as_deref(), Some("https"));
+        assert_eq!(parsed.domain, "localhost:8085");
+        assert_eq!(parsed.statement, None);
+        assert_eq!(parsed.chain_id, 137);
+    }
+
+    #[test]
+    fn test_rejects_malformed_and_invalid_messages() {
+        let wallet: LocalWallet = KEY.parse().unwrap();
+        let valid = message(wallet.address(), "2026-10-18T12:05:00Z");
+
+        // O endereço precisa do checksum EIP-55
+        let lowercase = valid.replace(&to_checksum(&wallet.address(), None), &format!("{:?}", wallet.address()));
+        assert!(matches!(lowercase.parse::<SiweMessage>(), Err(SiweError::Malformed(_))));
+        let short_nonce = valid.replace("32891756abcdef01", "abc");
+        assert!(matches!(short_nonce.parse::<SiweMessage>(), Err(SiweError::Malformed(_))));
+        assert!(matches!(format!("{}\n", valid).parse::<SiweMessage>(), Err(SiweError::Malformed(_))));
+
+        let parsed: SiweMessage = valid.parse().unwrap();
+        assert!(matches!(parsed.validate("evil.example", now()), Err(SiweError::DomainMismatch(_))));
+        let expired: SiweMessage = message(wallet.address(), "2026-10-18T12:00:30Z").parse().unwrap();
+        assert!(matches!(expired.validate("app.ibelieve.finance", now()), Err(SiweError::Expired)));
+        let early = now() - Duration::minutes(5);
+        assert!(matches!(parsed.validate("app.ibelieve.finance", early), Err(SiweError::NotYetValid)));
+    }
+
+    #[actix_rt::test]
+    async fn test_verifies_personal_sign_signature() {
+        let wallet: LocalWallet = KEY.parse().unwrap();
+        let text = message(wallet.address(), "2026-10-18T12:05:00Z");
+        let signature = Bytes::from(wallet.sign_message(&text).await.unwrap().to_vec());
+
+        let parsed = verifier().verify(&text, &signature, now()).await.unwrap();
+        assert_eq!(parsed.address, wallet.address());
+
+        // Assinada por outra conta, numa rede sem RPC para EIP-1271
+        let other = LocalWallet::new(&mut ethers::core::rand::thread_rng());
+        let forged = Bytes::from(other.sign_message(&text).await.unwrap().to_vec());
+        assert!(matches!(
+            verifier().verify(&text, &forged, now()).await,
+            Err(SiweError::UnsupportedChain(1))
+        ));
+    }
+
+    #[test]
+    fn test_rpc_urls_follow_blockchain_config() {
+        let vars = |pairs: &'static [(&'static str, &'static str)]| {
+            move |name: &str| pairs.iter().find(|(key, _)| *key == name).map(|(_, value)| value.to_string())
+        };
+
+        let (domain, ttl, urls) = SiweVerifier::from_vars(vars(&[
+            ("SIWE_DOMAIN", "app.ibelieve.finance"),
+            ("BLOCKCHAIN_CHAINS", "1,137"),
+            ("CHAIN_1_RPC_URLS", "https://a.example, https://b.example"),
+            ("CHAIN_137_RPC_URLS", "https://polygon.example"),
+        ]))
+        .unwrap();
+        assert_eq!(domain, "app.ibelieve.finance");
+        assert_eq!(ttl, Duration::seconds(300));
+        assert_eq!(urls.get(&1).map(String::as_str), Some("https://a.example"));
+        assert_eq!(urls.get(&137).map(String::as_str), Some("https://polygon.example"));
+
+        let (_, ttl, urls) = SiweVerifier::from_vars(vars(&[
+            ("SIWE_DOMAIN", "app.ibelieve.finance"),
+            ("SIWE_NONCE_TTL_SECS", "60"),
+            ("ETHEREUM_RPC_URL", "http://localhost:8545"),
+            ("ETHEREUM_CHAIN_ID", "31337"),
+        ]))
+        .unwrap();
+        assert_eq!(ttl, Duration::seconds(60));
+        assert_eq!(urls.get(&31337).map(String::as_str), Some("http://localhost:8545"));
+
+        assert!(matches!(SiweVerifier::from_vars(vars(&[("SIWE_DOMAIN", " ")])), Err(SiweError::Config(_))));
+    }
+}
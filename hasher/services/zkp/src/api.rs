@@ -1,23 +1,46 @@
-use actix_web::{get, post, web, HttpResponse, Responder};
-use common::models::{ZkpProof, ErrorResponse, User};
+use actix_web::{get, http::StatusCode, post, put, web, HttpRequest, HttpResponse, Responder};
+use common::bulkhead::Bulkhead;
+use common::models::{ErrorResponse, User};
 use log::{error, info};
 use sqlx::PgPool;
 use uuid::Uuid;
 
-use crate::services::ZkpService;
+use crate::circuits::{registry, CircuitError, CircuitSelector, SOLVENCY_CIRCUIT};
+use crate::models::{
+    AnalysisQuery, AttributeProofRequest, EvmCalldataRequest, EvmProofRequest, ExportQuery, ProofJob, RevocationReason,
+    RevokeProofRequest, UpdateWhitelistRequest, UpdateWhitelistResponse, VerifyBatchRequest, VerifyProofRequest,
+    WhitelistProofRequest,
+};
+use crate::services::{
+    analyze_circuit, proof_anchor, AccessError, AnchorError, ApiAccess, Caller, AttributeError, AttributeService, EvmParameterStore, EvmService,
+    JobError, ParameterStore, ParameterStoreError, ProfileError, ProofError, ProofJobQueue, ProofStatusService,
+    StatusError, VerificationError, WhitelistError, WhitelistService, ZkpService,
+};
 
 #[derive(Debug, serde::Deserialize)]
 pub struct GenerateProofRequest {
     pub user_id: Uuid,
+    /// Limite mínimo de saldo, em decimal na unidade mínima do ativo
+    pub threshold: String,
+    /// Circuito de solvência a usar; sem ele, a versão ativa mais recente
+    pub circuit: Option<CircuitSelector>,
+    /// Contexto de aplicação em que a prova vale (ex.: `loan:42`); o usuário
+    /// só obtém uma prova por contexto
+    pub context: String,
 }
 
-/// Gera uma prova de conhecimento zero
+/// Enfileira uma prova de solvência (saldo >= limite) de conhecimento zero.
+/// O andamento é consultado em `/jobs/{job_id}`. Só o próprio usuário pede as
+/// suas provas (token do login com a carteira).
 #[utoipa::path(
     post,
     path = "/generate",
     request_body = GenerateProofRequest,
     responses(
-        (status = 201, description = "Prova gerada com sucesso", body = ZkpProof),
+        (status = 202, description = "Job de prova enfileirado", body = ProofJob),
+        (status = 400, description = "Limite ou contexto inválido", body = ErrorResponse),
+        (status = 401, description = "Token ausente ou inválido", body = ErrorResponse),
+        (status = 403, description = "O token não é do usuário", body = ErrorResponse),
         (status = 404, description = "Usuário não encontrado", body = ErrorResponse),
         (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
     ),
@@ -25,52 +48,957 @@ pub struct GenerateProofRequest {
 )]
 #[post("/generate")]
 pub async fn generate_proof(
+    http: HttpRequest,
     request: web::Json<GenerateProofRequest>,
     pool: web::Data<PgPool>,
+    jobs: web::Data<ProofJobQueue>,
+    access: web::Data<ApiAccess>,
 ) -> impl Responder {
     info!("Recebida requisição para gerar prova para usuário: {}", request.user_id);
 
+    if let Err(e) = access.subject(authorization(&http), request.user_id) {
+        return access_error_response(e);
+    }
+
+    let threshold = match request.threshold.parse::<u128>() {
+        Ok(threshold) => threshold,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: "invalid_threshold".to_string(),
+                message: format!("Limite inválido: {}", e),
+            })
+        }
+    };
+
     // Buscar usuário
-    let user = match sqlx::query_as!(
+    let user = match fetch_user(pool.get_ref(), request.user_id).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    let version = match &request.circuit {
+        Some(circuit) if circuit.name != SOLVENCY_CIRCUIT => {
+            return job_error_response(JobError::UnsupportedCircuit(circuit.name.clone()))
+        }
+        Some(circuit) => circuit.version.as_deref(),
+        None => None,
+    };
+
+    // Saldo e limite são conferidos pelo worker; falhas ficam registradas no job
+    match jobs.enqueue_solvency(user.id, version, threshold, &request.context).await {
+        Ok(job) => HttpResponse::Accepted().json(job),
+        Err(e) => job_error_response(e),
+    }
+}
+
+/// Consulta o estado de um job de prova do usuário do token
+#[utoipa::path(
+    get,
+    path = "/jobs/{job_id}",
+    responses(
+        (status = 200, description = "Job encontrado", body = ProofJob),
+        (status = 401, description = "Token ausente ou inválido", body = ErrorResponse),
+        (status = 404, description = "Job não encontrado", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
+    ),
+    params(
+        ("job_id" = Uuid, Path, description = "ID do job")
+    ),
+    tag = "zkp"
+)]
+#[get("/jobs/{job_id}")]
+pub async fn get_job(
+    http: HttpRequest,
+    job_id: web::Path<Uuid>,
+    jobs: web::Data<ProofJobQueue>,
+    access: web::Data<ApiAccess>,
+) -> impl Responder {
+    match owned_job(&http, access.get_ref(), jobs.get_ref(), job_id.into_inner()).await {
+        Ok(job) => HttpResponse::Ok().json(job),
+        Err(response) => response,
+    }
+}
+
+/// Cancela um job de prova do usuário do token que ainda não terminou
+#[utoipa::path(
+    post,
+    path = "/jobs/{job_id}/cancel",
+    responses(
+        (status = 200, description = "Job cancelado", body = ProofJob),
+        (status = 401, description = "Token ausente ou inválido", body = ErrorResponse),
+        (status = 404, description = "Job não encontrado", body = ErrorResponse),
+        (status = 409, description = "Job já terminou", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
+    ),
+    params(
+        ("job_id" = Uuid, Path, description = "ID do job")
+    ),
+    tag = "zkp"
+)]
+#[post("/jobs/{job_id}/cancel")]
+pub async fn cancel_job(
+    http: HttpRequest,
+    job_id: web::Path<Uuid>,
+    jobs: web::Data<ProofJobQueue>,
+    access: web::Data<ApiAccess>,
+) -> impl Responder {
+    info!("Recebida requisição para cancelar o job {}", job_id);
+
+    let job = match owned_job(&http, access.get_ref(), jobs.get_ref(), job_id.into_inner()).await {
+        Ok(job) => job,
+        Err(response) => return response,
+    };
+
+    match jobs.cancel(job.id).await {
+        Ok(job) => HttpResponse::Ok().json(job),
+        Err(e) => job_error_response(e),
+    }
+}
+
+/// Job do usuário do token. Jobs de outros usuários respondem como
+/// inexistentes, para não revelar o motivo das falhas (ex.:
+/// `insufficient_balance`, que deixaria descobrir o saldo por busca binária).
+async fn owned_job(
+    http: &HttpRequest,
+    access: &ApiAccess,
+    jobs: &ProofJobQueue,
+    job_id: Uuid,
+) -> Result<ProofJob, HttpResponse> {
+    let user_id = match access.caller(authorization(http)) {
+        Ok(Caller::User(user_id)) => user_id,
+        Ok(Caller::Admin(_)) => return Err(access_error_response(AccessError::Forbidden)),
+        Err(e) => return Err(access_error_response(e)),
+    };
+
+    match jobs.get(job_id).await {
+        Ok(Some(job)) if job.user_id == user_id => Ok(job),
+        Ok(_) => Err(job_error_response(JobError::NotFound(job_id))),
+        Err(e) => Err(job_error_response(e)),
+    }
+}
+
+/// Cabeçalho `Authorization` da requisição
+fn authorization(http: &HttpRequest) -> Option<&str> {
+    http.headers().get("Authorization").and_then(|h| h.to_str().ok())
+}
+
+fn access_error_response(e: AccessError) -> HttpResponse {
+    let body = ErrorResponse {
+        error: e.code().to_string(),
+        message: e.to_string(),
+    };
+    match e {
+        AccessError::Unauthenticated => HttpResponse::Unauthorized().json(body),
+        AccessError::Forbidden => HttpResponse::Forbidden().json(body),
+        AccessError::Config(_) => {
+            error!("Erro de acesso: {}", e);
+            HttpResponse::InternalServerError().json(body)
+        }
+    }
+}
+
+fn job_error_response(e: JobError) -> HttpResponse {
+    let body = ErrorResponse {
+        error: e.code().to_string(),
+        message: e.to_string(),
+    };
+    match &e {
+        JobError::NotFound(_) => HttpResponse::NotFound().json(body),
+        JobError::AlreadyFinished { .. } => HttpResponse::Conflict().json(body),
+        JobError::UnsupportedCircuit(_) | JobError::Proof(ProofError::InvalidContext(_)) => {
+            HttpResponse::BadRequest().json(body)
+        }
+        JobError::Proof(ProofError::Circuit(c)) => HttpResponse::build(circuit_error_status(c)).json(body),
+        _ => {
+            error!("Erro no job de prova: {}", e);
+            HttpResponse::InternalServerError().json(body)
+        }
+    }
+}
+
+/// Status HTTP dos erros de seleção de circuito. Versões obsoletas respondem
+/// 410: continuam verificando, mas não geram provas novas.
+fn circuit_error_status(e: &CircuitError) -> StatusCode {
+    match e {
+        CircuitError::UnknownCircuit(_) | CircuitError::UnknownVersion { .. } => StatusCode::NOT_FOUND,
+        CircuitError::InvalidVersion(_) => StatusCode::BAD_REQUEST,
+        CircuitError::Deprecated { .. } | CircuitError::NoActiveVersion(_) => StatusCode::GONE,
+        CircuitError::SchemaMismatch { .. } | CircuitError::Synthesis(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+/// Lista o registro de circuitos: versões, esquema das entradas públicas,
+/// número de restrições e chave de verificação (`format` = `hex`, `json` ou `snarkjs`)
+#[utoipa::path(
+    get,
+    path = "/circuits",
+    params(
+        ("format" = Option<String>, Query, description = "hex (padrão), json ou snarkjs")
+    ),
+    responses(
+        (status = 200, description = "Circuitos registrados", body = Vec<CircuitInfo>),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
+    ),
+    tag = "zkp"
+)]
+#[get("/circuits")]
+pub async fn list_circuits(
+    query: web::Query<ExportQuery>,
+    pool: web::Data<PgPool>,
+    parameters: web::Data<ParameterStore>,
+) -> impl Responder {
+    let service = ZkpService::new(pool.get_ref(), parameters.get_ref());
+
+    match service.circuits(query.format).await {
+        Ok(circuits) => HttpResponse::Ok().json(circuits),
+        Err(e) => verification_error_response(VerificationError::Parameters(e)),
+    }
+}
+
+/// Analisa uma versão de circuito: restrições, variáveis, entradas públicas e
+/// densidade das combinações lineares, mais os tempos medidos de setup, prova
+/// e verificação de uma instância de exemplo (com `measure=true`). A medição
+/// ocupa uma vaga das provas e responde 503 quando todas seguem ocupadas.
+#[utoipa::path(
+    get,
+    path = "/circuits/{name}/analysis",
+    params(
+        ("name" = String, Path, description = "Nome do circuito"),
+        ("version" = Option<String>, Query, description = "Versão; sem ela, a ativa mais recente"),
+        ("measure" = Option<bool>, Query, description = "Mede os tempos (padrão false)")
+    ),
+    responses(
+        (status = 200, description = "Análise do circuito", body = CircuitAnalysis),
+        (status = 400, description = "Versão inválida", body = ErrorResponse),
+        (status = 404, description = "Circuito ou versão desconhecida", body = ErrorResponse),
+        (status = 503, description = "Vagas de prova ocupadas; a medição não foi feita", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
+    ),
+    tag = "zkp"
+)]
+#[get("/circuits/{name}/analysis")]
+pub async fn analyze_circuit_version(
+    name: web::Path<String>,
+    query: web::Query<AnalysisQuery>,
+    bulkhead: web::Data<Bulkhead>,
+) -> impl Responder {
+    info!("Recebida requisição para analisar o circuito {}", name);
+
+    // Versões obsoletas também podem ser analisadas
+    let result = match registry().resolve(&name, query.version.as_deref()) {
+        Ok(definition) => analyze_circuit(definition, query.measure, bulkhead.get_ref()).await,
+        Err(e) => Err(e.into()),
+    };
+
+    match result {
+        Ok(analysis) => HttpResponse::Ok().json(analysis),
+        Err(e) => {
+            let body = ErrorResponse {
+                error: e.code().to_string(),
+                message: e.to_string(),
+            };
+            match &e {
+                ProfileError::Circuit(c) => HttpResponse::build(circuit_error_status(c)).json(body),
+                ProfileError::NoSample(_) => HttpResponse::NotImplemented().json(body),
+                ProfileError::Busy => HttpResponse::ServiceUnavailable().json(body),
+                _ => {
+                    error!("Erro ao analisar circuito: {}", e);
+                    HttpResponse::InternalServerError().json(body)
+                }
+            }
+        }
+    }
+}
+
+/// Verifica uma prova sem acesso às provas armazenadas
+#[utoipa::path(
+    post,
+    path = "/verify",
+    request_body = VerifyProofRequest,
+    responses(
+        (status = 200, description = "Prova válida", body = VerifyProofResponse),
+        (status = 400, description = "Prova, chave, entradas ou contexto malformados", body = ErrorResponse),
+        (status = 404, description = "Versão de circuito desconhecida", body = ErrorResponse),
+        (status = 409, description = "Prova já usada no contexto", body = ErrorResponse),
+        (status = 422, description = "Prova inválida ou de outro contexto", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
+    ),
+    tag = "zkp"
+)]
+#[post("/verify")]
+pub async fn verify_proof(
+    request: web::Json<VerifyProofRequest>,
+    pool: web::Data<PgPool>,
+    parameters: web::Data<ParameterStore>,
+) -> impl Responder {
+    info!("Recebida requisição para verificar prova");
+
+    let service = ZkpService::new(pool.get_ref(), parameters.get_ref());
+
+    match service.verify_proof(&request).await {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(e) => verification_error_response(e),
+    }
+}
+
+/// Verifica um lote de provas contra a mesma chave, apontando as inválidas
+#[utoipa::path(
+    post,
+    path = "/verify/batch",
+    request_body = VerifyBatchRequest,
+    responses(
+        (status = 200, description = "Lote verificado; resultado por prova", body = VerifyBatchResponse),
+        (status = 400, description = "Chave ausente ou malformada, ou lote grande demais", body = ErrorResponse),
+        (status = 404, description = "Versão de circuito desconhecida", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
+    ),
+    tag = "zkp"
+)]
+#[post("/verify/batch")]
+pub async fn verify_batch(
+    request: web::Json<VerifyBatchRequest>,
+    pool: web::Data<PgPool>,
+    parameters: web::Data<ParameterStore>,
+) -> impl Responder {
+    info!(
+        "Recebida requisição para verificar lote ({} provas, {} salvas)",
+        request.proofs.len(),
+        request.proof_ids.len()
+    );
+
+    let service = ZkpService::new(pool.get_ref(), parameters.get_ref());
+
+    match service.verify_batch(&request).await {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(e) => verification_error_response(e),
+    }
+}
+
+fn verification_error_response(e: VerificationError) -> HttpResponse {
+    let body = ErrorResponse {
+        error: e.code().to_string(),
+        message: e.to_string(),
+    };
+    match &e {
+        VerificationError::InvalidProof
+        | VerificationError::ContextMismatch
+        | VerificationError::UnknownNullifier
+        | VerificationError::ProofRevoked(_)
+        | VerificationError::ProofExpired(_)
+        | VerificationError::ProofNotYetValid(_) => HttpResponse::UnprocessableEntity().json(body),
+        VerificationError::NullifierUsed(_) => HttpResponse::Conflict().json(body),
+        VerificationError::Circuit(c) => HttpResponse::build(circuit_error_status(c)).json(body),
+        VerificationError::ProofNotFound(_)
+        | VerificationError::Parameters(ParameterStoreError::UnknownCircuitVersion(_))
+        | VerificationError::Parameters(ParameterStoreError::UnknownFingerprint(_)) => {
+            HttpResponse::NotFound().json(body)
+        }
+        VerificationError::Parameters(_) | VerificationError::Database(_) | VerificationError::Internal(_) => {
+            error!("Erro ao verificar prova: {}", e);
+            HttpResponse::InternalServerError().json(body)
+        }
+        _ => HttpResponse::BadRequest().json(body),
+    }
+}
+
+/// Exporta a chave de verificação registrada para uma versão de circuito
+/// (`format` = `hex`, `json` ou `snarkjs`)
+#[utoipa::path(
+    get,
+    path = "/keys/{circuit_version}",
+    params(
+        ("circuit_version" = String, Path, description = "Versão do circuito"),
+        ("format" = Option<String>, Query, description = "hex (padrão), json ou snarkjs")
+    ),
+    responses(
+        (status = 200, description = "Chave de verificação", body = VerifyingKeyExport),
+        (status = 404, description = "Versão de circuito desconhecida", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
+    ),
+    tag = "zkp"
+)]
+#[get("/keys/{circuit_version}")]
+pub async fn get_verifying_key(
+    circuit_version: web::Path<String>,
+    query: web::Query<ExportQuery>,
+    pool: web::Data<PgPool>,
+    parameters: web::Data<ParameterStore>,
+) -> impl Responder {
+    let service = ZkpService::new(pool.get_ref(), parameters.get_ref());
+
+    match service.export_verifying_key(&circuit_version, query.format).await {
+        Ok(key) => HttpResponse::Ok().json(key),
+        Err(e) => verification_error_response(VerificationError::Parameters(e)),
+    }
+}
+
+/// Substitui o conjunto de carteiras aprovadas
+#[utoipa::path(
+    put,
+    path = "/whitelist",
+    request_body = UpdateWhitelistRequest,
+    responses(
+        (status = 200, description = "Whitelist atualizada", body = UpdateWhitelistResponse),
+        (status = 400, description = "Carteira inválida ou whitelist acima da capacidade", body = ErrorResponse),
+        (status = 401, description = "Token ausente ou inválido", body = ErrorResponse),
+        (status = 403, description = "O token não é de um serviço administrativo", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
+    ),
+    tag = "zkp"
+)]
+#[put("/whitelist")]
+pub async fn update_whitelist(
+    http: HttpRequest,
+    request: web::Json<UpdateWhitelistRequest>,
+    pool: web::Data<PgPool>,
+    parameters: web::Data<ParameterStore>,
+    access: web::Data<ApiAccess>,
+) -> impl Responder {
+    let admin = match access.admin(authorization(&http)) {
+        Ok(admin) => admin,
+        Err(e) => return access_error_response(e),
+    };
+    info!(
+        "Recebida requisição de {} para atualizar whitelist ({} carteiras)",
+        admin,
+        request.wallets.len()
+    );
+
+    let service = WhitelistService::new(pool.get_ref(), parameters.get_ref());
+
+    match service.replace_wallets(&request.wallets, &request.source).await {
+        Ok(wallet_count) => HttpResponse::Ok().json(UpdateWhitelistResponse { wallet_count }),
+        Err(e) => whitelist_error_response(e),
+    }
+}
+
+/// Publica a raiz da árvore com as carteiras aprovadas atuais
+#[utoipa::path(
+    post,
+    path = "/whitelist/root",
+    responses(
+        (status = 201, description = "Raiz publicada", body = WhitelistRoot),
+        (status = 401, description = "Token ausente ou inválido", body = ErrorResponse),
+        (status = 403, description = "O token não é de um serviço administrativo", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
+    ),
+    tag = "zkp"
+)]
+#[post("/whitelist/root")]
+pub async fn publish_whitelist_root(
+    http: HttpRequest,
+    pool: web::Data<PgPool>,
+    parameters: web::Data<ParameterStore>,
+    access: web::Data<ApiAccess>,
+) -> impl Responder {
+    let admin = match access.admin(authorization(&http)) {
+        Ok(admin) => admin,
+        Err(e) => return access_error_response(e),
+    };
+    info!("Recebida requisição de {} para publicar raiz da whitelist", admin);
+
+    let service = WhitelistService::new(pool.get_ref(), parameters.get_ref());
+
+    match service.publish_root().await {
+        Ok(root) => HttpResponse::Created().json(root),
+        Err(e) => whitelist_error_response(e),
+    }
+}
+
+/// Obtém a raiz publicada mais recente da whitelist
+#[utoipa::path(
+    get,
+    path = "/whitelist/root",
+    responses(
+        (status = 200, description = "Raiz encontrada", body = WhitelistRoot),
+        (status = 404, description = "Nenhuma raiz publicada", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
+    ),
+    tag = "zkp"
+)]
+#[get("/whitelist/root")]
+pub async fn get_whitelist_root(
+    pool: web::Data<PgPool>,
+    parameters: web::Data<ParameterStore>,
+) -> impl Responder {
+    let service = WhitelistService::new(pool.get_ref(), parameters.get_ref());
+
+    match service.current_root().await {
+        Ok(Some(root)) => HttpResponse::Ok().json(root),
+        Ok(None) => whitelist_error_response(WhitelistError::NoPublishedRoot),
+        Err(e) => whitelist_error_response(e),
+    }
+}
+
+/// Gera uma prova de que a carteira do usuário está na whitelist, sem revelar
+/// qual é. Só o próprio usuário pede as suas provas: o nullifier vale uma vez
+/// por contexto, e outro chamador o gastaria.
+#[utoipa::path(
+    post,
+    path = "/whitelist/proof",
+    request_body = WhitelistProofRequest,
+    responses(
+        (status = 201, description = "Prova gerada com sucesso", body = ZkpProof),
+        (status = 400, description = "Contexto inválido", body = ErrorResponse),
+        (status = 401, description = "Token ausente ou inválido", body = ErrorResponse),
+        (status = 403, description = "O token não é do usuário", body = ErrorResponse),
+        (status = 404, description = "Usuário não encontrado ou nenhuma raiz publicada", body = ErrorResponse),
+        (status = 409, description = "O usuário já tem uma prova no contexto", body = ErrorResponse),
+        (status = 422, description = "Carteira fora da whitelist publicada", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
+    ),
+    tag = "zkp"
+)]
+#[post("/whitelist/proof")]
+pub async fn generate_whitelist_proof(
+    http: HttpRequest,
+    request: web::Json<WhitelistProofRequest>,
+    pool: web::Data<PgPool>,
+    parameters: web::Data<ParameterStore>,
+    access: web::Data<ApiAccess>,
+) -> impl Responder {
+    info!("Recebida requisição para prova de whitelist do usuário: {}", request.user_id);
+
+    if let Err(e) = access.subject(authorization(&http), request.user_id) {
+        return access_error_response(e);
+    }
+
+    let user = match fetch_user(pool.get_ref(), request.user_id).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    let service = WhitelistService::new(pool.get_ref(), parameters.get_ref());
+
+    match service.generate_proof(&user, &request.context).await {
+        Ok(proof) => HttpResponse::Created().json(proof),
+        Err(e) => whitelist_error_response(e),
+    }
+}
+
+fn whitelist_error_response(e: WhitelistError) -> HttpResponse {
+    let body = ErrorResponse {
+        error: e.code().to_string(),
+        message: e.to_string(),
+    };
+    match &e {
+        WhitelistError::InvalidWallet(_)
+        | WhitelistError::Tree(_)
+        | WhitelistError::Proof(ProofError::InvalidContext(_)) => HttpResponse::BadRequest().json(body),
+        WhitelistError::Proof(ProofError::NullifierUsed(_)) => HttpResponse::Conflict().json(body),
+        WhitelistError::NoPublishedRoot => HttpResponse::NotFound().json(body),
+        WhitelistError::NotWhitelisted(_) => HttpResponse::UnprocessableEntity().json(body),
+        WhitelistError::Proof(ProofError::Circuit(c)) => HttpResponse::build(circuit_error_status(c)).json(body),
+        WhitelistError::Proof(_) | WhitelistError::Database(_) => {
+            error!("Erro na whitelist: {}", e);
+            HttpResponse::InternalServerError().json(body)
+        }
+    }
+}
+
+/// Gera uma prova de que os atributos atestados do usuário (idade, país de
+/// residência, nível de KYC) satisfazem os predicados, sem revelá-los. Só o
+/// próprio usuário pede as suas provas: a resposta diz se os predicados valem,
+/// e outro chamador poderia testá-los até descobrir os atributos.
+#[utoipa::path(
+    post,
+    path = "/attributes/proof",
+    request_body = AttributeProofRequest,
+    responses(
+        (status = 201, description = "Prova gerada com sucesso", body = ZkpProof),
+        (status = 400, description = "Predicado ou contexto inválido", body = ErrorResponse),
+        (status = 401, description = "Token ausente ou inválido", body = ErrorResponse),
+        (status = 403, description = "O token não é do usuário", body = ErrorResponse),
+        (status = 404, description = "Usuário ou atributos atestados não encontrados", body = ErrorResponse),
+        (status = 409, description = "O usuário já tem uma prova no contexto", body = ErrorResponse),
+        (status = 422, description = "Atributos não satisfazem os predicados", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
+    ),
+    tag = "zkp"
+)]
+#[post("/attributes/proof")]
+pub async fn generate_attribute_proof(
+    http: HttpRequest,
+    request: web::Json<AttributeProofRequest>,
+    pool: web::Data<PgPool>,
+    parameters: web::Data<ParameterStore>,
+    access: web::Data<ApiAccess>,
+) -> impl Responder {
+    info!("Recebida requisição para prova de atributos do usuário: {}", request.user_id);
+
+    if let Err(e) = access.subject(authorization(&http), request.user_id) {
+        return access_error_response(e);
+    }
+
+    let user = match fetch_user(pool.get_ref(), request.user_id).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    let service = AttributeService::new(pool.get_ref(), parameters.get_ref());
+
+    match service.generate_proof(&user, &request.predicates, &request.context).await {
+        Ok(proof) => HttpResponse::Created().json(proof),
+        Err(e) => attribute_error_response(e),
+    }
+}
+
+fn attribute_error_response(e: AttributeError) -> HttpResponse {
+    let body = ErrorResponse {
+        error: e.code().to_string(),
+        message: e.to_string(),
+    };
+    match &e {
+        AttributeError::InvalidPredicate(_) | AttributeError::Proof(ProofError::InvalidContext(_)) => {
+            HttpResponse::BadRequest().json(body)
+        }
+        AttributeError::Proof(ProofError::NullifierUsed(_)) => HttpResponse::Conflict().json(body),
+        AttributeError::NotFound(_) => HttpResponse::NotFound().json(body),
+        AttributeError::NotSatisfied => HttpResponse::UnprocessableEntity().json(body),
+        AttributeError::Proof(ProofError::Circuit(c)) => HttpResponse::build(circuit_error_status(c)).json(body),
+        AttributeError::InvalidAttributes(_) | AttributeError::Proof(_) | AttributeError::Database(_) => {
+            error!("Erro na prova de atributos: {}", e);
+            HttpResponse::InternalServerError().json(body)
+        }
+    }
+}
+
+/// Exporta o contrato Solidity que verifica on-chain as provas BN254 de uma versão de circuito
+#[utoipa::path(
+    get,
+    path = "/evm/{circuit_version}/verifier",
+    params(
+        ("circuit_version" = String, Path, description = "Versão do circuito")
+    ),
+    responses(
+        (status = 200, description = "Código-fonte do verificador", body = String, content_type = "text/plain"),
+        (status = 404, description = "Versão de circuito desconhecida", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
+    ),
+    tag = "zkp"
+)]
+#[get("/evm/{circuit_version}/verifier")]
+pub async fn get_evm_verifier(
+    circuit_version: web::Path<String>,
+    pool: web::Data<PgPool>,
+    evm_parameters: web::Data<EvmParameterStore>,
+) -> impl Responder {
+    let service = EvmService::new(pool.get_ref(), evm_parameters.get_ref());
+
+    match service.verifier_contract(&circuit_version).await {
+        Ok(source) => HttpResponse::Ok().content_type("text/plain; charset=utf-8").body(source),
+        Err(e) => verification_error_response(VerificationError::Parameters(e)),
+    }
+}
+
+/// Exporta a chave de verificação BN254 de uma versão de circuito
+#[utoipa::path(
+    get,
+    path = "/evm/{circuit_version}/key",
+    params(
+        ("circuit_version" = String, Path, description = "Versão do circuito"),
+        ("format" = Option<String>, Query, description = "hex (padrão), json ou snarkjs")
+    ),
+    responses(
+        (status = 200, description = "Chave de verificação", body = VerifyingKeyExport),
+        (status = 404, description = "Versão de circuito desconhecida", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
+    ),
+    tag = "zkp"
+)]
+#[get("/evm/{circuit_version}/key")]
+pub async fn get_evm_verifying_key(
+    circuit_version: web::Path<String>,
+    query: web::Query<ExportQuery>,
+    pool: web::Data<PgPool>,
+    evm_parameters: web::Data<EvmParameterStore>,
+) -> impl Responder {
+    let service = EvmService::new(pool.get_ref(), evm_parameters.get_ref());
+
+    match service.export_verifying_key(&circuit_version, query.format).await {
+        Ok(key) => HttpResponse::Ok().json(key),
+        Err(e) => verification_error_response(VerificationError::Parameters(e)),
+    }
+}
+
+/// Gera uma prova de solvência no BN254, com a calldata do verificador on-chain
+#[utoipa::path(
+    post,
+    path = "/evm/proofs",
+    request_body = EvmProofRequest,
+    responses(
+        (status = 201, description = "Prova gerada com sucesso", body = EvmProof),
+        (status = 400, description = "Limite inválido", body = ErrorResponse),
+        (status = 401, description = "Token ausente ou inválido", body = ErrorResponse),
+        (status = 403, description = "O token não é do usuário", body = ErrorResponse),
+        (status = 404, description = "Usuário ou saldo verificado não encontrado", body = ErrorResponse),
+        (status = 422, description = "Saldo abaixo do limite", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
+    ),
+    tag = "zkp"
+)]
+#[post("/evm/proofs")]
+pub async fn generate_evm_proof(
+    http: HttpRequest,
+    request: web::Json<EvmProofRequest>,
+    pool: web::Data<PgPool>,
+    evm_parameters: web::Data<EvmParameterStore>,
+    access: web::Data<ApiAccess>,
+) -> impl Responder {
+    info!("Recebida requisição para gerar prova BN254 para usuário: {}", request.user_id);
+
+    // Como em `/generate`: o resultado (saldo abaixo do limite) só vai para o usuário
+    if let Err(e) = access.subject(authorization(&http), request.user_id) {
+        return access_error_response(e);
+    }
+
+    let threshold = match request.threshold.parse::<u128>() {
+        Ok(threshold) => threshold,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: "invalid_threshold".to_string(),
+                message: format!("Limite inválido: {}", e),
+            })
+        }
+    };
+
+    let user = match fetch_user(pool.get_ref(), request.user_id).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    let service = EvmService::new(pool.get_ref(), evm_parameters.get_ref());
+
+    match service.generate_solvency_proof(user.id, threshold).await {
+        Ok(proof) => HttpResponse::Created().json(proof),
+        Err(e) => proof_error_response(e),
+    }
+}
+
+/// Monta a calldata de `verifyProof` para uma prova BN254, conferindo-a antes
+#[utoipa::path(
+    post,
+    path = "/evm/calldata",
+    request_body = EvmCalldataRequest,
+    responses(
+        (status = 200, description = "Calldata da prova", body = EvmCalldata),
+        (status = 400, description = "Prova ou entradas malformadas", body = ErrorResponse),
+        (status = 404, description = "Versão de circuito desconhecida", body = ErrorResponse),
+        (status = 422, description = "Prova inválida", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
+    ),
+    tag = "zkp"
+)]
+#[post("/evm/calldata")]
+pub async fn evm_calldata(
+    request: web::Json<EvmCalldataRequest>,
+    pool: web::Data<PgPool>,
+    evm_parameters: web::Data<EvmParameterStore>,
+) -> impl Responder {
+    let service = EvmService::new(pool.get_ref(), evm_parameters.get_ref());
+
+    match service.calldata(&request).await {
+        Ok(calldata) => HttpResponse::Ok().json(calldata),
+        Err(e) => verification_error_response(e),
+    }
+}
+
+fn proof_error_response(e: ProofError) -> HttpResponse {
+    let body = ErrorResponse {
+        error: e.code().to_string(),
+        message: e.to_string(),
+    };
+    match &e {
+        ProofError::BalanceNotFound(_) | ProofError::Parameters(ParameterStoreError::UnknownCircuitVersion(_)) => {
+            HttpResponse::NotFound().json(body)
+        }
+        ProofError::InsufficientBalance => HttpResponse::UnprocessableEntity().json(body),
+        ProofError::Circuit(c) => HttpResponse::build(circuit_error_status(c)).json(body),
+        _ => {
+            error!("Erro ao gerar prova: {}", e);
+            HttpResponse::InternalServerError().json(body)
+        }
+    }
+}
+
+async fn fetch_user(pool: &PgPool, user_id: Uuid) -> Result<User, HttpResponse> {
+    match sqlx::query_as!(
         User,
         "SELECT * FROM users WHERE id = $1",
-        request.user_id
+        user_id
     )
-    .fetch_optional(pool.get_ref())
+    .fetch_optional(pool)
     .await {
-        Ok(Some(user)) => user,
-        Ok(None) => {
-            return HttpResponse::NotFound().json(ErrorResponse {
-                error: "not_found".to_string(),
-                message: "Usuário não encontrado".to_string(),
-            })
-        }
+        Ok(Some(user)) => Ok(user),
+        Ok(None) => Err(HttpResponse::NotFound().json(ErrorResponse {
+            error: "not_found".to_string(),
+            message: "Usuário não encontrado".to_string(),
+        })),
         Err(e) => {
             error!("Erro ao buscar usuário: {}", e);
-            return HttpResponse::InternalServerError().json(ErrorResponse {
+            Err(HttpResponse::InternalServerError().json(ErrorResponse {
                 error: "internal_server_error".to_string(),
                 message: format!("Erro ao buscar usuário: {}", e),
-            });
+            }))
         }
-    };
+    }
+}
+
+/// Exporta uma prova salva com suas entradas públicas, na codificação
+/// canônica (`hex`), em JSON ou no formato do snarkjs
+#[utoipa::path(
+    get,
+    path = "/{proof_id}/export",
+    params(
+        ("proof_id" = Uuid, Path, description = "ID da prova"),
+        ("format" = Option<String>, Query, description = "hex (padrão), json ou snarkjs")
+    ),
+    responses(
+        (status = 200, description = "Prova exportada", body = ProofExport),
+        (status = 400, description = "Prova armazenada malformada", body = ErrorResponse),
+        (status = 404, description = "Prova não encontrada", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
+    ),
+    tag = "zkp"
+)]
+#[get("/{proof_id}/export")]
+pub async fn export_proof(
+    proof_id: web::Path<Uuid>,
+    query: web::Query<ExportQuery>,
+    pool: web::Data<PgPool>,
+    parameters: web::Data<ParameterStore>,
+) -> impl Responder {
+    let service = ZkpService::new(pool.get_ref(), parameters.get_ref());
 
-    // Criar serviço ZKP
-    let service = ZkpService::new(pool.get_ref());
+    match service.export_proof(*proof_id, query.format).await {
+        Ok(export) => HttpResponse::Ok().json(export),
+        Err(e) => verification_error_response(e),
+    }
+}
 
-    // Gerar prova
-    match service.generate_proof(&user).await {
-        Ok(proof) => HttpResponse::Created().json(proof),
+/// Consulta a ancoragem de uma prova no ZKProofRegistry: hash registrado,
+/// transação e confirmação
+#[utoipa::path(
+    get,
+    path = "/{proof_id}/anchor",
+    params(
+        ("proof_id" = Uuid, Path, description = "ID da prova")
+    ),
+    responses(
+        (status = 200, description = "Estado da ancoragem", body = ProofAnchor),
+        (status = 404, description = "Prova não encontrada", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
+    ),
+    tag = "zkp"
+)]
+#[get("/{proof_id}/anchor")]
+pub async fn get_proof_anchor(proof_id: web::Path<Uuid>, pool: web::Data<PgPool>) -> impl Responder {
+    match proof_anchor(pool.get_ref(), proof_id.into_inner()).await {
+        Ok(anchor) => HttpResponse::Ok().json(anchor),
         Err(e) => {
-            error!("Erro ao gerar prova: {}", e);
-            HttpResponse::InternalServerError().json(ErrorResponse {
-                error: "internal_server_error".to_string(),
-                message: format!("Erro ao gerar prova: {}", e),
-            })
+            let body = ErrorResponse {
+                error: e.code().to_string(),
+                message: e.to_string(),
+            };
+            match e {
+                AnchorError::NotFound(_) => HttpResponse::NotFound().json(body),
+                _ => {
+                    error!("Erro ao consultar ancoragem: {}", e);
+                    HttpResponse::InternalServerError().json(body)
+                }
+            }
+        }
+    }
+}
+
+/// Revoga uma prova emitida (ex.: o saldo mudou); a partir daí a verificação
+/// a recusa e a lista de status a marca como inválida. Pede o token de um
+/// serviço administrativo, ou o do usuário dono da prova com o motivo
+/// `user_request`.
+#[utoipa::path(
+    post,
+    path = "/{proof_id}/revoke",
+    params(
+        ("proof_id" = Uuid, Path, description = "ID da prova")
+    ),
+    request_body = RevokeProofRequest,
+    responses(
+        (status = 200, description = "Prova revogada", body = ProofStatus),
+        (status = 401, description = "Token ausente ou inválido", body = ErrorResponse),
+        (status = 403, description = "Motivo não permitido ao usuário", body = ErrorResponse),
+        (status = 404, description = "Prova não encontrada", body = ErrorResponse),
+        (status = 409, description = "Prova já revogada", body = ErrorResponse),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
+    ),
+    tag = "zkp"
+)]
+#[post("/{proof_id}/revoke")]
+pub async fn revoke_proof(
+    http: HttpRequest,
+    proof_id: web::Path<Uuid>,
+    request: web::Json<RevokeProofRequest>,
+    pool: web::Data<PgPool>,
+    access: web::Data<ApiAccess>,
+) -> impl Responder {
+    // O ID da prova aparece na exportação e na ancoragem, então não basta conhecê-lo
+    let owner = match access.caller(authorization(&http)) {
+        Ok(Caller::Admin(admin)) => {
+            info!("Revogação da prova {} pedida por {}", proof_id, admin);
+            None
+        }
+        Ok(Caller::User(user_id)) if matches!(request.reason, RevocationReason::UserRequest) => Some(user_id),
+        Ok(Caller::User(_)) => return access_error_response(AccessError::Forbidden),
+        Err(e) => return access_error_response(e),
+    };
+    let service = ProofStatusService::new(pool.get_ref());
+
+    match service
+        .revoke(*proof_id, request.reason, request.comment.as_deref(), owner)
+        .await
+    {
+        Ok(status) => HttpResponse::Ok().json(status),
+        Err(e) => status_error_response(e),
+    }
+}
+
+/// Lista de status das provas: bitstring comprimida com gzip em base64url,
+/// em que o bit `status_index` de cada prova fica ligado quando ela foi
+/// revogada ou expirou
+#[utoipa::path(
+    get,
+    path = "/status-list",
+    responses(
+        (status = 200, description = "Lista de status atual", body = StatusList),
+        (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
+    ),
+    tag = "zkp"
+)]
+#[get("/status-list")]
+pub async fn get_status_list(pool: web::Data<PgPool>) -> impl Responder {
+    let service = ProofStatusService::new(pool.get_ref());
+
+    match service.status_list().await {
+        Ok(list) => HttpResponse::Ok().json(list),
+        Err(e) => status_error_response(e),
+    }
+}
+
+fn status_error_response(e: StatusError) -> HttpResponse {
+    let body = ErrorResponse {
+        error: e.code().to_string(),
+        message: e.to_string(),
+    };
+    match e {
+        StatusError::NotFound(_) => HttpResponse::NotFound().json(body),
+        StatusError::AlreadyRevoked(_) => HttpResponse::Conflict().json(body),
+        StatusError::Internal(_) | StatusError::Database(_) => {
+            error!("Erro ao consultar status de prova: {}", e);
+            HttpResponse::InternalServerError().json(body)
         }
     }
 }
 
-/// Obtém uma prova de conhecimento zero
+/// Obtém uma prova de conhecimento zero, com sua validade e revogação
 #[utoipa::path(
     get,
     path = "/{proof_id}",
@@ -78,7 +1006,7 @@ pub async fn generate_proof(
         ("proof_id" = Uuid, Path, description = "ID da prova")
     ),
     responses(
-        (status = 200, description = "Prova encontrada com sucesso", body = ZkpProof),
+        (status = 200, description = "Prova encontrada com sucesso", body = StoredProof),
         (status = 404, description = "Prova não encontrada", body = ErrorResponse),
         (status = 500, description = "Erro interno do servidor", body = ErrorResponse)
     ),
@@ -88,10 +1016,11 @@ pub async fn generate_proof(
 pub async fn get_proof(
     proof_id: web::Path<Uuid>,
     pool: web::Data<PgPool>,
+    parameters: web::Data<ParameterStore>,
 ) -> impl Responder {
     info!("Recebida requisição para buscar prova: {}", proof_id);
 
-    let service = ZkpService::new(pool.get_ref());
+    let service = ZkpService::new(pool.get_ref(), parameters.get_ref());
 
     match service.get_proof(*proof_id).await {
         Ok(Some(proof)) => HttpResponse::Ok().json(proof),
@@ -0,0 +1,122 @@
+//! Ferramenta offline da cerimônia de setup (ver `zkp_service::ceremony`).
+//!
+//! ```text
+//! zkp-ceremony init <circuito> [versão] <fase1> <saída>
+//! zkp-ceremony contribute <entrada> <saída>
+//! zkp-ceremony verify <circuito> [versão] <fase1> <finais>
+//! zkp-ceremony export <entrada> <saída.params>
+//! ```
+//!
+//! `<fase1>` é o arquivo `phase1radix2m<exp>` da cerimônia Powers of Tau do
+//! Zcash do tamanho do circuito; `init` informa qual quando vier outro.
+//!
+//! O arquivo de `export` vai para `ZKP_PARAMS_DIR/<chave dos parâmetros>.params`,
+//! de onde o serviço o importa na próxima inicialização.
+
+use bls12_381::Scalar;
+use rand::rngs::OsRng;
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::process;
+
+use zkp_service::ceremony::{CeremonyParameters, Phase1, Qap};
+use zkp_service::circuits::{
+    registry, AttributeCircuit, CircuitDefinition, MembershipCircuit, Nullified, SimpleCircuit, SolvencyCircuit,
+    ATTRIBUTES_CIRCUIT_VERSION, MEMBERSHIP_CIRCUIT_VERSION, MEMBERSHIP_NULLIFIED_VERSION, SIMPLE_CIRCUIT_VERSION,
+    SOLVENCY_CIRCUIT_VERSION, SOLVENCY_NULLIFIED_VERSION,
+};
+
+const USAGE: &str = "uso:
+  zkp-ceremony init <circuito> [versão] <fase1> <saída>
+  zkp-ceremony contribute <entrada> <saída>
+  zkp-ceremony verify <circuito> [versão] <fase1> <finais>
+  zkp-ceremony export <entrada> <saída.params>";
+
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+
+    let result = match args.as_slice() {
+        ["init", name, phase1, output] => init(name, None, phase1, output),
+        ["init", name, version, phase1, output] => init(name, Some(version), phase1, output),
+        ["contribute", input, output] => contribute(input, output),
+        ["verify", name, phase1, last] => verify(name, None, phase1, last),
+        ["verify", name, version, phase1, last] => verify(name, Some(version), phase1, last),
+        ["export", input, output] => export(input, output),
+        _ => {
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+
+    if let Err(e) = result {
+        eprintln!("erro: {}", e);
+        process::exit(1);
+    }
+}
+
+/// Circuito da versão escolhida e a fase 1 lida de `phase1`
+fn load(
+    name: &str,
+    version: Option<&str>,
+    phase1: &str,
+) -> Result<(&'static CircuitDefinition, Qap, Phase1), Box<dyn Error>> {
+    let definition = registry().resolve(name, version)?;
+    let qap = match definition.parameters_key {
+        SIMPLE_CIRCUIT_VERSION => Qap::synthesize(SimpleCircuit { a: None, b: None })?,
+        SOLVENCY_CIRCUIT_VERSION => Qap::synthesize(SolvencyCircuit::<Scalar>::blank())?,
+        MEMBERSHIP_CIRCUIT_VERSION => Qap::synthesize(MembershipCircuit::<Scalar>::blank())?,
+        SOLVENCY_NULLIFIED_VERSION => Qap::synthesize(Nullified::blank(SolvencyCircuit::<Scalar>::blank()))?,
+        MEMBERSHIP_NULLIFIED_VERSION => Qap::synthesize(Nullified::blank(MembershipCircuit::<Scalar>::blank()))?,
+        ATTRIBUTES_CIRCUIT_VERSION => Qap::synthesize(Nullified::blank(AttributeCircuit::<Scalar>::blank()))?,
+        key => return Err(format!("circuito {} sem suporte à cerimônia", key).into()),
+    };
+    let phase1 = Phase1::read_file(Path::new(phase1), qap.domain_exp()?)?;
+
+    Ok((definition, qap, phase1))
+}
+
+fn init(name: &str, version: Option<&str>, phase1: &str, output: &str) -> Result<(), Box<dyn Error>> {
+    let (definition, qap, phase1) = load(name, version, phase1)?;
+    let params = CeremonyParameters::initialize(&qap, &phase1)?;
+
+    params.write_file(Path::new(output))?;
+    println!("{} {}: parâmetros iniciais em {}", definition.name, definition.version, output);
+    println!("hash do circuito: {}", hex::encode(params.circuit_hash));
+    println!("hash da fase 1: {}", hex::encode(params.phase1_hash));
+    Ok(())
+}
+
+fn contribute(input: &str, output: &str) -> Result<(), Box<dyn Error>> {
+    let mut params = CeremonyParameters::read_file(Path::new(input), true)?;
+    let hash = params.contribute(&mut OsRng);
+
+    params.write_file(Path::new(output))?;
+    println!("contribuição {}: {}", params.contributions.len(), hex::encode(hash));
+    Ok(())
+}
+
+fn verify(name: &str, version: Option<&str>, phase1: &str, last: &str) -> Result<(), Box<dyn Error>> {
+    let (_, qap, phase1) = load(name, version, phase1)?;
+    let last = CeremonyParameters::read_file(Path::new(last), true)?;
+
+    let hashes = last.verify(&qap, &phase1)?;
+    println!("hash da fase 1: {}", hex::encode(phase1.hash));
+    for (index, hash) in hashes.iter().enumerate() {
+        println!("contribuição {}: {}", index + 1, hex::encode(hash));
+    }
+    println!("transcript: {}", hex::encode(last.transcript_hash()));
+    Ok(())
+}
+
+fn export(input: &str, output: &str) -> Result<(), Box<dyn Error>> {
+    let params = CeremonyParameters::read_file(Path::new(input), true)?;
+
+    let mut writer = BufWriter::new(File::create(output)?);
+    params.params.write(&mut writer)?;
+    writer.flush()?;
+    println!("parâmetros exportados para {}", output);
+    Ok(())
+}
@@ -0,0 +1,273 @@
+use ark_bn254::Fr;
+use ark_ff::{BigInt, Field as ArkField, MontFp, PrimeField as ArkPrimeField, UniformRand};
+use ff::{Field, FieldBits, PrimeField, PrimeFieldBits};
+use rand::RngCore;
+use std::fmt;
+use std::iter::{Product, Sum};
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+/// Elemento do corpo escalar do BN254 com os traits do `ff`, para que os
+/// circuitos genéricos (`Circuit<S: PrimeField>`) rodem também no backend EVM.
+/// A representação (`to_repr`) é little-endian, como a do `bls12_381::Scalar`.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub struct Bn254Scalar(pub Fr);
+
+impl Bn254Scalar {
+    fn limbs(&self) -> [u64; 4] {
+        (self.0).0 .0
+    }
+
+    fn from_limbs(limbs: [u64; 4]) -> Self {
+        Bn254Scalar(ark_ff::Fp(BigInt(limbs), PhantomData))
+    }
+}
+
+impl From<Fr> for Bn254Scalar {
+    fn from(value: Fr) -> Self {
+        Bn254Scalar(value)
+    }
+}
+
+impl From<Bn254Scalar> for Fr {
+    fn from(value: Bn254Scalar) -> Self {
+        value.0
+    }
+}
+
+impl From<u64> for Bn254Scalar {
+    fn from(value: u64) -> Self {
+        Bn254Scalar(Fr::from(value))
+    }
+}
+
+impl fmt::Debug for Bn254Scalar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut bytes = self.to_repr();
+        bytes.reverse();
+        write!(f, "0x{}", hex::encode(bytes))
+    }
+}
+
+impl ConstantTimeEq for Bn254Scalar {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.limbs().ct_eq(&other.limbs())
+    }
+}
+
+impl ConditionallySelectable for Bn254Scalar {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        let (a, b) = (a.limbs(), b.limbs());
+        Self::from_limbs([
+            u64::conditional_select(&a[0], &b[0], choice),
+            u64::conditional_select(&a[1], &b[1], choice),
+            u64::conditional_select(&a[2], &b[2], choice),
+            u64::conditional_select(&a[3], &b[3], choice),
+        ])
+    }
+}
+
+impl Neg for Bn254Scalar {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Bn254Scalar(-self.0)
+    }
+}
+
+macro_rules! impl_binary_op {
+    ($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident) => {
+        impl $trait for Bn254Scalar {
+            type Output = Self;
+
+            fn $method(self, rhs: Self) -> Self {
+                Bn254Scalar(self.0.$method(rhs.0))
+            }
+        }
+
+        impl<'a> $trait<&'a Bn254Scalar> for Bn254Scalar {
+            type Output = Self;
+
+            fn $method(self, rhs: &'a Bn254Scalar) -> Self {
+                Bn254Scalar(self.0.$method(rhs.0))
+            }
+        }
+
+        impl $assign_trait for Bn254Scalar {
+            fn $assign_method(&mut self, rhs: Self) {
+                self.0.$assign_method(rhs.0);
+            }
+        }
+
+        impl<'a> $assign_trait<&'a Bn254Scalar> for Bn254Scalar {
+            fn $assign_method(&mut self, rhs: &'a Bn254Scalar) {
+                self.0.$assign_method(rhs.0);
+            }
+        }
+    };
+}
+
+impl_binary_op!(Add, add, AddAssign, add_assign);
+impl_binary_op!(Sub, sub, SubAssign, sub_assign);
+impl_binary_op!(Mul, mul, MulAssign, mul_assign);
+
+impl Sum for Bn254Scalar {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |acc, x| acc + x)
+    }
+}
+
+impl<'a> Sum<&'a Bn254Scalar> for Bn254Scalar {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |acc, x| acc + x)
+    }
+}
+
+impl Product for Bn254Scalar {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, |acc, x| acc * x)
+    }
+}
+
+impl<'a> Product<&'a Bn254Scalar> for Bn254Scalar {
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, |acc, x| acc * x)
+    }
+}
+
+impl Field for Bn254Scalar {
+    const ZERO: Self = Bn254Scalar(<Fr as ArkField>::ZERO);
+    const ONE: Self = Bn254Scalar(<Fr as ArkField>::ONE);
+
+    fn random(mut rng: impl RngCore) -> Self {
+        Bn254Scalar(Fr::rand(&mut rng))
+    }
+
+    fn square(&self) -> Self {
+        Bn254Scalar(ArkField::square(&self.0))
+    }
+
+    fn double(&self) -> Self {
+        Bn254Scalar(ArkField::double(&self.0))
+    }
+
+    fn invert(&self) -> CtOption<Self> {
+        match self.0.inverse() {
+            Some(inverse) => CtOption::new(Bn254Scalar(inverse), Choice::from(1)),
+            None => CtOption::new(Self::ZERO, Choice::from(0)),
+        }
+    }
+
+    fn sqrt_ratio(num: &Self, div: &Self) -> (Choice, Self) {
+        ff::helpers::sqrt_ratio_generic(num, div)
+    }
+
+    // Obrigatório: a implementação padrão usa `sqrt_ratio`, que por sua vez usa `sqrt`
+    fn sqrt(&self) -> CtOption<Self> {
+        match ArkField::sqrt(&self.0) {
+            Some(root) => CtOption::new(Bn254Scalar(root), Choice::from(1)),
+            None => CtOption::new(Self::ZERO, Choice::from(0)),
+        }
+    }
+}
+
+impl PrimeField for Bn254Scalar {
+    type Repr = [u8; 32];
+
+    fn from_repr(repr: Self::Repr) -> CtOption<Self> {
+        let mut limbs = [0u64; 4];
+        for (limb, chunk) in limbs.iter_mut().zip(repr.chunks(8)) {
+            *limb = u64::from_le_bytes(chunk.try_into().unwrap());
+        }
+
+        match Fr::from_bigint(BigInt(limbs)) {
+            Some(value) => CtOption::new(Bn254Scalar(value), Choice::from(1)),
+            None => CtOption::new(Self::ZERO, Choice::from(0)),
+        }
+    }
+
+    fn to_repr(&self) -> Self::Repr {
+        let mut repr = [0u8; 32];
+        for (chunk, limb) in repr.chunks_mut(8).zip(self.0.into_bigint().0) {
+            chunk.copy_from_slice(&limb.to_le_bytes());
+        }
+        repr
+    }
+
+    fn is_odd(&self) -> Choice {
+        Choice::from((self.0.into_bigint().0[0] & 1) as u8)
+    }
+
+    const MODULUS: &'static str = "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001";
+    const NUM_BITS: u32 = 254;
+    const CAPACITY: u32 = 253;
+    const TWO_INV: Self =
+        Bn254Scalar(MontFp!("10944121435919637611123202872628637544274182200208017171849102093287904247809"));
+    const MULTIPLICATIVE_GENERATOR: Self = Bn254Scalar(MontFp!("5"));
+    const S: u32 = 28;
+    const ROOT_OF_UNITY: Self =
+        Bn254Scalar(MontFp!("19103219067921713944291392827692070036145651957329286315305642004821462161904"));
+    const ROOT_OF_UNITY_INV: Self =
+        Bn254Scalar(MontFp!("776454056201908206186590970419435932130236139910903033203789591477115950462"));
+    const DELTA: Self =
+        Bn254Scalar(MontFp!("5266228460530200451425464971825753823072228272503274930591399474110020095489"));
+}
+
+impl PrimeFieldBits for Bn254Scalar {
+    type ReprBits = [u64; 4];
+
+    fn to_le_bits(&self) -> FieldBits<Self::ReprBits> {
+        FieldBits::new(self.0.into_bigint().0)
+    }
+
+    fn char_le_bits() -> FieldBits<Self::ReprBits> {
+        FieldBits::new(Fr::MODULUS.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::thread_rng;
+
+    #[test]
+    fn test_constants_match_field() {
+        assert_eq!(Bn254Scalar::TWO_INV.double(), Bn254Scalar::ONE);
+        assert_eq!(Bn254Scalar::ROOT_OF_UNITY * Bn254Scalar::ROOT_OF_UNITY_INV, Bn254Scalar::ONE);
+        assert_eq!(Bn254Scalar::ROOT_OF_UNITY.pow_vartime([1u64 << Bn254Scalar::S]), Bn254Scalar::ONE);
+        assert_ne!(Bn254Scalar::ROOT_OF_UNITY.pow_vartime([1u64 << (Bn254Scalar::S - 1)]), Bn254Scalar::ONE);
+        assert_eq!(
+            Bn254Scalar::MULTIPLICATIVE_GENERATOR.pow_vartime([1u64 << Bn254Scalar::S]),
+            Bn254Scalar::DELTA
+        );
+        assert_eq!(Bn254Scalar::from_str_vartime("21888242871839275222246405745257275088548364400416034343698204186575808495616"), Some(-Bn254Scalar::ONE));
+    }
+
+    #[test]
+    fn test_repr_round_trip_and_bounds() {
+        let value = Bn254Scalar::random(thread_rng());
+        assert_eq!(Bn254Scalar::from_repr(value.to_repr()).unwrap(), value);
+        assert_eq!(Bn254Scalar::from(7u64).to_repr()[0], 7);
+
+        // O módulo em little-endian não é um elemento válido
+        let mut modulus = hex::decode(&Bn254Scalar::MODULUS[2..]).unwrap();
+        modulus.reverse();
+        assert!(bool::from(Bn254Scalar::from_repr(modulus.try_into().unwrap()).is_none()));
+    }
+
+    #[test]
+    fn test_field_operations() {
+        let mut rng = thread_rng();
+        let a = Bn254Scalar::random(&mut rng);
+        let b = Bn254Scalar::random(&mut rng);
+
+        assert_eq!(a * a.invert().unwrap(), Bn254Scalar::ONE);
+        assert!(bool::from(Bn254Scalar::ZERO.invert().is_none()));
+        assert_eq!((a + b) - b, a);
+        assert_eq!(a.square().sqrt().unwrap().square(), a.square());
+        assert_eq!(Bn254Scalar::conditional_select(&a, &b, Choice::from(1)), b);
+        assert_eq!(Bn254Scalar::from(5u64).to_le_bits().iter().take(3).map(|b| *b).collect::<Vec<_>>(), [true, false, true]);
+        assert_eq!(Bn254Scalar::from_u128(u128::MAX) + Bn254Scalar::ONE, Bn254Scalar::from(2u64).pow_vartime([128u64]));
+    }
+}
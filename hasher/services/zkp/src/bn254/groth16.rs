@@ -0,0 +1,161 @@
+use ark_bn254::{Bn254, Fr};
+use ark_groth16::{prepare_verifying_key, Groth16, PreparedVerifyingKey, Proof, ProvingKey, VerifyingKey};
+use ark_relations::r1cs::SynthesisError;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+use bellman::Circuit;
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use super::{Bn254Circuit, Bn254Scalar};
+use crate::encoding::{decode, encode, EncodingError};
+
+#[derive(Debug, Error)]
+pub enum Bn254Error {
+    #[error("Erro ao sintetizar o circuito: {0}")]
+    Synthesis(#[from] SynthesisError),
+    #[error("Serialização inválida: {0}")]
+    Serialization(#[from] SerializationError),
+    #[error("Codificação inválida: {0}")]
+    Encoding(#[from] EncodingError),
+    #[error("Número de entradas públicas incorreto: esperado {expected}, recebido {actual}")]
+    WrongInputCount { expected: usize, actual: usize },
+}
+
+/// Chaves Groth16 de uma versão de circuito no BN254
+pub struct Bn254Parameters {
+    pub circuit_version: String,
+    pub fingerprint: String,
+    pub pk: ProvingKey<Bn254>,
+    pub pvk: PreparedVerifyingKey<Bn254>,
+}
+
+impl Bn254Parameters {
+    pub fn new(circuit_version: &str, pk: ProvingKey<Bn254>) -> Self {
+        Self {
+            circuit_version: circuit_version.to_string(),
+            fingerprint: bn254_fingerprint(&pk.vk),
+            pvk: prepare_verifying_key(&pk.vk),
+            pk,
+        }
+    }
+
+    /// Setup local (um único participante): serve para desenvolvimento, não
+    /// substitui uma cerimônia para chaves usadas on-chain
+    pub fn generate<C, R>(circuit_version: &str, blank: C, rng: &mut R) -> Result<Self, Bn254Error>
+    where
+        C: Circuit<Bn254Scalar>,
+        R: Rng,
+    {
+        let pk = Groth16::<Bn254>::generate_random_parameters_with_reduction(Bn254Circuit(blank), rng)?;
+        Ok(Self::new(circuit_version, pk))
+    }
+
+    pub fn vk(&self) -> &VerifyingKey<Bn254> {
+        &self.pk.vk
+    }
+
+    /// Formato não comprimido, como o dos parâmetros do bellman
+    pub fn write(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        self.pk
+            .serialize_uncompressed(&mut bytes)
+            .expect("escrita em memória não falha");
+        bytes
+    }
+
+    /// Lê parâmetros gravados por `write`. Os pontos não são revalidados,
+    /// pois vêm do próprio banco.
+    pub fn read(circuit_version: &str, bytes: &[u8]) -> Result<Self, Bn254Error> {
+        let pk = ProvingKey::<Bn254>::deserialize_uncompressed_unchecked(bytes)?;
+        Ok(Self::new(circuit_version, pk))
+    }
+}
+
+/// Fingerprint (SHA-256 em hex) da chave de verificação comprimida
+pub fn bn254_fingerprint(vk: &VerifyingKey<Bn254>) -> String {
+    let mut bytes = Vec::new();
+    vk.serialize_compressed(&mut bytes).expect("escrita em memória não falha");
+    hex::encode(Sha256::digest(&bytes))
+}
+
+pub fn create_bn254_proof<C, R>(params: &Bn254Parameters, circuit: C, rng: &mut R) -> Result<Proof<Bn254>, Bn254Error>
+where
+    C: Circuit<Bn254Scalar>,
+    R: Rng,
+{
+    Ok(Groth16::<Bn254>::create_random_proof_with_reduction(Bn254Circuit(circuit), &params.pk, rng)?)
+}
+
+pub fn verify_bn254_proof(
+    pvk: &PreparedVerifyingKey<Bn254>,
+    proof: &Proof<Bn254>,
+    public_inputs: &[Bn254Scalar],
+) -> Result<bool, Bn254Error> {
+    let expected = pvk.vk.gamma_abc_g1.len() - 1;
+    if public_inputs.len() != expected {
+        return Err(Bn254Error::WrongInputCount {
+            expected,
+            actual: public_inputs.len(),
+        });
+    }
+
+    let inputs = public_inputs.iter().map(|x| Fr::from(*x)).collect::<Vec<_>>();
+    Ok(Groth16::<Bn254>::verify_proof(pvk, proof, &inputs)?)
+}
+
+/// Codificação canônica da prova (ver `encoding`)
+pub fn write_bn254_proof(proof: &Proof<Bn254>) -> Vec<u8> {
+    encode(proof)
+}
+
+/// Lê uma prova na codificação canônica, conferindo que os pontos estão nos subgrupos corretos
+pub fn read_bn254_proof(bytes: &[u8]) -> Result<Proof<Bn254>, Bn254Error> {
+    Ok(decode(bytes)?)
+}
+
+pub fn write_bn254_verifying_key(vk: &VerifyingKey<Bn254>) -> Vec<u8> {
+    encode(vk)
+}
+
+pub fn read_bn254_verifying_key(bytes: &[u8]) -> Result<VerifyingKey<Bn254>, Bn254Error> {
+    Ok(decode(bytes)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuits::{wallet_leaf, whitelist_tree, MembershipCircuit, MEMBERSHIP_CIRCUIT_VERSION};
+    use rand::thread_rng;
+
+    #[test]
+    fn test_membership_round_trip_on_bn254() {
+        let mut rng = thread_rng();
+        let params = Bn254Parameters::generate(MEMBERSHIP_CIRCUIT_VERSION, MembershipCircuit::blank(), &mut rng).unwrap();
+
+        let wallets = (1..=5u64).map(Bn254Scalar::from).collect::<Vec<_>>();
+        let tree = whitelist_tree(&wallets).unwrap();
+        assert_eq!(tree.path(3).unwrap().root(wallet_leaf(wallets[3])), tree.root());
+
+        let circuit = MembershipCircuit {
+            wallet: Some(wallets[3]),
+            path: tree.path(3),
+        };
+        let proof = create_bn254_proof(&params, circuit, &mut rng).unwrap();
+        let proof = read_bn254_proof(&write_bn254_proof(&proof)).unwrap();
+
+        let inputs = MembershipCircuit::public_inputs(tree.root());
+        assert!(verify_bn254_proof(&params.pvk, &proof, &inputs).unwrap());
+        assert!(!verify_bn254_proof(&params.pvk, &proof, &[tree.root() + Bn254Scalar::from(1u64)]).unwrap());
+        assert!(matches!(
+            verify_bn254_proof(&params.pvk, &proof, &[]),
+            Err(Bn254Error::WrongInputCount { expected: 1, actual: 0 })
+        ));
+
+        // Parâmetros relidos do banco mantêm a mesma chave
+        let reloaded = Bn254Parameters::read(MEMBERSHIP_CIRCUIT_VERSION, &params.write()).unwrap();
+        assert_eq!(reloaded.fingerprint, params.fingerprint);
+        let vk = read_bn254_verifying_key(&write_bn254_verifying_key(params.vk())).unwrap();
+        assert_eq!(bn254_fingerprint(&vk), params.fingerprint);
+    }
+}
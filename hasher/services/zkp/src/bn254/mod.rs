@@ -0,0 +1,9 @@
+mod field;
+mod groth16;
+mod solidity;
+mod synthesis;
+
+pub use field::*;
+pub use groth16::*;
+pub use solidity::*;
+pub use synthesis::*;
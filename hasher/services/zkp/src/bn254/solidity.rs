@@ -0,0 +1,352 @@
+use ark_bn254::{Bn254, Fq, Fq2, G1Affine, G2Affine};
+use ark_ff::{BigInteger, PrimeField};
+use ark_groth16::{Proof, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+use std::fmt::Write;
+
+use super::Bn254Scalar;
+
+/// Assinatura da função exportada; é a mesma de `IGroth16Verifier`
+pub const VERIFY_PROOF_SIGNATURE: &str = "verifyProof(uint256[2],uint256[2][2],uint256[2],uint256[])";
+
+/// Argumentos de `verifyProof` em palavras de 32 bytes (hex big-endian).
+/// Em G2 a parte imaginária vem primeiro, como exige o precompile de pareamento (EIP-197).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EvmCalldata {
+    pub a: [String; 2],
+    pub b: [[String; 2]; 2],
+    pub c: [String; 2],
+    pub input: Vec<String>,
+    /// `keccak256(abi.encode(a, b, c, input))`, o identificador da prova no ZKProofRegistry
+    pub proof_hash: String,
+    /// Chamada completa (seletor + argumentos), pronta para `eth_call`
+    pub calldata: String,
+}
+
+type Word = [u8; 32];
+
+fn fq_word(value: &Fq) -> Word {
+    value.into_bigint().to_bytes_be().try_into().expect("Fq cabe em 32 bytes")
+}
+
+fn g1_words(point: &G1Affine) -> [Word; 2] {
+    [fq_word(&point.x), fq_word(&point.y)]
+}
+
+fn fq2_words(value: &Fq2) -> [Word; 2] {
+    [fq_word(&value.c1), fq_word(&value.c0)]
+}
+
+fn g2_words(point: &G2Affine) -> [[Word; 2]; 2] {
+    [fq2_words(&point.x), fq2_words(&point.y)]
+}
+
+fn usize_word(value: usize) -> Word {
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&(value as u64).to_be_bytes());
+    word
+}
+
+fn hex_word(word: &Word) -> String {
+    format!("0x{}", hex::encode(word))
+}
+
+fn decimal(word: &Word) -> String {
+    Fq::from_be_bytes_mod_order(word).into_bigint().to_string()
+}
+
+/// Codifica a prova para o verificador exportado por `export_verifier`
+pub fn proof_calldata(proof: &Proof<Bn254>, public_inputs: &[Bn254Scalar]) -> EvmCalldata {
+    let a = g1_words(&proof.a);
+    let b = g2_words(&proof.b);
+    let c = g1_words(&proof.c);
+    let input = public_inputs
+        .iter()
+        .map(|x| {
+            let mut word = ff::PrimeField::to_repr(x);
+            word.reverse();
+            word
+        })
+        .collect::<Vec<Word>>();
+
+    // abi.encode(uint256[2], uint256[2][2], uint256[2], uint256[]): os arrays de
+    // tamanho fixo vão inline e o dinâmico vai no fim, depois do seu offset
+    let mut words = Vec::with_capacity(10 + input.len());
+    words.extend(a);
+    words.extend(b.iter().flatten().copied());
+    words.extend(c);
+    words.push(usize_word(9 * 32));
+    words.push(usize_word(input.len()));
+    words.extend(input.iter().copied());
+    let arguments = words.concat();
+
+    let selector = &Keccak256::digest(VERIFY_PROOF_SIGNATURE.as_bytes())[..4];
+
+    EvmCalldata {
+        a: a.map(|w| hex_word(&w)),
+        b: b.map(|pair| pair.map(|w| hex_word(&w))),
+        c: c.map(|w| hex_word(&w)),
+        input: input.iter().map(hex_word).collect(),
+        proof_hash: format!("0x{}", hex::encode(Keccak256::digest(&arguments))),
+        calldata: format!("0x{}{}", hex::encode(selector), hex::encode(&arguments)),
+    }
+}
+
+/// Nome do contrato verificador de uma versão de circuito (ex.: `solvency-v1` → `SolvencyV1Verifier`)
+pub fn verifier_contract_name(circuit_version: &str) -> String {
+    let mut name = circuit_version
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            let first = chars.next().unwrap().to_ascii_uppercase();
+            std::iter::once(first).chain(chars).collect::<String>()
+        })
+        .collect::<String>();
+
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name.insert(0, 'C');
+    }
+    name + "Verifier"
+}
+
+/// Gera o contrato Solidity que verifica provas Groth16 feitas com `vk`,
+/// usando os precompiles do BN254 (EIP-196/197)
+pub fn export_verifier(vk: &VerifyingKey<Bn254>, circuit_version: &str, fingerprint: &str) -> String {
+    let g1 = |point: &G1Affine| g1_words(point).map(|w| decimal(&w));
+    let g2 = |point: &G2Affine| g2_words(point).map(|pair| pair.map(|w| decimal(&w)));
+
+    let [alpha_x, alpha_y] = g1(&vk.alpha_g1);
+    let [[beta_x1, beta_x2], [beta_y1, beta_y2]] = g2(&vk.beta_g2);
+    let [[gamma_x1, gamma_x2], [gamma_y1, gamma_y2]] = g2(&vk.gamma_g2);
+    let [[delta_x1, delta_x2], [delta_y1, delta_y2]] = g2(&vk.delta_g2);
+
+    let mut ic = String::new();
+    for (i, point) in vk.gamma_abc_g1.iter().enumerate() {
+        let [x, y] = g1(point);
+        writeln!(ic, "        if (i == {}) return ({}, {});", i, x, y).unwrap();
+    }
+
+    format!(
+        r#"// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+import "./interfaces/IGroth16Verifier.sol";
+
+/**
+ * @dev Verificador Groth16 (BN254) gerado pelo zkp-service
+ * Circuito: {circuit_version}
+ * Chave de verificação: {fingerprint}
+ */
+contract {name} is IGroth16Verifier {{
+    // Ordem do corpo base e do corpo escalar do BN254
+    uint256 internal constant PRIME_Q = 21888242871839275222246405745257275088696311157297823662689037894645226208583;
+    uint256 internal constant SNARK_SCALAR_FIELD = 21888242871839275222246405745257275088548364400416034343698204186575808495617;
+
+    uint256 internal constant ALPHA_X = {alpha_x};
+    uint256 internal constant ALPHA_Y = {alpha_y};
+    uint256 internal constant BETA_X1 = {beta_x1};
+    uint256 internal constant BETA_X2 = {beta_x2};
+    uint256 internal constant BETA_Y1 = {beta_y1};
+    uint256 internal constant BETA_Y2 = {beta_y2};
+    uint256 internal constant GAMMA_X1 = {gamma_x1};
+    uint256 internal constant GAMMA_X2 = {gamma_x2};
+    uint256 internal constant GAMMA_Y1 = {gamma_y1};
+    uint256 internal constant GAMMA_Y2 = {gamma_y2};
+    uint256 internal constant DELTA_X1 = {delta_x1};
+    uint256 internal constant DELTA_X2 = {delta_x2};
+    uint256 internal constant DELTA_Y1 = {delta_y1};
+    uint256 internal constant DELTA_Y2 = {delta_y2};
+
+    uint256 internal constant INPUT_COUNT = {input_count};
+
+    function _ic(uint256 i) internal pure returns (uint256, uint256) {{
+{ic}        revert("Indice de IC invalido");
+    }}
+
+    function _add(uint256 x1, uint256 y1, uint256 x2, uint256 y2) internal view returns (uint256, uint256) {{
+        uint256[4] memory input = [x1, y1, x2, y2];
+        uint256[2] memory result;
+        bool success;
+        assembly {{
+            success := staticcall(gas(), 0x06, input, 0x80, result, 0x40)
+        }}
+        require(success, "Falha na soma de pontos");
+        return (result[0], result[1]);
+    }}
+
+    function _mul(uint256 x, uint256 y, uint256 s) internal view returns (uint256, uint256) {{
+        uint256[3] memory input = [x, y, s];
+        uint256[2] memory result;
+        bool success;
+        assembly {{
+            success := staticcall(gas(), 0x07, input, 0x60, result, 0x40)
+        }}
+        require(success, "Falha na multiplicacao escalar");
+        return (result[0], result[1]);
+    }}
+
+    /**
+     * @dev Verifica e(-A, B) * e(alpha, beta) * e(vk_x, gamma) * e(C, delta) == 1
+     */
+    function verifyProof(
+        uint256[2] calldata a,
+        uint256[2][2] calldata b,
+        uint256[2] calldata c,
+        uint256[] calldata input
+    ) external view override returns (bool) {{
+        require(input.length == INPUT_COUNT, "Numero de entradas invalido");
+
+        (uint256 x, uint256 y) = _ic(0);
+        for (uint256 i = 0; i < input.length; i++) {{
+            require(input[i] < SNARK_SCALAR_FIELD, "Entrada fora do corpo");
+            (uint256 icX, uint256 icY) = _ic(i + 1);
+            (uint256 mulX, uint256 mulY) = _mul(icX, icY, input[i]);
+            (x, y) = _add(x, y, mulX, mulY);
+        }}
+
+        uint256[24] memory p;
+        p[0] = a[0];
+        p[1] = (PRIME_Q - (a[1] % PRIME_Q)) % PRIME_Q;
+        p[2] = b[0][0];
+        p[3] = b[0][1];
+        p[4] = b[1][0];
+        p[5] = b[1][1];
+        p[6] = ALPHA_X;
+        p[7] = ALPHA_Y;
+        p[8] = BETA_X1;
+        p[9] = BETA_X2;
+        p[10] = BETA_Y1;
+        p[11] = BETA_Y2;
+        p[12] = x;
+        p[13] = y;
+        p[14] = GAMMA_X1;
+        p[15] = GAMMA_X2;
+        p[16] = GAMMA_Y1;
+        p[17] = GAMMA_Y2;
+        p[18] = c[0];
+        p[19] = c[1];
+        p[20] = DELTA_X1;
+        p[21] = DELTA_X2;
+        p[22] = DELTA_Y1;
+        p[23] = DELTA_Y2;
+
+        uint256[1] memory out;
+        bool success;
+        assembly {{
+            success := staticcall(gas(), 0x08, p, 0x300, out, 0x20)
+        }}
+        require(success, "Falha no pareamento");
+        return out[0] == 1;
+    }}
+}}
+"#,
+        circuit_version = circuit_version,
+        fingerprint = fingerprint,
+        name = verifier_contract_name(circuit_version),
+        input_count = vk.gamma_abc_g1.len() - 1,
+        ic = ic,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bn254::{create_bn254_proof, Bn254Parameters};
+    use crate::circuits::{commit_balance, SolvencyCircuit, SOLVENCY_CIRCUIT_VERSION};
+    use ark_bn254::{Fr, G1Projective};
+    use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+    use ark_ff::Zero;
+    use rand::thread_rng;
+
+    fn parse_word(word: &str) -> Fq {
+        Fq::from_be_bytes_mod_order(&hex::decode(&word[2..]).unwrap())
+    }
+
+    // Reproduz, a partir das palavras da calldata, a conta feita pelo contrato
+    fn evm_verify(vk: &VerifyingKey<Bn254>, calldata: &EvmCalldata) -> bool {
+        let g1 = |words: &[String; 2]| G1Affine::new(parse_word(&words[0]), parse_word(&words[1]));
+        // EIP-197: parte imaginária primeiro
+        let fq2 = |words: &[String; 2]| Fq2::new(parse_word(&words[1]), parse_word(&words[0]));
+        let b = G2Affine::new(fq2(&calldata.b[0]), fq2(&calldata.b[1]));
+
+        let mut vk_x = G1Projective::from(vk.gamma_abc_g1[0]);
+        for (word, ic) in calldata.input.iter().zip(&vk.gamma_abc_g1[1..]) {
+            vk_x += ic.mul_bigint(Fr::from_be_bytes_mod_order(&hex::decode(&word[2..]).unwrap()).into_bigint());
+        }
+
+        Bn254::multi_pairing(
+            [-g1(&calldata.a), vk.alpha_g1, vk_x.into_affine(), g1(&calldata.c)],
+            [b, vk.beta_g2, vk.gamma_g2, vk.delta_g2],
+        )
+        .is_zero()
+    }
+
+    fn solvency_proof() -> (Bn254Parameters, EvmCalldata) {
+        let mut rng = thread_rng();
+        let params = Bn254Parameters::generate(SOLVENCY_CIRCUIT_VERSION, SolvencyCircuit::blank(), &mut rng).unwrap();
+
+        let blinding = Bn254Scalar::from(7u64);
+        let circuit = SolvencyCircuit {
+            balance: Some(1_000),
+            blinding: Some(blinding),
+            threshold: Some(250),
+        };
+        let proof = create_bn254_proof(&params, circuit, &mut rng).unwrap();
+        let inputs = SolvencyCircuit::public_inputs(250, commit_balance(1_000, blinding));
+
+        let calldata = proof_calldata(&proof, &inputs);
+        (params, calldata)
+    }
+
+    #[test]
+    fn test_calldata_satisfies_pairing_check() {
+        let (params, calldata) = solvency_proof();
+        assert!(evm_verify(params.vk(), &calldata));
+
+        let mut tampered = calldata.clone();
+        tampered.input[0] = format!("0x{:064x}", 251);
+        assert!(!evm_verify(params.vk(), &tampered));
+    }
+
+    #[test]
+    fn test_calldata_abi_layout() {
+        let (_, calldata) = solvency_proof();
+        let bytes = hex::decode(&calldata.calldata[2..]).unwrap();
+
+        // seletor + 8 palavras da prova + offset + tamanho + 2 entradas
+        assert_eq!(bytes.len(), 4 + 32 * 12);
+        assert_eq!(&bytes[..4], &Keccak256::digest(VERIFY_PROOF_SIGNATURE.as_bytes())[..4]);
+        assert_eq!(&bytes[4 + 32 * 8 + 30..4 + 32 * 9], &[0x01, 0x20]);
+        assert_eq!(bytes[4 + 32 * 9 + 31], 2);
+        assert_eq!(&bytes[4 + 32 * 10..4 + 32 * 11], &hex::decode(&calldata.input[0][2..]).unwrap()[..]);
+        assert_eq!(calldata.input[0], format!("0x{:064x}", 250));
+        assert_eq!(
+            calldata.proof_hash,
+            format!("0x{}", hex::encode(Keccak256::digest(&bytes[4..])))
+        );
+    }
+
+    #[test]
+    fn test_export_verifier() {
+        let (params, _) = solvency_proof();
+        let source = export_verifier(params.vk(), SOLVENCY_CIRCUIT_VERSION, &params.fingerprint);
+
+        assert!(source.contains("contract SolvencyV1Verifier is IGroth16Verifier {"));
+        assert!(source.contains("uint256 internal constant INPUT_COUNT = 2;"));
+        assert!(source.contains("if (i == 2) return ("));
+        assert!(!source.contains("if (i == 3)"));
+        assert!(source.contains(&params.fingerprint));
+
+        let alpha_x = params.vk().alpha_g1.x().unwrap().into_bigint().to_string();
+        assert!(source.contains(&format!("ALPHA_X = {};", alpha_x)));
+    }
+
+    #[test]
+    fn test_verifier_contract_name() {
+        assert_eq!(verifier_contract_name("solvency-v1"), "SolvencyV1Verifier");
+        assert_eq!(verifier_contract_name("membership-v1"), "MembershipV1Verifier");
+        assert_eq!(verifier_contract_name("2fa_v3"), "C2faV3Verifier");
+    }
+}
@@ -0,0 +1,190 @@
+use ark_bn254::Fr;
+use ark_relations::r1cs::{
+    ConstraintSynthesizer, ConstraintSystemRef, LinearCombination as ArkLinearCombination,
+    SynthesisError as ArkSynthesisError, Variable as ArkVariable,
+};
+use bellman::{Circuit, ConstraintSystem, Index, LinearCombination, SynthesisError, Variable};
+
+use super::Bn254Scalar;
+
+/// Executa um circuito do bellman sobre o sistema de restrições do arkworks,
+/// de modo que o mesmo circuito sirva aos dois backends
+pub struct Bn254Circuit<C>(pub C);
+
+impl<C: Circuit<Bn254Scalar>> ConstraintSynthesizer<Fr> for Bn254Circuit<C> {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), ArkSynthesisError> {
+        let mut adapter = ArkAdapter {
+            cs,
+            inputs: vec![ArkVariable::One],
+            aux: Vec::new(),
+            error: None,
+        };
+
+        self.0.synthesize(&mut adapter).map_err(to_ark_error)?;
+
+        // `enforce` do bellman não devolve erro; o primeiro fica guardado aqui
+        match adapter.error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+}
+
+struct ArkAdapter {
+    cs: ConstraintSystemRef<Fr>,
+    inputs: Vec<ArkVariable>,
+    aux: Vec<ArkVariable>,
+    error: Option<ArkSynthesisError>,
+}
+
+impl ArkAdapter {
+    fn variable(&self, variable: Variable) -> ArkVariable {
+        match variable.get_unchecked() {
+            Index::Input(i) => self.inputs[i],
+            Index::Aux(i) => self.aux[i],
+        }
+    }
+
+    fn linear_combination(&self, lc: LinearCombination<Bn254Scalar>) -> ArkLinearCombination<Fr> {
+        ArkLinearCombination(
+            lc.as_ref()
+                .iter()
+                .map(|(variable, coeff)| (coeff.0, self.variable(*variable)))
+                .collect(),
+        )
+    }
+}
+
+impl ConstraintSystem<Bn254Scalar> for ArkAdapter {
+    type Root = Self;
+
+    fn alloc<F, A, AR>(&mut self, _annotation: A, f: F) -> Result<Variable, SynthesisError>
+    where
+        F: FnOnce() -> Result<Bn254Scalar, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        let variable = self
+            .cs
+            .new_witness_variable(|| f().map(Fr::from).map_err(to_ark_error))
+            .map_err(from_ark_error)?;
+        self.aux.push(variable);
+
+        Ok(Variable::new_unchecked(Index::Aux(self.aux.len() - 1)))
+    }
+
+    fn alloc_input<F, A, AR>(&mut self, _annotation: A, f: F) -> Result<Variable, SynthesisError>
+    where
+        F: FnOnce() -> Result<Bn254Scalar, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        let variable = self
+            .cs
+            .new_input_variable(|| f().map(Fr::from).map_err(to_ark_error))
+            .map_err(from_ark_error)?;
+        self.inputs.push(variable);
+
+        Ok(Variable::new_unchecked(Index::Input(self.inputs.len() - 1)))
+    }
+
+    fn enforce<A, AR, LA, LB, LC>(&mut self, _annotation: A, a: LA, b: LB, c: LC)
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+        LA: FnOnce(LinearCombination<Bn254Scalar>) -> LinearCombination<Bn254Scalar>,
+        LB: FnOnce(LinearCombination<Bn254Scalar>) -> LinearCombination<Bn254Scalar>,
+        LC: FnOnce(LinearCombination<Bn254Scalar>) -> LinearCombination<Bn254Scalar>,
+    {
+        let a = self.linear_combination(a(LinearCombination::zero()));
+        let b = self.linear_combination(b(LinearCombination::zero()));
+        let c = self.linear_combination(c(LinearCombination::zero()));
+
+        if let Err(e) = self.cs.enforce_constraint(a, b, c) {
+            self.error.get_or_insert(e);
+        }
+    }
+
+    // Os nomes só servem para depuração no TestConstraintSystem
+    fn push_namespace<NR, N>(&mut self, _name_fn: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+    }
+
+    fn pop_namespace(&mut self) {}
+
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+}
+
+fn to_ark_error(e: SynthesisError) -> ArkSynthesisError {
+    match e {
+        SynthesisError::AssignmentMissing => ArkSynthesisError::AssignmentMissing,
+        SynthesisError::DivisionByZero => ArkSynthesisError::DivisionByZero,
+        SynthesisError::UnexpectedIdentity => ArkSynthesisError::UnexpectedIdentity,
+        SynthesisError::PolynomialDegreeTooLarge => ArkSynthesisError::PolynomialDegreeTooLarge,
+        _ => ArkSynthesisError::Unsatisfiable,
+    }
+}
+
+fn from_ark_error(e: ArkSynthesisError) -> SynthesisError {
+    match e {
+        ArkSynthesisError::AssignmentMissing => SynthesisError::AssignmentMissing,
+        ArkSynthesisError::DivisionByZero => SynthesisError::DivisionByZero,
+        ArkSynthesisError::UnexpectedIdentity => SynthesisError::UnexpectedIdentity,
+        ArkSynthesisError::PolynomialDegreeTooLarge => SynthesisError::PolynomialDegreeTooLarge,
+        _ => SynthesisError::Unsatisfiable,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuits::{commit_balance, SolvencyCircuit};
+    use ark_relations::r1cs::ConstraintSystem as ArkConstraintSystem;
+    use bellman::gadgets::test::TestConstraintSystem;
+
+    fn solvency(balance: u128, threshold: u128) -> (SolvencyCircuit<Bn254Scalar>, Vec<Bn254Scalar>) {
+        let blinding = Bn254Scalar::from(42u64);
+        let circuit = SolvencyCircuit {
+            balance: Some(balance),
+            blinding: Some(blinding),
+            threshold: Some(threshold),
+        };
+        (circuit, SolvencyCircuit::public_inputs(threshold, commit_balance(balance, blinding)))
+    }
+
+    #[test]
+    fn test_adapter_preserves_constraints_and_inputs() {
+        let (circuit, inputs) = solvency(1_000, 500);
+
+        let mut reference = TestConstraintSystem::<Bn254Scalar>::new();
+        circuit.clone().synthesize(&mut reference).unwrap();
+        assert!(reference.is_satisfied());
+
+        let cs = ArkConstraintSystem::<Fr>::new_ref();
+        Bn254Circuit(circuit).generate_constraints(cs.clone()).unwrap();
+
+        assert!(cs.is_satisfied().unwrap());
+        assert_eq!(cs.num_constraints(), reference.num_constraints());
+        assert_eq!(cs.num_instance_variables(), inputs.len() + 1);
+
+        let instance = cs.borrow().unwrap().instance_assignment.clone();
+        assert_eq!(instance[0], Fr::from(1u64));
+        assert_eq!(instance[1..], inputs.iter().map(|x| x.0).collect::<Vec<_>>()[..]);
+    }
+
+    #[test]
+    fn test_adapter_rejects_false_statement() {
+        // Saldo abaixo do limite: a testemunha existe, mas não satisfaz o circuito
+        let (circuit, _) = solvency(400, 500);
+
+        let cs = ArkConstraintSystem::<Fr>::new_ref();
+        Bn254Circuit(circuit).generate_constraints(cs.clone()).unwrap();
+
+        assert!(!cs.is_satisfied().unwrap());
+    }
+}
@@ -0,0 +1,98 @@
+use bls12_381::{pairing, G1Affine, G1Projective, G2Affine, G2Projective};
+use group::Group;
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use sha2::{Digest, Sha256};
+use std::io::{self, Read, Write};
+
+use super::CeremonyError;
+
+/// Registro público de uma contribuição. `s_delta = s·δ` e `r_delta = r·δ`,
+/// com `r` derivado do transcript, provam que o participante conhece o `δ`
+/// que levou o `delta_g1` anterior a `delta_after`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Contribution {
+    pub delta_after: G1Affine,
+    pub s: G1Affine,
+    pub s_delta: G1Affine,
+    pub r_delta: G2Affine,
+    pub transcript: [u8; 32],
+}
+
+impl Contribution {
+    pub const SIZE: usize = 96 * 3 + 192 + 32;
+
+    /// Hash que o participante publica para achar sua contribuição na cadeia
+    pub fn hash(&self) -> [u8; 32] {
+        let mut bytes = Vec::with_capacity(Self::SIZE);
+        self.write(&mut bytes).expect("escrita em memória não falha");
+        Sha256::digest(&bytes).into()
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(&self.delta_after.to_uncompressed())?;
+        writer.write_all(&self.s.to_uncompressed())?;
+        writer.write_all(&self.s_delta.to_uncompressed())?;
+        writer.write_all(&self.r_delta.to_uncompressed())?;
+        writer.write_all(&self.transcript)
+    }
+
+    pub fn read<R: Read>(mut reader: R) -> Result<Self, CeremonyError> {
+        let delta_after = read_g1(&mut reader, "delta_after")?;
+        let s = read_g1(&mut reader, "s")?;
+        let s_delta = read_g1(&mut reader, "s_delta")?;
+
+        let r_delta = read_g2(&mut reader, "r_delta")?;
+
+        let mut transcript = [0u8; 32];
+        reader.read_exact(&mut transcript)?;
+
+        Ok(Self {
+            delta_after,
+            s,
+            s_delta,
+            r_delta,
+            transcript,
+        })
+    }
+}
+
+pub(super) fn read_g1<R: Read>(reader: &mut R, field: &str) -> Result<G1Affine, CeremonyError> {
+    let mut bytes = [0u8; 96];
+    reader.read_exact(&mut bytes)?;
+    Option::from(G1Affine::from_uncompressed(&bytes))
+        .ok_or_else(|| CeremonyError::InvalidFormat(format!("{} fora da curva", field)))
+}
+
+pub(super) fn read_g2<R: Read>(reader: &mut R, field: &str) -> Result<G2Affine, CeremonyError> {
+    let mut bytes = [0u8; 192];
+    reader.read_exact(&mut bytes)?;
+    Option::from(G2Affine::from_uncompressed(&bytes))
+        .ok_or_else(|| CeremonyError::InvalidFormat(format!("{} fora da curva", field)))
+}
+
+/// Ponto de G2 derivado do transcript, de logaritmo discreto desconhecido
+pub fn hash_to_g2(transcript: &[u8; 32]) -> G2Affine {
+    G2Affine::from(G2Projective::random(ChaCha20Rng::from_seed(*transcript)))
+}
+
+/// `e(a.0, b.1) == e(a.1, b.0)`, isto é, os pares em G1 e G2 têm a mesma razão
+pub fn same_ratio(a: (G1Affine, G1Affine), b: (G2Affine, G2Affine)) -> bool {
+    pairing(&a.0, &b.1) == pairing(&a.1, &b.0)
+}
+
+/// Combinação aleatória dos pares `(v1[i], v2[i])`: se `v2[i] = v1[i]·x` para
+/// todo `i`, as somas mantêm a razão `x`; se não, só por acaso desprezível
+pub fn merge_pairs(v1: &[G1Affine], v2: &[G1Affine]) -> (G1Affine, G1Affine) {
+    use ff::Field;
+
+    let mut rng = rand::thread_rng();
+    let (mut s1, mut s2) = (G1Projective::identity(), G1Projective::identity());
+    for (a, b) in v1.iter().zip(v2) {
+        let rho = bls12_381::Scalar::random(&mut rng);
+        s1 += a * rho;
+        s2 += b * rho;
+    }
+
+    (s1.into(), s2.into())
+}
@@ -0,0 +1,51 @@
+//! Cerimônia de setup em várias partes (fase 2 do Groth16), no formato da
+//! cerimônia Sapling do Zcash.
+//!
+//! Os parâmetros iniciais de um circuito, com `delta = 1`, são calculados de
+//! forma determinística a partir do circuito e de um arquivo público da fase 1
+//! (`phase1radix2m<exp>`, da cerimônia Powers of Tau do Zcash): o coordenador
+//! não sorteia nada, e quem verifica recalcula os mesmos parâmetros.
+//! Cada participante, offline, lê o arquivo da rodada anterior, multiplica
+//! `delta` por um segredo próprio (dividindo `h` e `l` por ele), anexa a
+//! prova de conhecimento desse segredo e grava um novo arquivo. Basta que um
+//! participante descarte seu segredo para que ninguém conheça o `delta` final.
+//!
+//! Formato do arquivo (inteiros em big-endian, pontos não comprimidos):
+//!
+//! ```text
+//! "IBFZKMPC" ‖ versão (1 byte) ‖ hash do circuito (32 bytes) ‖ hash da fase 1 (32 bytes)
+//! ‖ parâmetros no formato do bellman
+//! ‖ u32 n ‖ n × contribuição (delta_after, s, s_delta: G1; r_delta: G2; transcript: 32 bytes)
+//! ```
+
+mod contribution;
+mod parameters;
+mod phase1;
+mod qap;
+
+pub use contribution::*;
+pub use parameters::*;
+pub use phase1::*;
+pub use qap::*;
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum CeremonyError {
+    #[error("Erro de leitura ou escrita: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Arquivo de cerimônia inválido: {0}")]
+    InvalidFormat(String),
+    #[error("Erro ao sintetizar o circuito: {0}")]
+    Synthesis(#[from] bellman::SynthesisError),
+    #[error("Parâmetros de outro circuito (hash {actual}, esperado {expected})")]
+    CircuitMismatch { expected: String, actual: String },
+    #[error("Fase 1 inválida: {0}")]
+    InvalidPhase1(String),
+    #[error("Parâmetros de outra fase 1 (hash {actual}, esperado {expected})")]
+    Phase1Mismatch { expected: String, actual: String },
+    #[error("Contribuição {index} inválida: {reason}")]
+    InvalidContribution { index: usize, reason: &'static str },
+    #[error("Parâmetros alterados fora de uma contribuição: {0}")]
+    TamperedParameters(&'static str),
+}
@@ -0,0 +1,494 @@
+use bellman::groth16::{Parameters, VerifyingKey};
+use bls12_381::{Bls12, G1Affine, G1Projective, G2Affine, G2Projective, Scalar};
+use ff::Field;
+use group::{Curve, Group};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+use super::{hash_to_g2, merge_pairs, same_ratio, CeremonyError, Contribution, Phase1, Qap, Terms};
+
+const MAGIC: &[u8; 8] = b"IBFZKMPC";
+const FORMAT_VERSION: u8 = 2;
+
+/// Parâmetros de um circuito em uma rodada da cerimônia, com as contribuições
+/// feitas até ela
+pub struct CeremonyParameters {
+    pub circuit_hash: [u8; 32],
+    /// SHA-256 do arquivo da fase 1 de onde saíram os parâmetros iniciais
+    pub phase1_hash: [u8; 32],
+    pub params: Parameters<Bls12>,
+    pub contributions: Vec<Contribution>,
+}
+
+impl CeremonyParameters {
+    /// Parâmetros iniciais, com `delta = gamma = 1`, calculados só a partir do
+    /// circuito e da fase 1 pública: não há segredo do coordenador, e qualquer
+    /// um chega aos mesmos bytes
+    pub fn initialize(qap: &Qap, phase1: &Phase1) -> Result<Self, CeremonyError> {
+        let exp = qap.domain_exp()?;
+        if phase1.exp != exp {
+            return Err(CeremonyError::InvalidPhase1(format!(
+                "o circuito precisa de phase1radix2m{}, não de phase1radix2m{}",
+                exp, phase1.exp
+            )));
+        }
+
+        // u_i(τ), v_i(τ) e β·u_i(τ) + α·v_i(τ) + w_i(τ) de cada variável
+        let evaluate = |terms: &Terms| {
+            let (mut a, mut b_g1, mut b_g2, mut ext) = (
+                G1Projective::identity(),
+                G1Projective::identity(),
+                G2Projective::identity(),
+                G1Projective::identity(),
+            );
+            for &(constraint, coefficient) in &terms.a {
+                a += phase1.coeffs_g1[constraint] * coefficient;
+                ext += phase1.beta_coeffs_g1[constraint] * coefficient;
+            }
+            for &(constraint, coefficient) in &terms.b {
+                b_g1 += phase1.coeffs_g1[constraint] * coefficient;
+                b_g2 += phase1.coeffs_g2[constraint] * coefficient;
+                ext += phase1.alpha_coeffs_g1[constraint] * coefficient;
+            }
+            for &(constraint, coefficient) in &terms.c {
+                ext += phase1.coeffs_g1[constraint] * coefficient;
+            }
+            (a, b_g1, b_g2, ext)
+        };
+
+        let mut a = Vec::with_capacity(qap.inputs.len() + qap.aux.len());
+        let mut b_g1 = Vec::with_capacity(a.capacity());
+        let mut b_g2 = Vec::with_capacity(a.capacity());
+        let (mut ic, mut l) = (Vec::with_capacity(qap.inputs.len()), Vec::with_capacity(qap.aux.len()));
+        for (index, terms) in qap.inputs.iter().chain(&qap.aux).enumerate() {
+            let (u, v_g1, v_g2, ext) = evaluate(terms);
+            a.push(u);
+            b_g1.push(v_g1);
+            b_g2.push(v_g2);
+            if index < qap.inputs.len() {
+                ic.push(ext);
+            } else {
+                l.push(ext);
+            }
+        }
+
+        // Como no bellman, pontos no infinito saem das consultas de A e B
+        let (a, b_g1, b_g2) = (normalize(&a), normalize(&b_g1), normalize(&b_g2));
+        let params = Parameters {
+            vk: VerifyingKey {
+                alpha_g1: phase1.alpha_g1,
+                beta_g1: phase1.beta_g1,
+                beta_g2: phase1.beta_g2,
+                gamma_g2: G2Affine::generator(),
+                delta_g1: G1Affine::generator(),
+                delta_g2: G2Affine::generator(),
+                ic: normalize(&ic),
+            },
+            h: Arc::new(phase1.h.clone()),
+            l: Arc::new(normalize(&l)),
+            a: Arc::new(a.into_iter().filter(|point| !bool::from(point.is_identity())).collect()),
+            b_g1: Arc::new(b_g1.into_iter().filter(|point| !bool::from(point.is_identity())).collect()),
+            b_g2: Arc::new(b_g2.into_iter().filter(|point| !bool::from(point.is_identity())).collect()),
+        };
+
+        Ok(Self {
+            circuit_hash: qap.circuit_hash,
+            phase1_hash: phase1.hash,
+            params,
+            contributions: Vec::new(),
+        })
+    }
+
+    /// Multiplica `delta` por um segredo sorteado aqui e descartado ao fim.
+    /// Devolve o hash da contribuição, que o participante publica.
+    pub fn contribute<R: RngCore>(&mut self, rng: &mut R) -> [u8; 32] {
+        let delta = loop {
+            let delta = Scalar::random(&mut *rng);
+            if !bool::from(delta.is_zero()) {
+                break delta;
+            }
+        };
+        let delta_inverse = delta.invert().unwrap();
+
+        // Prova de conhecimento de delta, amarrada às contribuições anteriores
+        let s = G1Projective::random(&mut *rng).to_affine();
+        let s_delta = (s * delta).to_affine();
+        let transcript = contribution_transcript(&self.circuit_hash, &self.phase1_hash, &self.contributions, &s, &s_delta);
+        let r_delta = (hash_to_g2(&transcript) * delta).to_affine();
+
+        let vk = &mut self.params.vk;
+        vk.delta_g1 = (vk.delta_g1 * delta).to_affine();
+        vk.delta_g2 = (vk.delta_g2 * delta).to_affine();
+        self.params.h = Arc::new(scale(&self.params.h, delta_inverse));
+        self.params.l = Arc::new(scale(&self.params.l, delta_inverse));
+
+        let contribution = Contribution {
+            delta_after: self.params.vk.delta_g1,
+            s,
+            s_delta,
+            r_delta,
+            transcript,
+        };
+        let hash = contribution.hash();
+        self.contributions.push(contribution);
+
+        hash
+    }
+
+    /// Hash do transcript da cerimônia até esta rodada
+    pub fn transcript_hash(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(self.circuit_hash);
+        hasher.update(self.phase1_hash);
+        for contribution in &self.contributions {
+            hasher.update(contribution.hash());
+        }
+        hasher.finalize().into()
+    }
+
+    /// Confere a cadeia de contribuições desde os parâmetros iniciais, que
+    /// são recalculados do circuito e da fase 1, e devolve o hash de cada
+    /// contribuição, em ordem
+    pub fn verify(&self, qap: &Qap, phase1: &Phase1) -> Result<Vec<[u8; 32]>, CeremonyError> {
+        if self.circuit_hash != qap.circuit_hash {
+            return Err(CeremonyError::CircuitMismatch {
+                expected: hex::encode(qap.circuit_hash),
+                actual: hex::encode(self.circuit_hash),
+            });
+        }
+        if self.phase1_hash != phase1.hash {
+            return Err(CeremonyError::Phase1Mismatch {
+                expected: hex::encode(phase1.hash),
+                actual: hex::encode(self.phase1_hash),
+            });
+        }
+
+        let initial = Self::initialize(qap, phase1)?;
+        let (g1, g2) = (G1Affine::generator(), G2Affine::generator());
+        let (before, after) = (&initial.params, &self.params);
+
+        // Só delta, h e l mudam ao longo da cerimônia
+        if before.vk.alpha_g1 != after.vk.alpha_g1
+            || before.vk.beta_g1 != after.vk.beta_g1
+            || before.vk.beta_g2 != after.vk.beta_g2
+            || before.vk.gamma_g2 != after.vk.gamma_g2
+            || before.vk.ic != after.vk.ic
+        {
+            return Err(CeremonyError::TamperedParameters("chave de verificação"));
+        }
+        if before.a != after.a || before.b_g1 != after.b_g1 || before.b_g2 != after.b_g2 {
+            return Err(CeremonyError::TamperedParameters("consultas a e b"));
+        }
+        if before.h.len() != after.h.len() || before.l.len() != after.l.len() {
+            return Err(CeremonyError::TamperedParameters("tamanho de h ou l"));
+        }
+
+        let mut delta = g1;
+        let mut hashes = Vec::with_capacity(self.contributions.len());
+        for (index, contribution) in self.contributions.iter().enumerate() {
+            let invalid = |reason| CeremonyError::InvalidContribution { index, reason };
+
+            if bool::from(contribution.s.is_identity() | contribution.s_delta.is_identity()) {
+                return Err(invalid("s nulo"));
+            }
+            let transcript = contribution_transcript(
+                &self.circuit_hash,
+                &self.phase1_hash,
+                &self.contributions[..index],
+                &contribution.s,
+                &contribution.s_delta,
+            );
+            if transcript != contribution.transcript {
+                return Err(invalid("transcript não confere"));
+            }
+
+            let r = hash_to_g2(&transcript);
+            if !same_ratio((contribution.s, contribution.s_delta), (r, contribution.r_delta)) {
+                return Err(invalid("prova de conhecimento de delta inválida"));
+            }
+            if !same_ratio((delta, contribution.delta_after), (r, contribution.r_delta)) {
+                return Err(invalid("delta não deriva do delta anterior"));
+            }
+
+            delta = contribution.delta_after;
+            hashes.push(contribution.hash());
+        }
+
+        if after.vk.delta_g1 != delta {
+            return Err(CeremonyError::TamperedParameters("delta_g1 difere da última contribuição"));
+        }
+        if !same_ratio((g1, after.vk.delta_g1), (g2, after.vk.delta_g2)) {
+            return Err(CeremonyError::TamperedParameters("delta_g2 não corresponde a delta_g1"));
+        }
+
+        // h e l foram divididos pelo mesmo delta acumulado
+        if !same_ratio(merge_pairs(&after.h, &before.h), (g2, after.vk.delta_g2)) {
+            return Err(CeremonyError::TamperedParameters("h"));
+        }
+        if !same_ratio(merge_pairs(&after.l, &before.l), (g2, after.vk.delta_g2)) {
+            return Err(CeremonyError::TamperedParameters("l"));
+        }
+
+        Ok(hashes)
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[FORMAT_VERSION])?;
+        writer.write_all(&self.circuit_hash)?;
+        writer.write_all(&self.phase1_hash)?;
+        self.params.write(&mut writer)?;
+        writer.write_all(&(self.contributions.len() as u32).to_be_bytes())?;
+        for contribution in &self.contributions {
+            contribution.write(&mut writer)?;
+        }
+
+        Ok(())
+    }
+
+    /// `checked` confere que os pontos dos parâmetros estão no subgrupo: é
+    /// lento, mas necessário para arquivos recebidos de outros participantes
+    pub fn read<R: Read>(mut reader: R, checked: bool) -> Result<Self, CeremonyError> {
+        let mut header = [0u8; 9];
+        reader.read_exact(&mut header)?;
+        if &header[..8] != MAGIC {
+            return Err(CeremonyError::InvalidFormat("não é um arquivo de cerimônia".to_string()));
+        }
+        if header[8] != FORMAT_VERSION {
+            return Err(CeremonyError::InvalidFormat(format!("versão {} não suportada", header[8])));
+        }
+
+        let mut circuit_hash = [0u8; 32];
+        reader.read_exact(&mut circuit_hash)?;
+        let mut phase1_hash = [0u8; 32];
+        reader.read_exact(&mut phase1_hash)?;
+        let params = Parameters::<Bls12>::read(&mut reader, checked)?;
+
+        let mut count = [0u8; 4];
+        reader.read_exact(&mut count)?;
+        let contributions = (0..u32::from_be_bytes(count))
+            .map(|_| Contribution::read(&mut reader))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut rest = [0u8; 1];
+        if reader.read(&mut rest)? != 0 {
+            return Err(CeremonyError::InvalidFormat("bytes após a última contribuição".to_string()));
+        }
+
+        Ok(Self {
+            circuit_hash,
+            phase1_hash,
+            params,
+            contributions,
+        })
+    }
+
+    pub fn read_file(path: &Path, checked: bool) -> Result<Self, CeremonyError> {
+        Self::read(BufReader::new(File::open(path)?), checked)
+    }
+
+    pub fn write_file(&self, path: &Path) -> Result<(), CeremonyError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Transcript de uma contribuição: hashes do circuito e da fase 1,
+/// contribuições anteriores e `(s, s·δ)`
+fn contribution_transcript(
+    circuit_hash: &[u8; 32],
+    phase1_hash: &[u8; 32],
+    previous: &[Contribution],
+    s: &G1Affine,
+    s_delta: &G1Affine,
+) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(circuit_hash);
+    hasher.update(phase1_hash);
+    for contribution in previous {
+        hasher.update(contribution.hash());
+    }
+    hasher.update(s.to_uncompressed());
+    hasher.update(s_delta.to_uncompressed());
+    hasher.finalize().into()
+}
+
+fn normalize<C: Curve>(points: &[C]) -> Vec<C::AffineRepr>
+where
+    C::AffineRepr: Default + Clone,
+{
+    let mut affine = vec![C::AffineRepr::default(); points.len()];
+    C::batch_normalize(points, &mut affine);
+    affine
+}
+
+fn scale(points: &[G1Affine], factor: Scalar) -> Vec<G1Affine> {
+    normalize(&points.iter().map(|point| point * factor).collect::<Vec<G1Projective>>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ceremony::phase1::tests::{phase1_file, random_phase1};
+    use crate::circuits::SimpleCircuit;
+    use bellman::groth16::{create_random_proof, generate_parameters, prepare_verifying_key, verify_proof};
+    use bellman::{Circuit, ConstraintSystem, SynthesisError};
+    use rand::thread_rng;
+
+    /// `x³ + x + 5 = y`, com variáveis auxiliares em A, B e C
+    #[derive(Clone)]
+    struct CubeCircuit;
+
+    impl Circuit<Scalar> for CubeCircuit {
+        fn synthesize<CS: ConstraintSystem<Scalar>>(self, cs: &mut CS) -> Result<(), SynthesisError> {
+            let x = cs.alloc(|| "x", || Ok(Scalar::from(3u64)))?;
+            let x2 = cs.alloc(|| "x2", || Ok(Scalar::from(9u64)))?;
+            let x3 = cs.alloc(|| "x3", || Ok(Scalar::from(27u64)))?;
+            let y = cs.alloc_input(|| "y", || Ok(Scalar::from(35u64)))?;
+
+            cs.enforce(|| "x2", |lc| lc + x, |lc| lc + x, |lc| lc + x2);
+            cs.enforce(|| "x3", |lc| lc + x2, |lc| lc + x, |lc| lc + x3);
+            cs.enforce(
+                || "y",
+                |lc| lc + x3 + x + (Scalar::from(5u64), CS::one()),
+                |lc| lc + CS::one(),
+                |lc| lc + y,
+            );
+            Ok(())
+        }
+    }
+
+    fn round_trip(params: &CeremonyParameters) -> CeremonyParameters {
+        let mut bytes = Vec::new();
+        params.write(&mut bytes).unwrap();
+        CeremonyParameters::read(&bytes[..], true).unwrap()
+    }
+
+    fn ceremony(contributions: usize) -> (Qap, Phase1, CeremonyParameters, Vec<[u8; 32]>) {
+        let mut rng = thread_rng();
+        let qap = Qap::synthesize(SimpleCircuit { a: None, b: None }).unwrap();
+        let phase1 = random_phase1(qap.domain_exp().unwrap());
+
+        let mut current = round_trip(&CeremonyParameters::initialize(&qap, &phase1).unwrap());
+        let mut hashes = Vec::new();
+        for _ in 0..contributions {
+            hashes.push(current.contribute(&mut rng));
+            current = round_trip(&current);
+        }
+
+        (qap, phase1, current, hashes)
+    }
+
+    #[test]
+    fn test_initial_parameters_match_bellman() {
+        let mut rng = thread_rng();
+        let (tau, alpha, beta) = (Scalar::random(&mut rng), Scalar::random(&mut rng), Scalar::random(&mut rng));
+
+        // Com os segredos da fase 1 em mãos, o gerador do bellman chega aos
+        // mesmos parâmetros que a avaliação do QAP na base de Lagrange
+        let qap = Qap::synthesize(CubeCircuit).unwrap();
+        let exp = qap.domain_exp().unwrap();
+        let phase1 = Phase1::read(&phase1_file(exp, tau, alpha, beta)[..], exp).unwrap();
+
+        let initial = CeremonyParameters::initialize(&qap, &phase1).unwrap();
+        let expected = generate_parameters::<Bls12, _>(
+            CubeCircuit,
+            G1Projective::generator(),
+            G2Projective::generator(),
+            alpha,
+            beta,
+            Scalar::ONE,
+            Scalar::ONE,
+            tau,
+        )
+        .unwrap();
+        assert!(initial.params == expected);
+
+        // Outra fase 1 de tamanho diferente é recusada
+        let smaller = random_phase1(exp - 1);
+        assert!(matches!(
+            CeremonyParameters::initialize(&qap, &smaller),
+            Err(CeremonyError::InvalidPhase1(_))
+        ));
+    }
+
+    #[test]
+    fn test_contribution_chain_verifies_and_proves() {
+        let (qap, phase1, last, hashes) = ceremony(2);
+        let initial = CeremonyParameters::initialize(&qap, &phase1).unwrap();
+
+        assert_eq!(last.verify(&qap, &phase1).unwrap(), hashes);
+        assert!(initial.verify(&qap, &phase1).unwrap().is_empty());
+        assert_ne!(last.transcript_hash(), initial.transcript_hash());
+        assert_ne!(last.params.vk.delta_g1, initial.params.vk.delta_g1);
+
+        let proof = create_random_proof(SimpleCircuit { a: Some(3), b: Some(4) }, &last.params, &mut thread_rng()).unwrap();
+        let pvk = prepare_verifying_key(&last.params.vk);
+        assert!(verify_proof(&pvk, &proof, &[Scalar::from(7u64)]).is_ok());
+    }
+
+    #[test]
+    fn test_rejects_tampered_chain() {
+        let (qap, phase1, last, _) = ceremony(1);
+
+        let mut other = Qap::synthesize(SimpleCircuit { a: None, b: None }).unwrap();
+        other.circuit_hash[0] ^= 1;
+        assert!(matches!(last.verify(&other, &phase1), Err(CeremonyError::CircuitMismatch { .. })));
+        let other_phase1 = random_phase1(phase1.exp);
+        assert!(matches!(last.verify(&qap, &other_phase1), Err(CeremonyError::Phase1Mismatch { .. })));
+
+        // Parâmetros iniciais de outra fase 1 (com segredos conhecidos pelo
+        // coordenador) declarando o hash da pública
+        let mut forged = CeremonyParameters::initialize(&qap, &other_phase1).unwrap();
+        forged.phase1_hash = phase1.hash;
+        assert!(matches!(forged.verify(&qap, &phase1), Err(CeremonyError::TamperedParameters(_))));
+
+        // Delta trocado sem registrar a contribuição
+        let mut forged = round_trip(&last);
+        let x = Scalar::from(5u64);
+        forged.params.vk.delta_g1 = (forged.params.vk.delta_g1 * x).to_affine();
+        forged.params.vk.delta_g2 = (forged.params.vk.delta_g2 * x).to_affine();
+        assert!(matches!(forged.verify(&qap, &phase1), Err(CeremonyError::TamperedParameters(_))));
+
+        // Prova de conhecimento adulterada
+        let mut forged = round_trip(&last);
+        forged.contributions[0].r_delta = G2Affine::generator();
+        assert!(matches!(
+            forged.verify(&qap, &phase1),
+            Err(CeremonyError::InvalidContribution { index: 0, .. })
+        ));
+
+        // h fora da razão de delta
+        let mut forged = round_trip(&last);
+        let mut h = forged.params.h.to_vec();
+        h[0] = (h[0] * x).to_affine();
+        forged.params.h = Arc::new(h);
+        assert!(matches!(forged.verify(&qap, &phase1), Err(CeremonyError::TamperedParameters("h"))));
+    }
+
+    #[test]
+    fn test_read_rejects_malformed_files() {
+        let (_, _, last, _) = ceremony(1);
+        let mut bytes = Vec::new();
+        last.write(&mut bytes).unwrap();
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(matches!(CeremonyParameters::read(&trailing[..], false), Err(CeremonyError::InvalidFormat(_))));
+
+        let mut wrong_magic = bytes.clone();
+        wrong_magic[0] ^= 1;
+        assert!(matches!(CeremonyParameters::read(&wrong_magic[..], false), Err(CeremonyError::InvalidFormat(_))));
+
+        assert!(matches!(
+            CeremonyParameters::read(&bytes[..bytes.len() - 1], false),
+            Err(CeremonyError::Io(_))
+        ));
+    }
+}
@@ -0,0 +1,183 @@
+use bls12_381::{G1Affine, G2Affine};
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::{self, BufReader, Read};
+use std::path::Path;
+
+use super::{read_g1, read_g2, CeremonyError};
+
+/// Fase 1 pública (Powers of Tau) na base de Lagrange de um domínio de
+/// `2^exp` pontos, no formato `phase1radix2m<exp>` gerado pelo `prepare_phase2`
+/// da cerimônia Powers of Tau do Zcash:
+///
+/// ```text
+/// alpha_g1 ‖ beta_g1 ‖ beta_g2
+/// ‖ m × G1 (L_i(τ)) ‖ m × G2 (L_i(τ)) ‖ m × G1 (α·L_i(τ)) ‖ m × G1 (β·L_i(τ))
+/// ‖ (m - 1) × G1 (τ^i·(τ^m - 1))
+/// ```
+///
+/// Os parâmetros iniciais da fase 2 são calculados só a partir dela, então
+/// ninguém conhece `tau`, `alpha` e `beta` se a cerimônia pública foi honesta.
+pub struct Phase1 {
+    pub exp: u32,
+    /// SHA-256 do arquivo, para comparar com o publicado pela cerimônia
+    pub hash: [u8; 32],
+    pub(super) alpha_g1: G1Affine,
+    pub(super) beta_g1: G1Affine,
+    pub(super) beta_g2: G2Affine,
+    pub(super) coeffs_g1: Vec<G1Affine>,
+    pub(super) coeffs_g2: Vec<G2Affine>,
+    pub(super) alpha_coeffs_g1: Vec<G1Affine>,
+    pub(super) beta_coeffs_g1: Vec<G1Affine>,
+    pub(super) h: Vec<G1Affine>,
+}
+
+impl Phase1 {
+    /// Tamanho em bytes do arquivo de um domínio de `2^exp` pontos
+    pub fn size(exp: u32) -> u64 {
+        let m = 1u64 << exp;
+        96 * 2 + 192 + m * (96 * 3 + 192) + (m - 1) * 96
+    }
+
+    pub fn read<R: Read>(reader: R, exp: u32) -> Result<Self, CeremonyError> {
+        let m = 1usize << exp;
+        let mut reader = HashingReader {
+            reader,
+            hasher: Sha256::new(),
+        };
+
+        let alpha_g1 = read_g1(&mut reader, "alpha_g1")?;
+        let beta_g1 = read_g1(&mut reader, "beta_g1")?;
+        let beta_g2 = read_g2(&mut reader, "beta_g2")?;
+        let coeffs_g1 = (0..m).map(|_| read_g1(&mut reader, "coeffs_g1")).collect::<Result<Vec<_>, _>>()?;
+        let coeffs_g2 = (0..m).map(|_| read_g2(&mut reader, "coeffs_g2")).collect::<Result<Vec<_>, _>>()?;
+        let alpha_coeffs_g1 = (0..m)
+            .map(|_| read_g1(&mut reader, "alpha_coeffs_g1"))
+            .collect::<Result<Vec<_>, _>>()?;
+        let beta_coeffs_g1 = (0..m)
+            .map(|_| read_g1(&mut reader, "beta_coeffs_g1"))
+            .collect::<Result<Vec<_>, _>>()?;
+        let h = (0..m - 1).map(|_| read_g1(&mut reader, "h")).collect::<Result<Vec<_>, _>>()?;
+
+        let mut rest = [0u8; 1];
+        if reader.read(&mut rest)? != 0 {
+            return Err(CeremonyError::InvalidPhase1(format!("bytes após o fim de phase1radix2m{}", exp)));
+        }
+
+        Ok(Self {
+            exp,
+            hash: reader.hasher.finalize().into(),
+            alpha_g1,
+            beta_g1,
+            beta_g2,
+            coeffs_g1,
+            coeffs_g2,
+            alpha_coeffs_g1,
+            beta_coeffs_g1,
+            h,
+        })
+    }
+
+    /// Lê o arquivo da fase 1 do domínio de `2^exp` pontos; o tamanho é
+    /// conferido antes, para apontar o arquivo certo quando vier outro
+    pub fn read_file(path: &Path, exp: u32) -> Result<Self, CeremonyError> {
+        let file = File::open(path)?;
+        let (actual, expected) = (file.metadata()?.len(), Self::size(exp));
+        if actual != expected {
+            return Err(CeremonyError::InvalidPhase1(format!(
+                "{} tem {} bytes; o circuito precisa de phase1radix2m{} ({} bytes)",
+                path.display(),
+                actual,
+                exp,
+                expected
+            )));
+        }
+
+        Self::read(BufReader::with_capacity(1 << 20, file), exp)
+    }
+}
+
+/// Calcula o SHA-256 do que é lido
+struct HashingReader<R> {
+    reader: R,
+    hasher: Sha256,
+}
+
+impl<R: Read> Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.reader.read(buf)?;
+        self.hasher.update(&buf[..read]);
+        Ok(read)
+    }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+    use bls12_381::Scalar;
+    use ff::{Field, PrimeField};
+    use group::Curve;
+    use rand::thread_rng;
+    use std::io::Write;
+
+    /// Arquivo da fase 1 com segredos conhecidos, só para os testes
+    pub fn phase1_file(exp: u32, tau: Scalar, alpha: Scalar, beta: Scalar) -> Vec<u8> {
+        let m = 1usize << exp;
+        let (g1, g2) = (G1Affine::generator(), G2Affine::generator());
+
+        // Mesmo gerador do domínio que o bellman usa
+        let mut omega = Scalar::ROOT_OF_UNITY;
+        for _ in exp..Scalar::S {
+            omega = omega.square();
+        }
+
+        // L_i(τ) = (τ^m - 1) / m · ω^i / (τ - ω^i)
+        let z = Field::pow_vartime(&tau, [m as u64]) - Scalar::ONE;
+        let z_over_m = z * Scalar::from(m as u64).invert().unwrap();
+        let mut lagrange = Vec::with_capacity(m);
+        let mut omega_i = Scalar::ONE;
+        for _ in 0..m {
+            lagrange.push(z_over_m * omega_i * (tau - omega_i).invert().unwrap());
+            omega_i *= omega;
+        }
+
+        let mut bytes = Vec::new();
+        let g1_points = |bytes: &mut Vec<u8>, scalars: &mut dyn Iterator<Item = Scalar>| {
+            for scalar in scalars {
+                bytes.write_all(&(g1 * scalar).to_affine().to_uncompressed()).unwrap();
+            }
+        };
+        g1_points(&mut bytes, &mut [alpha, beta].into_iter());
+        bytes.write_all(&(g2 * beta).to_affine().to_uncompressed()).unwrap();
+        g1_points(&mut bytes, &mut lagrange.iter().copied());
+        for l in &lagrange {
+            bytes.write_all(&(g2 * l).to_affine().to_uncompressed()).unwrap();
+        }
+        g1_points(&mut bytes, &mut lagrange.iter().map(|l| alpha * l));
+        g1_points(&mut bytes, &mut lagrange.iter().map(|l| beta * l));
+        g1_points(&mut bytes, &mut (0..m - 1).map(|i| Field::pow_vartime(&tau, [i as u64]) * z));
+
+        bytes
+    }
+
+    pub fn random_phase1(exp: u32) -> Phase1 {
+        let mut rng = thread_rng();
+        let bytes = phase1_file(exp, Scalar::random(&mut rng), Scalar::random(&mut rng), Scalar::random(&mut rng));
+        Phase1::read(&bytes[..], exp).unwrap()
+    }
+
+    #[test]
+    fn test_read_checks_size_and_hashes_the_file() {
+        let mut rng = thread_rng();
+        let bytes = phase1_file(2, Scalar::random(&mut rng), Scalar::random(&mut rng), Scalar::random(&mut rng));
+        assert_eq!(bytes.len() as u64, Phase1::size(2));
+
+        let phase1 = Phase1::read(&bytes[..], 2).unwrap();
+        assert_eq!(phase1.hash, <[u8; 32]>::from(Sha256::digest(&bytes)));
+        assert_eq!((phase1.coeffs_g2.len(), phase1.h.len()), (4, 3));
+
+        // Arquivo de outro domínio
+        assert!(Phase1::read(&bytes[..], 1).is_err());
+        assert!(Phase1::read(&bytes[..], 3).is_err());
+    }
+}
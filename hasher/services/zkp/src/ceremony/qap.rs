@@ -0,0 +1,124 @@
+use bellman::{Circuit, ConstraintSystem, Index, LinearCombination, SynthesisError, Variable};
+use bls12_381::Scalar;
+use ff::{Field, PrimeField};
+
+use super::CeremonyError;
+use crate::circuits::CircuitShape;
+
+/// Termos `(restrição, coeficiente)` de uma variável em A, B e C
+#[derive(Default)]
+pub(super) struct Terms {
+    pub a: Vec<(usize, Scalar)>,
+    pub b: Vec<(usize, Scalar)>,
+    pub c: Vec<(usize, Scalar)>,
+}
+
+/// Restrições de um circuito agrupadas por variável, como no gerador de
+/// parâmetros do bellman, para avaliar o QAP na fase 1
+pub struct Qap {
+    pub circuit_hash: [u8; 32],
+    pub(super) inputs: Vec<Terms>,
+    pub(super) aux: Vec<Terms>,
+    pub(super) constraints: usize,
+}
+
+impl Qap {
+    pub fn synthesize<C: Circuit<Scalar> + Clone>(blank: C) -> Result<Self, CeremonyError> {
+        let mut qap = Self {
+            circuit_hash: CircuitShape::of(blank.clone())?.hash(),
+            inputs: vec![Terms::default()],
+            aux: Vec::new(),
+            constraints: 0,
+        };
+        blank.synthesize(&mut qap)?;
+
+        // Como no bellman: cada entrada pública entra em A de uma restrição
+        // própria, para que as consultas das entradas sejam independentes
+        for input in &mut qap.inputs {
+            input.a.push((qap.constraints, Scalar::ONE));
+            qap.constraints += 1;
+        }
+
+        Ok(qap)
+    }
+
+    /// Expoente do domínio de avaliação (`2^exp` pontos), que escolhe o
+    /// arquivo da fase 1
+    pub fn domain_exp(&self) -> Result<u32, CeremonyError> {
+        let mut exp = 0;
+        while (1usize << exp) < self.constraints {
+            exp += 1;
+            if exp >= Scalar::S {
+                return Err(SynthesisError::PolynomialDegreeTooLarge.into());
+            }
+        }
+
+        Ok(exp)
+    }
+
+    /// Anota os termos de `lc` na restrição atual, na consulta de `query`
+    fn record(&mut self, lc: &LinearCombination<Scalar>, query: fn(&mut Terms) -> &mut Vec<(usize, Scalar)>) {
+        for (variable, coefficient) in lc.as_ref() {
+            let terms = match variable.get_unchecked() {
+                Index::Input(index) => &mut self.inputs[index],
+                Index::Aux(index) => &mut self.aux[index],
+            };
+            query(terms).push((self.constraints, *coefficient));
+        }
+    }
+}
+
+impl ConstraintSystem<Scalar> for Qap {
+    type Root = Self;
+
+    fn one() -> Variable {
+        Variable::new_unchecked(Index::Input(0))
+    }
+
+    fn alloc<F, A, AR>(&mut self, _: A, _: F) -> Result<Variable, SynthesisError>
+    where
+        F: FnOnce() -> Result<Scalar, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.aux.push(Terms::default());
+        Ok(Variable::new_unchecked(Index::Aux(self.aux.len() - 1)))
+    }
+
+    fn alloc_input<F, A, AR>(&mut self, _: A, _: F) -> Result<Variable, SynthesisError>
+    where
+        F: FnOnce() -> Result<Scalar, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.inputs.push(Terms::default());
+        Ok(Variable::new_unchecked(Index::Input(self.inputs.len() - 1)))
+    }
+
+    fn enforce<A, AR, LA, LB, LC>(&mut self, _: A, a: LA, b: LB, c: LC)
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+        LA: FnOnce(LinearCombination<Scalar>) -> LinearCombination<Scalar>,
+        LB: FnOnce(LinearCombination<Scalar>) -> LinearCombination<Scalar>,
+        LC: FnOnce(LinearCombination<Scalar>) -> LinearCombination<Scalar>,
+    {
+        self.record(&a(LinearCombination::zero()), |terms| &mut terms.a);
+        self.record(&b(LinearCombination::zero()), |terms| &mut terms.b);
+        self.record(&c(LinearCombination::zero()), |terms| &mut terms.c);
+        self.constraints += 1;
+    }
+
+    fn push_namespace<NR, N>(&mut self, _: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+    }
+
+    fn pop_namespace(&mut self) {}
+
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+}
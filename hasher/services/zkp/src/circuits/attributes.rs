@@ -0,0 +1,333 @@
+use bellman::{gadgets::num::AllocatedNum, Circuit, ConstraintSystem, LinearCombination, SynthesisError};
+use chrono::{Datelike, NaiveDate};
+use ff::PrimeFieldBits;
+
+use crate::gadgets::{enforce_range, mimc_hash, mimc_hash_gadget, range_check};
+
+/// Versão atual do circuito de atributos (envolvido por `Nullified`)
+pub const ATTRIBUTES_CIRCUIT_VERSION: &str = "attributes-v1";
+
+/// Datas são inteiros `AAAAMMDD`
+const DATE_BITS: usize = 32;
+const AGE_BITS: usize = 8;
+const COUNTRY_BITS: usize = 16;
+const KYC_TIER_BITS: usize = 8;
+
+/// Atributos atestados de um usuário, no formato usado pelo circuito
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Attributes {
+    /// Data de nascimento como `AAAAMMDD`
+    pub birth_date: u32,
+    /// País de residência (ver `country_code`)
+    pub country: u16,
+    pub kyc_tier: u8,
+}
+
+/// Conjunto de predicados provados sobre os atributos. Os ausentes ficam com
+/// o valor neutro (idade 0, país 0, nível 0), que não restringe nada.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AttributePredicates {
+    /// Data (`AAAAMMDD`) em que a idade é calculada
+    pub reference_date: u32,
+    pub min_age: u8,
+    pub country: u16,
+    pub min_kyc_tier: u8,
+}
+
+impl AttributePredicates {
+    /// Mesma regra do circuito, para recusar antes de gerar a prova
+    pub fn is_satisfied_by(&self, attributes: &Attributes) -> bool {
+        let adult_from = attributes.birth_date as u64 + 10_000 * self.min_age as u64;
+
+        adult_from <= self.reference_date as u64
+            && (self.country == 0 || self.country == attributes.country)
+            && attributes.kyc_tier >= self.min_kyc_tier
+    }
+}
+
+/// Código de país de duas letras (ISO 3166-1 alfa-2, ex.: `BR`) como inteiro
+/// de 16 bits; nunca é 0, que significa "país não verificado"
+pub fn country_code(alpha2: &str) -> Option<u16> {
+    match alpha2.as_bytes() {
+        [a, b] if a.is_ascii_uppercase() && b.is_ascii_uppercase() => Some(u16::from_be_bytes([*a, *b])),
+        _ => None,
+    }
+}
+
+/// Data como inteiro `AAAAMMDD`; a ordem dos inteiros é a ordem das datas
+pub fn date_to_number(date: NaiveDate) -> u32 {
+    date.year() as u32 * 10_000 + date.month() * 100 + date.day()
+}
+
+/// Compromisso com os atributos, calculado fora do circuito
+pub fn commit_attributes<S: PrimeFieldBits>(attributes: &Attributes, blinding: S) -> S {
+    mimc_hash(&[
+        S::from(attributes.birth_date as u64),
+        S::from(attributes.country as u64),
+        S::from(attributes.kyc_tier as u64),
+        blinding,
+    ])
+}
+
+/// Prova predicados sobre atributos privados (idade mínima, país de
+/// residência, nível mínimo de KYC) amarrados ao compromisso público
+/// `MiMC(nascimento, país, nível, blinding)`.
+///
+/// Entradas públicas, nesta ordem: `reference_date`, `min_age`, `country`,
+/// `min_kyc_tier`, `commitment`.
+#[derive(Clone)]
+pub struct AttributeCircuit<S: PrimeFieldBits> {
+    pub attributes: Option<Attributes>,
+    pub blinding: Option<S>,
+    pub predicates: Option<AttributePredicates>,
+}
+
+impl<S: PrimeFieldBits> AttributeCircuit<S> {
+    /// Instância sem testemunha, usada para gerar parâmetros
+    pub fn blank() -> Self {
+        Self {
+            attributes: None,
+            blinding: None,
+            predicates: None,
+        }
+    }
+
+    pub fn public_inputs(predicates: &AttributePredicates, commitment: S) -> Vec<S> {
+        vec![
+            S::from(predicates.reference_date as u64),
+            S::from(predicates.min_age as u64),
+            S::from(predicates.country as u64),
+            S::from(predicates.min_kyc_tier as u64),
+            commitment,
+        ]
+    }
+}
+
+impl<S: PrimeFieldBits> Circuit<S> for AttributeCircuit<S> {
+    fn synthesize<CS: ConstraintSystem<S>>(self, cs: &mut CS) -> Result<(), SynthesisError> {
+        let predicates = self.predicates;
+        let attributes = self.attributes;
+        let public = |f: fn(&AttributePredicates) -> u64| {
+            move || predicates.map(|p| S::from(f(&p))).ok_or(SynthesisError::AssignmentMissing)
+        };
+        let private = |f: fn(&Attributes) -> u64| {
+            move || attributes.map(|a| S::from(f(&a))).ok_or(SynthesisError::AssignmentMissing)
+        };
+
+        let reference_date = cs.alloc_input(|| "reference_date", public(|p| p.reference_date as u64))?;
+        let min_age = cs.alloc_input(|| "min_age", public(|p| p.min_age as u64))?;
+        let country = cs.alloc_input(|| "country", public(|p| p.country as u64))?;
+        let min_kyc_tier = cs.alloc_input(|| "min_kyc_tier", public(|p| p.min_kyc_tier as u64))?;
+        let commitment = cs.alloc_input(
+            || "commitment",
+            || {
+                let attributes = attributes.ok_or(SynthesisError::AssignmentMissing)?;
+                let blinding = self.blinding.ok_or(SynthesisError::AssignmentMissing)?;
+                Ok(commit_attributes(&attributes, blinding))
+            },
+        )?;
+
+        let birth_date = AllocatedNum::alloc(cs.namespace(|| "birth_date"), private(|a| a.birth_date as u64))?;
+        let residence = AllocatedNum::alloc(cs.namespace(|| "residence"), private(|a| a.country as u64))?;
+        let kyc_tier = AllocatedNum::alloc(cs.namespace(|| "kyc_tier"), private(|a| a.kyc_tier as u64))?;
+        let blinding = AllocatedNum::alloc(cs.namespace(|| "blinding"), || {
+            self.blinding.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+
+        // Entradas públicas e atributos nos seus tamanhos, para que as
+        // diferenças abaixo não deem a volta no corpo
+        range_check(cs.namespace(|| "birth_date range"), &birth_date, DATE_BITS)?;
+        range_check(cs.namespace(|| "residence range"), &residence, COUNTRY_BITS)?;
+        range_check(cs.namespace(|| "kyc_tier range"), &kyc_tier, KYC_TIER_BITS)?;
+        for (name, variable, value, bits) in [
+            ("reference_date range", reference_date, predicates.map(|p| p.reference_date as u64), DATE_BITS),
+            ("min_age range", min_age, predicates.map(|p| p.min_age as u64), AGE_BITS),
+            ("country range", country, predicates.map(|p| p.country as u64), COUNTRY_BITS),
+            ("min_kyc_tier range", min_kyc_tier, predicates.map(|p| p.min_kyc_tier as u64), KYC_TIER_BITS),
+        ] {
+            enforce_range(cs.namespace(|| name), LinearCombination::zero() + variable, value.map(S::from), bits)?;
+        }
+
+        // Idade: nascimento + 10000·idade mínima <= data de referência (AAAAMMDD)
+        let age_margin = predicates.zip(attributes).map(|(p, a)| {
+            S::from(p.reference_date as u64) - S::from(a.birth_date as u64) - S::from(10_000 * p.min_age as u64)
+        });
+        enforce_range(
+            cs.namespace(|| "age margin"),
+            LinearCombination::zero() + reference_date - birth_date.get_variable() - (S::from(10_000), min_age),
+            age_margin,
+            DATE_BITS,
+        )?;
+
+        // País: (residência - país) · país = 0, ou seja, país 0 ou igual à residência
+        cs.enforce(
+            || "residency",
+            |lc| lc + residence.get_variable() - country,
+            |lc| lc + country,
+            |lc| lc,
+        );
+
+        // Nível de KYC: nível - nível mínimo cabe em KYC_TIER_BITS
+        let tier_margin = predicates
+            .zip(attributes)
+            .map(|(p, a)| S::from(a.kyc_tier as u64) - S::from(p.min_kyc_tier as u64));
+        enforce_range(
+            cs.namespace(|| "kyc_tier margin"),
+            LinearCombination::zero() + kyc_tier.get_variable() - min_kyc_tier,
+            tier_margin,
+            KYC_TIER_BITS,
+        )?;
+
+        let hash = mimc_hash_gadget(
+            cs.namespace(|| "commitment hash"),
+            &[birth_date, residence, kyc_tier, blinding],
+        )?;
+        cs.enforce(
+            || "commitment opening",
+            |lc| lc + hash.get_variable(),
+            |lc| lc + CS::one(),
+            |lc| lc + commitment,
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bellman::gadgets::test::TestConstraintSystem;
+    use bls12_381::Scalar;
+    use proptest::prelude::*;
+
+    use crate::circuits::harness::{arb_scalar, check_circuit};
+
+    const ATTRIBUTES: Attributes = Attributes {
+        birth_date: 20080301,
+        country: 0x4252,
+        kyc_tier: 2,
+    };
+
+    fn synthesize(predicates: AttributePredicates) -> TestConstraintSystem<Scalar> {
+        let mut cs = TestConstraintSystem::new();
+        AttributeCircuit {
+            attributes: Some(ATTRIBUTES),
+            blinding: Some(Scalar::from(11u64)),
+            predicates: Some(predicates),
+        }
+        .synthesize(&mut cs)
+        .unwrap();
+        cs
+    }
+
+    #[test]
+    fn test_satisfied_predicates() {
+        let predicates = AttributePredicates {
+            reference_date: 20260301,
+            min_age: 18,
+            country: country_code("BR").unwrap(),
+            min_kyc_tier: 2,
+        };
+        assert!(predicates.is_satisfied_by(&ATTRIBUTES));
+
+        let cs = synthesize(predicates);
+        assert!(cs.is_satisfied());
+        let commitment = commit_attributes(&ATTRIBUTES, Scalar::from(11u64));
+        assert!(cs.verify(&AttributeCircuit::public_inputs(&predicates, commitment)));
+
+        // Predicados ausentes não restringem
+        let only_age = AttributePredicates {
+            reference_date: 20260301,
+            min_age: 18,
+            ..Default::default()
+        };
+        assert!(synthesize(only_age).is_satisfied());
+    }
+
+    #[test]
+    fn test_unsatisfied_predicates() {
+        let base = AttributePredicates {
+            reference_date: 20260301,
+            ..Default::default()
+        };
+
+        // Faz 18 anos em 01/03/2026, não um dia antes
+        let underage = AttributePredicates {
+            reference_date: 20260228,
+            min_age: 18,
+            ..base
+        };
+        assert!(!underage.is_satisfied_by(&ATTRIBUTES));
+        assert_eq!(synthesize(underage).which_is_unsatisfied(), Some("age margin/range"));
+
+        let foreign = AttributePredicates {
+            country: country_code("AR").unwrap(),
+            ..base
+        };
+        assert!(!foreign.is_satisfied_by(&ATTRIBUTES));
+        assert_eq!(synthesize(foreign).which_is_unsatisfied(), Some("residency"));
+
+        let tier = AttributePredicates { min_kyc_tier: 3, ..base };
+        assert!(!tier.is_satisfied_by(&ATTRIBUTES));
+        assert_eq!(synthesize(tier).which_is_unsatisfied(), Some("kyc_tier margin/range"));
+    }
+
+    #[test]
+    fn test_encodings() {
+        assert_eq!(country_code("BR"), Some(0x4252));
+        assert_eq!(country_code("br"), None);
+        assert_eq!(country_code("BRA"), None);
+        assert_eq!(date_to_number(NaiveDate::from_ymd_opt(2008, 3, 1).unwrap()), 20080301);
+    }
+
+    fn arb_attributes() -> impl Strategy<Value = Attributes> {
+        (19000101..=20991231u32, 1..=u16::MAX, 0..=5u8).prop_map(|(birth_date, country, kyc_tier)| Attributes {
+            birth_date,
+            country,
+            kyc_tier,
+        })
+    }
+
+    /// Predicados que às vezes valem: o país é ausente, o de residência ou outro
+    fn arb_predicates(attributes: Attributes) -> impl Strategy<Value = AttributePredicates> {
+        let country = prop_oneof![Just(0), Just(attributes.country), any::<u16>()];
+        (19000101..=20991231u32, 0..=120u8, country, 0..=5u8).prop_map(
+            |(reference_date, min_age, country, min_kyc_tier)| AttributePredicates {
+                reference_date,
+                min_age,
+                country,
+                min_kyc_tier,
+            },
+        )
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        /// O circuito aceita exatamente o que `is_satisfied_by` aceita
+        #[test]
+        fn prop_circuit_matches_predicate_rule(
+            (attributes, predicates) in arb_attributes().prop_flat_map(|a| (Just(a), arb_predicates(a))),
+            blinding in arb_scalar(),
+        ) {
+            let inputs = AttributeCircuit::public_inputs(&predicates, commit_attributes(&attributes, blinding));
+            let circuit = AttributeCircuit {
+                attributes: Some(attributes),
+                blinding: Some(blinding),
+                predicates: Some(predicates),
+            };
+
+            match check_circuit(circuit, &inputs) {
+                Ok(_) => prop_assert!(predicates.is_satisfied_by(&attributes)),
+                Err(error) => {
+                    prop_assert!(!predicates.is_satisfied_by(&attributes));
+                    prop_assert!(
+                        matches!(error.constraint(), Some("age margin/range" | "residency" | "kyc_tier margin/range")),
+                        "{}",
+                        error
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,133 @@
+//! Harness de testes dos circuitos: sintetiza qualquer `Circuit<S>` num
+//! `TestConstraintSystem`, sem setup de Groth16, e diz qual restrição falhou
+//! pelo nome (caminho de namespaces, ex.: `statement/balance >= threshold/range`).
+
+use bellman::{gadgets::test::TestConstraintSystem, Circuit, SynthesisError};
+use bls12_381::Scalar;
+use ff::PrimeField;
+use proptest::prelude::*;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Unsatisfied {
+    /// A síntese não terminou (ex.: testemunha ausente)
+    #[error("Falha na síntese: {0}")]
+    Synthesis(#[from] SynthesisError),
+    /// Primeira restrição não satisfeita, pelo nome
+    #[error("Restrição não satisfeita: {0}")]
+    Constraint(String),
+    /// As restrições valem, mas as entradas públicas calculadas são outras
+    #[error("Entradas públicas não conferem ({actual} calculadas, {expected} esperadas)")]
+    PublicInputs { expected: usize, actual: usize },
+}
+
+impl Unsatisfied {
+    /// Nome da restrição que falhou, se foi esse o problema
+    pub fn constraint(&self) -> Option<&str> {
+        match self {
+            Unsatisfied::Constraint(name) => Some(name),
+            _ => None,
+        }
+    }
+}
+
+/// Tamanho do sistema de um circuito satisfeito
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Satisfied {
+    pub constraints: usize,
+    /// Entradas públicas, sem a constante 1
+    pub inputs: usize,
+}
+
+/// Sintetiza o circuito com a testemunha que ele carrega e confere as
+/// restrições e as entradas públicas esperadas (na ordem do circuito)
+pub fn check_circuit<S, C>(circuit: C, public_inputs: &[S]) -> Result<Satisfied, Unsatisfied>
+where
+    S: PrimeField,
+    C: Circuit<S>,
+{
+    let mut cs = TestConstraintSystem::new();
+    circuit.synthesize(&mut cs)?;
+
+    if let Some(name) = cs.which_is_unsatisfied() {
+        return Err(Unsatisfied::Constraint(name.to_string()));
+    }
+    // A primeira entrada é a constante 1, que não faz parte das públicas
+    let inputs = cs.num_inputs() - 1;
+    if !cs.verify(public_inputs) {
+        return Err(Unsatisfied::PublicInputs {
+            expected: public_inputs.len(),
+            actual: inputs,
+        });
+    }
+
+    Ok(Satisfied {
+        constraints: cs.num_constraints(),
+        inputs,
+    })
+}
+
+/// Elemento qualquer do corpo escalar do BLS12-381
+pub fn arb_scalar() -> impl Strategy<Value = Scalar> {
+    any::<[u64; 4]>().prop_map(Scalar::from_raw)
+}
+
+/// Par `(maior, menor)`, inclusive iguais
+pub fn arb_ordered_pair() -> impl Strategy<Value = (u128, u128)> {
+    (any::<u128>(), any::<u128>()).prop_map(|(a, b)| (a.max(b), a.min(b)))
+}
+
+/// Par `(menor, maior)` com o primeiro estritamente menor
+pub fn arb_strictly_less() -> impl Strategy<Value = (u128, u128)> {
+    (1..=u128::MAX).prop_flat_map(|high| (0..high, Just(high)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuits::{SimpleCircuit, SolvencyCircuit};
+    use ff::Field;
+
+    fn simple(a: u32, b: u32) -> SimpleCircuit {
+        SimpleCircuit {
+            a: Some(a as u64),
+            b: Some(b as u64),
+        }
+    }
+
+    #[test]
+    fn test_reports_missing_witness() {
+        let result = check_circuit(SimpleCircuit { a: Some(1), b: None }, &[Scalar::from(1u64)]);
+        assert!(matches!(result, Err(Unsatisfied::Synthesis(SynthesisError::AssignmentMissing))));
+    }
+
+    #[test]
+    fn test_reports_constraint_by_name() {
+        let circuit = SolvencyCircuit {
+            balance: Some(1),
+            blinding: Some(Scalar::ONE),
+            threshold: Some(2),
+        };
+        let error = check_circuit(circuit, &[]).unwrap_err();
+        assert_eq!(error.constraint(), Some("balance >= threshold/range"));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_simple_sum(a: u32, b: u32) {
+            let satisfied = check_circuit(simple(a, b), &[Scalar::from(a as u64 + b as u64)]).unwrap();
+            prop_assert_eq!(satisfied, Satisfied { constraints: 1, inputs: 1 });
+        }
+
+        #[test]
+        fn prop_simple_wrong_sum(a: u32, b: u32, c: u64) {
+            prop_assume!(c != a as u64 + b as u64);
+            let result = check_circuit(simple(a, b), &[Scalar::from(c)]);
+            prop_assert!(
+                matches!(result, Err(Unsatisfied::PublicInputs { expected: 1, actual: 1 })),
+                "{:?}",
+                result.err()
+            );
+        }
+    }
+}
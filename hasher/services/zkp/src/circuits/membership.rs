@@ -0,0 +1,180 @@
+use bellman::{gadgets::num::AllocatedNum, Circuit, ConstraintSystem, SynthesisError};
+use ff::PrimeField;
+
+use crate::gadgets::{merkle_root_gadget, mimc_hash, mimc_hash_gadget, MerkleError, MerklePath, MerkleTree};
+
+/// Primeira versão do circuito de pertinência à whitelist, sem nullifier (ver `Nullified`)
+pub const MEMBERSHIP_CIRCUIT_VERSION: &str = "membership-v1";
+
+/// Profundidade da árvore de carteiras aprovadas (até 256 folhas; o
+/// SubwalletRegistry limita o total a 100 subwallets)
+pub const WHITELIST_DEPTH: usize = 8;
+
+/// Prova que uma carteira privada é folha da árvore de carteiras aprovadas,
+/// sem revelar qual.
+///
+/// Entrada pública: `root`.
+#[derive(Clone)]
+pub struct MembershipCircuit<S: PrimeField> {
+    pub wallet: Option<S>,
+    pub path: Option<MerklePath<S>>,
+}
+
+impl<S: PrimeField> MembershipCircuit<S> {
+    /// Instância sem testemunha, usada para gerar parâmetros
+    pub fn blank() -> Self {
+        Self { wallet: None, path: None }
+    }
+
+    pub fn public_inputs(root: S) -> Vec<S> {
+        vec![root]
+    }
+}
+
+/// Converte um endereço `0x...` de 20 bytes em elemento do corpo (ignora o checksum de maiúsculas)
+pub fn wallet_to_field<S: PrimeField>(address: &str) -> Option<S> {
+    let hex_part = address.strip_prefix("0x").or_else(|| address.strip_prefix("0X"))?;
+    if hex_part.len() != 40 {
+        return None;
+    }
+
+    let bytes = hex::decode(hex_part).ok()?;
+    let hi = u32::from_be_bytes(bytes[..4].try_into().unwrap());
+    let lo = u128::from_be_bytes(bytes[4..].try_into().unwrap());
+    let two_128 = S::from_u128(u128::MAX) + S::ONE;
+
+    Some(S::from(hi as u64) * two_128 + S::from_u128(lo))
+}
+
+/// Folha da árvore para uma carteira
+pub fn wallet_leaf<S: PrimeField>(wallet: S) -> S {
+    mimc_hash(&[wallet])
+}
+
+/// Monta a árvore de carteiras aprovadas, na ordem recebida
+pub fn whitelist_tree<S: PrimeField>(wallets: &[S]) -> Result<MerkleTree<S>, MerkleError> {
+    MerkleTree::new(wallets.iter().copied().map(wallet_leaf).collect(), WHITELIST_DEPTH)
+}
+
+impl<S: PrimeField> Circuit<S> for MembershipCircuit<S> {
+    fn synthesize<CS: ConstraintSystem<S>>(self, cs: &mut CS) -> Result<(), SynthesisError> {
+        let wallet = AllocatedNum::alloc(cs.namespace(|| "wallet"), || {
+            self.wallet.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+
+        let leaf = mimc_hash_gadget(cs.namespace(|| "leaf"), &[wallet])?;
+        let root = merkle_root_gadget(cs.namespace(|| "path"), &leaf, self.path.as_ref(), WHITELIST_DEPTH)?;
+        root.inputize(cs.namespace(|| "root"))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bellman::gadgets::test::TestConstraintSystem;
+    use bellman::groth16::{create_random_proof, generate_random_parameters, prepare_verifying_key, verify_proof};
+    use bls12_381::{Bls12, Scalar};
+    use proptest::{prelude::*, sample::Index};
+    use rand::thread_rng;
+
+    use crate::circuits::harness::{arb_scalar, check_circuit, Unsatisfied};
+
+    fn wallets() -> Vec<Scalar> {
+        [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+        ]
+        .iter()
+        .map(|w| wallet_to_field(w).unwrap())
+        .collect()
+    }
+
+    #[test]
+    fn test_wallet_to_field() {
+        let lower = wallet_to_field::<Scalar>("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed");
+        assert!(lower.is_some());
+        assert_eq!(lower, wallet_to_field("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"));
+        assert_eq!(wallet_to_field::<Scalar>("0x0000000000000000000000000000000000000001"), Some(Scalar::from(1u64)));
+
+        assert!(wallet_to_field::<Scalar>("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").is_none());
+        assert!(wallet_to_field::<Scalar>("0x5aaeb6053f3e94c9b9a09f33669435e7ef1bea").is_none());
+        assert!(wallet_to_field::<Scalar>("0xzzaeb6053f3e94c9b9a09f33669435e7ef1beaed").is_none());
+    }
+
+    #[test]
+    fn test_member_wallet_is_satisfied() {
+        let wallets = wallets();
+        let tree = whitelist_tree(&wallets).unwrap();
+
+        let mut cs = TestConstraintSystem::new();
+        MembershipCircuit {
+            wallet: Some(wallets[2]),
+            path: tree.path(2),
+        }
+        .synthesize(&mut cs)
+        .unwrap();
+
+        assert!(cs.is_satisfied());
+        assert!(cs.verify(&MembershipCircuit::public_inputs(tree.root())));
+    }
+
+    #[test]
+    fn test_groth16_round_trip() {
+        let mut rng = thread_rng();
+        let params = generate_random_parameters::<Bls12, _, _>(MembershipCircuit::<Scalar>::blank(), &mut rng).unwrap();
+        let pvk = prepare_verifying_key(&params.vk);
+
+        let wallets = wallets();
+        let tree = whitelist_tree(&wallets).unwrap();
+        let circuit = MembershipCircuit {
+            wallet: Some(wallets[1]),
+            path: tree.path(1),
+        };
+        let proof = create_random_proof(circuit, &params, &mut rng).unwrap();
+        assert!(verify_proof(&pvk, &proof, &MembershipCircuit::public_inputs(tree.root())).is_ok());
+
+        // Uma carteira fora da árvore com o caminho de outra leva a outra raiz
+        let outsider = wallet_to_field("0x0000000000000000000000000000000000000001").unwrap();
+        let circuit = MembershipCircuit {
+            wallet: Some(outsider),
+            path: tree.path(1),
+        };
+        let proof = create_random_proof(circuit, &params, &mut rng).unwrap();
+        assert!(verify_proof(&pvk, &proof, &MembershipCircuit::public_inputs(tree.root())).is_err());
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(16))]
+
+        #[test]
+        fn prop_member_is_accepted(wallets in prop::collection::vec(arb_scalar(), 1..=12), index: Index) {
+            let tree = whitelist_tree(&wallets).unwrap();
+            let index = index.index(wallets.len());
+            let circuit = MembershipCircuit {
+                wallet: Some(wallets[index]),
+                path: tree.path(index),
+            };
+            prop_assert!(check_circuit(circuit, &MembershipCircuit::public_inputs(tree.root())).is_ok());
+        }
+
+        /// Com o caminho de um membro, quem está fora chega a outra raiz
+        #[test]
+        fn prop_outsider_reaches_other_root(
+            wallets in prop::collection::vec(arb_scalar(), 1..=12),
+            index: Index,
+            outsider in arb_scalar(),
+        ) {
+            prop_assume!(!wallets.contains(&outsider));
+            let tree = whitelist_tree(&wallets).unwrap();
+            let circuit = MembershipCircuit {
+                wallet: Some(outsider),
+                path: tree.path(index.index(wallets.len())),
+            };
+            let result = check_circuit(circuit, &MembershipCircuit::public_inputs(tree.root()));
+            prop_assert!(matches!(result, Err(Unsatisfied::PublicInputs { .. })), "{:?}", result);
+        }
+    }
+}
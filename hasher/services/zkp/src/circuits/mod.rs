@@ -0,0 +1,17 @@
+mod attributes;
+#[cfg(test)]
+pub(crate) mod harness;
+mod membership;
+mod nullifier;
+mod profile;
+mod registry;
+mod simple;
+mod solvency;
+
+pub use attributes::*;
+pub use membership::*;
+pub use nullifier::*;
+pub use profile::*;
+pub use registry::*;
+pub use simple::*;
+pub use solvency::*;
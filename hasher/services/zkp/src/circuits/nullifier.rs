@@ -0,0 +1,196 @@
+use bellman::{gadgets::num::AllocatedNum, Circuit, ConstraintSystem, SynthesisError};
+use ff::PrimeField;
+use sha2::{Digest, Sha256};
+
+use crate::gadgets::{mimc_hash, mimc_hash_gadget};
+
+/// Versão do circuito de solvência com nullifier
+pub const SOLVENCY_NULLIFIED_VERSION: &str = "solvency-v2";
+
+/// Versão do circuito de pertinência à whitelist com nullifier
+pub const MEMBERSHIP_NULLIFIED_VERSION: &str = "membership-v2";
+
+/// Tamanho máximo do contexto de aplicação (ex.: `loan:42`)
+pub const MAX_CONTEXT_LENGTH: usize = 128;
+
+/// Converte o contexto de aplicação em elemento do corpo: os 31 primeiros
+/// bytes do SHA-256, para caber no corpo sem redução
+pub fn context_to_field<S: PrimeField>(context: &str) -> S {
+    let digest = Sha256::digest(context.as_bytes());
+    let hi = u128::from_be_bytes(digest[..16].try_into().unwrap());
+    let mut lo = [0u8; 16];
+    lo[1..].copy_from_slice(&digest[16..31]);
+    let two_120 = S::from_u128(1 << 120);
+
+    S::from_u128(hi) * two_120 + S::from_u128(u128::from_be_bytes(lo))
+}
+
+/// Entradas públicas de um circuito `Nullified`: as do circuito original
+/// seguidas de `context` e `nullifier`
+pub fn nullified_inputs<S: PrimeField>(mut statement_inputs: Vec<S>, context: S, nullifier: S) -> Vec<S> {
+    statement_inputs.extend([context, nullifier]);
+    statement_inputs
+}
+
+/// Nullifier de um usuário em um contexto, calculado fora do circuito
+pub fn derive_nullifier<S: PrimeField>(secret: S, context: S) -> S {
+    mimc_hash(&[secret, context])
+}
+
+/// Acrescenta a um circuito as entradas públicas `context` e
+/// `nullifier = MiMC(secret, context)`, depois das entradas do próprio circuito.
+/// O mesmo segredo gera o mesmo nullifier no mesmo contexto, então uma prova
+/// (ou outra prova do mesmo usuário) não pode ser usada duas vezes nele, e
+/// contextos diferentes não revelam que as provas são do mesmo usuário, desde
+/// que as demais entradas públicas também não o revelem (os compromissos do
+/// enunciado usam um blinding novo a cada prova).
+///
+/// O circuito não amarra o segredo ao restante do enunciado: as provas são
+/// geradas pelo serviço, com o segredo guardado para cada usuário.
+#[derive(Clone)]
+pub struct Nullified<S: PrimeField, C> {
+    pub statement: C,
+    pub secret: Option<S>,
+    pub context: Option<S>,
+}
+
+impl<S: PrimeField, C> Nullified<S, C> {
+    /// Instância sem testemunha, usada para gerar parâmetros
+    pub fn blank(statement: C) -> Self {
+        Self {
+            statement,
+            secret: None,
+            context: None,
+        }
+    }
+}
+
+impl<S: PrimeField, C: Circuit<S>> Circuit<S> for Nullified<S, C> {
+    fn synthesize<CS: ConstraintSystem<S>>(self, cs: &mut CS) -> Result<(), SynthesisError> {
+        self.statement.synthesize(&mut cs.namespace(|| "statement"))?;
+
+        let context = AllocatedNum::alloc(cs.namespace(|| "context"), || {
+            self.context.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        context.inputize(cs.namespace(|| "context input"))?;
+
+        let secret = AllocatedNum::alloc(cs.namespace(|| "secret"), || {
+            self.secret.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+
+        let nullifier = mimc_hash_gadget(cs.namespace(|| "nullifier hash"), &[secret, context])?;
+        nullifier.inputize(cs.namespace(|| "nullifier"))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuits::{commit_balance, SolvencyCircuit};
+    use bellman::gadgets::test::TestConstraintSystem;
+    use bls12_381::Scalar;
+    use ff::Field;
+    use proptest::prelude::*;
+    use rand::thread_rng;
+
+    use crate::circuits::harness::{arb_ordered_pair, arb_scalar, arb_strictly_less, check_circuit, Unsatisfied};
+
+    fn solvency(secret: Scalar, context: &str) -> Nullified<Scalar, SolvencyCircuit<Scalar>> {
+        Nullified {
+            statement: SolvencyCircuit {
+                balance: Some(500),
+                blinding: Some(Scalar::from(7u64)),
+                threshold: Some(100),
+            },
+            secret: Some(secret),
+            context: Some(context_to_field(context)),
+        }
+    }
+
+    #[test]
+    fn test_nullifier_is_public_and_bound_to_context() {
+        let secret = Scalar::random(&mut thread_rng());
+        let context = context_to_field::<Scalar>("loan:42");
+        let nullifier = derive_nullifier(secret, context);
+        let statement = SolvencyCircuit::public_inputs(100, commit_balance(500, Scalar::from(7u64)));
+
+        let mut cs = TestConstraintSystem::new();
+        solvency(secret, "loan:42").synthesize(&mut cs).unwrap();
+        assert!(cs.is_satisfied());
+        assert!(cs.verify(&nullified_inputs(statement.clone(), context, nullifier)));
+
+        // Outro contexto ou outro nullifier não conferem
+        let other = context_to_field::<Scalar>("loan:43");
+        assert!(!cs.verify(&nullified_inputs(statement.clone(), other, nullifier)));
+        assert!(!cs.verify(&nullified_inputs(statement, context, nullifier + Scalar::ONE)));
+
+        // Determinístico por segredo e contexto
+        assert_eq!(derive_nullifier(secret, context), nullifier);
+        assert_ne!(derive_nullifier(secret, other), nullifier);
+        assert_ne!(derive_nullifier(secret + Scalar::ONE, context), nullifier);
+    }
+
+    #[test]
+    fn test_context_to_field() {
+        let a = context_to_field::<Scalar>("loan:42");
+        assert_eq!(a, context_to_field("loan:42"));
+        assert_ne!(a, context_to_field("loan:4"));
+        assert_ne!(context_to_field::<Scalar>(""), Scalar::ZERO);
+    }
+
+    fn nullified(
+        balance: u128,
+        threshold: u128,
+        secret: Scalar,
+        context: &str,
+    ) -> Nullified<Scalar, SolvencyCircuit<Scalar>> {
+        Nullified {
+            statement: SolvencyCircuit {
+                balance: Some(balance),
+                blinding: Some(Scalar::from(7u64)),
+                threshold: Some(threshold),
+            },
+            secret: Some(secret),
+            context: Some(context_to_field(context)),
+        }
+    }
+
+    fn expected_inputs(balance: u128, threshold: u128, secret: Scalar, context: &str) -> Vec<Scalar> {
+        let context = context_to_field(context);
+        let statement = SolvencyCircuit::public_inputs(threshold, commit_balance(balance, Scalar::from(7u64)));
+        nullified_inputs(statement, context, derive_nullifier(secret, context))
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        #[test]
+        fn prop_nullified_witness_is_accepted(
+            (balance, threshold) in arb_ordered_pair(),
+            secret in arb_scalar(),
+            context in "[a-z]{1,8}:[0-9]{1,6}",
+        ) {
+            let inputs = expected_inputs(balance, threshold, secret, &context);
+            prop_assert!(check_circuit(nullified(balance, threshold, secret, &context), &inputs).is_ok());
+        }
+
+        /// O enunciado continua valendo dentro do namespace `statement`
+        #[test]
+        fn prop_insolvent_statement_fails_in_namespace((balance, threshold) in arb_strictly_less(), secret in arb_scalar()) {
+            let inputs = expected_inputs(balance, threshold, secret, "loan:42");
+            let error = check_circuit(nullified(balance, threshold, secret, "loan:42"), &inputs).unwrap_err();
+            prop_assert_eq!(error.constraint(), Some("statement/balance >= threshold/range"));
+        }
+
+        /// Outro segredo gera outro nullifier no mesmo contexto
+        #[test]
+        fn prop_other_secret_fails_nullifier(secret in arb_scalar(), other in arb_scalar()) {
+            prop_assume!(secret != other);
+            let inputs = expected_inputs(500, 100, other, "loan:42");
+            let result = check_circuit(nullified(500, 100, secret, "loan:42"), &inputs);
+            prop_assert!(matches!(result, Err(Unsatisfied::PublicInputs { .. })), "{:?}", result);
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use bellman::{Circuit, SynthesisError};
+use bls12_381::Scalar;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::CircuitShape;
+
+/// Tamanho do sistema R1CS de um circuito, contado sem testemunha
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConstraintProfile {
+    pub constraints: usize,
+    /// Entradas públicas, sem a constante 1
+    pub public_inputs: usize,
+    pub private_variables: usize,
+    /// Termos somados nas combinações lineares A, B e C
+    pub lc_terms: usize,
+    /// Termos por restrição, em média
+    pub lc_density: f64,
+    pub max_lc_terms: usize,
+    /// Restrições por namespace de primeiro nível do circuito
+    pub namespaces: BTreeMap<String, usize>,
+}
+
+impl ConstraintProfile {
+    pub fn of<C: Circuit<Scalar>>(blank: C) -> Result<Self, SynthesisError> {
+        Ok(CircuitShape::of(blank)?.into())
+    }
+}
+
+impl From<CircuitShape> for ConstraintProfile {
+    fn from(shape: CircuitShape) -> Self {
+        Self {
+            constraints: shape.constraints,
+            public_inputs: shape.inputs - 1,
+            private_variables: shape.aux,
+            lc_terms: shape.lc_terms,
+            lc_density: match shape.constraints {
+                0 => 0.0,
+                constraints => shape.lc_terms as f64 / constraints as f64,
+            },
+            max_lc_terms: shape.max_lc_terms,
+            namespaces: shape.namespaces,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuits::{Nullified, SimpleCircuit, SolvencyCircuit, BALANCE_BITS};
+    use bellman::gadgets::test::TestConstraintSystem;
+
+    #[test]
+    fn test_profile_matches_synthesis() {
+        let simple = ConstraintProfile::of(SimpleCircuit { a: None, b: None }).unwrap();
+        let mut cs = TestConstraintSystem::<Scalar>::new();
+        SimpleCircuit { a: Some(1), b: Some(2) }.synthesize(&mut cs).unwrap();
+        assert_eq!(simple.constraints, cs.num_constraints());
+        assert_eq!(simple.public_inputs, cs.num_inputs() - 1);
+
+        // a · 1 = c - b
+        assert_eq!((simple.lc_terms, simple.max_lc_terms), (4, 2));
+        assert_eq!(simple.lc_density, 4.0);
+        assert_eq!(simple.namespaces, BTreeMap::from([(String::new(), 1)]));
+
+        // O nullifier é contado fora do namespace do enunciado
+        let nullified = ConstraintProfile::of(Nullified::blank(SolvencyCircuit::<Scalar>::blank())).unwrap();
+        let solvency = ConstraintProfile::of(SolvencyCircuit::<Scalar>::blank()).unwrap();
+        assert_eq!(nullified.namespaces["statement"], solvency.constraints);
+        assert_eq!(nullified.public_inputs, solvency.public_inputs + 2);
+
+        // A maior combinação é a recomposição dos bits do saldo
+        assert_eq!(solvency.max_lc_terms, BALANCE_BITS);
+    }
+}
@@ -0,0 +1,596 @@
+use bellman::{Circuit, ConstraintSystem, Index, LinearCombination, SynthesisError, Variable};
+use bls12_381::Scalar;
+use ff::PrimeField;
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+use thiserror::Error;
+
+use super::{
+    AttributeCircuit, MembershipCircuit, Nullified, SimpleCircuit, SolvencyCircuit, ATTRIBUTES_CIRCUIT_VERSION,
+    MEMBERSHIP_CIRCUIT_VERSION, MEMBERSHIP_NULLIFIED_VERSION, SIMPLE_CIRCUIT_VERSION, SOLVENCY_CIRCUIT_VERSION,
+    SOLVENCY_NULLIFIED_VERSION,
+};
+
+pub const SIMPLE_CIRCUIT: &str = "simple";
+pub const SOLVENCY_CIRCUIT: &str = "solvency";
+pub const MEMBERSHIP_CIRCUIT: &str = "membership";
+pub const ATTRIBUTES_CIRCUIT: &str = "attributes";
+
+#[derive(Debug, Error)]
+pub enum CircuitError {
+    #[error("Circuito desconhecido: {0}")]
+    UnknownCircuit(String),
+    #[error("Versão {version} do circuito {name} não existe")]
+    UnknownVersion { name: String, version: String },
+    #[error("Versão inválida (esperado MAJOR.MINOR.PATCH): {0}")]
+    InvalidVersion(String),
+    #[error("Versão {version} do circuito {name} está obsoleta e não gera novas provas")]
+    Deprecated { name: String, version: Version },
+    #[error("Circuito {0} não tem versão ativa")]
+    NoActiveVersion(String),
+    #[error("Circuito {name} declara {declared} entradas públicas, mas aloca {allocated}")]
+    SchemaMismatch { name: String, declared: usize, allocated: usize },
+    #[error("Erro ao sintetizar o circuito: {0}")]
+    Synthesis(#[from] SynthesisError),
+}
+
+impl CircuitError {
+    /// Código de erro exposto na API
+    pub fn code(&self) -> &'static str {
+        match self {
+            CircuitError::UnknownCircuit(_) | CircuitError::UnknownVersion { .. } => "unknown_circuit",
+            CircuitError::InvalidVersion(_) => "invalid_circuit_version",
+            CircuitError::Deprecated { .. } | CircuitError::NoActiveVersion(_) => "deprecated_circuit",
+            CircuitError::SchemaMismatch { .. } | CircuitError::Synthesis(_) => "internal_server_error",
+        }
+    }
+}
+
+/// Circuito escolhido pelo cliente; sem `version`, vale a versão ativa mais recente
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CircuitSelector {
+    pub name: String,
+    pub version: Option<String>,
+}
+
+/// Identificação completa de uma versão de circuito
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CircuitId {
+    pub name: String,
+    pub version: String,
+}
+
+/// Como interpretar uma entrada pública
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PublicInputKind {
+    /// Inteiro sem sinal de até 128 bits
+    Integer,
+    /// Elemento do corpo (ex.: compromisso ou raiz de Merkle), em hex
+    Field,
+}
+
+/// Entrada pública de um circuito, na ordem em que o verificador a espera
+#[derive(Debug, Clone, Serialize)]
+pub struct PublicInput {
+    pub name: &'static str,
+    pub kind: PublicInputKind,
+    pub description: &'static str,
+}
+
+/// Uma versão de circuito registrada. `parameters_key` é a chave dos
+/// parâmetros Groth16 em `zkp_parameters` (ex.: `solvency-v1`).
+#[derive(Debug, Clone)]
+pub struct CircuitDefinition {
+    pub name: &'static str,
+    pub version: Version,
+    pub parameters_key: &'static str,
+    pub description: &'static str,
+    pub public_inputs: Vec<PublicInput>,
+    pub constraint_count: usize,
+    /// Hash das restrições (ver `CircuitShape`)
+    pub circuit_hash: [u8; 32],
+    pub deprecated: bool,
+}
+
+impl CircuitDefinition {
+    /// Registra uma versão a partir do circuito sem testemunha, de onde saem
+    /// o número de restrições, o de entradas públicas e o hash do circuito
+    pub fn new<C: Circuit<Scalar>>(
+        name: &'static str,
+        version: &str,
+        parameters_key: &'static str,
+        description: &'static str,
+        public_inputs: Vec<PublicInput>,
+        blank: C,
+    ) -> Result<Self, CircuitError> {
+        let version = Version::parse(version).map_err(|_| CircuitError::InvalidVersion(version.to_string()))?;
+
+        let shape = CircuitShape::of(blank)?;
+
+        // A entrada pública 0 é a constante 1
+        if shape.inputs - 1 != public_inputs.len() {
+            return Err(CircuitError::SchemaMismatch {
+                name: name.to_string(),
+                declared: public_inputs.len(),
+                allocated: shape.inputs - 1,
+            });
+        }
+
+        Ok(Self {
+            name,
+            version,
+            parameters_key,
+            description,
+            public_inputs,
+            constraint_count: shape.constraints,
+            circuit_hash: shape.hash(),
+            deprecated: false,
+        })
+    }
+
+    /// Versão que ainda verifica provas antigas, mas não gera novas
+    pub fn deprecated(mut self) -> Self {
+        self.deprecated = true;
+        self
+    }
+
+    /// Posições das entradas `context` e `nullifier`, nos circuitos com nullifier
+    pub fn nullifier_inputs(&self) -> Option<(usize, usize)> {
+        let names = self.public_inputs.iter().map(|input| input.name).collect::<Vec<_>>();
+        match names.as_slice() {
+            [.., "context", "nullifier"] => Some((names.len() - 2, names.len() - 1)),
+            _ => None,
+        }
+    }
+
+    pub fn id(&self) -> CircuitId {
+        CircuitId {
+            name: self.name.to_string(),
+            version: self.version.to_string(),
+        }
+    }
+}
+
+/// Forma do sistema R1CS de um circuito: número de variáveis e restrições e
+/// um hash das restrições. Não avalia a testemunha, então aceita circuitos
+/// sem valores. O hash identifica o circuito nos parâmetros da cerimônia.
+pub struct CircuitShape {
+    /// Entradas públicas, contando a constante 1
+    pub inputs: usize,
+    pub aux: usize,
+    pub constraints: usize,
+    /// Termos somados nas combinações lineares A, B e C de todas as restrições
+    pub lc_terms: usize,
+    /// Maior combinação linear encontrada
+    pub max_lc_terms: usize,
+    /// Restrições por namespace de primeiro nível (`""` para as de fora)
+    pub namespaces: BTreeMap<String, usize>,
+    namespace: Vec<String>,
+    hasher: Sha256,
+}
+
+impl CircuitShape {
+    pub fn of<C: Circuit<Scalar>>(blank: C) -> Result<Self, SynthesisError> {
+        let mut shape = Self {
+            inputs: 1,
+            aux: 0,
+            constraints: 0,
+            lc_terms: 0,
+            max_lc_terms: 0,
+            namespaces: BTreeMap::new(),
+            namespace: Vec::new(),
+            hasher: Sha256::new(),
+        };
+        blank.synthesize(&mut shape)?;
+        Ok(shape)
+    }
+
+    /// SHA-256 das restrições (A, B e C de cada uma) e das contagens
+    pub fn hash(&self) -> [u8; 32] {
+        let mut hasher = self.hasher.clone();
+        for count in [self.inputs, self.aux, self.constraints] {
+            hasher.update((count as u64).to_be_bytes());
+        }
+        hasher.finalize().into()
+    }
+
+    fn hash_lc<S: PrimeField>(&mut self, lc: &LinearCombination<S>) {
+        let terms = lc.as_ref();
+        self.lc_terms += terms.len();
+        self.max_lc_terms = self.max_lc_terms.max(terms.len());
+        self.hasher.update((terms.len() as u64).to_be_bytes());
+        for (variable, coefficient) in terms {
+            let (tag, index) = match variable.get_unchecked() {
+                Index::Input(index) => (0u8, index),
+                Index::Aux(index) => (1u8, index),
+            };
+            self.hasher.update([tag]);
+            self.hasher.update((index as u64).to_be_bytes());
+            self.hasher.update(coefficient.to_repr().as_ref());
+        }
+    }
+}
+
+impl<S: PrimeField> ConstraintSystem<S> for CircuitShape {
+    type Root = Self;
+
+    fn one() -> Variable {
+        Variable::new_unchecked(Index::Input(0))
+    }
+
+    fn alloc<F, A, AR>(&mut self, _: A, _: F) -> Result<Variable, SynthesisError>
+    where
+        F: FnOnce() -> Result<S, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.aux += 1;
+        Ok(Variable::new_unchecked(Index::Aux(self.aux - 1)))
+    }
+
+    fn alloc_input<F, A, AR>(&mut self, _: A, _: F) -> Result<Variable, SynthesisError>
+    where
+        F: FnOnce() -> Result<S, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.inputs += 1;
+        Ok(Variable::new_unchecked(Index::Input(self.inputs - 1)))
+    }
+
+    fn enforce<A, AR, LA, LB, LC>(&mut self, _: A, a: LA, b: LB, c: LC)
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+        LA: FnOnce(LinearCombination<S>) -> LinearCombination<S>,
+        LB: FnOnce(LinearCombination<S>) -> LinearCombination<S>,
+        LC: FnOnce(LinearCombination<S>) -> LinearCombination<S>,
+    {
+        self.hash_lc(&a(LinearCombination::zero()));
+        self.hash_lc(&b(LinearCombination::zero()));
+        self.hash_lc(&c(LinearCombination::zero()));
+        self.constraints += 1;
+
+        let namespace = self.namespace.first().cloned().unwrap_or_default();
+        *self.namespaces.entry(namespace).or_default() += 1;
+    }
+
+    fn push_namespace<NR, N>(&mut self, name: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+        // Só o primeiro nível entra na contagem; os demais nomes nem são montados
+        let name = if self.namespace.is_empty() { name().into() } else { String::new() };
+        self.namespace.push(name);
+    }
+
+    fn pop_namespace(&mut self) {
+        self.namespace.pop();
+    }
+
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+}
+
+/// Versões de circuito conhecidas pelo serviço
+pub struct CircuitRegistry {
+    circuits: Vec<CircuitDefinition>,
+}
+
+impl CircuitRegistry {
+    pub fn new(circuits: Vec<CircuitDefinition>) -> Self {
+        Self { circuits }
+    }
+
+    /// Circuitos compilados neste serviço
+    pub fn builtin() -> Result<Self, CircuitError> {
+        Ok(Self::new(vec![
+            CircuitDefinition::new(
+                SIMPLE_CIRCUIT,
+                "1.0.0",
+                SIMPLE_CIRCUIT_VERSION,
+                "Demonstração: conhece a e b com a + b = c",
+                vec![PublicInput {
+                    name: "c",
+                    kind: PublicInputKind::Integer,
+                    description: "Soma pública",
+                }],
+                SimpleCircuit { a: None, b: None },
+            )?,
+            CircuitDefinition::new(
+                SOLVENCY_CIRCUIT,
+                "1.0.0",
+                SOLVENCY_CIRCUIT_VERSION,
+                "Saldo verificado maior ou igual a um limite, sem revelar o saldo",
+                solvency_inputs(),
+                SolvencyCircuit::<Scalar>::blank(),
+            )?
+            .deprecated(),
+            CircuitDefinition::new(
+                SOLVENCY_CIRCUIT,
+                "2.0.0",
+                SOLVENCY_NULLIFIED_VERSION,
+                "Saldo verificado maior ou igual a um limite, com nullifier por contexto de aplicação",
+                with_nullifier(solvency_inputs()),
+                Nullified::blank(SolvencyCircuit::<Scalar>::blank()),
+            )?,
+            CircuitDefinition::new(
+                MEMBERSHIP_CIRCUIT,
+                "1.0.0",
+                MEMBERSHIP_CIRCUIT_VERSION,
+                "Carteira pertence à lista de carteiras aprovadas",
+                membership_inputs(),
+                MembershipCircuit::<Scalar>::blank(),
+            )?
+            .deprecated(),
+            CircuitDefinition::new(
+                MEMBERSHIP_CIRCUIT,
+                "2.0.0",
+                MEMBERSHIP_NULLIFIED_VERSION,
+                "Carteira pertence à lista de carteiras aprovadas, com nullifier por contexto de aplicação",
+                with_nullifier(membership_inputs()),
+                Nullified::blank(MembershipCircuit::<Scalar>::blank()),
+            )?,
+            CircuitDefinition::new(
+                ATTRIBUTES_CIRCUIT,
+                "1.0.0",
+                ATTRIBUTES_CIRCUIT_VERSION,
+                "Atributos comprometidos (idade, país, nível de KYC) satisfazem os predicados, sem revelá-los",
+                with_nullifier(attributes_inputs()),
+                Nullified::blank(AttributeCircuit::<Scalar>::blank()),
+            )?,
+        ]))
+    }
+
+    pub fn all(&self) -> &[CircuitDefinition] {
+        &self.circuits
+    }
+
+    /// Versão de um circuito. Sem versão, a ativa mais recente.
+    pub fn resolve(&self, name: &str, version: Option<&str>) -> Result<&CircuitDefinition, CircuitError> {
+        let versions = self
+            .circuits
+            .iter()
+            .filter(|circuit| circuit.name == name)
+            .collect::<Vec<_>>();
+        if versions.is_empty() {
+            return Err(CircuitError::UnknownCircuit(name.to_string()));
+        }
+
+        match version {
+            Some(version) => {
+                let parsed = Version::parse(version).map_err(|_| CircuitError::InvalidVersion(version.to_string()))?;
+                versions
+                    .into_iter()
+                    .find(|circuit| circuit.version == parsed)
+                    .ok_or_else(|| CircuitError::UnknownVersion {
+                        name: name.to_string(),
+                        version: version.to_string(),
+                    })
+            }
+            None => versions
+                .into_iter()
+                .filter(|circuit| !circuit.deprecated)
+                .max_by(|a, b| a.version.cmp(&b.version))
+                .ok_or_else(|| CircuitError::NoActiveVersion(name.to_string())),
+        }
+    }
+
+    pub fn select(&self, selector: &CircuitSelector) -> Result<&CircuitDefinition, CircuitError> {
+        self.resolve(&selector.name, selector.version.as_deref())
+    }
+
+    /// Como `resolve`, recusando versões obsoletas: só estas geram provas novas
+    pub fn for_proving(&self, name: &str, version: Option<&str>) -> Result<&CircuitDefinition, CircuitError> {
+        let circuit = self.resolve(name, version)?;
+        if circuit.deprecated {
+            return Err(CircuitError::Deprecated {
+                name: circuit.name.to_string(),
+                version: circuit.version.clone(),
+            });
+        }
+
+        Ok(circuit)
+    }
+
+    /// Versão registrada com a chave de parâmetros `parameters_key`
+    pub fn by_parameters_key(&self, parameters_key: &str) -> Option<&CircuitDefinition> {
+        self.circuits
+            .iter()
+            .find(|circuit| circuit.parameters_key == parameters_key)
+    }
+}
+
+fn solvency_inputs() -> Vec<PublicInput> {
+    vec![
+        PublicInput {
+            name: "threshold",
+            kind: PublicInputKind::Integer,
+            description: "Limite mínimo, na unidade mínima do ativo",
+        },
+        PublicInput {
+            name: "commitment",
+            kind: PublicInputKind::Field,
+            description: "Compromisso MiMC(saldo, blinding)",
+        },
+    ]
+}
+
+fn membership_inputs() -> Vec<PublicInput> {
+    vec![PublicInput {
+        name: "root",
+        kind: PublicInputKind::Field,
+        description: "Raiz publicada da árvore de carteiras aprovadas",
+    }]
+}
+
+fn attributes_inputs() -> Vec<PublicInput> {
+    vec![
+        PublicInput {
+            name: "reference_date",
+            kind: PublicInputKind::Integer,
+            description: "Data AAAAMMDD em que a idade é calculada",
+        },
+        PublicInput {
+            name: "min_age",
+            kind: PublicInputKind::Integer,
+            description: "Idade mínima em anos; 0 não verifica",
+        },
+        PublicInput {
+            name: "country",
+            kind: PublicInputKind::Integer,
+            description: "País de residência exigido (ver country_code); 0 não verifica",
+        },
+        PublicInput {
+            name: "min_kyc_tier",
+            kind: PublicInputKind::Integer,
+            description: "Nível mínimo de KYC; 0 não verifica",
+        },
+        PublicInput {
+            name: "commitment",
+            kind: PublicInputKind::Field,
+            description: "Compromisso MiMC(nascimento, país, nível de KYC, blinding)",
+        },
+    ]
+}
+
+/// Entradas acrescentadas por `Nullified`
+fn with_nullifier(mut inputs: Vec<PublicInput>) -> Vec<PublicInput> {
+    inputs.extend([
+        PublicInput {
+            name: "context",
+            kind: PublicInputKind::Field,
+            description: "Contexto de aplicação (ex.: loan:42), convertido com context_to_field",
+        },
+        PublicInput {
+            name: "nullifier",
+            kind: PublicInputKind::Field,
+            description: "MiMC(segredo do usuário, contexto); único por contexto",
+        },
+    ]);
+    inputs
+}
+
+/// Registro dos circuitos embutidos, montado na primeira consulta
+pub fn registry() -> &'static CircuitRegistry {
+    static REGISTRY: OnceLock<CircuitRegistry> = OnceLock::new();
+    REGISTRY.get_or_init(|| CircuitRegistry::builtin().expect("circuitos embutidos são consistentes"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bellman::gadgets::test::TestConstraintSystem;
+
+    fn definition(version: &str, parameters_key: &'static str) -> CircuitDefinition {
+        CircuitDefinition::new(
+            SIMPLE_CIRCUIT,
+            version,
+            parameters_key,
+            "",
+            vec![PublicInput {
+                name: "c",
+                kind: PublicInputKind::Integer,
+                description: "",
+            }],
+            SimpleCircuit { a: None, b: None },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_builtin_counts_match_synthesis() {
+        let circuit = registry().resolve(SOLVENCY_CIRCUIT, Some("1.0.0")).unwrap();
+        assert_eq!(circuit.parameters_key, SOLVENCY_CIRCUIT_VERSION);
+        assert_eq!(circuit.public_inputs.len(), 2);
+
+        // Mesma contagem do TestConstraintSystem com testemunha
+        let mut cs = TestConstraintSystem::<Scalar>::new();
+        SolvencyCircuit {
+            balance: Some(500),
+            blinding: Some(Scalar::from(7u64)),
+            threshold: Some(100),
+        }
+        .synthesize(&mut cs)
+        .unwrap();
+        assert!(cs.is_satisfied());
+        assert_eq!(circuit.constraint_count, cs.num_constraints());
+
+        for circuit in registry().all() {
+            assert_eq!(registry().by_parameters_key(circuit.parameters_key).unwrap().id(), circuit.id());
+        }
+
+        // O hash depende só da forma do circuito
+        let simple = registry().resolve(SIMPLE_CIRCUIT, None).unwrap();
+        let shape = CircuitShape::of(SimpleCircuit { a: Some(1), b: Some(2) }).unwrap();
+        assert_eq!(shape.hash(), simple.circuit_hash);
+        assert_ne!(simple.circuit_hash, circuit.circuit_hash);
+
+        // A versão ativa tem nullifier nas duas últimas entradas
+        assert!(circuit.deprecated);
+        assert_eq!(circuit.nullifier_inputs(), None);
+        let active = registry().resolve(SOLVENCY_CIRCUIT, None).unwrap();
+        assert_eq!(active.parameters_key, SOLVENCY_NULLIFIED_VERSION);
+        assert_eq!(active.nullifier_inputs(), Some((2, 3)));
+        assert_eq!(
+            registry().resolve(MEMBERSHIP_CIRCUIT, None).unwrap().nullifier_inputs(),
+            Some((1, 2))
+        );
+        assert_eq!(
+            registry().resolve(ATTRIBUTES_CIRCUIT, None).unwrap().nullifier_inputs(),
+            Some((5, 6))
+        );
+    }
+
+    #[test]
+    fn test_schema_must_match_public_inputs() {
+        let result = CircuitDefinition::new(
+            SIMPLE_CIRCUIT,
+            "1.0.0",
+            SIMPLE_CIRCUIT_VERSION,
+            "",
+            vec![],
+            SimpleCircuit { a: None, b: None },
+        );
+        assert!(matches!(
+            result,
+            Err(CircuitError::SchemaMismatch { declared: 0, allocated: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_deprecated_versions_verify_but_do_not_prove() {
+        let registry = CircuitRegistry::new(vec![
+            definition("1.0.0", "simple-v1").deprecated(),
+            definition("1.1.0", "simple-v1.1"),
+            definition("2.0.0-beta.1", "simple-v2").deprecated(),
+        ]);
+
+        // Sem versão: a ativa mais recente, ignorando as obsoletas
+        assert_eq!(registry.resolve(SIMPLE_CIRCUIT, None).unwrap().parameters_key, "simple-v1.1");
+
+        // Versão obsoleta continua resolvível para verificação
+        assert!(registry.resolve(SIMPLE_CIRCUIT, Some("1.0.0")).unwrap().deprecated);
+        assert!(matches!(
+            registry.for_proving(SIMPLE_CIRCUIT, Some("1.0.0")),
+            Err(CircuitError::Deprecated { .. })
+        ));
+        assert!(registry.for_proving(SIMPLE_CIRCUIT, Some("1.1.0")).is_ok());
+
+        assert!(matches!(registry.resolve(SIMPLE_CIRCUIT, Some("1.0")), Err(CircuitError::InvalidVersion(_))));
+        assert!(matches!(
+            registry.resolve(SIMPLE_CIRCUIT, Some("3.0.0")),
+            Err(CircuitError::UnknownVersion { .. })
+        ));
+        assert!(matches!(registry.resolve("other", None), Err(CircuitError::UnknownCircuit(_))));
+
+        let retired = CircuitRegistry::new(vec![definition("1.0.0", "simple-v1").deprecated()]);
+        assert!(matches!(retired.resolve(SIMPLE_CIRCUIT, None), Err(CircuitError::NoActiveVersion(_))));
+    }
+}
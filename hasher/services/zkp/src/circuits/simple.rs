@@ -0,0 +1,46 @@
+use bellman::{Circuit, ConstraintSystem, SynthesisError};
+use bls12_381::Scalar;
+
+/// Versão atual do circuito de demonstração
+pub const SIMPLE_CIRCUIT_VERSION: &str = "simple-v1";
+
+// Circuito simples para demonstração
+#[derive(Clone)]
+pub struct SimpleCircuit {
+    pub a: Option<u64>,
+    pub b: Option<u64>,
+}
+
+impl Circuit<Scalar> for SimpleCircuit {
+    fn synthesize<CS: ConstraintSystem<Scalar>>(
+        self,
+        cs: &mut CS,
+    ) -> Result<(), SynthesisError> {
+        let a = cs.alloc(|| "a", || {
+            self.a.ok_or(SynthesisError::AssignmentMissing)
+                .map(Scalar::from)
+        })?;
+
+        let b = cs.alloc(|| "b", || {
+            self.b.ok_or(SynthesisError::AssignmentMissing)
+                .map(Scalar::from)
+        })?;
+
+        let c = cs.alloc_input(|| "c", || {
+            let mut a = self.a.ok_or(SynthesisError::AssignmentMissing)?;
+            let b = self.b.ok_or(SynthesisError::AssignmentMissing)?;
+
+            a += b;
+            Ok(Scalar::from(a))
+        })?;
+
+        cs.enforce(
+            || "a + b = c",
+            |lc| lc + a,
+            |lc| lc + CS::one(),
+            |lc| lc + c - b,
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,189 @@
+use bellman::{gadgets::num::AllocatedNum, Circuit, ConstraintSystem, LinearCombination, SynthesisError};
+use ff::PrimeFieldBits;
+
+use crate::gadgets::{enforce_range, mimc_hash, mimc_hash_gadget, range_check};
+
+/// Primeira versão do circuito de solvência, sem nullifier (ver `Nullified`)
+pub const SOLVENCY_CIRCUIT_VERSION: &str = "solvency-v1";
+
+/// Saldos e limites são inteiros de até 128 bits (unidade mínima do ativo)
+pub const BALANCE_BITS: usize = 128;
+
+/// Prova que um saldo privado é maior ou igual a um limite público, amarrado
+/// ao compromisso público `MiMC(saldo, blinding)`.
+///
+/// Entradas públicas, nesta ordem: `threshold`, `commitment`.
+#[derive(Clone)]
+pub struct SolvencyCircuit<S: PrimeFieldBits> {
+    pub balance: Option<u128>,
+    pub blinding: Option<S>,
+    pub threshold: Option<u128>,
+}
+
+impl<S: PrimeFieldBits> SolvencyCircuit<S> {
+    /// Instância sem testemunha, usada para gerar parâmetros
+    pub fn blank() -> Self {
+        Self {
+            balance: None,
+            blinding: None,
+            threshold: None,
+        }
+    }
+
+    pub fn public_inputs(threshold: u128, commitment: S) -> Vec<S> {
+        vec![S::from_u128(threshold), commitment]
+    }
+}
+
+/// Compromisso com o saldo, calculado fora do circuito
+pub fn commit_balance<S: PrimeFieldBits>(balance: u128, blinding: S) -> S {
+    mimc_hash(&[S::from_u128(balance), blinding])
+}
+
+impl<S: PrimeFieldBits> Circuit<S> for SolvencyCircuit<S> {
+    fn synthesize<CS: ConstraintSystem<S>>(self, cs: &mut CS) -> Result<(), SynthesisError> {
+        let threshold = cs.alloc_input(
+            || "threshold",
+            || self.threshold.map(S::from_u128).ok_or(SynthesisError::AssignmentMissing),
+        )?;
+
+        let commitment = cs.alloc_input(
+            || "commitment",
+            || {
+                let balance = self.balance.ok_or(SynthesisError::AssignmentMissing)?;
+                let blinding = self.blinding.ok_or(SynthesisError::AssignmentMissing)?;
+                Ok(commit_balance(balance, blinding))
+            },
+        )?;
+
+        let balance = AllocatedNum::alloc(cs.namespace(|| "balance"), || {
+            self.balance.map(S::from_u128).ok_or(SynthesisError::AssignmentMissing)
+        })?;
+
+        let blinding = AllocatedNum::alloc(cs.namespace(|| "blinding"), || {
+            self.blinding.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+
+        // Saldo e limite cabem em BALANCE_BITS
+        range_check(cs.namespace(|| "balance range"), &balance, BALANCE_BITS)?;
+        enforce_range(
+            cs.namespace(|| "threshold range"),
+            LinearCombination::zero() + threshold,
+            self.threshold.map(S::from_u128),
+            BALANCE_BITS,
+        )?;
+
+        // saldo - limite também cabe em BALANCE_BITS, logo saldo >= limite
+        // (o limite é entrada pública, por isso não usa enforce_greater_or_equal)
+        let difference = balance
+            .get_value()
+            .zip(self.threshold)
+            .map(|(balance, threshold)| balance - S::from_u128(threshold));
+        enforce_range(
+            cs.namespace(|| "balance >= threshold"),
+            LinearCombination::zero() + balance.get_variable() - threshold,
+            difference,
+            BALANCE_BITS,
+        )?;
+
+        let hash = mimc_hash_gadget(cs.namespace(|| "commitment hash"), &[balance, blinding])?;
+        cs.enforce(
+            || "commitment opening",
+            |lc| lc + hash.get_variable(),
+            |lc| lc + CS::one(),
+            |lc| lc + commitment,
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bellman::groth16::{create_random_proof, generate_random_parameters, prepare_verifying_key, verify_proof};
+    use bls12_381::{Bls12, Scalar};
+    use ff::Field;
+    use proptest::prelude::*;
+    use rand::thread_rng;
+
+    use crate::circuits::harness::{arb_ordered_pair, arb_scalar, arb_strictly_less, check_circuit};
+
+    fn circuit(balance: u128, threshold: u128, blinding: Scalar) -> SolvencyCircuit<Scalar> {
+        SolvencyCircuit {
+            balance: Some(balance),
+            blinding: Some(blinding),
+            threshold: Some(threshold),
+        }
+    }
+
+    #[test]
+    fn test_balance_above_threshold_is_satisfied() {
+        let blinding = Scalar::random(&mut thread_rng());
+
+        for (balance, threshold) in [(100, 100), (101, 100), (u128::MAX, 0), (u128::MAX, u128::MAX)] {
+            let inputs = SolvencyCircuit::public_inputs(threshold, commit_balance(balance, blinding));
+            assert!(check_circuit(circuit(balance, threshold, blinding), &inputs).is_ok(), "{} >= {}", balance, threshold);
+        }
+    }
+
+    #[test]
+    fn test_balance_below_threshold_is_unsatisfied() {
+        let inputs = SolvencyCircuit::public_inputs(100, commit_balance(99, Scalar::ONE));
+        let error = check_circuit(circuit(99, 100, Scalar::ONE), &inputs).unwrap_err();
+        assert_eq!(error.constraint(), Some("balance >= threshold/range"));
+    }
+
+    #[test]
+    fn test_commitment_hides_balance_behind_blinding() {
+        let mut rng = thread_rng();
+        let (r1, r2) = (Scalar::random(&mut rng), Scalar::random(&mut rng));
+
+        assert_ne!(commit_balance(1_000, r1), commit_balance(1_000, r2));
+        assert_ne!(commit_balance(1_000, r1), commit_balance(1_001, r1));
+        assert_eq!(commit_balance(1_000, r1), commit_balance(1_000, r1));
+    }
+
+    #[test]
+    fn test_groth16_round_trip() {
+        let mut rng = thread_rng();
+        let params = generate_random_parameters::<Bls12, _, _>(SolvencyCircuit::<Scalar>::blank(), &mut rng).unwrap();
+        let pvk = prepare_verifying_key(&params.vk);
+
+        let blinding = Scalar::random(&mut rng);
+        let proof = create_random_proof(circuit(5_000, 1_000, blinding), &params, &mut rng).unwrap();
+        let commitment = commit_balance(5_000, blinding);
+
+        assert!(verify_proof(&pvk, &proof, &SolvencyCircuit::public_inputs(1_000, commitment)).is_ok());
+        assert!(verify_proof(&pvk, &proof, &SolvencyCircuit::public_inputs(1_001, commitment)).is_err());
+        assert!(verify_proof(&pvk, &proof, &SolvencyCircuit::public_inputs(1_000, commitment + Scalar::ONE)).is_err());
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        #[test]
+        fn prop_solvent_witness_is_accepted((balance, threshold) in arb_ordered_pair(), blinding in arb_scalar()) {
+            let inputs = SolvencyCircuit::public_inputs(threshold, commit_balance(balance, blinding));
+            prop_assert!(check_circuit(circuit(balance, threshold, blinding), &inputs).is_ok());
+        }
+
+        #[test]
+        fn prop_insolvent_witness_fails_range((balance, threshold) in arb_strictly_less(), blinding in arb_scalar()) {
+            let inputs = SolvencyCircuit::public_inputs(threshold, commit_balance(balance, blinding));
+            let error = check_circuit(circuit(balance, threshold, blinding), &inputs).unwrap_err();
+            prop_assert_eq!(error.constraint(), Some("balance >= threshold/range"));
+        }
+
+        #[test]
+        fn prop_other_blinding_fails_commitment(
+            (balance, threshold) in arb_ordered_pair(),
+            blinding in arb_scalar(),
+            other in arb_scalar(),
+        ) {
+            prop_assume!(blinding != other);
+            let inputs = SolvencyCircuit::public_inputs(threshold, commit_balance(balance, other));
+            prop_assert!(check_circuit(circuit(balance, threshold, blinding), &inputs).is_err());
+        }
+    }
+}
@@ -0,0 +1,543 @@
+//! Codificação canônica de provas, chaves de verificação e entradas públicas.
+//!
+//! Todo valor começa com um cabeçalho de dois bytes, `[versão][curva]`, seguido
+//! dos campos na ordem abaixo. Inteiros são `u32` big-endian.
+//!
+//! | Curva | id | G1 | G2 |
+//! |-------|----|----|----|
+//! | BLS12-381 | `0x01` | 48 bytes | 96 bytes |
+//! | BN254 | `0x02` | 32 bytes | 64 bytes |
+//!
+//! Pontos vão comprimidos, com a coordenada `x` em big-endian e as flags nos
+//! bits mais altos do primeiro byte:
+//! - BLS12-381: formato da Zcash, o mesmo do `bls12_381` (`0x80` comprimido,
+//!   `0x40` infinito, `0x20` maior `y`).
+//! - BN254: `0x80` maior `y`, `0x40` infinito. Em G2, `x` é `c1 || c0`, a ordem
+//!   dos precompiles da EVM (EIP-197).
+//!
+//! Campos (versão `0x01`):
+//! - Prova: `A (G1) || B (G2) || C (G1)`. No BLS12-381 o corpo é idêntico ao
+//!   formato comprimido do bellman.
+//! - Chave de verificação no BLS12-381: `alpha_g1 || beta_g1 || beta_g2 ||
+//!   gamma_g2 || delta_g1 || delta_g2 || n || ic[0..n]`, a ordem do bellman.
+//! - Chave de verificação no BN254: `alpha_g1 || beta_g2 || gamma_g2 ||
+//!   delta_g2 || n || ic[0..n]`.
+//! - Entradas públicas: `n || x[0..n]`, cada uma com 32 bytes big-endian;
+//!   valores fora do corpo escalar são rejeitados.
+
+use ark_bn254::{Bn254, Fq, Fq2};
+use ark_ec::short_weierstrass::Affine;
+use ark_ec::AffineRepr;
+use ark_ff::{BigInteger, PrimeField as ArkPrimeField};
+use bellman::groth16::{Proof, VerifyingKey};
+use bls12_381::{Bls12, Scalar};
+use ff::PrimeField;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::bn254::Bn254Scalar;
+
+/// Versão atual da codificação
+pub const ENCODING_VERSION: u8 = 1;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum EncodingError {
+    #[error("Versão de codificação não suportada: {0}")]
+    UnsupportedVersion(u8),
+    #[error("Curva desconhecida: {0}")]
+    UnknownCurve(u8),
+    #[error("Curva incorreta: esperada {expected}, recebida {actual}")]
+    CurveMismatch { expected: Curve, actual: Curve },
+    #[error("Dados truncados")]
+    Truncated,
+    #[error("{0} bytes sobrando após o fim do valor")]
+    TrailingBytes(usize),
+    #[error("Ponto inválido em {0}")]
+    InvalidPoint(&'static str),
+    #[error("Entrada pública {0} fora do corpo escalar")]
+    InvalidScalar(usize),
+    #[error("Hex inválido: {0}")]
+    InvalidHex(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Curve {
+    #[serde(rename = "bls12-381")]
+    Bls12_381,
+    #[serde(rename = "bn254")]
+    Bn254,
+}
+
+impl Curve {
+    pub fn id(&self) -> u8 {
+        match self {
+            Curve::Bls12_381 => 0x01,
+            Curve::Bn254 => 0x02,
+        }
+    }
+
+    pub fn from_id(id: u8) -> Result<Self, EncodingError> {
+        match id {
+            0x01 => Ok(Curve::Bls12_381),
+            0x02 => Ok(Curve::Bn254),
+            other => Err(EncodingError::UnknownCurve(other)),
+        }
+    }
+}
+
+impl std::fmt::Display for Curve {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Curve::Bls12_381 => f.write_str("bls12-381"),
+            Curve::Bn254 => f.write_str("bn254"),
+        }
+    }
+}
+
+/// Ponto de curva no formato comprimido descrito no topo do módulo
+pub trait CompressedPoint: Sized {
+    const SIZE: usize;
+
+    fn compress(&self) -> Vec<u8>;
+
+    /// Descomprime e confere que o ponto está na curva e no subgrupo correto
+    fn decompress(bytes: &[u8]) -> Option<Self>;
+}
+
+impl CompressedPoint for bls12_381::G1Affine {
+    const SIZE: usize = 48;
+
+    fn compress(&self) -> Vec<u8> {
+        self.to_compressed().to_vec()
+    }
+
+    fn decompress(bytes: &[u8]) -> Option<Self> {
+        Option::from(Self::from_compressed(bytes.try_into().ok()?))
+    }
+}
+
+impl CompressedPoint for bls12_381::G2Affine {
+    const SIZE: usize = 96;
+
+    fn compress(&self) -> Vec<u8> {
+        self.to_compressed().to_vec()
+    }
+
+    fn decompress(bytes: &[u8]) -> Option<Self> {
+        Option::from(Self::from_compressed(bytes.try_into().ok()?))
+    }
+}
+
+const BN254_GREATEST_FLAG: u8 = 0x80;
+const BN254_INFINITY_FLAG: u8 = 0x40;
+
+fn fq_to_be(value: &Fq) -> Vec<u8> {
+    value.into_bigint().to_bytes_be()
+}
+
+/// Lê um elemento de Fq em big-endian, rejeitando valores >= p
+fn fq_from_be(bytes: &[u8]) -> Option<Fq> {
+    let value = Fq::from_be_bytes_mod_order(bytes);
+    (fq_to_be(&value) == bytes).then_some(value)
+}
+
+/// Separa as flags do primeiro byte; um ponto no infinito deve ser todo zero
+fn split_bn254_flags(bytes: &[u8], size: usize) -> Option<(bool, bool, Vec<u8>)> {
+    if bytes.len() != size {
+        return None;
+    }
+    let flags = bytes[0] & (BN254_GREATEST_FLAG | BN254_INFINITY_FLAG);
+    let mut x = bytes.to_vec();
+    x[0] &= !flags;
+
+    let infinity = flags & BN254_INFINITY_FLAG != 0;
+    if infinity && (flags != BN254_INFINITY_FLAG || x.iter().any(|b| *b != 0)) {
+        return None;
+    }
+    Some((infinity, flags & BN254_GREATEST_FLAG != 0, x))
+}
+
+// Tipos concretos: pelos aliases do ark_bn254 as duas implementações conflitariam
+impl CompressedPoint for Affine<ark_bn254::g1::Config> {
+    const SIZE: usize = 32;
+
+    fn compress(&self) -> Vec<u8> {
+        match self.xy() {
+            None => {
+                let mut bytes = vec![0u8; Self::SIZE];
+                bytes[0] = BN254_INFINITY_FLAG;
+                bytes
+            }
+            Some((x, y)) => {
+                let mut bytes = fq_to_be(x);
+                if *y > -*y {
+                    bytes[0] |= BN254_GREATEST_FLAG;
+                }
+                bytes
+            }
+        }
+    }
+
+    fn decompress(bytes: &[u8]) -> Option<Self> {
+        let (infinity, greatest, x) = split_bn254_flags(bytes, Self::SIZE)?;
+        if infinity {
+            return Some(Self::zero());
+        }
+        // O cofator de G1 é 1: todo ponto da curva está no subgrupo
+        Self::get_point_from_x_unchecked(fq_from_be(&x)?, greatest)
+    }
+}
+
+impl CompressedPoint for Affine<ark_bn254::g2::Config> {
+    const SIZE: usize = 64;
+
+    fn compress(&self) -> Vec<u8> {
+        match self.xy() {
+            None => {
+                let mut bytes = vec![0u8; Self::SIZE];
+                bytes[0] = BN254_INFINITY_FLAG;
+                bytes
+            }
+            Some((x, y)) => {
+                let mut bytes = [fq_to_be(&x.c1), fq_to_be(&x.c0)].concat();
+                if *y > -*y {
+                    bytes[0] |= BN254_GREATEST_FLAG;
+                }
+                bytes
+            }
+        }
+    }
+
+    fn decompress(bytes: &[u8]) -> Option<Self> {
+        let (infinity, greatest, x) = split_bn254_flags(bytes, Self::SIZE)?;
+        if infinity {
+            return Some(Self::zero());
+        }
+        let x = Fq2::new(fq_from_be(&x[32..])?, fq_from_be(&x[..32])?);
+        let point = Self::get_point_from_x_unchecked(x, greatest)?;
+        point.is_in_correct_subgroup_assuming_on_curve().then_some(point)
+    }
+}
+
+/// Cursor sobre o corpo de um valor codificado
+pub struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub fn take(&mut self, len: usize) -> Result<&'a [u8], EncodingError> {
+        if self.bytes.len() < len {
+            return Err(EncodingError::Truncated);
+        }
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    pub fn u32(&mut self) -> Result<u32, EncodingError> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    pub fn point<P: CompressedPoint>(&mut self, field: &'static str) -> Result<P, EncodingError> {
+        P::decompress(self.take(P::SIZE)?).ok_or(EncodingError::InvalidPoint(field))
+    }
+
+    /// Lê `n` seguido de `n` pontos, sem pré-alocar a partir de um `n` não confiável
+    pub fn points<P: CompressedPoint>(&mut self, field: &'static str) -> Result<Vec<P>, EncodingError> {
+        let count = self.u32()? as usize;
+        if self.bytes.len() < count.saturating_mul(P::SIZE) {
+            return Err(EncodingError::Truncated);
+        }
+        (0..count).map(|_| self.point(field)).collect()
+    }
+}
+
+/// Valor com codificação canônica
+pub trait CanonicalEncoding: Sized {
+    const CURVE: Curve;
+
+    fn write_fields(&self, out: &mut Vec<u8>);
+
+    fn read_fields(reader: &mut Reader<'_>) -> Result<Self, EncodingError>;
+}
+
+pub fn encode<T: CanonicalEncoding>(value: &T) -> Vec<u8> {
+    let mut out = vec![ENCODING_VERSION, T::CURVE.id()];
+    value.write_fields(&mut out);
+    out
+}
+
+pub fn decode<T: CanonicalEncoding>(bytes: &[u8]) -> Result<T, EncodingError> {
+    let (curve, body) = read_header(bytes)?;
+    if curve != T::CURVE {
+        return Err(EncodingError::CurveMismatch { expected: T::CURVE, actual: curve });
+    }
+
+    let mut reader = Reader { bytes: body };
+    let value = T::read_fields(&mut reader)?;
+    if !reader.bytes.is_empty() {
+        return Err(EncodingError::TrailingBytes(reader.bytes.len()));
+    }
+    Ok(value)
+}
+
+/// Confere a versão e devolve a curva e o corpo do valor
+pub fn read_header(bytes: &[u8]) -> Result<(Curve, &[u8]), EncodingError> {
+    match bytes {
+        [ENCODING_VERSION, curve, body @ ..] => Ok((Curve::from_id(*curve)?, body)),
+        [version, _, ..] => Err(EncodingError::UnsupportedVersion(*version)),
+        _ => Err(EncodingError::Truncated),
+    }
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+pub fn from_hex(value: &str) -> Result<Vec<u8>, EncodingError> {
+    let digits = value.strip_prefix("0x").unwrap_or(value);
+    hex::decode(digits).map_err(|e| EncodingError::InvalidHex(e.to_string()))
+}
+
+fn write_points<P: CompressedPoint>(out: &mut Vec<u8>, points: &[P]) {
+    out.extend_from_slice(&(points.len() as u32).to_be_bytes());
+    for point in points {
+        out.extend(point.compress());
+    }
+}
+
+impl CanonicalEncoding for Proof<Bls12> {
+    const CURVE: Curve = Curve::Bls12_381;
+
+    fn write_fields(&self, out: &mut Vec<u8>) {
+        out.extend(self.a.compress());
+        out.extend(self.b.compress());
+        out.extend(self.c.compress());
+    }
+
+    fn read_fields(reader: &mut Reader<'_>) -> Result<Self, EncodingError> {
+        Ok(Proof {
+            a: reader.point("a")?,
+            b: reader.point("b")?,
+            c: reader.point("c")?,
+        })
+    }
+}
+
+impl CanonicalEncoding for VerifyingKey<Bls12> {
+    const CURVE: Curve = Curve::Bls12_381;
+
+    fn write_fields(&self, out: &mut Vec<u8>) {
+        out.extend(self.alpha_g1.compress());
+        out.extend(self.beta_g1.compress());
+        out.extend(self.beta_g2.compress());
+        out.extend(self.gamma_g2.compress());
+        out.extend(self.delta_g1.compress());
+        out.extend(self.delta_g2.compress());
+        write_points(out, &self.ic);
+    }
+
+    fn read_fields(reader: &mut Reader<'_>) -> Result<Self, EncodingError> {
+        Ok(VerifyingKey {
+            alpha_g1: reader.point("alpha_g1")?,
+            beta_g1: reader.point("beta_g1")?,
+            beta_g2: reader.point("beta_g2")?,
+            gamma_g2: reader.point("gamma_g2")?,
+            delta_g1: reader.point("delta_g1")?,
+            delta_g2: reader.point("delta_g2")?,
+            ic: reader.points("ic")?,
+        })
+    }
+}
+
+impl CanonicalEncoding for ark_groth16::Proof<Bn254> {
+    const CURVE: Curve = Curve::Bn254;
+
+    fn write_fields(&self, out: &mut Vec<u8>) {
+        out.extend(self.a.compress());
+        out.extend(self.b.compress());
+        out.extend(self.c.compress());
+    }
+
+    fn read_fields(reader: &mut Reader<'_>) -> Result<Self, EncodingError> {
+        Ok(ark_groth16::Proof {
+            a: reader.point("a")?,
+            b: reader.point("b")?,
+            c: reader.point("c")?,
+        })
+    }
+}
+
+impl CanonicalEncoding for ark_groth16::VerifyingKey<Bn254> {
+    const CURVE: Curve = Curve::Bn254;
+
+    fn write_fields(&self, out: &mut Vec<u8>) {
+        out.extend(self.alpha_g1.compress());
+        out.extend(self.beta_g2.compress());
+        out.extend(self.gamma_g2.compress());
+        out.extend(self.delta_g2.compress());
+        write_points(out, &self.gamma_abc_g1);
+    }
+
+    fn read_fields(reader: &mut Reader<'_>) -> Result<Self, EncodingError> {
+        Ok(ark_groth16::VerifyingKey {
+            alpha_g1: reader.point("alpha_g1")?,
+            beta_g2: reader.point("beta_g2")?,
+            gamma_g2: reader.point("gamma_g2")?,
+            delta_g2: reader.point("delta_g2")?,
+            gamma_abc_g1: reader.points("ic")?,
+        })
+    }
+}
+
+fn write_scalars<S: PrimeField<Repr = [u8; 32]>>(out: &mut Vec<u8>, values: &[S]) {
+    out.extend_from_slice(&(values.len() as u32).to_be_bytes());
+    for value in values {
+        let mut bytes = value.to_repr();
+        bytes.reverse();
+        out.extend_from_slice(&bytes);
+    }
+}
+
+fn read_scalars<S: PrimeField<Repr = [u8; 32]>>(reader: &mut Reader<'_>) -> Result<Vec<S>, EncodingError> {
+    let count = reader.u32()? as usize;
+    if reader.bytes.len() < count.saturating_mul(32) {
+        return Err(EncodingError::Truncated);
+    }
+    (0..count)
+        .map(|index| {
+            let mut repr: [u8; 32] = reader.take(32)?.try_into().unwrap();
+            repr.reverse();
+            Option::from(S::from_repr(repr)).ok_or(EncodingError::InvalidScalar(index))
+        })
+        .collect()
+}
+
+impl CanonicalEncoding for Vec<Scalar> {
+    const CURVE: Curve = Curve::Bls12_381;
+
+    fn write_fields(&self, out: &mut Vec<u8>) {
+        write_scalars(out, self);
+    }
+
+    fn read_fields(reader: &mut Reader<'_>) -> Result<Self, EncodingError> {
+        read_scalars(reader)
+    }
+}
+
+impl CanonicalEncoding for Vec<Bn254Scalar> {
+    const CURVE: Curve = Curve::Bn254;
+
+    fn write_fields(&self, out: &mut Vec<u8>) {
+        write_scalars(out, self);
+    }
+
+    fn read_fields(reader: &mut Reader<'_>) -> Result<Self, EncodingError> {
+        read_scalars(reader)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bn254::{create_bn254_proof, Bn254Parameters};
+    use crate::circuits::{wallet_leaf, whitelist_tree, MembershipCircuit, SimpleCircuit, MEMBERSHIP_CIRCUIT_VERSION};
+    use bellman::groth16::{create_random_proof, generate_random_parameters};
+    use ff::Field;
+    use rand::thread_rng;
+
+    #[test]
+    fn test_bls12_381_round_trip_matches_bellman() {
+        let mut rng = thread_rng();
+        let params = generate_random_parameters::<Bls12, _, _>(SimpleCircuit { a: None, b: None }, &mut rng).unwrap();
+        let proof = create_random_proof(SimpleCircuit { a: Some(3), b: Some(4) }, &params, &mut rng).unwrap();
+
+        // O corpo da prova é o formato comprimido do bellman (base da migração)
+        let bytes = encode(&proof);
+        let mut legacy = Vec::new();
+        proof.write(&mut legacy).unwrap();
+        assert_eq!(bytes[..2], [ENCODING_VERSION, 0x01]);
+        assert_eq!(bytes[2..], legacy[..]);
+        assert_eq!(decode::<Proof<Bls12>>(&bytes).unwrap(), proof);
+
+        let vk_bytes = encode(&params.vk);
+        let vk = decode::<VerifyingKey<Bls12>>(&vk_bytes).unwrap();
+        assert!(vk == params.vk);
+        // Mesmos bytes no formato do bellman, logo o mesmo fingerprint
+        let (mut restored, mut original) = (Vec::new(), Vec::new());
+        vk.write(&mut restored).unwrap();
+        params.vk.write(&mut original).unwrap();
+        assert_eq!(restored, original);
+        assert_eq!(vk_bytes.len(), 2 + 48 * 3 + 96 * 3 + 4 + 48 * params.vk.ic.len());
+
+        let inputs = vec![Scalar::from(7u64), -Scalar::ONE];
+        assert_eq!(decode::<Vec<Scalar>>(&encode(&inputs)).unwrap(), inputs);
+    }
+
+    #[test]
+    fn test_bn254_round_trip() {
+        let mut rng = thread_rng();
+        let params = Bn254Parameters::generate(MEMBERSHIP_CIRCUIT_VERSION, MembershipCircuit::blank(), &mut rng).unwrap();
+        let wallets = (1..=3u64).map(Bn254Scalar::from).collect::<Vec<_>>();
+        let tree = whitelist_tree(&wallets).unwrap();
+        assert_eq!(tree.path(1).unwrap().root(wallet_leaf(wallets[1])), tree.root());
+        let circuit = MembershipCircuit {
+            wallet: Some(wallets[1]),
+            path: tree.path(1),
+        };
+        let proof = create_bn254_proof(&params, circuit, &mut rng).unwrap();
+
+        let bytes = encode(&proof);
+        assert_eq!(bytes.len(), 2 + 32 * 2 + 64);
+        assert_eq!(decode::<ark_groth16::Proof<Bn254>>(&bytes).unwrap(), proof);
+        assert_eq!(decode::<ark_groth16::VerifyingKey<Bn254>>(&encode(params.vk())).unwrap(), *params.vk());
+
+        let inputs = vec![tree.root(), -Bn254Scalar::ONE];
+        assert_eq!(decode::<Vec<Bn254Scalar>>(&encode(&inputs)).unwrap(), inputs);
+
+        // Ponto no infinito e os dois sinais de y
+        let zero = ark_bn254::G2Affine::zero();
+        assert_eq!(ark_bn254::G2Affine::decompress(&zero.compress()), Some(zero));
+        let g = ark_bn254::G1Affine::generator();
+        assert_eq!(ark_bn254::G1Affine::decompress(&(-g).compress()), Some(-g));
+        assert_ne!(g.compress(), (-g).compress());
+    }
+
+    #[test]
+    fn test_rejects_malformed_values() {
+        let point = bls12_381::G1Affine::generator();
+        let proof = Proof::<Bls12> {
+            a: point,
+            b: bls12_381::G2Affine::generator(),
+            c: point,
+        };
+        let bytes = encode(&proof);
+
+        let mut wrong_version = bytes.clone();
+        wrong_version[0] = 2;
+        assert_eq!(decode::<Proof<Bls12>>(&wrong_version), Err(EncodingError::UnsupportedVersion(2)));
+
+        assert_eq!(
+            decode::<ark_groth16::Proof<Bn254>>(&bytes),
+            Err(EncodingError::CurveMismatch { expected: Curve::Bn254, actual: Curve::Bls12_381 })
+        );
+        assert_eq!(decode::<Proof<Bls12>>(&bytes[..bytes.len() - 1]), Err(EncodingError::Truncated));
+        assert_eq!(decode::<Proof<Bls12>>(&[bytes.clone(), vec![0]].concat()), Err(EncodingError::TrailingBytes(1)));
+
+        // Flag de infinito com x diferente de zero
+        let mut tampered = bytes.clone();
+        tampered[2] |= 0x40;
+        assert_eq!(decode::<Proof<Bls12>>(&tampered), Err(EncodingError::InvalidPoint("a")));
+
+        // Entrada igual ao módulo não é um elemento do corpo
+        let modulus = hex::decode(&Scalar::MODULUS[2..]).unwrap();
+        let encoded = [vec![ENCODING_VERSION, 0x01, 0, 0, 0, 1], modulus].concat();
+        assert_eq!(decode::<Vec<Scalar>>(&encoded), Err(EncodingError::InvalidScalar(0)));
+
+        // n enorme não provoca alocação
+        assert_eq!(
+            decode::<Vec<Scalar>>(&[ENCODING_VERSION, 0x01, 0xff, 0xff, 0xff, 0xff]),
+            Err(EncodingError::Truncated)
+        );
+    }
+}
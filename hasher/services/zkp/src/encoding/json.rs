@@ -0,0 +1,225 @@
+use ark_bn254::Bn254;
+use bellman::groth16::{Proof, VerifyingKey};
+use bls12_381::Bls12;
+use serde::{Deserialize, Serialize};
+
+use super::{decode, encode, from_hex, read_header, to_hex, CompressedPoint, Curve, EncodingError, ENCODING_VERSION};
+
+/// Prova na forma JSON: cada ponto em hex, no formato comprimido canônico
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofJson {
+    pub version: u8,
+    pub curve: Curve,
+    pub a: String,
+    pub b: String,
+    pub c: String,
+}
+
+/// Chave de verificação na forma JSON. `beta_g1` e `delta_g1` só existem no
+/// BLS12-381, onde fazem parte da chave do bellman.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifyingKeyJson {
+    pub version: u8,
+    pub curve: Curve,
+    pub alpha_g1: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub beta_g1: Option<String>,
+    pub beta_g2: String,
+    pub gamma_g2: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delta_g1: Option<String>,
+    pub delta_g2: String,
+    pub ic: Vec<String>,
+}
+
+fn point_hex<P: CompressedPoint>(point: &P) -> String {
+    to_hex(&point.compress())
+}
+
+fn field_bytes(value: &Option<String>, field: &'static str) -> Result<Vec<u8>, EncodingError> {
+    from_hex(value.as_deref().ok_or(EncodingError::InvalidPoint(field))?)
+}
+
+impl ProofJson {
+    /// Lê uma prova codificada, de qualquer curva
+    pub fn from_canonical(bytes: &[u8]) -> Result<Self, EncodingError> {
+        let (curve, _) = read_header(bytes)?;
+        let (a, b, c) = match curve {
+            Curve::Bls12_381 => {
+                let proof = decode::<Proof<Bls12>>(bytes)?;
+                (point_hex(&proof.a), point_hex(&proof.b), point_hex(&proof.c))
+            }
+            Curve::Bn254 => {
+                let proof = decode::<ark_groth16::Proof<Bn254>>(bytes)?;
+                (point_hex(&proof.a), point_hex(&proof.b), point_hex(&proof.c))
+            }
+        };
+
+        Ok(Self {
+            version: ENCODING_VERSION,
+            curve,
+            a,
+            b,
+            c,
+        })
+    }
+
+    /// Volta à codificação canônica, conferindo cada ponto
+    pub fn to_canonical(&self) -> Result<Vec<u8>, EncodingError> {
+        if self.version != ENCODING_VERSION {
+            return Err(EncodingError::UnsupportedVersion(self.version));
+        }
+
+        let bytes = [
+            vec![self.version, self.curve.id()],
+            from_hex(&self.a)?,
+            from_hex(&self.b)?,
+            from_hex(&self.c)?,
+        ]
+        .concat();
+
+        Self::from_canonical(&bytes)?;
+        Ok(bytes)
+    }
+}
+
+impl VerifyingKeyJson {
+    /// Lê uma chave de verificação codificada, de qualquer curva
+    pub fn from_canonical(bytes: &[u8]) -> Result<Self, EncodingError> {
+        let (curve, _) = read_header(bytes)?;
+
+        Ok(match curve {
+            Curve::Bls12_381 => {
+                let vk = decode::<VerifyingKey<Bls12>>(bytes)?;
+                Self {
+                    version: ENCODING_VERSION,
+                    curve,
+                    alpha_g1: point_hex(&vk.alpha_g1),
+                    beta_g1: Some(point_hex(&vk.beta_g1)),
+                    beta_g2: point_hex(&vk.beta_g2),
+                    gamma_g2: point_hex(&vk.gamma_g2),
+                    delta_g1: Some(point_hex(&vk.delta_g1)),
+                    delta_g2: point_hex(&vk.delta_g2),
+                    ic: vk.ic.iter().map(point_hex).collect(),
+                }
+            }
+            Curve::Bn254 => {
+                let vk = decode::<ark_groth16::VerifyingKey<Bn254>>(bytes)?;
+                Self {
+                    version: ENCODING_VERSION,
+                    curve,
+                    alpha_g1: point_hex(&vk.alpha_g1),
+                    beta_g1: None,
+                    beta_g2: point_hex(&vk.beta_g2),
+                    gamma_g2: point_hex(&vk.gamma_g2),
+                    delta_g1: None,
+                    delta_g2: point_hex(&vk.delta_g2),
+                    ic: vk.gamma_abc_g1.iter().map(point_hex).collect(),
+                }
+            }
+        })
+    }
+
+    /// Volta à codificação canônica, conferindo cada ponto
+    pub fn to_canonical(&self) -> Result<Vec<u8>, EncodingError> {
+        if self.version != ENCODING_VERSION {
+            return Err(EncodingError::UnsupportedVersion(self.version));
+        }
+
+        let mut bytes = vec![self.version, self.curve.id()];
+        bytes.extend(from_hex(&self.alpha_g1)?);
+        if self.curve == Curve::Bls12_381 {
+            bytes.extend(field_bytes(&self.beta_g1, "beta_g1")?);
+        }
+        bytes.extend(from_hex(&self.beta_g2)?);
+        bytes.extend(from_hex(&self.gamma_g2)?);
+        if self.curve == Curve::Bls12_381 {
+            bytes.extend(field_bytes(&self.delta_g1, "delta_g1")?);
+        }
+        bytes.extend(from_hex(&self.delta_g2)?);
+        bytes.extend((self.ic.len() as u32).to_be_bytes());
+        for point in &self.ic {
+            bytes.extend(from_hex(point)?);
+        }
+
+        Self::from_canonical(&bytes)?;
+        Ok(bytes)
+    }
+}
+
+impl From<&Proof<Bls12>> for ProofJson {
+    fn from(proof: &Proof<Bls12>) -> Self {
+        Self::from_canonical(&encode(proof)).expect("codificação própria é válida")
+    }
+}
+
+impl From<&VerifyingKey<Bls12>> for VerifyingKeyJson {
+    fn from(vk: &VerifyingKey<Bls12>) -> Self {
+        Self::from_canonical(&encode(vk)).expect("codificação própria é válida")
+    }
+}
+
+impl From<&ark_groth16::Proof<Bn254>> for ProofJson {
+    fn from(proof: &ark_groth16::Proof<Bn254>) -> Self {
+        Self::from_canonical(&encode(proof)).expect("codificação própria é válida")
+    }
+}
+
+impl From<&ark_groth16::VerifyingKey<Bn254>> for VerifyingKeyJson {
+    fn from(vk: &ark_groth16::VerifyingKey<Bn254>) -> Self {
+        Self::from_canonical(&encode(vk)).expect("codificação própria é válida")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuits::SimpleCircuit;
+    use bellman::groth16::{create_random_proof, generate_random_parameters};
+    use rand::thread_rng;
+
+    #[test]
+    fn test_json_round_trip() {
+        let mut rng = thread_rng();
+        let params = generate_random_parameters::<Bls12, _, _>(SimpleCircuit { a: None, b: None }, &mut rng).unwrap();
+        let proof = create_random_proof(SimpleCircuit { a: Some(3), b: Some(4) }, &params, &mut rng).unwrap();
+
+        let json = ProofJson::from(&proof);
+        let text = serde_json::to_string(&json).unwrap();
+        assert!(text.contains("\"curve\":\"bls12-381\""));
+        let parsed = serde_json::from_str::<ProofJson>(&text).unwrap();
+        assert_eq!(parsed.to_canonical().unwrap(), encode(&proof));
+
+        let vk_json = VerifyingKeyJson::from(&params.vk);
+        assert_eq!(vk_json.ic.len(), 2);
+        let parsed = serde_json::from_str::<VerifyingKeyJson>(&serde_json::to_string(&vk_json).unwrap()).unwrap();
+        assert!(decode::<VerifyingKey<Bls12>>(&parsed.to_canonical().unwrap()).unwrap() == params.vk);
+
+        // Sem os campos exclusivos do BLS12-381 a chave não fecha
+        let mut incomplete = vk_json.clone();
+        incomplete.beta_g1 = None;
+        assert_eq!(incomplete.to_canonical(), Err(EncodingError::InvalidPoint("beta_g1")));
+
+        let mut swapped = json.clone();
+        std::mem::swap(&mut swapped.a, &mut swapped.b);
+        assert!(swapped.to_canonical().is_err());
+    }
+
+    #[test]
+    fn test_bn254_json_omits_bls_only_fields() {
+        let g1 = ark_bn254::G1Affine::new(1u64.into(), 2u64.into());
+        let vk = ark_groth16::VerifyingKey::<Bn254> {
+            alpha_g1: g1,
+            beta_g2: ark_bn254::G2Affine::default(),
+            gamma_g2: ark_bn254::G2Affine::default(),
+            delta_g2: ark_bn254::G2Affine::default(),
+            gamma_abc_g1: vec![g1, g1],
+        };
+
+        let json = VerifyingKeyJson::from(&vk);
+        let text = serde_json::to_string(&json).unwrap();
+        assert!(!text.contains("beta_g1"));
+        assert_eq!(json.alpha_g1, format!("0x{:064x}", 1));
+        assert_eq!(json.to_canonical().unwrap(), encode(&vk));
+    }
+}
@@ -0,0 +1,7 @@
+mod canonical;
+mod json;
+mod snarkjs;
+
+pub use canonical::*;
+pub use json::*;
+pub use snarkjs::*;
@@ -0,0 +1,304 @@
+//! Exportação no formato JSON do snarkjs (`proof.json`, `verification_key.json`
+//! e `public.json`). Coordenadas em decimal e projetivas (`z = 1`); em G2 cada
+//! coordenada é `[c0, c1]`. O infinito segue o snarkjs: `["0", "1", "0"]`.
+
+use ark_bn254::Bn254;
+use ark_ec::AffineRepr;
+use ark_ff::PrimeField as ArkPrimeField;
+use bellman::groth16::{Proof, VerifyingKey};
+use bls12_381::Bls12;
+use ff::PrimeField;
+use serde::{Deserialize, Serialize};
+
+type G1Json = [String; 3];
+type G2Json = [[String; 2]; 3];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnarkjsProof {
+    pub pi_a: G1Json,
+    pub pi_b: G2Json,
+    pub pi_c: G1Json,
+    pub protocol: String,
+    pub curve: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnarkjsVerifyingKey {
+    pub protocol: String,
+    pub curve: String,
+    #[serde(rename = "nPublic")]
+    pub n_public: usize,
+    pub vk_alpha_1: G1Json,
+    pub vk_beta_2: G2Json,
+    pub vk_gamma_2: G2Json,
+    pub vk_delta_2: G2Json,
+    #[serde(rename = "IC")]
+    pub ic: Vec<G1Json>,
+}
+
+/// Conversão para o formato do snarkjs
+pub trait ToSnarkjs {
+    type Output;
+
+    fn to_snarkjs(&self) -> Self::Output;
+}
+
+/// Inteiro big-endian em decimal
+fn decimal(bytes: &[u8]) -> String {
+    // Dígitos em little-endian; para cada byte, dígitos = dígitos * 256 + byte
+    let mut digits = vec![0u8];
+    for byte in bytes {
+        let mut carry = *byte as u32;
+        for digit in digits.iter_mut() {
+            let acc = *digit as u32 * 256 + carry;
+            *digit = (acc % 10) as u8;
+            carry = acc / 10;
+        }
+        while carry > 0 {
+            digits.push((carry % 10) as u8);
+            carry /= 10;
+        }
+    }
+    digits.iter().rev().map(|d| char::from(b'0' + d)).collect()
+}
+
+fn strings<const N: usize>(values: [&str; N]) -> [String; N] {
+    values.map(str::to_string)
+}
+
+const G1_INFINITY: [&str; 3] = ["0", "1", "0"];
+
+fn g2_infinity() -> G2Json {
+    [strings(["0", "0"]), strings(["1", "0"]), strings(["0", "0"])]
+}
+
+// BLS12-381: a forma não comprimida do `bls12_381` traz x e y em big-endian
+// (em G2, `c1` antes de `c0`), com as flags nos 3 bits altos do primeiro byte
+fn bls_g1(point: &bls12_381::G1Affine) -> G1Json {
+    if bool::from(point.is_identity()) {
+        return strings(G1_INFINITY);
+    }
+    let mut bytes = point.to_uncompressed();
+    bytes[0] &= 0x1f;
+    [decimal(&bytes[..48]), decimal(&bytes[48..]), "1".to_string()]
+}
+
+fn bls_g2(point: &bls12_381::G2Affine) -> G2Json {
+    if bool::from(point.is_identity()) {
+        return g2_infinity();
+    }
+    let mut bytes = point.to_uncompressed();
+    bytes[0] &= 0x1f;
+    let words = bytes.chunks(48).map(decimal).collect::<Vec<_>>();
+    [
+        [words[1].clone(), words[0].clone()],
+        [words[3].clone(), words[2].clone()],
+        strings(["1", "0"]),
+    ]
+}
+
+fn bn_g1(point: &ark_bn254::G1Affine) -> G1Json {
+    match point.xy() {
+        None => strings(G1_INFINITY),
+        Some((x, y)) => [x.into_bigint().to_string(), y.into_bigint().to_string(), "1".to_string()],
+    }
+}
+
+fn bn_g2(point: &ark_bn254::G2Affine) -> G2Json {
+    match point.xy() {
+        None => g2_infinity(),
+        Some((x, y)) => [
+            [x.c0.into_bigint().to_string(), x.c1.into_bigint().to_string()],
+            [y.c0.into_bigint().to_string(), y.c1.into_bigint().to_string()],
+            strings(["1", "0"]),
+        ],
+    }
+}
+
+const BLS12_381_CURVE: &str = "bls12381";
+const BN254_CURVE: &str = "bn128";
+
+impl ToSnarkjs for Proof<Bls12> {
+    type Output = SnarkjsProof;
+
+    fn to_snarkjs(&self) -> SnarkjsProof {
+        SnarkjsProof {
+            pi_a: bls_g1(&self.a),
+            pi_b: bls_g2(&self.b),
+            pi_c: bls_g1(&self.c),
+            protocol: "groth16".to_string(),
+            curve: BLS12_381_CURVE.to_string(),
+        }
+    }
+}
+
+impl ToSnarkjs for VerifyingKey<Bls12> {
+    type Output = SnarkjsVerifyingKey;
+
+    fn to_snarkjs(&self) -> SnarkjsVerifyingKey {
+        SnarkjsVerifyingKey {
+            protocol: "groth16".to_string(),
+            curve: BLS12_381_CURVE.to_string(),
+            n_public: self.ic.len() - 1,
+            vk_alpha_1: bls_g1(&self.alpha_g1),
+            vk_beta_2: bls_g2(&self.beta_g2),
+            vk_gamma_2: bls_g2(&self.gamma_g2),
+            vk_delta_2: bls_g2(&self.delta_g2),
+            ic: self.ic.iter().map(bls_g1).collect(),
+        }
+    }
+}
+
+impl ToSnarkjs for ark_groth16::Proof<Bn254> {
+    type Output = SnarkjsProof;
+
+    fn to_snarkjs(&self) -> SnarkjsProof {
+        SnarkjsProof {
+            pi_a: bn_g1(&self.a),
+            pi_b: bn_g2(&self.b),
+            pi_c: bn_g1(&self.c),
+            protocol: "groth16".to_string(),
+            curve: BN254_CURVE.to_string(),
+        }
+    }
+}
+
+impl ToSnarkjs for ark_groth16::VerifyingKey<Bn254> {
+    type Output = SnarkjsVerifyingKey;
+
+    fn to_snarkjs(&self) -> SnarkjsVerifyingKey {
+        SnarkjsVerifyingKey {
+            protocol: "groth16".to_string(),
+            curve: BN254_CURVE.to_string(),
+            n_public: self.gamma_abc_g1.len() - 1,
+            vk_alpha_1: bn_g1(&self.alpha_g1),
+            vk_beta_2: bn_g2(&self.beta_g2),
+            vk_gamma_2: bn_g2(&self.gamma_g2),
+            vk_delta_2: bn_g2(&self.delta_g2),
+            ic: self.gamma_abc_g1.iter().map(bn_g1).collect(),
+        }
+    }
+}
+
+/// Entradas públicas em decimal (`public.json`)
+pub fn snarkjs_public_inputs<S: PrimeField<Repr = [u8; 32]>>(values: &[S]) -> Vec<String> {
+    values
+        .iter()
+        .map(|value| {
+            let mut bytes = value.to_repr();
+            bytes.reverse();
+            decimal(&bytes)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bn254::{create_bn254_proof, Bn254Parameters, Bn254Scalar};
+    use crate::circuits::{commit_balance, SimpleCircuit, SolvencyCircuit, SOLVENCY_CIRCUIT_VERSION};
+    use ark_bn254::{Fq, Fq2, Fr};
+    use ark_groth16::Groth16;
+    use bellman::groth16::{create_random_proof, generate_random_parameters};
+    use bls12_381::Scalar;
+    use rand::thread_rng;
+    use std::str::FromStr;
+
+    fn fq(value: &str) -> Fq {
+        Fq::from_str(value).unwrap()
+    }
+
+    fn parse_g1(point: &G1Json) -> ark_bn254::G1Affine {
+        assert_eq!(point[2], "1");
+        ark_bn254::G1Affine::new(fq(&point[0]), fq(&point[1]))
+    }
+
+    fn parse_g2(point: &G2Json) -> ark_bn254::G2Affine {
+        assert_eq!(point[2], strings(["1", "0"]));
+        ark_bn254::G2Affine::new(
+            Fq2::new(fq(&point[0][0]), fq(&point[0][1])),
+            Fq2::new(fq(&point[1][0]), fq(&point[1][1])),
+        )
+    }
+
+    #[test]
+    fn test_decimal() {
+        assert_eq!(decimal(&[]), "0");
+        assert_eq!(decimal(&[0, 0]), "0");
+        assert_eq!(decimal(&[0x01, 0x00]), "256");
+        assert_eq!(decimal(&u64::MAX.to_be_bytes()), u64::MAX.to_string());
+        assert_eq!(decimal(&[0xff; 16]), u128::MAX.to_string());
+    }
+
+    #[test]
+    fn test_bn254_export_verifies_after_reimport() {
+        // Reconstrói prova e chave só a partir do JSON, como o snarkjs faria
+        let mut rng = thread_rng();
+        let params = Bn254Parameters::generate(SOLVENCY_CIRCUIT_VERSION, SolvencyCircuit::blank(), &mut rng).unwrap();
+        let blinding = Bn254Scalar::from(9u64);
+        let circuit = SolvencyCircuit {
+            balance: Some(500),
+            blinding: Some(blinding),
+            threshold: Some(100),
+        };
+        let proof = create_bn254_proof(&params, circuit, &mut rng).unwrap();
+        let inputs = SolvencyCircuit::public_inputs(100, commit_balance(500, blinding));
+
+        let proof_json = serde_json::to_value(proof.to_snarkjs()).unwrap();
+        let vk_json = serde_json::to_value(params.vk().to_snarkjs()).unwrap();
+        assert_eq!(proof_json["curve"], "bn128");
+        assert_eq!(vk_json["nPublic"], 2);
+
+        let proof = serde_json::from_value::<SnarkjsProof>(proof_json).unwrap();
+        let vk = serde_json::from_value::<SnarkjsVerifyingKey>(vk_json).unwrap();
+        let vk = ark_groth16::VerifyingKey::<Bn254> {
+            alpha_g1: parse_g1(&vk.vk_alpha_1),
+            beta_g2: parse_g2(&vk.vk_beta_2),
+            gamma_g2: parse_g2(&vk.vk_gamma_2),
+            delta_g2: parse_g2(&vk.vk_delta_2),
+            gamma_abc_g1: vk.ic.iter().map(parse_g1).collect(),
+        };
+        let proof = ark_groth16::Proof::<Bn254> {
+            a: parse_g1(&proof.pi_a),
+            b: parse_g2(&proof.pi_b),
+            c: parse_g1(&proof.pi_c),
+        };
+        let public = snarkjs_public_inputs(&inputs)
+            .iter()
+            .map(|value| Fr::from_str(value).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(public[0], Fr::from(100u64));
+
+        let pvk = ark_groth16::prepare_verifying_key(&vk);
+        assert!(Groth16::<Bn254>::verify_proof(&pvk, &proof, &public).unwrap());
+    }
+
+    #[test]
+    fn test_bls12_381_coordinates() {
+        let mut rng = thread_rng();
+        let params = generate_random_parameters::<Bls12, _, _>(SimpleCircuit { a: None, b: None }, &mut rng).unwrap();
+        let proof = create_random_proof(SimpleCircuit { a: Some(3), b: Some(4) }, &params, &mut rng).unwrap();
+
+        let json = proof.to_snarkjs();
+        assert_eq!(json.curve, "bls12381");
+
+        // x de A sem as flags, em decimal
+        let mut x = proof.a.to_compressed();
+        x[0] &= 0x1f;
+        assert_eq!(json.pi_a[0], decimal(&x));
+
+        // Em G2 a ordem é [c0, c1], o inverso da serialização do bls12_381
+        let b = proof.b.to_uncompressed();
+        assert_eq!(json.pi_b[0][0], decimal(&b[48..96]));
+        assert_eq!(json.pi_b[1][1], decimal(&b[96..144]));
+
+        let vk = params.vk.to_snarkjs();
+        assert_eq!(vk.n_public, 1);
+        assert_eq!(vk.ic.len(), 2);
+        assert_eq!(
+            bls_g1(&bls12_381::G1Affine::identity()),
+            strings(G1_INFINITY)
+        );
+        assert_eq!(snarkjs_public_inputs(&[Scalar::from(7u64)]), vec!["7".to_string()]);
+    }
+}
@@ -0,0 +1,241 @@
+use bellman::{
+    gadgets::{
+        boolean::{AllocatedBit, Boolean},
+        num::AllocatedNum,
+    },
+    ConstraintSystem, SynthesisError,
+};
+use ff::PrimeField;
+use thiserror::Error;
+
+use super::{conditionally_select, mimc_hash, mimc_hash_gadget, MIMC_CONSTRAINTS_PER_INPUT};
+
+/// Restrições de cada nível do caminho: bit de direção, duas seleções e o hash do nó
+pub const MERKLE_LEVEL_CONSTRAINTS: usize = 1 + 2 + 1 + 2 * MIMC_CONSTRAINTS_PER_INPUT;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum MerkleError {
+    #[error("Árvore de profundidade {depth} comporta até {capacity} folhas, recebidas {actual}")]
+    TooManyLeaves { depth: usize, capacity: usize, actual: usize },
+}
+
+/// Hash de um nó interno a partir dos filhos
+pub fn merkle_node<S: PrimeField>(left: S, right: S) -> S {
+    mimc_hash(&[left, right])
+}
+
+/// Caminho de autenticação de uma folha, da base para a raiz
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerklePath<S: PrimeField> {
+    pub siblings: Vec<S>,
+    /// `true` quando o nó atual é o filho direito naquele nível
+    pub is_right: Vec<bool>,
+}
+
+impl<S: PrimeField> MerklePath<S> {
+    pub fn depth(&self) -> usize {
+        self.siblings.len()
+    }
+
+    /// Recalcula a raiz a partir da folha
+    pub fn root(&self, leaf: S) -> S {
+        self.siblings
+            .iter()
+            .zip(&self.is_right)
+            .fold(leaf, |node, (sibling, is_right)| {
+                if *is_right {
+                    merkle_node(*sibling, node)
+                } else {
+                    merkle_node(node, *sibling)
+                }
+            })
+    }
+}
+
+/// Árvore de Merkle binária de profundidade fixa. Posições sem folha valem zero,
+/// e as subárvores vazias são pré-calculadas.
+#[derive(Debug, Clone)]
+pub struct MerkleTree<S: PrimeField> {
+    levels: Vec<Vec<S>>,
+    empty: Vec<S>,
+}
+
+impl<S: PrimeField> MerkleTree<S> {
+    pub fn new(leaves: Vec<S>, depth: usize) -> Result<Self, MerkleError> {
+        let capacity = 1usize << depth;
+        if leaves.len() > capacity {
+            return Err(MerkleError::TooManyLeaves { depth, capacity, actual: leaves.len() });
+        }
+
+        let mut empty = vec![S::ZERO];
+        for i in 0..depth {
+            empty.push(merkle_node(empty[i], empty[i]));
+        }
+
+        let mut levels = vec![leaves];
+        for i in 0..depth {
+            let next = levels[i]
+                .chunks(2)
+                .map(|pair| merkle_node(pair[0], pair.get(1).copied().unwrap_or(empty[i])))
+                .collect();
+            levels.push(next);
+        }
+
+        Ok(Self { levels, empty })
+    }
+
+    pub fn depth(&self) -> usize {
+        self.levels.len() - 1
+    }
+
+    pub fn len(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.levels[0].is_empty()
+    }
+
+    pub fn root(&self) -> S {
+        self.levels[self.depth()].first().copied().unwrap_or(self.empty[self.depth()])
+    }
+
+    /// Caminho de autenticação da folha em `index`, se existir
+    pub fn path(&self, index: usize) -> Option<MerklePath<S>> {
+        if index >= self.len() {
+            return None;
+        }
+
+        let (siblings, is_right) = (0..self.depth())
+            .map(|level| {
+                let position = index >> level;
+                let sibling = self.levels[level]
+                    .get(position ^ 1)
+                    .copied()
+                    .unwrap_or(self.empty[level]);
+                (sibling, position & 1 == 1)
+            })
+            .unzip();
+
+        Some(MerklePath { siblings, is_right })
+    }
+}
+
+/// Recalcula em circuito a raiz a partir de `leaf` e do caminho (testemunha privada).
+/// Custa `MERKLE_LEVEL_CONSTRAINTS` por nível.
+pub fn merkle_root_gadget<S, CS>(
+    mut cs: CS,
+    leaf: &AllocatedNum<S>,
+    path: Option<&MerklePath<S>>,
+    depth: usize,
+) -> Result<AllocatedNum<S>, SynthesisError>
+where
+    S: PrimeField,
+    CS: ConstraintSystem<S>,
+{
+    if let Some(path) = path {
+        assert_eq!(path.depth(), depth, "caminho com profundidade diferente da árvore");
+    }
+
+    let mut node = leaf.clone();
+
+    for level in 0..depth {
+        let mut cs = cs.namespace(|| format!("level {}", level));
+
+        let is_right = Boolean::from(AllocatedBit::alloc(
+            cs.namespace(|| "is right"),
+            path.map(|p| p.is_right[level]),
+        )?);
+        let sibling = AllocatedNum::alloc(cs.namespace(|| "sibling"), || {
+            path.map(|p| p.siblings[level]).ok_or(SynthesisError::AssignmentMissing)
+        })?;
+
+        let left = conditionally_select(cs.namespace(|| "left"), &sibling, &node, &is_right)?;
+        let right = conditionally_select(cs.namespace(|| "right"), &node, &sibling, &is_right)?;
+
+        node = mimc_hash_gadget(cs.namespace(|| "node"), &[left, right])?;
+    }
+
+    Ok(node)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bellman::gadgets::test::TestConstraintSystem;
+    use bls12_381::Scalar;
+    use ff::Field;
+
+    const DEPTH: usize = 3;
+
+    fn leaves(n: u64) -> Vec<Scalar> {
+        (1..=n).map(Scalar::from).collect()
+    }
+
+    fn synthesize(leaf: Scalar, path: &MerklePath<Scalar>) -> (TestConstraintSystem<Scalar>, AllocatedNum<Scalar>) {
+        let mut cs = TestConstraintSystem::new();
+        let leaf = AllocatedNum::alloc(cs.namespace(|| "leaf"), || Ok(leaf)).unwrap();
+        let root = merkle_root_gadget(cs.namespace(|| "merkle"), &leaf, Some(path), DEPTH).unwrap();
+        (cs, root)
+    }
+
+    #[test]
+    fn test_paths_lead_to_root() {
+        for n in [1, 2, 5, 8] {
+            let tree = MerkleTree::new(leaves(n), DEPTH).unwrap();
+            for (i, leaf) in leaves(n).into_iter().enumerate() {
+                assert_eq!(tree.path(i).unwrap().root(leaf), tree.root(), "{} folhas, índice {}", n, i);
+            }
+            assert!(tree.path(n as usize).is_none());
+        }
+    }
+
+    #[test]
+    fn test_root_commits_to_leaves() {
+        let root = MerkleTree::new(leaves(5), DEPTH).unwrap().root();
+
+        assert_ne!(root, MerkleTree::new(leaves(4), DEPTH).unwrap().root());
+        assert_ne!(root, MerkleTree::new(leaves(5), DEPTH + 1).unwrap().root());
+
+        let mut reordered = leaves(5);
+        reordered.swap(0, 1);
+        assert_ne!(root, MerkleTree::new(reordered, DEPTH).unwrap().root());
+    }
+
+    #[test]
+    fn test_rejects_too_many_leaves() {
+        assert_eq!(
+            MerkleTree::new(leaves(9), DEPTH).unwrap_err(),
+            MerkleError::TooManyLeaves { depth: DEPTH, capacity: 8, actual: 9 }
+        );
+    }
+
+    #[test]
+    fn test_gadget_matches_native_root() {
+        let tree = MerkleTree::new(leaves(5), DEPTH).unwrap();
+        let (cs, root) = synthesize(Scalar::from(4u64), &tree.path(3).unwrap());
+
+        assert!(cs.is_satisfied());
+        assert_eq!(cs.num_constraints(), DEPTH * MERKLE_LEVEL_CONSTRAINTS);
+        assert_eq!(root.get_value(), Some(tree.root()));
+    }
+
+    #[test]
+    fn test_gadget_does_not_reach_root_for_other_leaf() {
+        let tree = MerkleTree::new(leaves(5), DEPTH).unwrap();
+        let (cs, root) = synthesize(Scalar::from(9u64), &tree.path(3).unwrap());
+
+        // O circuito é satisfeito, mas a raiz calculada não é a publicada
+        assert!(cs.is_satisfied());
+        assert_ne!(root.get_value(), Some(tree.root()));
+    }
+
+    #[test]
+    fn test_gadget_rejects_non_boolean_direction() {
+        let tree = MerkleTree::new(leaves(5), DEPTH).unwrap();
+        let (mut cs, _) = synthesize(Scalar::from(4u64), &tree.path(3).unwrap());
+        cs.set("merkle/level 1/is right/boolean", Scalar::ONE.double());
+
+        assert_eq!(cs.which_is_unsatisfied(), Some("merkle/level 1/is right/boolean constraint"));
+    }
+}
@@ -0,0 +1,156 @@
+use bellman::{gadgets::num::AllocatedNum, ConstraintSystem, LinearCombination, SynthesisError};
+use ff::PrimeField;
+use sha2::{Digest, Sha256};
+
+/// Número de rodadas da permutação MiMC-5 (suficiente para campos de ~255 bits)
+pub const MIMC_ROUNDS: usize = 110;
+
+const MIMC_SEED: &[u8] = b"ibelieve.mimc5";
+
+/// Restrições por elemento absorvido pelo hash
+pub const MIMC_CONSTRAINTS_PER_INPUT: usize = 3 * MIMC_ROUNDS + 1;
+
+fn mimc_round_constants<S: PrimeField>() -> Vec<S> {
+    let two_128 = S::from_u128(u128::MAX) + S::ONE;
+
+    (0..MIMC_ROUNDS as u32)
+        .map(|i| {
+            let digest = Sha256::new()
+                .chain_update(MIMC_SEED)
+                .chain_update(i.to_be_bytes())
+                .finalize();
+            let hi = u128::from_be_bytes(digest[..16].try_into().unwrap());
+            let lo = u128::from_be_bytes(digest[16..].try_into().unwrap());
+            S::from_u128(hi) * two_128 + S::from_u128(lo)
+        })
+        .collect()
+}
+
+/// MiMC com expoente 5 e hash Miyaguchi-Preneel, calculado fora do circuito.
+///
+/// O expoente 5 exige mdc(5, p - 1) = 1, o que vale para o corpo escalar do BLS12-381.
+pub fn mimc_hash<S: PrimeField>(inputs: &[S]) -> S {
+    let constants = mimc_round_constants::<S>();
+    let mut h = S::ZERO;
+
+    for input in inputs {
+        let mut x = *input;
+        for c in &constants {
+            let t = x + h + c;
+            x = t.square().square() * t;
+        }
+        h = x + h + h + input;
+    }
+
+    h
+}
+
+/// Versão em circuito de `mimc_hash`. Custa `1 + n * MIMC_CONSTRAINTS_PER_INPUT` restrições para `n` entradas.
+pub fn mimc_hash_gadget<S, CS>(mut cs: CS, inputs: &[AllocatedNum<S>]) -> Result<AllocatedNum<S>, SynthesisError>
+where
+    S: PrimeField,
+    CS: ConstraintSystem<S>,
+{
+    let constants = mimc_round_constants::<S>();
+    let mut h = AllocatedNum::alloc(cs.namespace(|| "iv"), || Ok(S::ZERO))?;
+    cs.enforce(|| "iv = 0", |lc| lc + h.get_variable(), |lc| lc + CS::one(), |lc| lc);
+
+    for (j, input) in inputs.iter().enumerate() {
+        let mut cs = cs.namespace(|| format!("input {}", j));
+        let mut x = input.clone();
+
+        for (i, c) in constants.iter().enumerate() {
+            let mut cs = cs.namespace(|| format!("round {}", i));
+            let t_value = x.get_value().zip(h.get_value()).map(|(x, h)| x + h + c);
+            let t = |lc: LinearCombination<S>| lc + x.get_variable() + h.get_variable() + (*c, CS::one());
+
+            let t2 = AllocatedNum::alloc(cs.namespace(|| "t^2"), || {
+                t_value.map(|t| t.square()).ok_or(SynthesisError::AssignmentMissing)
+            })?;
+            cs.enforce(|| "t * t = t^2", t, t, |lc| lc + t2.get_variable());
+
+            let t4 = t2.square(cs.namespace(|| "t^4"))?;
+
+            let t5 = AllocatedNum::alloc(cs.namespace(|| "t^5"), || {
+                t_value.map(|t| t.square().square() * t).ok_or(SynthesisError::AssignmentMissing)
+            })?;
+            cs.enforce(|| "t^4 * t = t^5", |lc| lc + t4.get_variable(), t, |lc| lc + t5.get_variable());
+
+            x = t5;
+        }
+
+        // h' = E_h(m) + h + m, onde E_h(m) = x + h
+        let next = AllocatedNum::alloc(cs.namespace(|| "h"), || {
+            let x = x.get_value().ok_or(SynthesisError::AssignmentMissing)?;
+            let h = h.get_value().ok_or(SynthesisError::AssignmentMissing)?;
+            let m = input.get_value().ok_or(SynthesisError::AssignmentMissing)?;
+            Ok(x + h + h + m)
+        })?;
+        cs.enforce(
+            || "miyaguchi-preneel",
+            |lc| lc + x.get_variable() + h.get_variable() + h.get_variable() + input.get_variable(),
+            |lc| lc + CS::one(),
+            |lc| lc + next.get_variable(),
+        );
+        h = next;
+    }
+
+    Ok(h)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bellman::gadgets::test::TestConstraintSystem;
+    use bls12_381::Scalar;
+    use ff::Field;
+    use rand::thread_rng;
+
+    fn synthesize(inputs: &[Scalar]) -> (TestConstraintSystem<Scalar>, AllocatedNum<Scalar>) {
+        let mut cs = TestConstraintSystem::new();
+        let inputs = inputs
+            .iter()
+            .enumerate()
+            .map(|(i, v)| AllocatedNum::alloc(cs.namespace(|| format!("value {}", i)), || Ok(*v)).unwrap())
+            .collect::<Vec<_>>();
+        let hash = mimc_hash_gadget(cs.namespace(|| "mimc"), &inputs).unwrap();
+        (cs, hash)
+    }
+
+    #[test]
+    fn test_gadget_matches_native_hash() {
+        let mut rng = thread_rng();
+        let inputs = [Scalar::random(&mut rng), Scalar::random(&mut rng), Scalar::random(&mut rng)];
+
+        let (cs, hash) = synthesize(&inputs);
+        assert!(cs.is_satisfied());
+        assert_eq!(cs.num_constraints(), 1 + 3 * MIMC_CONSTRAINTS_PER_INPUT);
+        assert_eq!(hash.get_value(), Some(mimc_hash(&inputs)));
+    }
+
+    #[test]
+    fn test_hash_depends_on_order_and_length() {
+        let (a, b) = (Scalar::from(1u64), Scalar::from(2u64));
+
+        assert_ne!(mimc_hash(&[a, b]), mimc_hash(&[b, a]));
+        assert_ne!(mimc_hash(&[a]), mimc_hash(&[a, Scalar::ZERO]));
+    }
+
+    #[test]
+    fn test_gadget_rejects_wrong_round_output() {
+        let (mut cs, _) = synthesize(&[Scalar::from(7u64)]);
+        let path = "mimc/input 0/round 3/t^5/num";
+        let tampered = cs.get(path) + Scalar::ONE;
+        cs.set(path, tampered);
+
+        assert_eq!(cs.which_is_unsatisfied(), Some("mimc/input 0/round 3/t^4 * t = t^5"));
+    }
+
+    #[test]
+    fn test_gadget_rejects_wrong_digest() {
+        let (mut cs, _) = synthesize(&[Scalar::from(7u64)]);
+        cs.set("mimc/input 0/h/num", Scalar::ZERO);
+
+        assert_eq!(cs.which_is_unsatisfied(), Some("mimc/input 0/miyaguchi-preneel"));
+    }
+}
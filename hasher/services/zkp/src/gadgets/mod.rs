@@ -0,0 +1,13 @@
+mod comparison;
+mod merkle;
+mod mimc;
+mod pedersen;
+mod range;
+mod select;
+
+pub use comparison::*;
+pub use merkle::*;
+pub use mimc::*;
+pub use pedersen::*;
+pub use range::*;
+pub use select::*;
@@ -0,0 +1,295 @@
+use bellman::{
+    gadgets::{
+        boolean::{AllocatedBit, Boolean},
+        num::AllocatedNum,
+    },
+    ConstraintSystem, LinearCombination, SynthesisError,
+};
+use bls12_381::Scalar;
+use ff::{Field, PrimeField, PrimeFieldBits};
+use jubjub::{AffinePoint, ExtendedPoint};
+use sha2::{Digest, Sha256};
+
+/// Bits da randomicidade do compromisso (tamanho do corpo escalar do Jubjub)
+pub const PEDERSEN_RANDOMNESS_BITS: usize = jubjub::Fr::NUM_BITS as usize;
+
+/// Restrições de cada soma de pontos em coordenadas de Edwards
+pub const EDWARDS_ADD_CONSTRAINTS: usize = 6;
+
+const VALUE_GENERATOR_SEED: &[u8] = b"ibelieve.pedersen.value";
+const RANDOMNESS_GENERATOR_SEED: &[u8] = b"ibelieve.pedersen.randomness";
+
+/// Ponto do Jubjub alocado no circuito, em coordenadas afins (u, v)
+#[derive(Clone)]
+pub struct EdwardsPoint {
+    pub u: AllocatedNum<Scalar>,
+    pub v: AllocatedNum<Scalar>,
+}
+
+/// Compromisso de Pedersen `valor * G + r * H` sobre o Jubjub, a curva cujo
+/// corpo base é o corpo escalar do BLS12-381. G e H são derivados por hash,
+/// sem logaritmo discreto conhecido entre eles.
+pub fn pedersen_commit(value: u128, randomness: &jubjub::Fr) -> AffinePoint {
+    let (g, h) = pedersen_generators();
+    let value = jubjub::Fr::from_u128(value);
+
+    AffinePoint::from(g * value + h * randomness)
+}
+
+/// Versão em circuito de `pedersen_commit`, a partir dos bits little-endian do
+/// valor e da randomicidade. Custa `EDWARDS_ADD_CONSTRAINTS` por bit, menos uma soma.
+pub fn pedersen_commit_gadget<CS>(
+    mut cs: CS,
+    value: &[Boolean],
+    randomness: &[Boolean],
+) -> Result<EdwardsPoint, SynthesisError>
+where
+    CS: ConstraintSystem<Scalar>,
+{
+    assert!(value.len() <= 128, "valor maior que 128 bits");
+    assert!(randomness.len() <= PEDERSEN_RANDOMNESS_BITS, "randomicidade maior que o corpo");
+
+    let (g, h) = pedersen_generators();
+    let terms = value
+        .iter()
+        .zip(powers_of_two(g))
+        .chain(randomness.iter().zip(powers_of_two(h)));
+
+    let mut accumulator: Option<PointTerm> = None;
+    let mut result = None;
+
+    for (i, (bit, base)) in terms.enumerate() {
+        let term = PointTerm::select::<CS>(bit, &base);
+
+        accumulator = Some(match accumulator {
+            None => term,
+            Some(acc) => {
+                let sum = edwards_add(cs.namespace(|| format!("add {}", i)), &acc, &term)?;
+                let next = PointTerm::from(&sum);
+                result = Some(sum);
+                next
+            }
+        });
+    }
+
+    // Com pelo menos dois bits o acumulador sempre passa por uma soma alocada
+    result.ok_or(SynthesisError::Unsatisfiable)
+}
+
+/// Aloca os bits da randomicidade do compromisso
+pub fn alloc_randomness_bits<CS>(mut cs: CS, randomness: Option<jubjub::Fr>) -> Result<Vec<Boolean>, SynthesisError>
+where
+    CS: ConstraintSystem<Scalar>,
+{
+    let bits = randomness.map(|r| r.to_le_bits());
+
+    (0..PEDERSEN_RANDOMNESS_BITS)
+        .map(|i| {
+            AllocatedBit::alloc(cs.namespace(|| format!("bit {}", i)), bits.as_ref().map(|b| b[i]))
+                .map(Boolean::from)
+        })
+        .collect()
+}
+
+/// Ponto escolhido entre a identidade (0, 1) e uma constante conforme um bit;
+/// as coordenadas são combinações lineares do bit e não custam restrições
+struct PointTerm {
+    u: (LinearCombination<Scalar>, Option<Scalar>),
+    v: (LinearCombination<Scalar>, Option<Scalar>),
+}
+
+impl PointTerm {
+    /// `bit ? base : identidade`
+    fn select<CS: ConstraintSystem<Scalar>>(bit: &Boolean, base: &AffinePoint) -> Self {
+        let (u, v) = (base.get_u(), base.get_v());
+        let value = bit.get_value();
+
+        Self {
+            u: (bit.lc(CS::one(), u), value.map(|b| if b { u } else { Scalar::ZERO })),
+            v: (
+                LinearCombination::zero() + CS::one() + &bit.lc(CS::one(), v - Scalar::ONE),
+                value.map(|b| if b { v } else { Scalar::ONE }),
+            ),
+        }
+    }
+}
+
+impl From<&EdwardsPoint> for PointTerm {
+    fn from(point: &EdwardsPoint) -> Self {
+        Self {
+            u: (LinearCombination::zero() + point.u.get_variable(), point.u.get_value()),
+            v: (LinearCombination::zero() + point.v.get_variable(), point.v.get_value()),
+        }
+    }
+}
+
+/// Valores intermediários de uma soma de pontos
+#[derive(Clone, Copy)]
+struct AdditionWitness {
+    uu: Scalar,
+    a: Scalar,
+    b: Scalar,
+    c: Scalar,
+    u: Scalar,
+    v: Scalar,
+}
+
+/// Soma completa em Edwards torcida (a = -1):
+/// u3 = (u1 v2 + v1 u2) / (1 + d u1 u2 v1 v2), v3 = (v1 v2 + u1 u2) / (1 - d u1 u2 v1 v2)
+fn edwards_add<CS>(mut cs: CS, p: &PointTerm, q: &PointTerm) -> Result<EdwardsPoint, SynthesisError>
+where
+    CS: ConstraintSystem<Scalar>,
+{
+    let d = edwards_d();
+
+    // Os denominadores nunca se anulam no Jubjub (d não é quadrado)
+    let witness = (|| {
+        let (u1, v1, u2, v2) = (p.u.1?, p.v.1?, q.u.1?, q.v.1?);
+        let (a, b) = (u1 * v2, v1 * u2);
+        let c = d * a * b;
+        let uu = (u1 + v1) * (u2 + v2);
+        let u = (a + b) * Option::<Scalar>::from((Scalar::ONE + c).invert())?;
+        let v = (uu - a - b) * Option::<Scalar>::from((Scalar::ONE - c).invert())?;
+        Some(AdditionWitness { uu, a, b, c, u, v })
+    })();
+    let value = |f: fn(AdditionWitness) -> Scalar| witness.map(f).ok_or(SynthesisError::AssignmentMissing);
+
+    let uu = AllocatedNum::alloc(cs.namespace(|| "U"), || value(|w| w.uu))?;
+    cs.enforce(
+        || "U = (u1 + v1)(u2 + v2)",
+        |lc| lc + &p.u.0 + &p.v.0,
+        |lc| lc + &q.u.0 + &q.v.0,
+        |lc| lc + uu.get_variable(),
+    );
+
+    let a = AllocatedNum::alloc(cs.namespace(|| "A"), || value(|w| w.a))?;
+    cs.enforce(|| "A = u1 v2", |lc| lc + &p.u.0, |lc| lc + &q.v.0, |lc| lc + a.get_variable());
+
+    let b = AllocatedNum::alloc(cs.namespace(|| "B"), || value(|w| w.b))?;
+    cs.enforce(|| "B = v1 u2", |lc| lc + &p.v.0, |lc| lc + &q.u.0, |lc| lc + b.get_variable());
+
+    let c = AllocatedNum::alloc(cs.namespace(|| "C"), || value(|w| w.c))?;
+    cs.enforce(
+        || "C = d A B",
+        |lc| lc + (d, a.get_variable()),
+        |lc| lc + b.get_variable(),
+        |lc| lc + c.get_variable(),
+    );
+
+    let u = AllocatedNum::alloc(cs.namespace(|| "u"), || value(|w| w.u))?;
+    cs.enforce(
+        || "u3 (1 + C) = A + B",
+        |lc| lc + u.get_variable(),
+        |lc| lc + CS::one() + c.get_variable(),
+        |lc| lc + a.get_variable() + b.get_variable(),
+    );
+
+    let v = AllocatedNum::alloc(cs.namespace(|| "v"), || value(|w| w.v))?;
+    cs.enforce(
+        || "v3 (1 - C) = U - A - B",
+        |lc| lc + v.get_variable(),
+        |lc| lc + CS::one() - c.get_variable(),
+        |lc| lc + uu.get_variable() - a.get_variable() - b.get_variable(),
+    );
+
+    Ok(EdwardsPoint { u, v })
+}
+
+/// d = -(10240 / 10241)
+fn edwards_d() -> Scalar {
+    -(Scalar::from(10240u64) * Scalar::from(10241u64).invert().unwrap())
+}
+
+fn pedersen_generators() -> (ExtendedPoint, ExtendedPoint) {
+    (hash_to_point(VALUE_GENERATOR_SEED), hash_to_point(RANDOMNESS_GENERATOR_SEED))
+}
+
+/// Deriva um ponto de ordem prima tentando codificações SHA-256(seed || contador)
+fn hash_to_point(seed: &[u8]) -> ExtendedPoint {
+    (0u32..)
+        .find_map(|counter| {
+            let digest: [u8; 32] = Sha256::new()
+                .chain_update(seed)
+                .chain_update(counter.to_be_bytes())
+                .finalize()
+                .into();
+            let point: Option<AffinePoint> = AffinePoint::from_bytes(digest).into();
+            point
+                .map(|p| ExtendedPoint::from(p).mul_by_cofactor())
+                .filter(|p| !bool::from(p.is_identity()))
+        })
+        .unwrap()
+}
+
+/// base, 2 * base, 4 * base, ...
+fn powers_of_two(base: ExtendedPoint) -> impl Iterator<Item = AffinePoint> {
+    std::iter::successors(Some(base), |p| Some(p.double())).map(AffinePoint::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bellman::gadgets::{boolean::u64_into_boolean_vec_le, test::TestConstraintSystem};
+    use rand::thread_rng;
+
+    const VALUE_BITS: usize = 64;
+
+    fn synthesize(value: u64, randomness: jubjub::Fr) -> (TestConstraintSystem<Scalar>, EdwardsPoint) {
+        let mut cs = TestConstraintSystem::new();
+        let value = u64_into_boolean_vec_le(cs.namespace(|| "value"), Some(value)).unwrap();
+        let randomness = alloc_randomness_bits(cs.namespace(|| "randomness"), Some(randomness)).unwrap();
+        let commitment = pedersen_commit_gadget(cs.namespace(|| "commitment"), &value, &randomness).unwrap();
+        (cs, commitment)
+    }
+
+    #[test]
+    fn test_generators_are_on_curve_and_independent() {
+        let (g, h) = pedersen_generators();
+        let d = edwards_d();
+
+        for point in [g, h] {
+            let point = AffinePoint::from(point);
+            let (u2, v2) = (point.get_u().square(), point.get_v().square());
+            assert_eq!(v2 - u2, Scalar::ONE + d * u2 * v2);
+            assert!(bool::from(point.is_prime_order()));
+        }
+        assert_ne!(AffinePoint::from(g), AffinePoint::from(h));
+    }
+
+    #[test]
+    fn test_gadget_matches_native_commitment() {
+        let randomness = jubjub::Fr::random(&mut thread_rng());
+        let (cs, commitment) = synthesize(1_000_000, randomness);
+
+        let expected = pedersen_commit(1_000_000, &randomness);
+        assert!(cs.is_satisfied());
+        assert_eq!(commitment.u.get_value(), Some(expected.get_u()));
+        assert_eq!(commitment.v.get_value(), Some(expected.get_v()));
+
+        let additions = VALUE_BITS + PEDERSEN_RANDOMNESS_BITS - 1;
+        assert_eq!(
+            cs.num_constraints(),
+            VALUE_BITS + PEDERSEN_RANDOMNESS_BITS + additions * EDWARDS_ADD_CONSTRAINTS
+        );
+    }
+
+    #[test]
+    fn test_commitment_is_hiding_and_binding() {
+        let mut rng = thread_rng();
+        let (r1, r2) = (jubjub::Fr::random(&mut rng), jubjub::Fr::random(&mut rng));
+
+        assert_ne!(pedersen_commit(42, &r1), pedersen_commit(42, &r2));
+        assert_ne!(pedersen_commit(42, &r1), pedersen_commit(43, &r1));
+        assert_eq!(pedersen_commit(0, &jubjub::Fr::zero()), AffinePoint::identity());
+    }
+
+    #[test]
+    fn test_gadget_rejects_wrong_sum() {
+        let (mut cs, _) = synthesize(5, jubjub::Fr::one());
+        let path = "commitment/add 10/u/num";
+        let tampered = cs.get(path) + Scalar::ONE;
+        cs.set(path, tampered);
+
+        assert_eq!(cs.which_is_unsatisfied(), Some("commitment/add 10/u3 (1 + C) = A + B"));
+    }
+}
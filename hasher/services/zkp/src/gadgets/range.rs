@@ -0,0 +1,96 @@
+use bellman::{
+    gadgets::{boolean::AllocatedBit, num::AllocatedNum},
+    ConstraintSystem, LinearCombination, SynthesisError,
+};
+use ff::PrimeFieldBits;
+
+/// Garante que `0 <= lc < 2^bits`, onde `value` é o valor de `lc`.
+///
+/// Aloca os `bits` bits menos significativos (little-endian) e exige que a
+/// recomposição seja igual a `lc`. Custa `bits + 1` restrições.
+pub fn enforce_range<S, CS>(
+    mut cs: CS,
+    lc: LinearCombination<S>,
+    value: Option<S>,
+    bits: usize,
+) -> Result<Vec<AllocatedBit>, SynthesisError>
+where
+    S: PrimeFieldBits,
+    CS: ConstraintSystem<S>,
+{
+    // Com bits >= CAPACITY a recomposição daria a volta no módulo
+    assert!(bits < S::CAPACITY as usize, "intervalo maior que o corpo");
+
+    let value_bits = value.map(|v| v.to_le_bits());
+    let mut packed = LinearCombination::zero();
+    let mut coeff = S::ONE;
+    let mut allocated = Vec::with_capacity(bits);
+
+    for i in 0..bits {
+        let bit = AllocatedBit::alloc(
+            cs.namespace(|| format!("bit {}", i)),
+            value_bits.as_ref().map(|b| b[i]),
+        )?;
+        packed = packed + (coeff, bit.get_variable());
+        coeff = coeff.double();
+        allocated.push(bit);
+    }
+
+    cs.enforce(|| "range", |_| packed, |lc| lc + CS::one(), |_| lc);
+
+    Ok(allocated)
+}
+
+/// Garante que `num` cabe em `bits` bits e devolve sua decomposição little-endian
+pub fn range_check<S, CS>(cs: CS, num: &AllocatedNum<S>, bits: usize) -> Result<Vec<AllocatedBit>, SynthesisError>
+where
+    S: PrimeFieldBits,
+    CS: ConstraintSystem<S>,
+{
+    enforce_range(cs, LinearCombination::zero() + num.get_variable(), num.get_value(), bits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bellman::gadgets::test::TestConstraintSystem;
+    use bls12_381::Scalar;
+    use ff::{Field, PrimeField};
+
+    fn synthesize(value: Scalar, bits: usize) -> TestConstraintSystem<Scalar> {
+        let mut cs = TestConstraintSystem::new();
+        let num = AllocatedNum::alloc(cs.namespace(|| "value"), || Ok(value)).unwrap();
+        range_check(cs.namespace(|| "check"), &num, bits).unwrap();
+        cs
+    }
+
+    #[test]
+    fn test_range_check_constraint_count() {
+        assert_eq!(synthesize(Scalar::ZERO, 8).num_constraints(), 9);
+        assert_eq!(synthesize(Scalar::ZERO, 128).num_constraints(), 129);
+    }
+
+    #[test]
+    fn test_range_check_accepts_values_in_range() {
+        for value in [0u128, 1, 200, 255] {
+            assert!(synthesize(Scalar::from_u128(value), 8).is_satisfied(), "{}", value);
+        }
+        assert!(synthesize(Scalar::from_u128(u128::MAX), 128).is_satisfied());
+    }
+
+    #[test]
+    fn test_range_check_rejects_values_out_of_range() {
+        assert_eq!(synthesize(Scalar::from(256u64), 8).which_is_unsatisfied(), Some("check/range"));
+        assert!(!synthesize(-Scalar::ONE, 128).is_satisfied());
+    }
+
+    #[test]
+    fn test_range_check_rejects_tampered_bits() {
+        let mut cs = synthesize(Scalar::from(5u64), 8);
+        cs.set("check/bit 1/boolean", Scalar::ONE);
+        assert_eq!(cs.which_is_unsatisfied(), Some("check/range"));
+
+        cs.set("check/bit 1/boolean", Scalar::from(2u64));
+        assert_eq!(cs.which_is_unsatisfied(), Some("check/bit 1/boolean constraint"));
+    }
+}
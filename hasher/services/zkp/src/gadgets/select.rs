@@ -0,0 +1,85 @@
+use bellman::{
+    gadgets::{boolean::Boolean, num::AllocatedNum},
+    ConstraintSystem, SynthesisError,
+};
+use ff::PrimeField;
+
+/// Devolve `a` se `condition` for verdadeiro, senão `b`. Custa 1 restrição.
+pub fn conditionally_select<S, CS>(
+    mut cs: CS,
+    a: &AllocatedNum<S>,
+    b: &AllocatedNum<S>,
+    condition: &Boolean,
+) -> Result<AllocatedNum<S>, SynthesisError>
+where
+    S: PrimeField,
+    CS: ConstraintSystem<S>,
+{
+    let result = AllocatedNum::alloc(cs.namespace(|| "result"), || {
+        let condition = condition.get_value().ok_or(SynthesisError::AssignmentMissing)?;
+        let selected = if condition { a.get_value() } else { b.get_value() };
+        selected.ok_or(SynthesisError::AssignmentMissing)
+    })?;
+
+    // (a - b) * condition = result - b
+    cs.enforce(
+        || "select",
+        |lc| lc + a.get_variable() - b.get_variable(),
+        |_| condition.lc(CS::one(), S::ONE),
+        |lc| lc + result.get_variable() - b.get_variable(),
+    );
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bellman::gadgets::{boolean::AllocatedBit, test::TestConstraintSystem};
+    use bls12_381::Scalar;
+
+    fn synthesize<F>(condition: F) -> (TestConstraintSystem<Scalar>, AllocatedNum<Scalar>)
+    where
+        F: FnOnce(&mut TestConstraintSystem<Scalar>) -> Boolean,
+    {
+        let mut cs = TestConstraintSystem::new();
+        let a = AllocatedNum::alloc(cs.namespace(|| "a"), || Ok(Scalar::from(10u64))).unwrap();
+        let b = AllocatedNum::alloc(cs.namespace(|| "b"), || Ok(Scalar::from(20u64))).unwrap();
+        let condition = condition(&mut cs);
+        let result = conditionally_select(cs.namespace(|| "select"), &a, &b, &condition).unwrap();
+        (cs, result)
+    }
+
+    fn bit(cs: &mut TestConstraintSystem<Scalar>, value: bool) -> Boolean {
+        Boolean::from(AllocatedBit::alloc(cs.namespace(|| "condition"), Some(value)).unwrap())
+    }
+
+    #[test]
+    fn test_select_follows_condition() {
+        for (condition, expected) in [(true, 10u64), (false, 20)] {
+            let (cs, result) = synthesize(|cs| bit(cs, condition));
+            assert!(cs.is_satisfied());
+            assert_eq!(cs.num_constraints(), 2);
+            assert_eq!(result.get_value(), Some(Scalar::from(expected)));
+        }
+    }
+
+    #[test]
+    fn test_select_with_constant_and_negated_condition() {
+        let (cs, result) = synthesize(|_| Boolean::constant(true));
+        assert!(cs.is_satisfied());
+        assert_eq!(cs.num_constraints(), 1);
+        assert_eq!(result.get_value(), Some(Scalar::from(10u64)));
+
+        let (cs, result) = synthesize(|cs| bit(cs, true).not());
+        assert!(cs.is_satisfied());
+        assert_eq!(result.get_value(), Some(Scalar::from(20u64)));
+    }
+
+    #[test]
+    fn test_select_rejects_wrong_result() {
+        let (mut cs, _) = synthesize(|cs| bit(cs, true));
+        cs.set("select/result/num", Scalar::from(20u64));
+        assert_eq!(cs.which_is_unsatisfied(), Some("select/select"));
+    }
+}
@@ -1,21 +1,93 @@
 pub mod api;
+pub mod bn254;
+pub mod ceremony;
+pub mod circuits;
+pub mod encoding;
+pub mod gadgets;
 pub mod models;
 pub mod services;
 
 use actix_web::{web, App, HttpServer};
-use sqlx::PgPool;
+use common::bulkhead::Bulkhead;
+use log::info;
 use std::error::Error;
+use std::sync::Arc;
+
+use crate::services::{
+    AnchorConfig, ApiAccess, EvmParameterStore, EvmService, ParameterStore, ProofAnchorer, ProofJobQueue, ZkpService,
+};
+
+/// Provas simultâneas quando `ZKP_PROOF_WORKERS` não está definida
+const DEFAULT_PROOF_WORKERS: usize = 2;
 
 pub async fn run_server(database_url: &str, port: u16) -> Result<(), Box<dyn Error>> {
     let pool = common::db::setup_database(database_url).await?;
+
+    // Carregar (ou gerar uma única vez) as chaves de cada versão de circuito
+    let parameters = ParameterStore::new(&pool);
+    ZkpService::new(&pool, &parameters).ensure_parameters().await?;
+
+    // Chaves BN254 dos circuitos com verificador on-chain
+    let evm_parameters = EvmParameterStore::new(&pool);
+    EvmService::new(&pool, &evm_parameters).ensure_parameters().await?;
+
+    // Jobs de prova: retomar os interrompidos e iniciar os workers
+    let workers = std::env::var("ZKP_PROOF_WORKERS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_PROOF_WORKERS);
+    // As medições do perfilador dividem as vagas com as provas
+    let bulkhead = Arc::new(Bulkhead::new(workers, 30_000));
+    let jobs = ProofJobQueue::new(&pool, &parameters, bulkhead.clone());
+    jobs.requeue_interrupted().await?;
+    jobs.start();
+
+    // Ancoragem das provas no ZKProofRegistry, quando configurada
+    match AnchorConfig::from_env()? {
+        Some(config) => ProofAnchorer::connect(&pool, &config).await?.start(),
+        None => info!("ANCHOR_REGISTRY_ADDRESS não definida; ancoragem on-chain desligada"),
+    }
+
+    // Quem pode pedir provas e operações administrativas pela API
+    let access = web::Data::new(ApiAccess::from_env()?);
+
     let pool = web::Data::new(pool);
+    let parameters = web::Data::new(parameters);
+    let evm_parameters = web::Data::new(evm_parameters);
+    let jobs = web::Data::new(jobs);
+    let bulkhead = web::Data::from(bulkhead);
 
     HttpServer::new(move || {
         App::new()
             .app_data(pool.clone())
+            .app_data(parameters.clone())
+            .app_data(evm_parameters.clone())
+            .app_data(jobs.clone())
+            .app_data(bulkhead.clone())
+            .app_data(access.clone())
             .service(
                 web::scope("/api/v1/zkp")
                     .service(api::generate_proof)
+                    .service(api::get_job)
+                    .service(api::cancel_job)
+                    .service(api::verify_proof)
+                    .service(api::verify_batch)
+                    .service(api::list_circuits)
+                    .service(api::analyze_circuit_version)
+                    .service(api::get_verifying_key)
+                    .service(api::update_whitelist)
+                    .service(api::publish_whitelist_root)
+                    .service(api::get_whitelist_root)
+                    .service(api::generate_whitelist_proof)
+                    .service(api::generate_attribute_proof)
+                    .service(api::get_evm_verifier)
+                    .service(api::get_evm_verifying_key)
+                    .service(api::generate_evm_proof)
+                    .service(api::evm_calldata)
+                    .service(api::export_proof)
+                    .service(api::get_proof_anchor)
+                    .service(api::get_status_list)
+                    .service(api::revoke_proof)
                     .service(api::get_proof)
             )
     })
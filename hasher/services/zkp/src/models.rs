@@ -1,6 +1,16 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
+use common::models::{ErrorResponse, ZkpProof};
+use ff::PrimeField;
+
+use crate::bn254::EvmCalldata;
+use crate::circuits::{CircuitId, CircuitSelector, ConstraintProfile, PublicInput};
+use crate::encoding::{
+    encode, snarkjs_public_inputs, to_hex, CanonicalEncoding, ProofJson, SnarkjsProof, SnarkjsVerifyingKey,
+    ToSnarkjs, VerifyingKeyJson,
+};
+use crate::services::format_scalar;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ZKProof {
@@ -16,9 +26,501 @@ pub struct GenerateProofRequest {
     pub public_inputs: Vec<String>,
 }
 
+/// Verificação avulsa: usa `verification_key` quando informada, senão a chave
+/// do circuito escolhido em `circuit` (ou, por compatibilidade, a registrada
+/// para a chave de parâmetros `circuit_version`, ex.: `solvency-v1`). Prova e
+/// chave vão na codificação canônica (ver `encoding`); o formato comprimido do
+/// bellman ainda é aceito.
+///
+/// Com `context`, a prova precisa ser de um circuito com nullifier, gerada para
+/// esse contexto, e é consumida: uma segunda verificação nele é recusada.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct VerifyProofRequest {
     pub proof: Vec<u8>,
     pub public_inputs: Vec<String>,
-    pub verification_key: Vec<u8>,
-} 
\ No newline at end of file
+    pub verification_key: Option<Vec<u8>>,
+    pub circuit: Option<CircuitSelector>,
+    pub circuit_version: Option<String>,
+    pub context: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VerifyProofResponse {
+    pub valid: bool,
+    pub key_fingerprint: String,
+    pub circuit_version: Option<String>,
+    pub circuit: Option<CircuitId>,
+    /// Nullifier consumido, quando `context` foi informado
+    pub nullifier: Option<String>,
+    /// Validade e revogação, quando a prova foi emitida por este serviço
+    pub status: Option<ProofStatus>,
+} 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BatchProof {
+    pub proof: Vec<u8>,
+    pub public_inputs: Vec<String>,
+}
+
+/// Verificação em lote. As provas em `proofs` usam `verification_key` ou a
+/// chave de `circuit` (ou `circuit_version`); as de `proof_ids` são lidas de
+/// `zkp_proofs` e verificadas com a chave com que foram geradas.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VerifyBatchRequest {
+    #[serde(default)]
+    pub proofs: Vec<BatchProof>,
+    #[serde(default)]
+    pub proof_ids: Vec<Uuid>,
+    pub verification_key: Option<Vec<u8>>,
+    pub circuit: Option<CircuitSelector>,
+    pub circuit_version: Option<String>,
+}
+
+/// Resultado de um item do lote; `index` segue a ordem `proofs` e depois `proof_ids`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BatchVerificationResult {
+    pub index: usize,
+    pub proof_id: Option<Uuid>,
+    pub valid: bool,
+    pub error: Option<ErrorResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VerifyBatchResponse {
+    pub total: usize,
+    pub valid: usize,
+    pub invalid: usize,
+    pub results: Vec<BatchVerificationResult>,
+}
+
+/// Substitui o conjunto de carteiras aprovadas (ex.: subwallets ativas do SubwalletRegistry)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateWhitelistRequest {
+    pub wallets: Vec<String>,
+    pub source: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateWhitelistResponse {
+    pub wallet_count: usize,
+}
+
+/// Raiz publicada da árvore de carteiras aprovadas
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WhitelistRoot {
+    pub id: Uuid,
+    pub root: String,
+    pub wallet_count: usize,
+    pub published_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WhitelistProofRequest {
+    pub user_id: Uuid,
+    /// Contexto de aplicação em que a prova vale (ex.: `loan:42`)
+    pub context: String,
+}
+
+/// Predicado sobre os atributos atestados do usuário
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AttributePredicate {
+    /// Idade na data da prova maior ou igual a `years`
+    AgeAtLeast { years: u8 },
+    /// País de residência, ISO 3166-1 alfa-2 (ex.: `BR`)
+    ResidentOf { country: String },
+    KycTierAtLeast { tier: u8 },
+}
+
+/// Prova de um conjunto de predicados, no máximo um de cada tipo
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AttributeProofRequest {
+    pub user_id: Uuid,
+    /// Contexto de aplicação em que a prova vale (ex.: `onboarding:partner-7`)
+    pub context: String,
+    pub predicates: Vec<AttributePredicate>,
+}
+
+/// Estados de um job de geração de prova
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+impl JobStatus {
+    /// Valor gravado em `zkp_jobs.status`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobStatus::Queued => "queued",
+            JobStatus::Running => "running",
+            JobStatus::Succeeded => "succeeded",
+            JobStatus::Failed => "failed",
+            JobStatus::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        [
+            JobStatus::Queued,
+            JobStatus::Running,
+            JobStatus::Succeeded,
+            JobStatus::Failed,
+            JobStatus::Cancelled,
+        ]
+        .into_iter()
+        .find(|status| status.as_str() == value)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        matches!(self, JobStatus::Succeeded | JobStatus::Failed | JobStatus::Cancelled)
+    }
+}
+
+impl std::fmt::Display for JobStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Job de geração de prova. `queue_time_ms` é a espera na fila e `run_time_ms`
+/// a duração da prova; `proof_id` aponta para `zkp_proofs` quando o job termina bem.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProofJob {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub circuit_version: String,
+    pub status: JobStatus,
+    pub proof_id: Option<Uuid>,
+    pub error: Option<ErrorResponse>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub queue_time_ms: Option<i64>,
+    pub run_time_ms: Option<i64>,
+}
+
+/// Prova de solvência no BN254 para verificação on-chain
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EvmProofRequest {
+    pub user_id: Uuid,
+    /// Limite mínimo de saldo, em decimal na unidade mínima do ativo
+    pub threshold: String,
+}
+
+/// Prova BN254 (formato comprimido do arkworks) com a calldata do verificador
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EvmProof {
+    pub circuit_version: String,
+    pub key_fingerprint: String,
+    pub proof: Vec<u8>,
+    pub public_inputs: Vec<String>,
+    pub calldata: EvmCalldata,
+}
+
+/// Calldata de `verifyProof` para uma prova BN254 já gerada
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EvmCalldataRequest {
+    pub circuit_version: String,
+    pub proof: Vec<u8>,
+    pub public_inputs: Vec<String>,
+}
+
+/// Formatos de exportação de provas e chaves
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// Codificação canônica em hex
+    #[default]
+    Hex,
+    /// Pontos comprimidos em hex, campo a campo
+    Json,
+    /// `proof.json`, `public.json` e `verification_key.json` do snarkjs
+    Snarkjs,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExportQuery {
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+/// Prova e entradas públicas no formato pedido
+#[derive(Debug, Serialize)]
+#[serde(tag = "format", rename_all = "lowercase")]
+pub enum ExportedProof {
+    Hex { proof: String, public_inputs: String },
+    Json { proof: ProofJson, public_inputs: Vec<String> },
+    Snarkjs { proof: Box<SnarkjsProof>, public: Vec<String> },
+}
+
+impl ExportedProof {
+    pub fn new<P, S>(proof: &P, public_inputs: &[S], format: ExportFormat) -> Self
+    where
+        P: CanonicalEncoding + ToSnarkjs<Output = SnarkjsProof>,
+        S: PrimeField<Repr = [u8; 32]>,
+        Vec<S>: CanonicalEncoding,
+    {
+        match format {
+            ExportFormat::Hex => ExportedProof::Hex {
+                proof: to_hex(&encode(proof)),
+                public_inputs: to_hex(&encode(&public_inputs.to_vec())),
+            },
+            ExportFormat::Json => ExportedProof::Json {
+                proof: ProofJson::from_canonical(&encode(proof)).expect("codificação própria é válida"),
+                public_inputs: public_inputs.iter().map(format_scalar).collect(),
+            },
+            ExportFormat::Snarkjs => ExportedProof::Snarkjs {
+                proof: Box::new(proof.to_snarkjs()),
+                public: snarkjs_public_inputs(public_inputs),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProofExport {
+    pub proof_id: Uuid,
+    pub key_fingerprint: Option<String>,
+    pub status: ProofStatus,
+    #[serde(flatten)]
+    pub exported: ExportedProof,
+}
+
+/// Chave de verificação no formato pedido
+#[derive(Debug, Serialize)]
+#[serde(tag = "format", content = "verification_key", rename_all = "lowercase")]
+pub enum ExportedKey {
+    Hex(String),
+    Json(Box<VerifyingKeyJson>),
+    Snarkjs(Box<SnarkjsVerifyingKey>),
+}
+
+impl ExportedKey {
+    pub fn new<V>(vk: &V, format: ExportFormat) -> Self
+    where
+        V: CanonicalEncoding + ToSnarkjs<Output = SnarkjsVerifyingKey>,
+    {
+        match format {
+            ExportFormat::Hex => ExportedKey::Hex(to_hex(&encode(vk))),
+            ExportFormat::Json => {
+                ExportedKey::Json(Box::new(
+                    VerifyingKeyJson::from_canonical(&encode(vk)).expect("codificação própria é válida"),
+                ))
+            }
+            ExportFormat::Snarkjs => ExportedKey::Snarkjs(Box::new(vk.to_snarkjs())),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct VerifyingKeyExport {
+    pub circuit_version: String,
+    pub key_fingerprint: String,
+    #[serde(flatten)]
+    pub exported: ExportedKey,
+}
+
+/// Versão de circuito no registro, com sua chave de verificação no formato pedido
+#[derive(Debug, Serialize)]
+pub struct CircuitInfo {
+    pub name: String,
+    pub version: String,
+    pub description: String,
+    pub deprecated: bool,
+    pub constraint_count: usize,
+    /// Hash das restrições, o mesmo registrado nos arquivos da cerimônia de setup
+    pub circuit_hash: String,
+    pub public_inputs: Vec<PublicInput>,
+    /// Chave dos parâmetros em `zkp_parameters` (ex.: `solvency-v1`)
+    pub parameters_key: String,
+    pub key_fingerprint: String,
+    #[serde(flatten)]
+    pub verification_key: ExportedKey,
+}
+
+/// Escolha da versão e da medição em `/circuits/{name}/analysis`
+#[derive(Debug, Deserialize)]
+pub struct AnalysisQuery {
+    pub version: Option<String>,
+    /// Mede setup, prova e verificação; sem ele, só conta restrições
+    #[serde(default)]
+    pub measure: bool,
+}
+
+/// Tempos medidos de uma instância de exemplo, em segundos
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CircuitTimings {
+    pub setup_seconds: f64,
+    pub proving_seconds: f64,
+    pub verification_seconds: f64,
+}
+
+/// Análise de uma versão de circuito: sistema R1CS e tempos medidos
+#[derive(Debug, Serialize)]
+pub struct CircuitAnalysis {
+    pub circuit: CircuitId,
+    pub parameters_key: String,
+    pub constraints: ConstraintProfile,
+    pub timings: Option<CircuitTimings>,
+}
+
+/// Estados da ancoragem de uma prova no ZKProofRegistry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnchorStatus {
+    /// Ainda não submetida (ou de volta à fila após falha)
+    Pending,
+    /// Transação enviada, aguardando confirmações
+    Submitted,
+    Confirmed,
+    Failed,
+}
+
+impl AnchorStatus {
+    /// Valor gravado em `zkp_proofs.anchor_status`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AnchorStatus::Pending => "pending",
+            AnchorStatus::Submitted => "submitted",
+            AnchorStatus::Confirmed => "confirmed",
+            AnchorStatus::Failed => "failed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        [
+            AnchorStatus::Pending,
+            AnchorStatus::Submitted,
+            AnchorStatus::Confirmed,
+            AnchorStatus::Failed,
+        ]
+        .into_iter()
+        .find(|status| status.as_str() == value)
+    }
+}
+
+impl std::fmt::Display for AnchorStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Ancoragem de uma prova salva. `anchor_hash` é o valor registrado no
+/// contrato; `retry_at` só existe em falhas que ainda serão tentadas de novo.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProofAnchor {
+    pub proof_id: Uuid,
+    pub status: AnchorStatus,
+    pub anchor_hash: Option<String>,
+    pub tx_hash: Option<String>,
+    pub block_number: Option<i64>,
+    pub attempts: i32,
+    pub error: Option<String>,
+    pub submitted_at: Option<DateTime<Utc>>,
+    pub retry_at: Option<DateTime<Utc>>,
+    pub anchored_at: Option<DateTime<Utc>>,
+}
+
+/// Situação de uma prova emitida pelo serviço
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProofState {
+    Valid,
+    NotYetValid,
+    Expired,
+    Revoked,
+}
+
+/// Motivos de revogação de uma prova
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RevocationReason {
+    /// Substituída por uma prova mais recente
+    Superseded,
+    BalanceChanged,
+    AttributesChanged,
+    KeyCompromise,
+    UserRequest,
+    Fraud,
+}
+
+impl RevocationReason {
+    /// Valor gravado em `zkp_proofs.revocation_reason`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RevocationReason::Superseded => "superseded",
+            RevocationReason::BalanceChanged => "balance_changed",
+            RevocationReason::AttributesChanged => "attributes_changed",
+            RevocationReason::KeyCompromise => "key_compromise",
+            RevocationReason::UserRequest => "user_request",
+            RevocationReason::Fraud => "fraud",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        [
+            RevocationReason::Superseded,
+            RevocationReason::BalanceChanged,
+            RevocationReason::AttributesChanged,
+            RevocationReason::KeyCompromise,
+            RevocationReason::UserRequest,
+            RevocationReason::Fraud,
+        ]
+        .into_iter()
+        .find(|reason| reason.as_str() == value)
+    }
+}
+
+impl std::fmt::Display for RevocationReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Revocation {
+    pub reason: RevocationReason,
+    pub comment: Option<String>,
+    pub revoked_at: DateTime<Utc>,
+}
+
+/// Validade e revogação de uma prova emitida. `status_index` é a posição da
+/// prova na lista de status publicada em `/status-list`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProofStatus {
+    pub state: ProofState,
+    pub valid_from: DateTime<Utc>,
+    pub valid_until: DateTime<Utc>,
+    pub status_index: i64,
+    pub revocation: Option<Revocation>,
+}
+
+/// Prova salva com sua situação atual
+#[derive(Debug, Serialize)]
+pub struct StoredProof {
+    #[serde(flatten)]
+    pub proof: ZkpProof,
+    pub status: ProofStatus,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RevokeProofRequest {
+    pub reason: RevocationReason,
+    pub comment: Option<String>,
+}
+
+/// Lista de status das provas emitidas: o bit `status_index` (a partir do
+/// bit mais significativo do primeiro byte) vale 1 quando a prova foi
+/// revogada ou expirou. `encoded_list` é o bitstring comprimido com gzip, em
+/// base64url sem preenchimento.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StatusList {
+    pub issued_at: DateTime<Utc>,
+    /// Tamanho do bitstring, em bits
+    pub size: usize,
+    pub encoded_list: String,
+}
@@ -0,0 +1,157 @@
+use common::auth::{Auth, Claims};
+use std::collections::BTreeSet;
+use uuid::Uuid;
+
+/// Serviço dos tokens emitidos no login SIWE do serviço de usuários; `sub` é
+/// o ID do usuário
+pub const USER_SERVICE: &str = "user-service";
+
+#[derive(Debug, thiserror::Error)]
+pub enum AccessError {
+    #[error("Configuração de acesso inválida: {0}")]
+    Config(String),
+    #[error("Token ausente ou inválido")]
+    Unauthenticated,
+    #[error("Operação não permitida para o token informado")]
+    Forbidden,
+}
+
+impl AccessError {
+    /// Código de erro exposto na API
+    pub fn code(&self) -> &'static str {
+        match self {
+            AccessError::Config(_) => "config_error",
+            AccessError::Unauthenticated => "unauthorized",
+            AccessError::Forbidden => "forbidden",
+        }
+    }
+}
+
+/// Quem chama a API, pelo token JWT assinado com `JWT_SECRET`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Caller {
+    /// Usuário logado com a carteira (token do serviço de usuários)
+    User(Uuid),
+    /// Serviço administrativo de `ZKP_ADMIN_SERVICES`
+    Admin(String),
+}
+
+/// Acesso à API de provas. Provas, jobs e resultados de um usuário só são
+/// pedidos e vistos com o token do próprio usuário: sem isso, qualquer um
+/// consultaria o saldo ou os atributos de outro repetindo pedidos com limites
+/// ou predicados diferentes. Operações administrativas (whitelist, revogação)
+/// pedem o token de um dos serviços de `ZKP_ADMIN_SERVICES` (separados por
+/// vírgula); sem a variável, ninguém as faz.
+#[derive(Clone)]
+pub struct ApiAccess {
+    auth: Auth,
+    admins: BTreeSet<String>,
+}
+
+impl ApiAccess {
+    pub fn new(secret: &[u8], admins: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            auth: Auth::new(secret),
+            admins: admins
+                .into_iter()
+                .map(|service| service.trim().to_string())
+                .filter(|service| !service.is_empty() && service != USER_SERVICE)
+                .collect(),
+        }
+    }
+
+    pub fn from_env() -> Result<Self, AccessError> {
+        let secret = std::env::var("JWT_SECRET")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .ok_or_else(|| AccessError::Config("JWT_SECRET deve estar definida".to_string()))?;
+        let admins = std::env::var("ZKP_ADMIN_SERVICES").unwrap_or_default();
+
+        Ok(Self::new(secret.as_bytes(), admins.split(',').map(str::to_string)))
+    }
+
+    /// Valida o cabeçalho `Authorization: Bearer <token>` e identifica quem chama
+    pub fn caller(&self, header: Option<&str>) -> Result<Caller, AccessError> {
+        let claims = self.claims(header)?;
+        if claims.service == USER_SERVICE {
+            Ok(Caller::User(claims.sub))
+        } else if self.admins.contains(&claims.service) {
+            Ok(Caller::Admin(claims.service))
+        } else {
+            Err(AccessError::Forbidden)
+        }
+    }
+
+    /// Exige o token do próprio usuário
+    pub fn subject(&self, header: Option<&str>, user_id: Uuid) -> Result<(), AccessError> {
+        match self.caller(header)? {
+            Caller::User(sub) if sub == user_id => Ok(()),
+            _ => Err(AccessError::Forbidden),
+        }
+    }
+
+    /// Exige o token de um serviço administrativo e devolve o seu nome
+    pub fn admin(&self, header: Option<&str>) -> Result<String, AccessError> {
+        match self.caller(header)? {
+            Caller::Admin(service) => Ok(service),
+            Caller::User(_) => Err(AccessError::Forbidden),
+        }
+    }
+
+    fn claims(&self, header: Option<&str>) -> Result<Claims, AccessError> {
+        header
+            .and_then(|header| header.strip_prefix("Bearer "))
+            .and_then(|token| self.auth.verify_token(token).ok())
+            .ok_or(AccessError::Unauthenticated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bearer(auth: &Auth, sub: Uuid, service: &str) -> String {
+        format!("Bearer {}", auth.generate_token(sub, service).unwrap())
+    }
+
+    #[test]
+    fn test_subject_requires_the_users_own_token() {
+        let access = ApiAccess::new(b"segredo", ["compliance".to_string()]);
+        let auth = Auth::new(b"segredo");
+        let (user, other) = (Uuid::new_v4(), Uuid::new_v4());
+
+        assert!(access.subject(Some(&bearer(&auth, user, USER_SERVICE)), user).is_ok());
+        assert!(matches!(
+            access.subject(Some(&bearer(&auth, other, USER_SERVICE)), user),
+            Err(AccessError::Forbidden)
+        ));
+        // Nem um serviço administrativo pede provas em nome do usuário
+        assert!(matches!(
+            access.subject(Some(&bearer(&auth, user, "compliance")), user),
+            Err(AccessError::Forbidden)
+        ));
+        assert!(matches!(access.subject(None, user), Err(AccessError::Unauthenticated)));
+        let forged = bearer(&Auth::new(b"outro"), user, USER_SERVICE);
+        assert!(matches!(access.subject(Some(&forged), user), Err(AccessError::Unauthenticated)));
+    }
+
+    #[test]
+    fn test_admin_requires_a_configured_service() {
+        let access = ApiAccess::new(b"segredo", ["compliance".to_string(), USER_SERVICE.to_string()]);
+        let auth = Auth::new(b"segredo");
+
+        assert_eq!(
+            access.admin(Some(&bearer(&auth, Uuid::nil(), "compliance"))).unwrap(),
+            "compliance"
+        );
+        assert!(matches!(
+            access.admin(Some(&bearer(&auth, Uuid::nil(), "ai"))),
+            Err(AccessError::Forbidden)
+        ));
+        // Usuários nunca são administradores, mesmo listados
+        assert!(matches!(
+            access.admin(Some(&bearer(&auth, Uuid::new_v4(), USER_SERVICE))),
+            Err(AccessError::Forbidden)
+        ));
+    }
+}
@@ -0,0 +1,865 @@
+use chrono::{Duration as ChronoDuration, Utc};
+use common::auth::Auth;
+use common::models::{SignerTransaction, SubmissionStatus};
+use ethers::{
+    abi::{self, Token},
+    contract::{abigen, ContractCall, ContractError},
+    middleware::SignerMiddleware,
+    providers::{Http, Middleware, Provider},
+    signers::{LocalWallet, Signer},
+    types::{Address, Bytes, H256, U256, U64},
+    utils::keccak256,
+};
+use ff::PrimeField;
+use log::{error, info, warn};
+use serde::Serialize;
+use sqlx::PgPool;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::models::{AnchorStatus, ProofAnchor};
+use crate::services::parse_public_inputs;
+
+/// Intervalo entre as passadas do worker de ancoragem
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Provas tratadas por passada, em cada etapa
+const BATCH_SIZE: i64 = 20;
+/// Confirmações exigidas quando `ANCHOR_CONFIRMATIONS` não está definida
+const DEFAULT_CONFIRMATIONS: u64 = 3;
+/// Tentativas de submissão quando `ANCHOR_MAX_ATTEMPTS` não está definida
+const DEFAULT_MAX_ATTEMPTS: i32 = 5;
+/// Espera antes de dar por descartada uma transação que o nó não conhece mais
+const DROP_GRACE_SECONDS: i64 = 120;
+/// Espera pela mineração da aprovação de ACME antes de submeter a prova
+const APPROVAL_TIMEOUT: Duration = Duration::from_secs(300);
+
+abigen!(
+    ZkProofRegistry,
+    r#"[
+        function submitProof(bytes32 proofHash)
+        function getDiscountedCost(address subwallet) view returns (uint256)
+        function acmeToken() view returns (address)
+        event ProofSubmitted(bytes32 indexed proofId, address indexed submitter)
+    ]"#;
+
+    AcmeToken,
+    r#"[
+        function allowance(address owner, address spender) view returns (uint256)
+        function approve(address spender, uint256 amount) returns (bool)
+    ]"#
+);
+
+type AnchorClient = SignerMiddleware<Provider<Http>, LocalWallet>;
+
+#[derive(Debug, Error)]
+pub enum AnchorError {
+    #[error("Prova {0} não encontrada")]
+    NotFound(Uuid),
+    #[error("Configuração de ancoragem inválida: {0}")]
+    Config(String),
+    #[error("Erro na rede: {0}")]
+    Chain(String),
+    #[error("Prova armazenada inválida: {0}")]
+    InvalidProof(String),
+    #[error("Estado de ancoragem desconhecido: {0}")]
+    UnknownStatus(String),
+    #[error("Erro de banco de dados: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl AnchorError {
+    /// Código de erro exposto na API
+    pub fn code(&self) -> &'static str {
+        match self {
+            AnchorError::NotFound(_) => "not_found",
+            _ => "internal_server_error",
+        }
+    }
+}
+
+/// Quem assina as transações de ancoragem. A conta precisa ser uma subwallet
+/// ativa com ACME.
+#[derive(Clone)]
+pub enum AnchorSigner {
+    /// Chave privada local (`ANCHOR_PRIVATE_KEY`)
+    PrivateKey(String),
+    /// Signer do blockchain-service (`ANCHOR_SUBMITTER_URL`), que gere nonce,
+    /// taxas e substituições; a conta `ANCHOR_ACCOUNT` fica no keystore dele
+    /// e os pedidos levam um token assinado com `JWT_SECRET`
+    Submitter {
+        url: String,
+        jwt_secret: String,
+        account: Address,
+    },
+}
+
+/// Conta e contrato usados na ancoragem. Sem `ANCHOR_REGISTRY_ADDRESS` a
+/// ancoragem fica desligada; as provas continuam `pending` até ser ligada.
+#[derive(Clone)]
+pub struct AnchorConfig {
+    pub rpc_url: String,
+    pub signer: AnchorSigner,
+    pub registry: Address,
+    pub confirmations: u64,
+    pub max_attempts: i32,
+    /// Bloco do deploy do registro, início da busca por `ProofSubmitted`.
+    /// Obrigatório: a partir do bloco 0 o `eth_getLogs` percorre a rede
+    /// inteira e os nós públicos recusam o intervalo.
+    pub from_block: u64,
+}
+
+impl AnchorConfig {
+    pub fn from_env() -> Result<Option<Self>, AnchorError> {
+        // Variáveis vazias (ex.: repassadas pelo docker-compose) valem como ausentes
+        let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+
+        let registry = match var("ANCHOR_REGISTRY_ADDRESS") {
+            Some(address) => address
+                .parse()
+                .map_err(|_| AnchorError::Config(format!("ANCHOR_REGISTRY_ADDRESS inválido: {}", address)))?,
+            None => return Ok(None),
+        };
+        let required = |name: &str| var(name).ok_or_else(|| AnchorError::Config(format!("{} deve estar definida", name)));
+        let number = |name: &str, default: u64| match var(name) {
+            Some(value) => value
+                .parse::<u64>()
+                .map_err(|_| AnchorError::Config(format!("{} deve ser um número válido", name))),
+            None => Ok(default),
+        };
+
+        let signer = match var("ANCHOR_SUBMITTER_URL") {
+            Some(url) => AnchorSigner::Submitter {
+                url: url.trim_end_matches('/').to_string(),
+                jwt_secret: required("JWT_SECRET")?,
+                account: required("ANCHOR_ACCOUNT")?
+                    .parse()
+                    .map_err(|_| AnchorError::Config("ANCHOR_ACCOUNT inválida".to_string()))?,
+            },
+            None => AnchorSigner::PrivateKey(required("ANCHOR_PRIVATE_KEY")?),
+        };
+
+        Ok(Some(Self {
+            rpc_url: required("ANCHOR_RPC_URL")?,
+            signer,
+            registry,
+            confirmations: number("ANCHOR_CONFIRMATIONS", DEFAULT_CONFIRMATIONS)?.max(1),
+            max_attempts: number("ANCHOR_MAX_ATTEMPTS", DEFAULT_MAX_ATTEMPTS as u64)?.clamp(1, i32::MAX as u64) as i32,
+            from_block: required("ANCHOR_FROM_BLOCK")?
+                .parse()
+                .map_err(|_| AnchorError::Config("ANCHOR_FROM_BLOCK deve ser um número válido".to_string()))?,
+        }))
+    }
+}
+
+/// Hash ancorado de uma prova: `keccak256(abi.encode(proof_data, inputs))`,
+/// com `proof_data` em `bytes` e as entradas públicas em `uint256[]`, para
+/// que qualquer um o recalcule (inclusive em Solidity) a partir da exportação.
+pub fn anchor_hash(proof_data: &[u8], public_inputs: &[String]) -> Result<H256, AnchorError> {
+    let inputs = parse_public_inputs(public_inputs)
+        .map_err(|e| AnchorError::InvalidProof(e.to_string()))?
+        .iter()
+        .map(|input| Token::Uint(U256::from_little_endian(&input.to_repr())))
+        .collect();
+
+    Ok(H256(keccak256(abi::encode(&[
+        Token::Bytes(proof_data.to_vec()),
+        Token::Array(inputs),
+    ]))))
+}
+
+/// Espera antes da próxima tentativa: 30 s dobrando a cada falha, até 1 h
+pub fn retry_delay(attempts: i32) -> ChronoDuration {
+    let exponent = attempts.saturating_sub(1).clamp(0, 7) as u32;
+    ChronoDuration::seconds((30i64 << exponent).min(3600))
+}
+
+/// Situação on-chain de uma transação de ancoragem
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxState {
+    Pending,
+    /// O nó não conhece mais a transação
+    Dropped,
+    Reverted,
+    Mined { block: u64, confirmations: u64 },
+}
+
+/// Transação de ancoragem enviada; `submission` é o id no signer do
+/// blockchain-service, quando o envio passou por ele
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SentTransaction {
+    pub tx_hash: H256,
+    pub submission: Option<Uuid>,
+}
+
+/// Pedido ao `POST /submissions` do blockchain-service
+#[derive(Serialize)]
+struct SubmissionRequest<'a> {
+    chain_id: u64,
+    from: Address,
+    to: Address,
+    data: &'a Bytes,
+    purpose: &'a str,
+}
+
+/// Por onde as transações saem: assinadas aqui ou pelo blockchain-service
+#[derive(Clone)]
+enum Sender {
+    Wallet(Arc<AnchorClient>),
+    Submitter {
+        http: reqwest::Client,
+        url: String,
+        auth: Auth,
+        account: Address,
+    },
+}
+
+impl Sender {
+    fn address(&self) -> Address {
+        match self {
+            Sender::Wallet(client) => client.address(),
+            Sender::Submitter { account, .. } => *account,
+        }
+    }
+
+    async fn send(
+        &self,
+        chain_id: u64,
+        call: ContractCall<Provider<Http>, impl abi::Detokenize>,
+        to: Address,
+        purpose: &str,
+    ) -> Result<SentTransaction, AnchorError> {
+        let data = call
+            .calldata()
+            .ok_or_else(|| AnchorError::Chain("chamada sem calldata".to_string()))?;
+
+        match self {
+            Sender::Wallet(client) => {
+                let tx = call.tx.clone();
+                let pending = client.send_transaction(tx, None).await.map_err(chain_error)?;
+                Ok(SentTransaction {
+                    tx_hash: *pending,
+                    submission: None,
+                })
+            }
+            Sender::Submitter { http, url, auth, account } => {
+                let request = SubmissionRequest {
+                    chain_id,
+                    from: *account,
+                    to,
+                    data: &data,
+                    purpose,
+                };
+                let response = http
+                    .post(format!("{}/api/v1/blockchain/submissions", url))
+                    .bearer_auth(service_token(auth)?)
+                    .json(&request)
+                    .send()
+                    .await
+                    .map_err(chain_error)?;
+                let transaction = submitter_response(response).await?;
+                Ok(SentTransaction {
+                    tx_hash: parse_tx_hash(&transaction.tx_hash)?,
+                    submission: Some(transaction.id),
+                })
+            }
+        }
+    }
+
+    /// Estado atual de um envio feito pelo blockchain-service; o hash muda
+    /// quando a transação presa é substituída por taxas maiores
+    async fn submission(&self, id: Uuid) -> Result<SignerTransaction, AnchorError> {
+        let Sender::Submitter { http, url, .. } = self else {
+            return Err(AnchorError::Config("envio sem o signer do blockchain-service".to_string()));
+        };
+
+        let response = http
+            .get(format!("{}/api/v1/blockchain/submissions/{}", url, id))
+            .send()
+            .await
+            .map_err(chain_error)?;
+        submitter_response(response).await
+    }
+}
+
+/// Token dos pedidos ao signer, emitido em nome do serviço `zkp`
+fn service_token(auth: &Auth) -> Result<String, AnchorError> {
+    auth.generate_token(Uuid::nil(), "zkp")
+        .map_err(|e| AnchorError::Config(format!("falha ao gerar o token do signer: {}", e)))
+}
+
+async fn submitter_response(response: reqwest::Response) -> Result<SignerTransaction, AnchorError> {
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(AnchorError::Chain(format!("signer respondeu {}: {}", status, body)));
+    }
+
+    response.json().await.map_err(chain_error)
+}
+
+fn parse_tx_hash(value: &str) -> Result<H256, AnchorError> {
+    value
+        .parse()
+        .map_err(|_| AnchorError::InvalidProof(format!("transação inválida: {}", value)))
+}
+
+/// Acesso ao `ZKProofRegistry`: leituras pelo RPC e envios pelo signer
+/// configurado
+#[derive(Clone)]
+pub struct RegistryClient {
+    provider: Provider<Http>,
+    chain_id: u64,
+    sender: Sender,
+    registry: ZkProofRegistry<Provider<Http>>,
+    acme_token: AcmeToken<Provider<Http>>,
+    from_block: u64,
+}
+
+impl RegistryClient {
+    pub async fn connect(config: &AnchorConfig) -> Result<Self, AnchorError> {
+        let provider = Provider::<Http>::try_from(config.rpc_url.as_str())
+            .map_err(|e| AnchorError::Config(format!("ANCHOR_RPC_URL inválida: {}", e)))?;
+        let chain_id = provider.get_chainid().await.map_err(chain_error)?.as_u64();
+
+        let sender = match &config.signer {
+            AnchorSigner::PrivateKey(private_key) => {
+                let wallet = private_key
+                    .parse::<LocalWallet>()
+                    .map_err(|_| AnchorError::Config("ANCHOR_PRIVATE_KEY inválida".to_string()))?
+                    .with_chain_id(chain_id);
+                Sender::Wallet(Arc::new(SignerMiddleware::new(provider.clone(), wallet)))
+            }
+            AnchorSigner::Submitter { url, jwt_secret, account } => Sender::Submitter {
+                http: reqwest::Client::new(),
+                url: url.clone(),
+                auth: Auth::new(jwt_secret.as_bytes()),
+                account: *account,
+            },
+        };
+
+        let client = Arc::new(provider.clone());
+        let registry = ZkProofRegistry::new(config.registry, client.clone());
+        let acme_token = registry.acme_token().call().await.map_err(contract_error)?;
+
+        Ok(Self {
+            acme_token: AcmeToken::new(acme_token, client),
+            registry,
+            provider,
+            chain_id,
+            sender,
+            from_block: config.from_block,
+        })
+    }
+
+    pub fn signer(&self) -> Address {
+        self.sender.address()
+    }
+
+    /// Transação e bloco do `ProofSubmitted` de `hash`, se já está registrado
+    pub async fn find_submission(&self, hash: H256) -> Result<Option<(H256, u64)>, AnchorError> {
+        let events = self
+            .registry
+            .proof_submitted_filter()
+            .topic1(hash)
+            .from_block(self.from_block)
+            .query_with_meta()
+            .await
+            .map_err(contract_error)?;
+
+        Ok(events
+            .first()
+            .map(|(_, meta)| (meta.transaction_hash, meta.block_number.as_u64())))
+    }
+
+    /// Envia `submitProof(hash)` sem esperar a mineração; aprova antes o
+    /// gasto de ACME pelo registro quando a permissão não cobre o custo
+    pub async fn submit(&self, hash: H256) -> Result<SentTransaction, AnchorError> {
+        self.ensure_allowance().await?;
+
+        let call = self.registry.submit_proof(hash.to_fixed_bytes()).from(self.signer());
+        self.sender
+            .send(self.chain_id, call, self.registry.address(), "anchor")
+            .await
+    }
+
+    async fn ensure_allowance(&self) -> Result<(), AnchorError> {
+        let signer = self.signer();
+        let spender = self.registry.address();
+        let cost = self.registry.get_discounted_cost(signer).call().await.map_err(contract_error)?;
+        let allowance = self.acme_token.allowance(signer, spender).call().await.map_err(contract_error)?;
+        if allowance >= cost {
+            return Ok(());
+        }
+
+        info!("Aprovando o gasto de ACME pelo ZKProofRegistry {:?}", spender);
+        let call = self.acme_token.approve(spender, U256::MAX).from(signer);
+        let mut sent = self
+            .sender
+            .send(self.chain_id, call, self.acme_token.address(), "anchor-approval")
+            .await?;
+
+        let deadline = tokio::time::Instant::now() + APPROVAL_TIMEOUT;
+        while tokio::time::Instant::now() < deadline {
+            sent = self
+                .current(sent)
+                .await?
+                .ok_or_else(|| AnchorError::Chain("aprovação de ACME descartada pelo signer".to_string()))?;
+            match self.transaction_state(sent.tx_hash).await? {
+                TxState::Mined { .. } => return Ok(()),
+                TxState::Reverted => break,
+                TxState::Pending | TxState::Dropped => tokio::time::sleep(Duration::from_secs(1)).await,
+            }
+        }
+
+        Err(AnchorError::Chain("aprovação de ACME não confirmada".to_string()))
+    }
+
+    /// Hash atual da transação: o signer do blockchain-service pode tê-la
+    /// substituído por outra de mesmo nonce. `None` quando ele desistiu dela
+    /// (o nonce foi ocupado por outra transação).
+    pub async fn current(&self, sent: SentTransaction) -> Result<Option<SentTransaction>, AnchorError> {
+        let Some(id) = sent.submission else {
+            return Ok(Some(sent));
+        };
+
+        let transaction = self.sender.submission(id).await?;
+        if transaction.status == SubmissionStatus::Failed {
+            warn!(
+                "Envio {} abandonado pelo signer: {}",
+                id,
+                transaction.failure_reason.unwrap_or_default()
+            );
+            return Ok(None);
+        }
+
+        Ok(Some(SentTransaction {
+            tx_hash: parse_tx_hash(&transaction.tx_hash)?,
+            submission: Some(id),
+        }))
+    }
+
+    pub async fn transaction_state(&self, tx_hash: H256) -> Result<TxState, AnchorError> {
+        let provider = &self.provider;
+
+        match provider.get_transaction_receipt(tx_hash).await.map_err(chain_error)? {
+            Some(receipt) if receipt.status == Some(U64::zero()) => Ok(TxState::Reverted),
+            Some(receipt) => match receipt.block_number {
+                Some(block) => {
+                    let latest = provider.get_block_number().await.map_err(chain_error)?;
+                    Ok(TxState::Mined {
+                        block: block.as_u64(),
+                        confirmations: latest.as_u64().saturating_sub(block.as_u64()) + 1,
+                    })
+                }
+                None => Ok(TxState::Pending),
+            },
+            None => match provider.get_transaction(tx_hash).await.map_err(chain_error)? {
+                Some(_) => Ok(TxState::Pending),
+                None => Ok(TxState::Dropped),
+            },
+        }
+    }
+}
+
+fn chain_error(e: impl std::fmt::Display) -> AnchorError {
+    AnchorError::Chain(e.to_string())
+}
+
+fn contract_error<M: Middleware>(e: ContractError<M>) -> AnchorError {
+    match e.decode_revert::<String>() {
+        Some(reason) => AnchorError::Chain(format!("transação revertida: {}", reason)),
+        None => chain_error(e),
+    }
+}
+
+/// Ancora no `ZKProofRegistry` o `anchor_hash` de cada prova salva em
+/// `zkp_proofs`, acompanhando a transação até `confirmations` blocos. Falhas
+/// (envio recusado, transação revertida ou descartada) voltam a ser tentadas
+/// com espera crescente, até `max_attempts` submissões.
+///
+/// Antes de cada submissão o registro é consultado, para que uma transação
+/// antiga minerada depois de dada por perdida não seja paga duas vezes. As
+/// transações saem de um único signer: deve haver uma única instância. Com o
+/// signer do blockchain-service, nonce, taxas e substituições ficam com ele.
+#[derive(Clone)]
+pub struct ProofAnchorer {
+    pool: PgPool,
+    chain: RegistryClient,
+    confirmations: u64,
+    max_attempts: i32,
+}
+
+impl ProofAnchorer {
+    pub async fn connect(pool: &PgPool, config: &AnchorConfig) -> Result<Self, AnchorError> {
+        Ok(Self {
+            pool: pool.clone(),
+            chain: RegistryClient::connect(config).await?,
+            confirmations: config.confirmations,
+            max_attempts: config.max_attempts,
+        })
+    }
+
+    pub fn start(&self) {
+        let anchorer = self.clone();
+        tokio::spawn(async move {
+            loop {
+                if let Err(e) = anchorer.run_once().await {
+                    error!("Falha na passada de ancoragem: {}", e);
+                }
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        });
+
+        info!("Ancoragem de provas iniciada com o signer {:?}", self.chain.signer());
+    }
+
+    /// Uma passada: confirma as transações enviadas e submete as pendentes
+    pub async fn run_once(&self) -> Result<(), AnchorError> {
+        self.confirm_submitted().await?;
+        self.submit_pending().await
+    }
+
+    async fn submit_pending(&self) -> Result<(), AnchorError> {
+        let proofs = sqlx::query!(
+            "SELECT id, proof_data, public_inputs, anchor_attempts FROM zkp_proofs \
+            WHERE anchor_status = 'pending' OR (anchor_status = 'failed' AND anchor_retry_at <= CURRENT_TIMESTAMP) \
+            ORDER BY created_at LIMIT $1",
+            BATCH_SIZE
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        for proof in proofs {
+            let attempts = proof.anchor_attempts + 1;
+            let hash = match anchor_hash(&proof.proof_data, &proof.public_inputs) {
+                Ok(hash) => hash,
+                Err(e) => {
+                    // Tentar de novo não muda os dados salvos
+                    warn!("Prova {} não pode ser ancorada: {}", proof.id, e);
+                    self.mark_failed(proof.id, attempts, &e, false).await?;
+                    continue;
+                }
+            };
+
+            let submitted = match self.chain.find_submission(hash).await {
+                Ok(Some((tx_hash, _))) => {
+                    info!("Prova {} já estava registrada na transação {:?}", proof.id, tx_hash);
+                    Ok(SentTransaction {
+                        tx_hash,
+                        submission: None,
+                    })
+                }
+                Ok(None) => self.chain.submit(hash).await,
+                Err(e) => Err(e),
+            };
+
+            match submitted {
+                Ok(sent) => {
+                    sqlx::query!(
+                        "UPDATE zkp_proofs SET anchor_hash = $2, anchor_status = 'submitted', anchor_tx_hash = $3, \
+                        anchor_submission_id = $4, anchor_attempts = $5, anchor_error = NULL, \
+                        anchor_submitted_at = CURRENT_TIMESTAMP, anchor_retry_at = NULL, updated_at = CURRENT_TIMESTAMP \
+                        WHERE id = $1",
+                        proof.id,
+                        format!("{:?}", hash),
+                        format!("{:?}", sent.tx_hash),
+                        sent.submission,
+                        attempts
+                    )
+                    .execute(&self.pool)
+                    .await?;
+                    info!("Prova {} submetida na transação {:?}", proof.id, sent.tx_hash);
+                }
+                Err(e) => {
+                    warn!("Falha ao submeter a prova {} (tentativa {}): {}", proof.id, attempts, e);
+                    self.mark_failed(proof.id, attempts, &e, true).await?;
+                    // Falhas de rede ou do signer (sem ACME, subwallet inativa)
+                    // se repetiriam nas próximas provas; elas esperam a próxima passada
+                    break;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn confirm_submitted(&self) -> Result<(), AnchorError> {
+        let proofs = sqlx::query!(
+            "SELECT id, anchor_tx_hash AS \"anchor_tx_hash!\", anchor_submission_id, anchor_attempts, \
+            anchor_submitted_at AS \"anchor_submitted_at!\" FROM zkp_proofs \
+            WHERE anchor_status = 'submitted' ORDER BY anchor_submitted_at LIMIT $1",
+            BATCH_SIZE
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        for proof in proofs {
+            let sent = SentTransaction {
+                tx_hash: parse_tx_hash(&proof.anchor_tx_hash)?,
+                submission: proof.anchor_submission_id,
+            };
+
+            let tx_hash = match self.chain.current(sent).await {
+                Ok(Some(current)) => current.tx_hash,
+                Ok(None) => {
+                    let e = AnchorError::Chain(format!("envio {:?} abandonado pelo signer", sent.submission));
+                    self.mark_failed(proof.id, proof.anchor_attempts, &e, true).await?;
+                    continue;
+                }
+                Err(e) => {
+                    warn!("Falha ao consultar o envio {:?}: {}", sent.submission, e);
+                    continue;
+                }
+            };
+            if tx_hash != sent.tx_hash {
+                info!("Transação da prova {} substituída por {:?}", proof.id, tx_hash);
+                sqlx::query!(
+                    "UPDATE zkp_proofs SET anchor_tx_hash = $2, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+                    proof.id,
+                    format!("{:?}", tx_hash)
+                )
+                .execute(&self.pool)
+                .await?;
+            }
+
+            let state = match self.chain.transaction_state(tx_hash).await {
+                Ok(state) => state,
+                Err(e) => {
+                    warn!("Falha ao consultar a transação {:?}: {}", tx_hash, e);
+                    continue;
+                }
+            };
+
+            match state {
+                TxState::Mined { block, confirmations } if confirmations >= self.confirmations => {
+                    sqlx::query!(
+                        "UPDATE zkp_proofs SET anchor_status = 'confirmed', anchor_block = $2, \
+                        anchored_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+                        proof.id,
+                        block as i64
+                    )
+                    .execute(&self.pool)
+                    .await?;
+                    info!("Prova {} ancorada no bloco {}", proof.id, block);
+                }
+                TxState::Reverted => {
+                    let e = AnchorError::Chain(format!("transação {:?} revertida", tx_hash));
+                    self.mark_failed(proof.id, proof.anchor_attempts, &e, true).await?;
+                }
+                // O signer do blockchain-service reenvia as transações descartadas
+                TxState::Dropped
+                    if sent.submission.is_none()
+                        && Utc::now() - proof.anchor_submitted_at > ChronoDuration::seconds(DROP_GRACE_SECONDS) =>
+                {
+                    let e = AnchorError::Chain(format!("transação {:?} descartada", tx_hash));
+                    self.mark_failed(proof.id, proof.anchor_attempts, &e, true).await?;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Registra a falha; com `retry`, agenda a próxima tentativa enquanto
+    /// houver tentativas
+    async fn mark_failed(&self, proof_id: Uuid, attempts: i32, e: &AnchorError, retry: bool) -> Result<(), AnchorError> {
+        let retry_at = (retry && attempts < self.max_attempts).then(|| Utc::now() + retry_delay(attempts));
+
+        sqlx::query!(
+            "UPDATE zkp_proofs SET anchor_status = 'failed', anchor_attempts = $2, anchor_error = $3, \
+            anchor_retry_at = $4, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+            proof_id,
+            attempts,
+            e.to_string(),
+            retry_at
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if retry_at.is_none() {
+            error!("Ancoragem da prova {} abandonada após {} tentativas", proof_id, attempts);
+        }
+
+        Ok(())
+    }
+}
+
+/// Estado da ancoragem de uma prova salva
+pub async fn proof_anchor(pool: &PgPool, proof_id: Uuid) -> Result<ProofAnchor, AnchorError> {
+    let row = sqlx::query!(
+        "SELECT id, anchor_status, anchor_hash, anchor_tx_hash, anchor_block, anchor_attempts, anchor_error, \
+        anchor_submitted_at, anchor_retry_at, anchored_at FROM zkp_proofs WHERE id = $1",
+        proof_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or(AnchorError::NotFound(proof_id))?;
+
+    Ok(ProofAnchor {
+        proof_id: row.id,
+        status: AnchorStatus::parse(&row.anchor_status).ok_or(AnchorError::UnknownStatus(row.anchor_status))?,
+        anchor_hash: row.anchor_hash,
+        tx_hash: row.anchor_tx_hash,
+        block_number: row.anchor_block,
+        attempts: row.anchor_attempts,
+        error: row.anchor_error,
+        submitted_at: row.anchor_submitted_at,
+        retry_at: row.anchor_retry_at,
+        anchored_at: row.anchored_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::RngCore;
+
+    #[test]
+    fn test_anchor_hash_matches_abi_encode() {
+        let proof_data = [1u8, 2, 3];
+        let inputs = vec!["0x05".to_string(), "7".to_string()];
+
+        // abi.encode(bytes, uint256[]): dois offsets, bytes com tamanho e
+        // preenchimento, array com tamanho e elementos
+        let mut encoded = Vec::new();
+        for value in [0x40u64, 0x80, 3] {
+            encoded.extend_from_slice(&word(value));
+        }
+        let mut data = [0u8; 32];
+        data[..3].copy_from_slice(&proof_data);
+        encoded.extend_from_slice(&data);
+        for value in [2u64, 5, 7] {
+            encoded.extend_from_slice(&word(value));
+        }
+
+        let hash = anchor_hash(&proof_data, &inputs).unwrap();
+        assert_eq!(hash, H256(keccak256(&encoded)));
+
+        // Qualquer mudança na prova ou nas entradas muda o hash
+        assert_ne!(anchor_hash(&[1, 2, 4], &inputs).unwrap(), hash);
+        assert_ne!(anchor_hash(&proof_data, &inputs[..1]).unwrap(), hash);
+        assert!(matches!(
+            anchor_hash(&proof_data, &["0xzz".to_string()]),
+            Err(AnchorError::InvalidProof(_))
+        ));
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        assert_eq!(retry_delay(1), ChronoDuration::seconds(30));
+        assert_eq!(retry_delay(3), ChronoDuration::seconds(120));
+        assert_eq!(retry_delay(20), ChronoDuration::hours(1));
+    }
+
+    fn word(value: u64) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        U256::from(value).to_big_endian(&mut bytes);
+        bytes
+    }
+
+    abigen!(
+        RegistryLinks,
+        r#"[
+            function subwalletRegistry() view returns (address)
+        ]"#;
+
+        DevSubwalletRegistry,
+        r#"[
+            function registerSubwallet(address wallet, string name)
+            function isValidSubwallet(address wallet) view returns (bool)
+        ]"#;
+
+        DevAcmeToken,
+        r#"[
+            function mint(address to, uint256 amount)
+        ]"#
+    );
+
+    /// Primeira conta padrão do anvil e do `hardhat node`, dona dos contratos
+    /// implantados por `scripts/deploy.ts`
+    const DEV_PRIVATE_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    fn dev_chain_config() -> AnchorConfig {
+        AnchorConfig {
+            rpc_url: std::env::var("ANCHOR_TEST_RPC_URL").unwrap_or_else(|_| "http://localhost:8545".to_string()),
+            signer: AnchorSigner::PrivateKey(DEV_PRIVATE_KEY.to_string()),
+            registry: std::env::var("ANCHOR_TEST_REGISTRY")
+                .expect("ANCHOR_TEST_REGISTRY deve ter o endereço do ZKProofRegistry")
+                .parse()
+                .unwrap(),
+            confirmations: 2,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            from_block: 0,
+        }
+    }
+
+    /// Registra o signer como subwallet e lhe dá ACME para pagar o registro
+    async fn fund_signer(chain: &RegistryClient) {
+        let signer = chain.signer();
+        let wallet = DEV_PRIVATE_KEY.parse::<LocalWallet>().unwrap().with_chain_id(chain.chain_id);
+        let client = Arc::new(SignerMiddleware::new(chain.provider.clone(), wallet));
+        let links = RegistryLinks::new(chain.registry.address(), client.clone());
+        let subwallets = DevSubwalletRegistry::new(links.subwallet_registry().call().await.unwrap(), client.clone());
+
+        if !subwallets.is_valid_subwallet(signer).call().await.unwrap() {
+            let call = subwallets.register_subwallet(signer, "anchor".to_string());
+            call.send().await.unwrap().await.unwrap();
+        }
+
+        let token = DevAcmeToken::new(chain.acme_token.address(), client);
+        let call = token.mint(signer, U256::exp10(18));
+        call.send().await.unwrap().await.unwrap();
+    }
+
+    async fn wait_mined(chain: &RegistryClient, tx_hash: H256) -> TxState {
+        for _ in 0..50 {
+            match chain.transaction_state(tx_hash).await.unwrap() {
+                TxState::Pending => tokio::time::sleep(Duration::from_millis(100)).await,
+                state => return state,
+            }
+        }
+        panic!("transação {:?} não minerada", tx_hash);
+    }
+
+    /// Contra um nó local (`anvil` ou `npx hardhat node`) com os contratos
+    /// implantados por `npx hardhat run scripts/deploy.ts --network localhost`:
+    ///
+    /// `ANCHOR_TEST_REGISTRY=0x... cargo test -p zkp-service anchoring -- --ignored`
+    #[tokio::test]
+    #[ignore]
+    async fn test_anchors_on_dev_chain() {
+        let config = dev_chain_config();
+        let chain = RegistryClient::connect(&config).await.unwrap();
+        fund_signer(&chain).await;
+
+        let mut proof_data = [0u8; 64];
+        rand::thread_rng().fill_bytes(&mut proof_data);
+        let hash = anchor_hash(&proof_data, &["1".to_string()]).unwrap();
+        assert_eq!(chain.find_submission(hash).await.unwrap(), None);
+
+        let sent = chain.submit(hash).await.unwrap();
+        assert_eq!(sent.submission, None);
+        let tx_hash = sent.tx_hash;
+        let TxState::Mined { block, confirmations } = wait_mined(&chain, tx_hash).await else {
+            panic!("transação de ancoragem falhou");
+        };
+        assert!(confirmations < config.confirmations);
+
+        // Um bloco a mais completa as confirmações exigidas
+        let _: serde_json::Value = chain.provider.request("evm_mine", ()).await.unwrap();
+        assert_eq!(
+            chain.transaction_state(tx_hash).await.unwrap(),
+            TxState::Mined {
+                block,
+                confirmations: config.confirmations,
+            }
+        );
+
+        // O evento recupera a transação, e o contrato recusa uma segunda submissão
+        assert_eq!(chain.find_submission(hash).await.unwrap(), Some((tx_hash, block)));
+        assert!(matches!(chain.submit(hash).await, Err(AnchorError::Chain(_))));
+    }
+}
@@ -0,0 +1,205 @@
+use bls12_381::Scalar;
+use chrono::{NaiveDate, Utc};
+use ff::Field;
+use rand::thread_rng;
+use sqlx::PgPool;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::circuits::{
+    commit_attributes, country_code, date_to_number, registry, AttributeCircuit, AttributePredicates, Attributes,
+    ATTRIBUTES_CIRCUIT,
+};
+use crate::models::AttributePredicate;
+use crate::services::{ParameterStore, ProofError, ZkpService};
+use common::models::{User, ZkpProof};
+
+#[derive(Debug, Error)]
+pub enum AttributeError {
+    #[error("Atributos atestados não encontrados para o usuário {0}")]
+    NotFound(Uuid),
+    #[error("Predicado inválido: {0}")]
+    InvalidPredicate(String),
+    #[error("Os atributos do usuário não satisfazem os predicados")]
+    NotSatisfied,
+    #[error("Atributos atestados inválidos: {0}")]
+    InvalidAttributes(String),
+    #[error(transparent)]
+    Proof(#[from] ProofError),
+    #[error("Erro de banco de dados: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl AttributeError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            AttributeError::NotFound(_) => "not_found",
+            AttributeError::InvalidPredicate(_) => "invalid_predicate",
+            AttributeError::NotSatisfied => "predicate_not_satisfied",
+            AttributeError::Proof(e) => e.code(),
+            AttributeError::InvalidAttributes(_) | AttributeError::Database(_) => "internal_server_error",
+        }
+    }
+}
+
+/// Provas de predicados sobre os atributos atestados do usuário (ver
+/// `AttributeCircuit`). Os atributos ficam em `user_attributes`, mantidos pelo
+/// serviço de usuários; o parceiro recebe só os predicados e o compromisso.
+pub struct AttributeService {
+    pool: PgPool,
+    zkp: ZkpService,
+}
+
+impl AttributeService {
+    pub fn new(pool: &PgPool, parameters: &ParameterStore) -> Self {
+        Self {
+            pool: pool.clone(),
+            zkp: ZkpService::new(pool, parameters),
+        }
+    }
+
+    /// Prova que os atributos do usuário satisfazem `predicates` na data de
+    /// hoje (UTC), valendo uma única vez no contexto de aplicação `context`
+    pub async fn generate_proof(
+        &self,
+        user: &User,
+        predicates: &[AttributePredicate],
+        context: &str,
+    ) -> Result<ZkpProof, AttributeError> {
+        let definition = registry().for_proving(ATTRIBUTES_CIRCUIT, None).map_err(ProofError::from)?;
+        let predicates = resolve_predicates(predicates, date_to_number(Utc::now().date_naive()))?;
+        let attributes = self.attested_attributes(user.id).await?;
+
+        // O circuito recusaria a testemunha; melhor falhar antes de provar
+        if !predicates.is_satisfied_by(&attributes) {
+            return Err(AttributeError::NotSatisfied);
+        }
+
+        // Blinding novo a cada prova, para o compromisso não ligar as provas
+        // do usuário em contextos diferentes
+        let blinding = Scalar::random(&mut thread_rng());
+        let circuit = AttributeCircuit {
+            attributes: Some(attributes),
+            blinding: Some(blinding),
+            predicates: Some(predicates),
+        };
+        let statement = AttributeCircuit::public_inputs(&predicates, commit_attributes(&attributes, blinding));
+
+        let (circuit, public_inputs, nullifier) = self
+            .zkp
+            .nullified(user.id, definition, context, circuit, statement)
+            .await?;
+
+        Ok(self
+            .zkp
+            .prove(user.id, definition, circuit, &public_inputs, &nullifier)
+            .await?)
+    }
+
+    /// Lê os atributos atestados do usuário
+    async fn attested_attributes(&self, user_id: Uuid) -> Result<Attributes, AttributeError> {
+        let row = sqlx::query!(
+            "SELECT birth_date, country, kyc_tier FROM user_attributes WHERE user_id = $1",
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(AttributeError::NotFound(user_id))?;
+
+        to_attributes(row.birth_date, &row.country, row.kyc_tier)
+    }
+}
+
+fn to_attributes(birth_date: NaiveDate, country: &str, kyc_tier: i16) -> Result<Attributes, AttributeError> {
+    Ok(Attributes {
+        birth_date: date_to_number(birth_date),
+        country: country_code(country)
+            .ok_or_else(|| AttributeError::InvalidAttributes(format!("país {}", country)))?,
+        kyc_tier: u8::try_from(kyc_tier)
+            .map_err(|_| AttributeError::InvalidAttributes(format!("nível de KYC {}", kyc_tier)))?,
+    })
+}
+
+/// Converte os predicados pedidos em entradas do circuito: ao menos um, no
+/// máximo um de cada tipo, com valores que de fato restringem algo
+fn resolve_predicates(
+    predicates: &[AttributePredicate],
+    reference_date: u32,
+) -> Result<AttributePredicates, AttributeError> {
+    if predicates.is_empty() {
+        return Err(AttributeError::InvalidPredicate("informe ao menos um predicado".to_string()));
+    }
+
+    let mut resolved = AttributePredicates {
+        reference_date,
+        ..Default::default()
+    };
+
+    for predicate in predicates {
+        let (name, value, previous) = match predicate {
+            AttributePredicate::AgeAtLeast { years } => (
+                "age_at_least",
+                *years as u16,
+                std::mem::replace(&mut resolved.min_age, *years) as u16,
+            ),
+            AttributePredicate::ResidentOf { country } => {
+                let code = country_code(country)
+                    .ok_or_else(|| AttributeError::InvalidPredicate(format!("país inválido: {}", country)))?;
+                ("resident_of", code, std::mem::replace(&mut resolved.country, code))
+            }
+            AttributePredicate::KycTierAtLeast { tier } => (
+                "kyc_tier_at_least",
+                *tier as u16,
+                std::mem::replace(&mut resolved.min_kyc_tier, *tier) as u16,
+            ),
+        };
+
+        // Zero é o valor neutro do circuito: não restringiria nada e marca o
+        // predicado ainda não visto
+        if value == 0 {
+            return Err(AttributeError::InvalidPredicate(format!("{} deve ser maior que zero", name)));
+        }
+        if previous != 0 {
+            return Err(AttributeError::InvalidPredicate(format!("{} repetido", name)));
+        }
+    }
+
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_predicates() {
+        let predicates = vec![
+            AttributePredicate::AgeAtLeast { years: 18 },
+            AttributePredicate::ResidentOf { country: "BR".to_string() },
+        ];
+        assert_eq!(
+            resolve_predicates(&predicates, 20260301).unwrap(),
+            AttributePredicates {
+                reference_date: 20260301,
+                min_age: 18,
+                country: 0x4252,
+                min_kyc_tier: 0,
+            }
+        );
+
+        for invalid in [
+            vec![],
+            vec![AttributePredicate::KycTierAtLeast { tier: 0 }],
+            vec![AttributePredicate::ResidentOf { country: "Brasil".to_string() }],
+            vec![
+                AttributePredicate::AgeAtLeast { years: 18 },
+                AttributePredicate::AgeAtLeast { years: 21 },
+            ],
+        ] {
+            assert!(matches!(
+                resolve_predicates(&invalid, 20260301),
+                Err(AttributeError::InvalidPredicate(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use bellman::Circuit;
+use log::{info, warn};
+use rand::thread_rng;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use crate::bn254::{Bn254Parameters, Bn254Scalar};
+use crate::services::ParameterStoreError;
+
+/// Equivalente ao `ParameterStore` para o backend BN254: uma chave por versão
+/// de circuito em `zkp_evm_parameters`, com cache em memória
+#[derive(Clone)]
+pub struct EvmParameterStore {
+    pool: PgPool,
+    cache: Arc<RwLock<HashMap<String, Arc<Bn254Parameters>>>>,
+}
+
+impl EvmParameterStore {
+    pub fn new(pool: &PgPool) -> Self {
+        Self {
+            pool: pool.clone(),
+            cache: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Obtém os parâmetros de uma versão já registrada
+    pub async fn get(&self, circuit_version: &str) -> Result<Arc<Bn254Parameters>, ParameterStoreError> {
+        if let Some(params) = self.cache.read().unwrap().get(circuit_version) {
+            return Ok(params.clone());
+        }
+
+        match self.load(circuit_version).await? {
+            Some(params) => Ok(self.cache_insert(params)),
+            None => Err(ParameterStoreError::UnknownCircuitVersion(circuit_version.to_string())),
+        }
+    }
+
+    /// Obtém os parâmetros de uma versão, gerando-os na primeira vez
+    pub async fn get_or_generate<C>(
+        &self,
+        circuit_version: &str,
+        circuit: C,
+    ) -> Result<Arc<Bn254Parameters>, ParameterStoreError>
+    where
+        C: Circuit<Bn254Scalar> + Send + 'static,
+    {
+        match self.get(circuit_version).await {
+            Err(ParameterStoreError::UnknownCircuitVersion(_)) => {}
+            other => return other,
+        }
+
+        info!("Gerando parâmetros BN254 para o circuito {}", circuit_version);
+        let version = circuit_version.to_string();
+        let params = tokio::task::spawn_blocking(move || {
+            Bn254Parameters::generate(&version, circuit, &mut thread_rng())
+        })
+        .await
+        .map_err(|e| ParameterStoreError::InvalidParameters(e.to_string()))??;
+
+        self.store(params).await
+    }
+
+    async fn store(&self, params: Bn254Parameters) -> Result<Arc<Bn254Parameters>, ParameterStoreError> {
+        // Outra instância pode ter registrado a mesma versão ao mesmo tempo;
+        // o que está no banco prevalece
+        sqlx::query!(
+            "INSERT INTO zkp_evm_parameters (circuit_version, fingerprint, parameters) \
+            VALUES ($1, $2, $3) ON CONFLICT (circuit_version) DO NOTHING",
+            params.circuit_version,
+            params.fingerprint,
+            params.write()
+        )
+        .execute(&self.pool)
+        .await?;
+
+        let stored = self.load(&params.circuit_version).await?
+            .ok_or_else(|| ParameterStoreError::UnknownCircuitVersion(params.circuit_version.clone()))?;
+
+        if stored.fingerprint != params.fingerprint {
+            return Err(ParameterStoreError::FingerprintMismatch {
+                version: params.circuit_version,
+                existing: stored.fingerprint,
+            });
+        }
+
+        info!("Parâmetros BN254 do circuito {} registrados ({})", params.circuit_version, params.fingerprint);
+        Ok(self.cache_insert(stored))
+    }
+
+    async fn load(&self, circuit_version: &str) -> Result<Option<Bn254Parameters>, ParameterStoreError> {
+        let row = sqlx::query!(
+            "SELECT fingerprint, parameters FROM zkp_evm_parameters WHERE circuit_version = $1",
+            circuit_version
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let row = match row {
+            Some(row) => row,
+            None => return Ok(None),
+        };
+
+        // A leitura sem validação dos pontos é lenta o bastante para sair do executor
+        let version = circuit_version.to_string();
+        let bytes = row.parameters;
+        let params = tokio::task::spawn_blocking(move || Bn254Parameters::read(&version, &bytes))
+            .await
+            .map_err(|e| ParameterStoreError::InvalidParameters(e.to_string()))??;
+
+        if params.fingerprint != row.fingerprint {
+            warn!("Fingerprint BN254 divergente para o circuito {}", circuit_version);
+            return Err(ParameterStoreError::InvalidParameters(format!(
+                "fingerprint armazenado {} não corresponde à chave {}",
+                row.fingerprint, params.fingerprint
+            )));
+        }
+
+        Ok(Some(params))
+    }
+
+    fn cache_insert(&self, params: Bn254Parameters) -> Arc<Bn254Parameters> {
+        let params = Arc::new(params);
+        self.cache
+            .write()
+            .unwrap()
+            .insert(params.circuit_version.clone(), params.clone());
+        params
+    }
+}
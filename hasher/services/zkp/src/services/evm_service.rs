@@ -0,0 +1,142 @@
+use bellman::Circuit;
+use ff::Field;
+use log::info;
+use rand::thread_rng;
+use sqlx::PgPool;
+use std::error::Error;
+use uuid::Uuid;
+
+use crate::bn254::{
+    create_bn254_proof, export_verifier, proof_calldata, read_bn254_proof, verify_bn254_proof, write_bn254_proof,
+    Bn254Error, Bn254Scalar, EvmCalldata,
+};
+use crate::circuits::{
+    commit_balance, MembershipCircuit, SolvencyCircuit, MEMBERSHIP_CIRCUIT_VERSION, SOLVENCY_CIRCUIT_VERSION,
+};
+use crate::models::{EvmCalldataRequest, EvmProof, ExportFormat, ExportedKey, VerifyingKeyExport};
+use crate::services::{
+    format_scalar, parse_field_elements, verified_balance, EvmParameterStore, ParameterStoreError, ProofError,
+    VerificationError,
+};
+use common::metrics::{Timer, ZKP_GENERATION_TIME};
+
+/// Provas no BN254, verificáveis on-chain pelo contrato exportado para cada
+/// versão de circuito. Os circuitos são os mesmos do backend BLS12-381.
+pub struct EvmService {
+    pool: PgPool,
+    parameters: EvmParameterStore,
+}
+
+impl EvmService {
+    pub fn new(pool: &PgPool, parameters: &EvmParameterStore) -> Self {
+        Self {
+            pool: pool.clone(),
+            parameters: parameters.clone(),
+        }
+    }
+
+    /// Garante chaves BN254 para os circuitos que têm verificador on-chain
+    pub async fn ensure_parameters(&self) -> Result<(), Box<dyn Error>> {
+        self.ensure_circuit(SOLVENCY_CIRCUIT_VERSION, SolvencyCircuit::<Bn254Scalar>::blank()).await?;
+        self.ensure_circuit(MEMBERSHIP_CIRCUIT_VERSION, MembershipCircuit::<Bn254Scalar>::blank()).await?;
+
+        Ok(())
+    }
+
+    async fn ensure_circuit<C>(&self, version: &str, blank: C) -> Result<(), Box<dyn Error>>
+    where
+        C: Circuit<Bn254Scalar> + Send + 'static,
+    {
+        let params = self.parameters.get_or_generate(version, blank).await?;
+
+        info!("Circuito {} pronto no BN254 ({})", version, params.fingerprint);
+        Ok(())
+    }
+
+    /// Código Solidity do verificador da chave registrada para `circuit_version`
+    pub async fn verifier_contract(&self, circuit_version: &str) -> Result<String, ParameterStoreError> {
+        let params = self.parameters.get(circuit_version).await?;
+
+        Ok(export_verifier(params.vk(), circuit_version, &params.fingerprint))
+    }
+
+    /// Chave de verificação BN254 de `circuit_version`, no formato pedido
+    pub async fn export_verifying_key(
+        &self,
+        circuit_version: &str,
+        format: ExportFormat,
+    ) -> Result<VerifyingKeyExport, ParameterStoreError> {
+        let params = self.parameters.get(circuit_version).await?;
+
+        Ok(VerifyingKeyExport {
+            circuit_version: circuit_version.to_string(),
+            key_fingerprint: params.fingerprint.clone(),
+            exported: ExportedKey::new(params.vk(), format),
+        })
+    }
+
+    /// Prova de solvência no BN254, com a calldata para o verificador on-chain.
+    /// A prova não é salva em `zkp_proofs`; seu identificador é o `proof_hash`.
+    pub async fn generate_solvency_proof(&self, user_id: Uuid, threshold: u128) -> Result<EvmProof, ProofError> {
+        let balance = verified_balance(&self.pool, user_id).await?;
+
+        if balance < threshold {
+            return Err(ProofError::InsufficientBalance);
+        }
+
+        // Blinding novo a cada prova, para o compromisso não ligar as provas do usuário
+        let blinding = Bn254Scalar::random(&mut thread_rng());
+        let circuit = SolvencyCircuit {
+            balance: Some(balance),
+            blinding: Some(blinding),
+            threshold: Some(threshold),
+        };
+        let public_inputs = SolvencyCircuit::public_inputs(threshold, commit_balance(balance, blinding));
+
+        let params = self.parameters.get(SOLVENCY_CIRCUIT_VERSION).await?;
+        let key_fingerprint = params.fingerprint.clone();
+
+        let inputs = public_inputs.clone();
+        let (proof, valid) = tokio::task::spawn_blocking(move || {
+            let proof = {
+                let _timer = Timer::new(&ZKP_GENERATION_TIME, vec![params.circuit_version.as_str(), "prove"]);
+                create_bn254_proof(&params, circuit, &mut thread_rng())?
+            };
+            let valid = verify_bn254_proof(&params.pvk, &proof, &inputs)?;
+            Ok::<_, Bn254Error>((proof, valid))
+        })
+        .await
+        .map_err(|e| ProofError::Internal(e.to_string()))??;
+
+        if !valid {
+            return Err(ProofError::Internal("prova gerada não confere com a chave".to_string()));
+        }
+
+        Ok(EvmProof {
+            circuit_version: SOLVENCY_CIRCUIT_VERSION.to_string(),
+            key_fingerprint,
+            proof: write_bn254_proof(&proof),
+            public_inputs: public_inputs.iter().map(format_scalar).collect(),
+            calldata: proof_calldata(&proof, &public_inputs),
+        })
+    }
+
+    /// Calldata de `verifyProof` para uma prova BN254. A prova é conferida antes
+    /// com a chave registrada, para não gastar gas com uma chamada que falharia.
+    pub async fn calldata(&self, request: &EvmCalldataRequest) -> Result<EvmCalldata, VerificationError> {
+        let proof = read_bn254_proof(&request.proof)
+            .map_err(|e| VerificationError::MalformedProof(e.to_string()))?;
+        let public_inputs = parse_field_elements::<Bn254Scalar>(&request.public_inputs)?;
+
+        let params = self.parameters.get(&request.circuit_version).await?;
+
+        match verify_bn254_proof(&params.pvk, &proof, &public_inputs) {
+            Ok(true) => Ok(proof_calldata(&proof, &public_inputs)),
+            Ok(false) => Err(VerificationError::InvalidProof),
+            Err(Bn254Error::WrongInputCount { expected, actual }) => {
+                Err(VerificationError::WrongInputCount { expected, actual })
+            }
+            Err(e) => Err(VerificationError::Internal(e.to_string())),
+        }
+    }
+}
@@ -1,3 +1,25 @@
+mod access;
+mod anchoring;
+mod attributes;
+mod evm_parameter_store;
+mod evm_service;
+mod parameter_store;
+mod profiler;
+mod proof_jobs;
+mod proof_status;
+mod verifier;
+mod whitelist;
 mod zkp_service;
 
-pub use zkp_service::*; 
\ No newline at end of file
+pub use access::*;
+pub use anchoring::*;
+pub use attributes::*;
+pub use evm_parameter_store::*;
+pub use evm_service::*;
+pub use parameter_store::*;
+pub use profiler::*;
+pub use proof_jobs::*;
+pub use proof_status::*;
+pub use verifier::*;
+pub use whitelist::*;
+pub use zkp_service::*;
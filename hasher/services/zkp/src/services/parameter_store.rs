@@ -0,0 +1,261 @@
+use bellman::{
+    groth16::{generate_random_parameters, prepare_verifying_key, Parameters, PreparedVerifyingKey, VerifyingKey},
+    Circuit,
+};
+use bls12_381::{Bls12, Scalar};
+use log::{info, warn};
+use rand::thread_rng;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ParameterStoreError {
+    #[error("Versão de circuito desconhecida: {0}")]
+    UnknownCircuitVersion(String),
+    #[error("Chave de verificação desconhecida: {0}")]
+    UnknownFingerprint(String),
+    #[error("Parâmetros inválidos: {0}")]
+    InvalidParameters(String),
+    #[error("Versão {version} já registrada com outra chave ({existing})")]
+    FingerprintMismatch { version: String, existing: String },
+    #[error("Erro ao gerar parâmetros: {0}")]
+    Synthesis(#[from] bellman::SynthesisError),
+    #[error(transparent)]
+    Bn254(#[from] crate::bn254::Bn254Error),
+    #[error("Erro de banco de dados: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+/// Chaves Groth16 de uma versão de circuito, prontas para provar e verificar
+pub struct CircuitParameters {
+    pub circuit_version: String,
+    pub fingerprint: String,
+    pub params: Parameters<Bls12>,
+    pub pvk: PreparedVerifyingKey<Bls12>,
+}
+
+impl CircuitParameters {
+    fn new(circuit_version: &str, params: Parameters<Bls12>) -> Self {
+        Self {
+            circuit_version: circuit_version.to_string(),
+            fingerprint: fingerprint(&params.vk),
+            pvk: prepare_verifying_key(&params.vk),
+            params,
+        }
+    }
+}
+
+/// Calcula o fingerprint (SHA-256 em hex) da chave de verificação serializada
+pub fn fingerprint(vk: &VerifyingKey<Bls12>) -> String {
+    let mut bytes = Vec::new();
+    vk.write(&mut bytes).expect("escrita em memória não falha");
+    hex::encode(Sha256::digest(&bytes))
+}
+
+/// Armazena os parâmetros Groth16 no Postgres, uma vez por versão de circuito,
+/// e mantém em memória os que já foram carregados
+#[derive(Clone)]
+pub struct ParameterStore {
+    pool: PgPool,
+    cache: Arc<RwLock<HashMap<String, Arc<CircuitParameters>>>>,
+}
+
+impl ParameterStore {
+    pub fn new(pool: &PgPool) -> Self {
+        Self {
+            pool: pool.clone(),
+            cache: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Obtém os parâmetros de uma versão já registrada
+    pub async fn get(&self, circuit_version: &str) -> Result<Arc<CircuitParameters>, ParameterStoreError> {
+        if let Some(params) = self.cache.read().unwrap().get(circuit_version) {
+            return Ok(params.clone());
+        }
+
+        match self.load(circuit_version).await? {
+            Some(params) => Ok(self.cache_insert(params)),
+            None => Err(ParameterStoreError::UnknownCircuitVersion(circuit_version.to_string())),
+        }
+    }
+
+    /// Obtém os parâmetros pela chave de verificação (ex.: a referenciada por uma prova salva)
+    pub async fn get_by_fingerprint(&self, fingerprint: &str) -> Result<Arc<CircuitParameters>, ParameterStoreError> {
+        let cached = self
+            .cache
+            .read()
+            .unwrap()
+            .values()
+            .find(|params| params.fingerprint == fingerprint)
+            .cloned();
+        if let Some(params) = cached {
+            return Ok(params);
+        }
+
+        let version = sqlx::query_scalar!(
+            "SELECT circuit_version FROM zkp_parameters WHERE fingerprint = $1",
+            fingerprint
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| ParameterStoreError::UnknownFingerprint(fingerprint.to_string()))?;
+
+        self.get(&version).await
+    }
+
+    /// Obtém os parâmetros de uma versão, gerando-os na primeira vez
+    pub async fn get_or_generate<C>(
+        &self,
+        circuit_version: &str,
+        circuit: C,
+    ) -> Result<Arc<CircuitParameters>, ParameterStoreError>
+    where
+        C: Circuit<Scalar> + Send + 'static,
+    {
+        match self.get(circuit_version).await {
+            Err(ParameterStoreError::UnknownCircuitVersion(_)) => {}
+            other => return other,
+        }
+
+        info!("Gerando parâmetros para o circuito {}", circuit_version);
+        let params = tokio::task::spawn_blocking(move || {
+            generate_random_parameters::<Bls12, _, _>(circuit, &mut thread_rng())
+        })
+        .await
+        .map_err(|e| ParameterStoreError::InvalidParameters(e.to_string()))??;
+
+        self.store(circuit_version, params).await
+    }
+
+    /// Importa parâmetros gerados externamente (ex.: cerimônia de setup)
+    pub async fn import(
+        &self,
+        circuit_version: &str,
+        bytes: &[u8],
+    ) -> Result<Arc<CircuitParameters>, ParameterStoreError> {
+        let params = Parameters::<Bls12>::read(bytes, true)
+            .map_err(|e| ParameterStoreError::InvalidParameters(e.to_string()))?;
+
+        self.store(circuit_version, params).await
+    }
+
+    /// Importa parâmetros de um arquivo no disco
+    pub async fn import_file(
+        &self,
+        circuit_version: &str,
+        path: &Path,
+    ) -> Result<Arc<CircuitParameters>, ParameterStoreError> {
+        let bytes = tokio::fs::read(path)
+            .await
+            .map_err(|e| ParameterStoreError::InvalidParameters(e.to_string()))?;
+
+        self.import(circuit_version, &bytes).await
+    }
+
+    async fn store(
+        &self,
+        circuit_version: &str,
+        params: Parameters<Bls12>,
+    ) -> Result<Arc<CircuitParameters>, ParameterStoreError> {
+        let params = CircuitParameters::new(circuit_version, params);
+
+        let mut bytes = Vec::new();
+        params.params.write(&mut bytes)
+            .map_err(|e| ParameterStoreError::InvalidParameters(e.to_string()))?;
+
+        // Outra instância pode ter registrado a mesma versão ao mesmo tempo;
+        // o que está no banco prevalece
+        sqlx::query!(
+            "INSERT INTO zkp_parameters (circuit_version, fingerprint, parameters) \
+            VALUES ($1, $2, $3) ON CONFLICT (circuit_version) DO NOTHING",
+            params.circuit_version,
+            params.fingerprint,
+            bytes
+        )
+        .execute(&self.pool)
+        .await?;
+
+        let stored = self.load(circuit_version).await?
+            .ok_or_else(|| ParameterStoreError::UnknownCircuitVersion(circuit_version.to_string()))?;
+
+        if stored.fingerprint != params.fingerprint {
+            return Err(ParameterStoreError::FingerprintMismatch {
+                version: circuit_version.to_string(),
+                existing: stored.fingerprint,
+            });
+        }
+
+        info!("Parâmetros do circuito {} registrados ({})", circuit_version, params.fingerprint);
+        Ok(self.cache_insert(stored))
+    }
+
+    async fn load(&self, circuit_version: &str) -> Result<Option<CircuitParameters>, ParameterStoreError> {
+        let row = sqlx::query!(
+            "SELECT fingerprint, parameters FROM zkp_parameters WHERE circuit_version = $1",
+            circuit_version
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let row = match row {
+            Some(row) => row,
+            None => return Ok(None),
+        };
+
+        let params = Parameters::<Bls12>::read(&row.parameters[..], false)
+            .map_err(|e| ParameterStoreError::InvalidParameters(e.to_string()))?;
+        let params = CircuitParameters::new(circuit_version, params);
+
+        if params.fingerprint != row.fingerprint {
+            warn!("Fingerprint divergente para o circuito {}", circuit_version);
+            return Err(ParameterStoreError::InvalidParameters(format!(
+                "fingerprint armazenado {} não corresponde à chave {}",
+                row.fingerprint, params.fingerprint
+            )));
+        }
+
+        Ok(Some(params))
+    }
+
+    fn cache_insert(&self, params: CircuitParameters) -> Arc<CircuitParameters> {
+        let params = Arc::new(params);
+        self.cache
+            .write()
+            .unwrap()
+            .insert(params.circuit_version.clone(), params.clone());
+        params
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuits::SimpleCircuit;
+
+    fn generate() -> Parameters<Bls12> {
+        generate_random_parameters::<Bls12, _, _>(SimpleCircuit { a: None, b: None }, &mut thread_rng())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_fingerprint_survives_serialization() {
+        let params = generate();
+
+        let mut bytes = Vec::new();
+        params.write(&mut bytes).unwrap();
+        let restored = Parameters::<Bls12>::read(&bytes[..], true).unwrap();
+
+        assert_eq!(fingerprint(&params.vk), fingerprint(&restored.vk));
+        assert_eq!(fingerprint(&params.vk).len(), 64);
+    }
+
+    #[test]
+    fn test_fingerprint_differs_between_setups() {
+        assert_ne!(fingerprint(&generate().vk), fingerprint(&generate().vk));
+    }
+}
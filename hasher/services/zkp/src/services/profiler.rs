@@ -0,0 +1,271 @@
+use bellman::{
+    groth16::{create_random_proof, generate_random_parameters, prepare_verifying_key, verify_proof},
+    Circuit, SynthesisError,
+};
+use bls12_381::{Bls12, Scalar};
+use ff::Field;
+use rand::thread_rng;
+use std::time::Instant;
+use thiserror::Error;
+
+use crate::circuits::{
+    commit_attributes, commit_balance, context_to_field, country_code, derive_nullifier, nullified_inputs,
+    whitelist_tree, AttributeCircuit, AttributePredicates, Attributes, CircuitDefinition, CircuitError, ConstraintProfile,
+    MembershipCircuit, Nullified, SimpleCircuit, SolvencyCircuit, ATTRIBUTES_CIRCUIT_VERSION,
+    MEMBERSHIP_CIRCUIT_VERSION, MEMBERSHIP_NULLIFIED_VERSION, SIMPLE_CIRCUIT_VERSION, SOLVENCY_CIRCUIT_VERSION,
+    SOLVENCY_NULLIFIED_VERSION,
+};
+use crate::gadgets::MerkleError;
+use crate::models::{CircuitAnalysis, CircuitTimings};
+use common::bulkhead::Bulkhead;
+use common::metrics::ZKP_PROFILE_TIME;
+
+#[derive(Debug, Error)]
+pub enum ProfileError {
+    #[error(transparent)]
+    Circuit(#[from] CircuitError),
+    #[error("Circuito {0} sem instância de exemplo para medição")]
+    NoSample(String),
+    #[error("A prova de exemplo do circuito {0} não verificou")]
+    InvalidSample(String),
+    #[error("Todas as vagas de prova estão ocupadas; tente medir mais tarde")]
+    Busy,
+    #[error("Erro ao sintetizar o circuito: {0}")]
+    Synthesis(#[from] SynthesisError),
+    #[error(transparent)]
+    Tree(#[from] MerkleError),
+    #[error("Erro interno: {0}")]
+    Internal(String),
+}
+
+impl ProfileError {
+    /// Código de erro exposto na API
+    pub fn code(&self) -> &'static str {
+        match self {
+            ProfileError::Circuit(e) => e.code(),
+            ProfileError::NoSample(_) => "unsupported_circuit",
+            ProfileError::Busy => "service_busy",
+            _ => "internal_server_error",
+        }
+    }
+}
+
+/// Analisa uma versão de circuito: tamanho do sistema R1CS e, com `measure`,
+/// o tempo de gerar parâmetros, provar e verificar uma instância de exemplo.
+/// Os tempos também vão para `ZKP_PROFILE_TIME`, por circuito e etapa.
+///
+/// A medição gera parâmetros novos a cada chamada e pode levar segundos nos
+/// circuitos maiores: ela ocupa uma vaga do bulkhead das provas, para não
+/// competir sem limite com os jobs, e roda no pool de threads bloqueantes.
+pub async fn analyze_circuit(
+    definition: &'static CircuitDefinition,
+    measure: bool,
+    bulkhead: &Bulkhead,
+) -> Result<CircuitAnalysis, ProfileError> {
+    let _permit = match measure {
+        true => Some(bulkhead.acquire().await.map_err(|_| ProfileError::Busy)?),
+        false => None,
+    };
+    let (constraints, timings) = tokio::task::spawn_blocking(move || profile_definition(definition, measure))
+        .await
+        .map_err(|e| ProfileError::Internal(e.to_string()))??;
+
+    Ok(CircuitAnalysis {
+        circuit: definition.id(),
+        parameters_key: definition.parameters_key.to_string(),
+        constraints,
+        timings,
+    })
+}
+
+fn profile_definition(
+    definition: &CircuitDefinition,
+    measure: bool,
+) -> Result<(ConstraintProfile, Option<CircuitTimings>), ProfileError> {
+    let key = definition.parameters_key;
+    match key {
+        SIMPLE_CIRCUIT_VERSION => profile(key, SimpleCircuit { a: None, b: None }, measure, || {
+            Ok((SimpleCircuit { a: Some(2), b: Some(3) }, vec![Scalar::from(5u64)]))
+        }),
+        SOLVENCY_CIRCUIT_VERSION => profile(key, SolvencyCircuit::blank(), measure, || Ok(solvency_sample())),
+        MEMBERSHIP_CIRCUIT_VERSION => profile(key, MembershipCircuit::blank(), measure, membership_sample),
+        SOLVENCY_NULLIFIED_VERSION => profile(key, Nullified::blank(SolvencyCircuit::blank()), measure, || {
+            Ok(nullified_sample(solvency_sample()))
+        }),
+        MEMBERSHIP_NULLIFIED_VERSION => profile(key, Nullified::blank(MembershipCircuit::blank()), measure, || {
+            Ok(nullified_sample(membership_sample()?))
+        }),
+        ATTRIBUTES_CIRCUIT_VERSION => profile(key, Nullified::blank(AttributeCircuit::blank()), measure, || {
+            Ok(nullified_sample(attributes_sample()))
+        }),
+        key => Err(ProfileError::NoSample(key.to_string())),
+    }
+}
+
+fn profile<C, F>(
+    key: &str,
+    blank: C,
+    measure: bool,
+    sample: F,
+) -> Result<(ConstraintProfile, Option<CircuitTimings>), ProfileError>
+where
+    C: Circuit<Scalar> + Clone,
+    F: FnOnce() -> Result<(C, Vec<Scalar>), ProfileError>,
+{
+    let constraints = ConstraintProfile::of(blank.clone())?;
+    if !measure {
+        return Ok((constraints, None));
+    }
+
+    let (circuit, inputs) = sample()?;
+    Ok((constraints, Some(time_groth16(key, blank, circuit, &inputs)?)))
+}
+
+/// Cronometra setup, prova e verificação Groth16 de uma instância
+fn time_groth16<C: Circuit<Scalar>>(
+    key: &str,
+    blank: C,
+    circuit: C,
+    inputs: &[Scalar],
+) -> Result<CircuitTimings, ProfileError> {
+    let mut rng = thread_rng();
+
+    let started = Instant::now();
+    let params = generate_random_parameters::<Bls12, _, _>(blank, &mut rng)?;
+    let setup_seconds = started.elapsed().as_secs_f64();
+
+    let started = Instant::now();
+    let proof = create_random_proof(circuit, &params, &mut rng)?;
+    let proving_seconds = started.elapsed().as_secs_f64();
+
+    let started = Instant::now();
+    let pvk = prepare_verifying_key(&params.vk);
+    let valid = verify_proof(&pvk, &proof, inputs).is_ok();
+    let verification_seconds = started.elapsed().as_secs_f64();
+
+    if !valid {
+        return Err(ProfileError::InvalidSample(key.to_string()));
+    }
+
+    for (stage, seconds) in [
+        ("setup", setup_seconds),
+        ("prove", proving_seconds),
+        ("verify", verification_seconds),
+    ] {
+        ZKP_PROFILE_TIME.with_label_values(&[key, stage]).observe(seconds);
+    }
+
+    Ok(CircuitTimings {
+        setup_seconds,
+        proving_seconds,
+        verification_seconds,
+    })
+}
+
+fn solvency_sample() -> (SolvencyCircuit<Scalar>, Vec<Scalar>) {
+    let blinding = Scalar::random(&mut thread_rng());
+    let circuit = SolvencyCircuit {
+        balance: Some(u64::MAX as u128),
+        blinding: Some(blinding),
+        threshold: Some(1),
+    };
+
+    (circuit, SolvencyCircuit::public_inputs(1, commit_balance(u64::MAX as u128, blinding)))
+}
+
+fn membership_sample() -> Result<(MembershipCircuit<Scalar>, Vec<Scalar>), ProfileError> {
+    let wallet = Scalar::random(&mut thread_rng());
+    let tree = whitelist_tree(&[wallet])?;
+    let circuit = MembershipCircuit {
+        wallet: Some(wallet),
+        path: tree.path(0),
+    };
+
+    Ok((circuit, MembershipCircuit::public_inputs(tree.root())))
+}
+
+fn attributes_sample() -> (AttributeCircuit<Scalar>, Vec<Scalar>) {
+    let attributes = Attributes {
+        birth_date: 19900517,
+        country: country_code("BR").unwrap(),
+        kyc_tier: 2,
+    };
+    let predicates = AttributePredicates {
+        reference_date: 20260101,
+        min_age: 18,
+        country: attributes.country,
+        min_kyc_tier: 2,
+    };
+    let blinding = Scalar::random(&mut thread_rng());
+    let circuit = AttributeCircuit {
+        attributes: Some(attributes),
+        blinding: Some(blinding),
+        predicates: Some(predicates),
+    };
+
+    (circuit, AttributeCircuit::public_inputs(&predicates, commit_attributes(&attributes, blinding)))
+}
+
+/// Envolve uma instância de exemplo em `Nullified`, com segredo sorteado
+fn nullified_sample<C>((statement, inputs): (C, Vec<Scalar>)) -> (Nullified<Scalar, C>, Vec<Scalar>) {
+    let secret = Scalar::random(&mut thread_rng());
+    let context = context_to_field("profile");
+    let circuit = Nullified {
+        statement,
+        secret: Some(secret),
+        context: Some(context),
+    };
+
+    (circuit, nullified_inputs(inputs, context, derive_nullifier(secret, context)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuits::registry;
+    use bellman::gadgets::test::TestConstraintSystem;
+
+    fn assert_sample<C: Circuit<Scalar>>((circuit, inputs): (C, Vec<Scalar>)) {
+        let mut cs = TestConstraintSystem::new();
+        circuit.synthesize(&mut cs).unwrap();
+        assert!(cs.is_satisfied());
+        assert!(cs.verify(&inputs));
+    }
+
+    #[test]
+    fn test_samples_satisfy_circuits() {
+        assert_sample(nullified_sample(solvency_sample()));
+        assert_sample(nullified_sample(membership_sample().unwrap()));
+        assert_sample(nullified_sample(attributes_sample()));
+
+        // Todo circuito registrado tem perfil
+        for definition in registry().all() {
+            let (constraints, timings) = profile_definition(definition, false).unwrap();
+            assert_eq!(constraints.constraints, definition.constraint_count);
+            assert!(timings.is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_measurement_waits_for_a_proving_slot() {
+        let definition = registry().by_parameters_key(SIMPLE_CIRCUIT_VERSION).unwrap();
+        let bulkhead = Bulkhead::new(1, 50);
+        let _proving = bulkhead.acquire().await.unwrap();
+
+        // Contar restrições não disputa vaga; medir com tudo ocupado é recusado
+        let analysis = analyze_circuit(definition, false, &bulkhead).await.unwrap();
+        assert!(analysis.timings.is_none());
+        assert!(matches!(
+            analyze_circuit(definition, true, &bulkhead).await,
+            Err(ProfileError::Busy)
+        ));
+    }
+
+    #[test]
+    fn test_measures_groth16_stages() {
+        let definition = registry().by_parameters_key(SIMPLE_CIRCUIT_VERSION).unwrap();
+        let (_, timings) = profile_definition(definition, true).unwrap();
+        let timings = timings.unwrap();
+        assert!(timings.setup_seconds > 0.0 && timings.proving_seconds > 0.0 && timings.verification_seconds > 0.0);
+    }
+}
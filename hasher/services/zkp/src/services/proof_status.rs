@@ -0,0 +1,324 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Duration, Utc};
+use flate2::{write::GzEncoder, Compression};
+use log::info;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::OnceLock;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::models::{ProofState, ProofStatus, Revocation, RevocationReason, StatusList};
+use crate::services::VerificationError;
+
+/// Validade das provas quando `ZKP_PROOF_VALIDITY_DAYS` não está definida
+pub const DEFAULT_VALIDITY_DAYS: i64 = 90;
+
+/// Tamanho mínimo da lista de status (16 KiB), para que o tamanho da lista
+/// não revele quantas provas foram emitidas
+const MIN_STATUS_LIST_BITS: usize = 131_072;
+
+#[derive(Debug, Error)]
+pub enum StatusError {
+    #[error("Prova {0} não encontrada")]
+    NotFound(Uuid),
+    #[error("A prova {0} já foi revogada")]
+    AlreadyRevoked(Uuid),
+    #[error("Erro interno: {0}")]
+    Internal(String),
+    #[error("Erro de banco de dados: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl StatusError {
+    /// Código de erro exposto na API
+    pub fn code(&self) -> &'static str {
+        match self {
+            StatusError::NotFound(_) => "not_found",
+            StatusError::AlreadyRevoked(_) => "already_revoked",
+            StatusError::Internal(_) | StatusError::Database(_) => "internal_server_error",
+        }
+    }
+}
+
+/// Validade das provas novas, lida uma vez de `ZKP_PROOF_VALIDITY_DAYS`
+pub fn proof_validity() -> Duration {
+    static VALIDITY: OnceLock<Duration> = OnceLock::new();
+
+    *VALIDITY.get_or_init(|| {
+        let days = std::env::var("ZKP_PROOF_VALIDITY_DAYS")
+            .ok()
+            .and_then(|value| value.parse::<i64>().ok())
+            .filter(|days| *days > 0)
+            .unwrap_or(DEFAULT_VALIDITY_DAYS);
+        Duration::days(days)
+    })
+}
+
+/// Monta a situação de uma prova a partir das colunas de `zkp_proofs`
+pub(crate) fn proof_status(
+    now: DateTime<Utc>,
+    valid_from: DateTime<Utc>,
+    valid_until: DateTime<Utc>,
+    status_index: i64,
+    revoked_at: Option<DateTime<Utc>>,
+    revocation_reason: Option<String>,
+    revocation_comment: Option<String>,
+) -> ProofStatus {
+    // A restrição da coluna só admite motivos conhecidos
+    let revocation = revoked_at.zip(revocation_reason.as_deref().and_then(RevocationReason::parse)).map(
+        |(revoked_at, reason)| Revocation {
+            reason,
+            comment: revocation_comment,
+            revoked_at,
+        },
+    );
+
+    let state = if revoked_at.is_some() {
+        ProofState::Revoked
+    } else if now < valid_from {
+        ProofState::NotYetValid
+    } else if now >= valid_until {
+        ProofState::Expired
+    } else {
+        ProofState::Valid
+    };
+
+    ProofStatus {
+        state,
+        valid_from,
+        valid_until,
+        status_index,
+        revocation,
+    }
+}
+
+/// Recusa provas revogadas ou fora do período de validade
+pub fn ensure_usable(status: &ProofStatus) -> Result<(), VerificationError> {
+    match status.state {
+        ProofState::Valid => Ok(()),
+        ProofState::NotYetValid => Err(VerificationError::ProofNotYetValid(status.valid_from)),
+        ProofState::Expired => Err(VerificationError::ProofExpired(status.valid_until)),
+        ProofState::Revoked => Err(VerificationError::ProofRevoked(
+            status
+                .revocation
+                .as_ref()
+                .map_or_else(|| "motivo desconhecido".to_string(), |r| r.reason.to_string()),
+        )),
+    }
+}
+
+/// Enunciado de uma prova: a chave de verificação (impressão digital) e as
+/// entradas públicas na forma de `format_scalar`. Qualquer um re-randomiza uma
+/// prova Groth16 sem a testemunha, então a situação de uma prova emitida é
+/// buscada pelo enunciado e não pelos bytes da prova.
+pub fn statement_hash(key_fingerprint: &str, public_inputs: &[String]) -> Vec<u8> {
+    Sha256::digest(format!("{}:{}", key_fingerprint, public_inputs.join(","))).to_vec()
+}
+
+/// Bitstring de `size` bits com os índices em `set` ligados, do bit mais
+/// significativo do primeiro byte em diante, comprimido com gzip e em base64url
+pub fn encode_status_list(size: usize, set: &[usize]) -> Result<String, std::io::Error> {
+    let mut bits = vec![0u8; size.div_ceil(8)];
+    for &index in set.iter().filter(|&&index| index < size) {
+        bits[index / 8] |= 0x80 >> (index % 8);
+    }
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&bits)?;
+    Ok(URL_SAFE_NO_PAD.encode(encoder.finish()?))
+}
+
+/// Validade, revogação e lista de status das provas em `zkp_proofs`
+pub struct ProofStatusService {
+    pool: PgPool,
+}
+
+impl ProofStatusService {
+    pub fn new(pool: &PgPool) -> Self {
+        Self { pool: pool.clone() }
+    }
+
+    pub async fn status(&self, proof_id: Uuid) -> Result<ProofStatus, StatusError> {
+        self.statuses(&[proof_id])
+            .await?
+            .remove(&proof_id)
+            .ok_or(StatusError::NotFound(proof_id))
+    }
+
+    pub async fn statuses(&self, proof_ids: &[Uuid]) -> Result<HashMap<Uuid, ProofStatus>, sqlx::Error> {
+        let now = Utc::now();
+        let rows = sqlx::query!(
+            "SELECT id, valid_from, valid_until, status_index, revoked_at, revocation_reason, revocation_comment \
+            FROM zkp_proofs WHERE id = ANY($1)",
+            proof_ids
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let status = proof_status(
+                    now,
+                    row.valid_from,
+                    row.valid_until,
+                    row.status_index,
+                    row.revoked_at,
+                    row.revocation_reason,
+                    row.revocation_comment,
+                );
+                (row.id, status)
+            })
+            .collect())
+    }
+
+    /// Situação das provas emitidas para os enunciados em `statements` (ver
+    /// `statement_hash`), para a verificação avulsa; enunciados de fora não
+    /// aparecem. Com mais de uma emissão, vale a mais recente.
+    pub async fn statuses_by_statement(
+        &self,
+        statements: &[Vec<u8>],
+    ) -> Result<HashMap<Vec<u8>, ProofStatus>, sqlx::Error> {
+        let now = Utc::now();
+        let rows = sqlx::query!(
+            "SELECT DISTINCT ON (statement_hash) statement_hash AS \"statement_hash!\", valid_from, valid_until, \
+            status_index, revoked_at, revocation_reason, revocation_comment FROM zkp_proofs \
+            WHERE statement_hash = ANY($1) ORDER BY statement_hash, status_index DESC",
+            statements
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let status = proof_status(
+                    now,
+                    row.valid_from,
+                    row.valid_until,
+                    row.status_index,
+                    row.revoked_at,
+                    row.revocation_reason,
+                    row.revocation_comment,
+                );
+                (row.statement_hash, status)
+            })
+            .collect())
+    }
+
+    /// Revoga uma prova; a revogação é definitiva. Com `owner`, só revoga
+    /// as provas desse usuário: as de outros respondem como inexistentes.
+    pub async fn revoke(
+        &self,
+        proof_id: Uuid,
+        reason: RevocationReason,
+        comment: Option<&str>,
+        owner: Option<Uuid>,
+    ) -> Result<ProofStatus, StatusError> {
+        if let Some(owner) = owner {
+            let proof_owner = sqlx::query_scalar!("SELECT user_id FROM zkp_proofs WHERE id = $1", proof_id)
+                .fetch_optional(&self.pool)
+                .await?;
+            if proof_owner != Some(owner) {
+                return Err(StatusError::NotFound(proof_id));
+            }
+        }
+
+        let revoked = sqlx::query!(
+            "UPDATE zkp_proofs SET revoked_at = CURRENT_TIMESTAMP, revocation_reason = $2, revocation_comment = $3, \
+            updated_at = CURRENT_TIMESTAMP WHERE id = $1 AND revoked_at IS NULL",
+            proof_id,
+            reason.as_str(),
+            comment
+        )
+        .execute(&self.pool)
+        .await?
+        .rows_affected();
+
+        let status = self.status(proof_id).await?;
+        if revoked == 0 {
+            return Err(StatusError::AlreadyRevoked(proof_id));
+        }
+
+        info!("Prova {} revogada ({})", proof_id, reason);
+        Ok(status)
+    }
+
+    /// Lista de status atual: revogadas e expiradas ficam com o bit ligado
+    pub async fn status_list(&self) -> Result<StatusList, StatusError> {
+        let issued_at = Utc::now();
+        let last = sqlx::query_scalar!("SELECT MAX(status_index) FROM zkp_proofs")
+            .fetch_one(&self.pool)
+            .await?
+            .unwrap_or(0);
+        let unusable = sqlx::query_scalar!(
+            "SELECT status_index FROM zkp_proofs WHERE revoked_at IS NOT NULL OR valid_until <= $1",
+            issued_at
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let size = (last as usize + 1).next_multiple_of(8).max(MIN_STATUS_LIST_BITS);
+        let set = unusable.into_iter().map(|index| index as usize).collect::<Vec<_>>();
+        let encoded_list = encode_status_list(size, &set).map_err(|e| StatusError::Internal(e.to_string()))?;
+
+        Ok(StatusList {
+            issued_at,
+            size,
+            encoded_list,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+
+    #[test]
+    fn test_proof_state() {
+        let issued = Utc::now() - Duration::days(10);
+        let status = |now, revoked_at: Option<DateTime<Utc>>| {
+            proof_status(
+                now,
+                issued,
+                issued + Duration::days(90),
+                7,
+                revoked_at,
+                revoked_at.map(|_| "balance_changed".to_string()),
+                None,
+            )
+        };
+
+        assert_eq!(status(Utc::now(), None).state, ProofState::Valid);
+        assert!(ensure_usable(&status(Utc::now(), None)).is_ok());
+        assert_eq!(status(issued - Duration::seconds(1), None).state, ProofState::NotYetValid);
+
+        // Seis meses depois a prova não vale mais
+        let expired = status(issued + Duration::days(180), None);
+        assert_eq!(expired.state, ProofState::Expired);
+        assert!(matches!(ensure_usable(&expired), Err(VerificationError::ProofExpired(_))));
+
+        let revoked = status(Utc::now(), Some(Utc::now()));
+        assert_eq!(revoked.state, ProofState::Revoked);
+        assert_eq!(revoked.revocation.as_ref().unwrap().reason, RevocationReason::BalanceChanged);
+        assert!(matches!(ensure_usable(&revoked), Err(VerificationError::ProofRevoked(_))));
+    }
+
+    #[test]
+    fn test_encode_status_list() {
+        let encoded = encode_status_list(16, &[0, 9, 15, 40]).unwrap();
+
+        let mut bits = Vec::new();
+        GzDecoder::new(URL_SAFE_NO_PAD.decode(encoded).unwrap().as_slice())
+            .read_to_end(&mut bits)
+            .unwrap();
+
+        // Índices fora da lista são ignorados
+        assert_eq!(bits, vec![0b1000_0000, 0b0100_0001]);
+    }
+}
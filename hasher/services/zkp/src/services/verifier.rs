@@ -0,0 +1,345 @@
+use bellman::groth16::{batch, prepare_verifying_key, verify_proof, Proof, VerifyingKey};
+use bls12_381::{Bls12, Scalar};
+use chrono::{DateTime, Utc};
+use ff::PrimeField;
+use std::time::Instant;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::circuits::CircuitError;
+use crate::encoding::decode;
+use crate::services::ParameterStoreError;
+
+#[derive(Debug, Error)]
+pub enum VerificationError {
+    #[error("Prova malformada: {0}")]
+    MalformedProof(String),
+    #[error("Chave de verificação malformada: {0}")]
+    MalformedVerifyingKey(String),
+    #[error("Entrada pública {index} inválida: {value}")]
+    MalformedPublicInput { index: usize, value: String },
+    #[error("Número de entradas públicas incorreto: esperado {expected}, recebido {actual}")]
+    WrongInputCount { expected: usize, actual: usize },
+    #[error("Informe verification_key ou circuit_version")]
+    MissingVerifyingKey,
+    #[error("Prova inválida")]
+    InvalidProof,
+    #[error("Prova {0} não encontrada")]
+    ProofNotFound(Uuid),
+    #[error("Lote com {actual} provas excede o limite de {max}")]
+    BatchTooLarge { max: usize, actual: usize },
+    #[error("Contexto de aplicação inválido: {0}")]
+    InvalidContext(String),
+    #[error("Contexto informado para uma prova sem nullifier; escolha um circuito com nullifier em circuit")]
+    NullifierNotSupported,
+    #[error("A prova foi gerada para outro contexto")]
+    ContextMismatch,
+    #[error("Nullifier não registrado neste contexto")]
+    UnknownNullifier,
+    #[error("A prova já foi usada no contexto {0}")]
+    NullifierUsed(String),
+    #[error("Prova revogada: {0}")]
+    ProofRevoked(String),
+    #[error("Prova expirada em {0}")]
+    ProofExpired(DateTime<Utc>),
+    #[error("Prova válida somente a partir de {0}")]
+    ProofNotYetValid(DateTime<Utc>),
+    #[error("Erro de banco de dados: {0}")]
+    Database(#[from] sqlx::Error),
+    #[error("Erro interno: {0}")]
+    Internal(String),
+    #[error(transparent)]
+    Parameters(#[from] ParameterStoreError),
+    #[error(transparent)]
+    Circuit(#[from] CircuitError),
+}
+
+impl VerificationError {
+    /// Código de erro exposto na API
+    pub fn code(&self) -> &'static str {
+        match self {
+            VerificationError::MalformedProof(_) => "malformed_proof",
+            VerificationError::MalformedVerifyingKey(_) => "malformed_verification_key",
+            VerificationError::MalformedPublicInput { .. } => "malformed_public_input",
+            VerificationError::WrongInputCount { .. } => "wrong_input_count",
+            VerificationError::MissingVerifyingKey => "missing_verification_key",
+            VerificationError::InvalidProof => "invalid_proof",
+            VerificationError::ProofNotFound(_) => "not_found",
+            VerificationError::BatchTooLarge { .. } => "batch_too_large",
+            VerificationError::InvalidContext(_) => "invalid_context",
+            VerificationError::NullifierNotSupported => "nullifier_not_supported",
+            VerificationError::ContextMismatch => "context_mismatch",
+            VerificationError::UnknownNullifier => "unknown_nullifier",
+            VerificationError::NullifierUsed(_) => "nullifier_used",
+            VerificationError::ProofRevoked(_) => "proof_revoked",
+            VerificationError::ProofExpired(_) => "proof_expired",
+            VerificationError::ProofNotYetValid(_) => "proof_not_yet_valid",
+            VerificationError::Database(_) | VerificationError::Internal(_) => "internal_server_error",
+            VerificationError::Parameters(ParameterStoreError::UnknownCircuitVersion(_)) => "unknown_circuit_version",
+            VerificationError::Parameters(ParameterStoreError::UnknownFingerprint(_)) => "unknown_verification_key",
+            VerificationError::Parameters(_) => "internal_server_error",
+            VerificationError::Circuit(e) => e.code(),
+        }
+    }
+}
+
+/// Aceita a codificação canônica e, para provas gravadas antes dela, o
+/// formato comprimido do bellman
+pub fn parse_proof(bytes: &[u8]) -> Result<Proof<Bls12>, VerificationError> {
+    decode::<Proof<Bls12>>(bytes)
+        .or_else(|e| Proof::<Bls12>::read(bytes).map_err(|_| e.to_string()))
+        .map_err(VerificationError::MalformedProof)
+}
+
+/// Aceita a codificação canônica ou o formato não comprimido do bellman
+pub fn parse_verifying_key(bytes: &[u8]) -> Result<VerifyingKey<Bls12>, VerificationError> {
+    decode::<VerifyingKey<Bls12>>(bytes)
+        .or_else(|e| VerifyingKey::<Bls12>::read(bytes).map_err(|_| e.to_string()))
+        .map_err(VerificationError::MalformedVerifyingKey)
+}
+
+/// Converte uma entrada pública em escalar. Aceita decimal ou hex big-endian
+/// com prefixo `0x`; valores fora do corpo são rejeitados em vez de reduzidos.
+pub fn parse_scalar(value: &str) -> Option<Scalar> {
+    parse_field_element(value)
+}
+
+/// Como `parse_scalar`, para qualquer corpo com representação little-endian
+/// de 32 bytes (ex.: `Bn254Scalar`)
+pub fn parse_field_element<S: PrimeField<Repr = [u8; 32]>>(value: &str) -> Option<S> {
+    let mut repr = [0u8; 32];
+
+    if let Some(hex_value) = value.strip_prefix("0x") {
+        if hex_value.is_empty() || hex_value.len() > 64 {
+            return None;
+        }
+        let padded = format!("{:0>64}", hex_value);
+        let bytes = hex::decode(padded).ok()?;
+        for (i, byte) in bytes.iter().rev().enumerate() {
+            repr[i] = *byte;
+        }
+    } else {
+        if value.is_empty() || !value.bytes().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        // repr = repr * 10 + dígito, em little-endian, sem estourar 256 bits
+        for digit in value.bytes().map(|c| c - b'0') {
+            let mut carry = digit as u16;
+            for byte in repr.iter_mut() {
+                let acc = (*byte as u16) * 10 + carry;
+                *byte = acc as u8;
+                carry = acc >> 8;
+            }
+            if carry != 0 {
+                return None;
+            }
+        }
+    }
+
+    Option::from(S::from_repr(repr))
+}
+
+/// Representação hex big-endian com prefixo `0x`, inversa de `parse_scalar`
+pub fn format_scalar<S: PrimeField<Repr = [u8; 32]>>(value: &S) -> String {
+    let mut bytes = value.to_repr();
+    bytes.reverse();
+    format!("0x{}", hex::encode(bytes))
+}
+
+pub fn parse_public_inputs(values: &[String]) -> Result<Vec<Scalar>, VerificationError> {
+    parse_field_elements(values)
+}
+
+pub fn parse_field_elements<S: PrimeField<Repr = [u8; 32]>>(values: &[String]) -> Result<Vec<S>, VerificationError> {
+    values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| {
+            parse_field_element(value).ok_or_else(|| VerificationError::MalformedPublicInput {
+                index,
+                value: value.clone(),
+            })
+        })
+        .collect()
+}
+
+fn check_input_count(vk: &VerifyingKey<Bls12>, public_inputs: &[Scalar]) -> Result<(), VerificationError> {
+    let expected = vk.ic.len().saturating_sub(1);
+    if public_inputs.len() != expected {
+        return Err(VerificationError::WrongInputCount {
+            expected,
+            actual: public_inputs.len(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Verifica uma prova Groth16 contra a chave informada
+pub fn verify(
+    vk: &VerifyingKey<Bls12>,
+    proof: &Proof<Bls12>,
+    public_inputs: &[Scalar],
+) -> Result<(), VerificationError> {
+    check_input_count(vk, public_inputs)?;
+
+    let _timer = common::metrics::ZKP_VERIFICATION_TIME.start_timer();
+    verify_proof(&prepare_verifying_key(vk), proof, public_inputs)
+        .map_err(|_| VerificationError::InvalidProof)
+}
+
+/// Verifica várias provas contra a mesma chave com uma combinação linear
+/// aleatória; se o lote falhar, confere uma a uma para apontar as inválidas.
+/// Devolve um resultado por item, na ordem recebida.
+pub fn verify_batch(
+    vk: &VerifyingKey<Bls12>,
+    items: &[(Proof<Bls12>, Vec<Scalar>)],
+) -> Vec<Result<(), VerificationError>> {
+    let start = Instant::now();
+
+    let mut results = items
+        .iter()
+        .map(|(_, inputs)| check_input_count(vk, inputs))
+        .collect::<Vec<_>>();
+    let well_formed = (0..items.len()).filter(|&i| results[i].is_ok()).collect::<Vec<_>>();
+
+    let mut verifier = batch::Verifier::new();
+    for &i in &well_formed {
+        verifier.queue((&items[i].0, items[i].1.as_slice()));
+    }
+
+    if !well_formed.is_empty() && verifier.verify_multicore(vk).is_err() {
+        let pvk = prepare_verifying_key(vk);
+        for &i in &well_formed {
+            results[i] = verify_proof(&pvk, &items[i].0, &items[i].1).map_err(|_| VerificationError::InvalidProof);
+        }
+    }
+
+    // O histograma mede tempo por prova: no lote, registra o tempo amortizado
+    // de cada uma, de modo que count/sum continua dando a vazão
+    if !well_formed.is_empty() {
+        let per_proof = start.elapsed().as_secs_f64() / well_formed.len() as f64;
+        for _ in &well_formed {
+            common::metrics::ZKP_VERIFICATION_TIME.observe(per_proof);
+        }
+    }
+
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuits::SimpleCircuit;
+    use crate::encoding::encode;
+    use bellman::groth16::{create_random_proof, generate_random_parameters};
+    use ff::Field;
+    use rand::thread_rng;
+
+    #[test]
+    fn test_parse_scalar_decimal_and_hex() {
+        assert_eq!(parse_scalar("7"), Some(Scalar::from(7u64)));
+        assert_eq!(parse_scalar("0x07"), Some(Scalar::from(7u64)));
+        assert_eq!(parse_scalar("0x0100"), Some(Scalar::from(256u64)));
+        assert_eq!(parse_scalar("18446744073709551616"), Some(Scalar::from(u64::MAX) + Scalar::ONE));
+    }
+
+    #[test]
+    fn test_parse_scalar_rejects_invalid_values() {
+        assert_eq!(parse_scalar(""), None);
+        assert_eq!(parse_scalar("-1"), None);
+        assert_eq!(parse_scalar("abc"), None);
+        assert_eq!(parse_scalar("0x"), None);
+        // Módulo do corpo escalar do BLS12-381
+        assert_eq!(
+            parse_scalar("52435875175126190479447740508185965837690552500527637822603658699938581184513"),
+            None
+        );
+        assert_eq!(parse_scalar(&"9".repeat(80)), None);
+    }
+
+    #[test]
+    fn test_format_scalar_round_trip() {
+        let value = Scalar::from(u64::MAX) * Scalar::from(12345u64);
+        assert_eq!(parse_scalar(&format_scalar(&value)), Some(value));
+        assert_eq!(format_scalar(&Scalar::from(7u64)), format!("0x{:0>64}", "07"));
+    }
+
+    #[test]
+    fn test_parse_public_inputs_reports_index() {
+        let inputs = vec!["1".to_string(), "x".to_string()];
+        match parse_public_inputs(&inputs) {
+            Err(VerificationError::MalformedPublicInput { index, .. }) => assert_eq!(index, 1),
+            other => panic!("resultado inesperado: {:?}", other.map(|v| v.len())),
+        }
+    }
+
+    #[test]
+    fn test_parse_proof_rejects_garbage() {
+        assert!(matches!(parse_proof(b"invalid_proof"), Err(VerificationError::MalformedProof(_))));
+    }
+
+    #[test]
+    fn test_parse_accepts_canonical_and_legacy_formats() {
+        let mut rng = thread_rng();
+        let params = generate_random_parameters::<Bls12, _, _>(SimpleCircuit { a: None, b: None }, &mut rng).unwrap();
+        let proof = create_random_proof(SimpleCircuit { a: Some(3), b: Some(4) }, &params, &mut rng).unwrap();
+
+        let mut legacy = Vec::new();
+        proof.write(&mut legacy).unwrap();
+        assert!(parse_proof(&legacy).unwrap() == proof);
+        assert!(parse_proof(&encode(&proof)).unwrap() == proof);
+
+        let mut legacy = Vec::new();
+        params.vk.write(&mut legacy).unwrap();
+        assert!(parse_verifying_key(&legacy).unwrap() == params.vk);
+        assert!(parse_verifying_key(&encode(&params.vk)).unwrap() == params.vk);
+    }
+
+    #[test]
+    fn test_verify_checks_input_count_and_validity() {
+        let mut rng = thread_rng();
+        let params = generate_random_parameters::<Bls12, _, _>(SimpleCircuit { a: None, b: None }, &mut rng).unwrap();
+        let proof = create_random_proof(SimpleCircuit { a: Some(3), b: Some(4) }, &params, &mut rng).unwrap();
+
+        let mut bytes = Vec::new();
+        proof.write(&mut bytes).unwrap();
+        let proof = parse_proof(&bytes).unwrap();
+
+        assert!(verify(&params.vk, &proof, &[Scalar::from(7u64)]).is_ok());
+        assert!(matches!(
+            verify(&params.vk, &proof, &[Scalar::from(8u64)]),
+            Err(VerificationError::InvalidProof)
+        ));
+        assert!(matches!(
+            verify(&params.vk, &proof, &[]),
+            Err(VerificationError::WrongInputCount { expected: 1, actual: 0 })
+        ));
+    }
+
+    #[test]
+    fn test_verify_batch_reports_each_item() {
+        let mut rng = thread_rng();
+        let params = generate_random_parameters::<Bls12, _, _>(SimpleCircuit { a: None, b: None }, &mut rng).unwrap();
+
+        let mut items = (0..6u64)
+            .map(|i| {
+                let proof = create_random_proof(SimpleCircuit { a: Some(i), b: Some(1) }, &params, &mut rng).unwrap();
+                (proof, vec![Scalar::from(i + 1)])
+            })
+            .collect::<Vec<_>>();
+        assert!(verify_batch(&params.vk, &items).iter().all(|r| r.is_ok()));
+
+        // Uma entrada errada e uma contagem errada no meio do lote
+        items[2].1 = vec![Scalar::from(100u64)];
+        items[4].1 = vec![];
+        let results = verify_batch(&params.vk, &items);
+
+        assert!(matches!(results[2], Err(VerificationError::InvalidProof)));
+        assert!(matches!(results[4], Err(VerificationError::WrongInputCount { expected: 1, actual: 0 })));
+        for i in [0, 1, 3, 5] {
+            assert!(results[i].is_ok(), "item {}", i);
+        }
+        assert!(verify_batch(&params.vk, &[]).is_empty());
+    }
+}
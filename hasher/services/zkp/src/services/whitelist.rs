@@ -0,0 +1,211 @@
+use bls12_381::Scalar;
+use chrono::{DateTime, Utc};
+use log::info;
+use sqlx::PgPool;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::circuits::{registry, wallet_to_field, whitelist_tree, MembershipCircuit, MEMBERSHIP_CIRCUIT};
+use crate::gadgets::MerkleError;
+use crate::models::WhitelistRoot;
+use crate::services::{format_scalar, ParameterStore, ProofError, ZkpService};
+use common::models::{User, ZkpProof};
+
+#[derive(Debug, Error)]
+pub enum WhitelistError {
+    #[error("Endereço de carteira inválido: {0}")]
+    InvalidWallet(String),
+    #[error(transparent)]
+    Tree(#[from] MerkleError),
+    #[error("Nenhuma raiz da whitelist foi publicada")]
+    NoPublishedRoot,
+    #[error("Carteira {0} não consta na raiz publicada")]
+    NotWhitelisted(String),
+    #[error(transparent)]
+    Proof(#[from] ProofError),
+    #[error("Erro de banco de dados: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl WhitelistError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            WhitelistError::InvalidWallet(_) => "invalid_wallet",
+            WhitelistError::Tree(_) => "too_many_wallets",
+            WhitelistError::NoPublishedRoot => "not_found",
+            WhitelistError::NotWhitelisted(_) => "not_whitelisted",
+            WhitelistError::Proof(e) => e.code(),
+            WhitelistError::Database(_) => "internal_server_error",
+        }
+    }
+}
+
+/// Mantém o conjunto de carteiras aprovadas e as raízes publicadas da árvore.
+/// Cada raiz guarda a lista de carteiras na ordem das folhas, para que provas
+/// continuem sendo geradas contra ela depois que o conjunto mudar.
+pub struct WhitelistService {
+    pool: PgPool,
+    zkp: ZkpService,
+}
+
+impl WhitelistService {
+    pub fn new(pool: &PgPool, parameters: &ParameterStore) -> Self {
+        Self {
+            pool: pool.clone(),
+            zkp: ZkpService::new(pool, parameters),
+        }
+    }
+
+    /// Substitui o conjunto de carteiras aprovadas
+    pub async fn replace_wallets(&self, wallets: &[String], source: &str) -> Result<usize, WhitelistError> {
+        let mut normalized = wallets
+            .iter()
+            .map(|wallet| normalize_wallet(wallet))
+            .collect::<Result<Vec<_>, _>>()?;
+        normalized.sort();
+        normalized.dedup();
+
+        // A árvore tem capacidade fixa; recusar antes de gravar
+        let leaves = normalized.iter().map(|w| wallet_to_field::<Scalar>(w).unwrap()).collect::<Vec<_>>();
+        whitelist_tree(&leaves)?;
+
+        let mut tx = self.pool.begin().await?;
+        sqlx::query!("DELETE FROM whitelisted_wallets").execute(&mut *tx).await?;
+        for wallet in &normalized {
+            sqlx::query!(
+                "INSERT INTO whitelisted_wallets (wallet_address, source) VALUES ($1, $2)",
+                wallet,
+                source
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        Ok(normalized.len())
+    }
+
+    /// Monta a árvore com as carteiras aprovadas e publica a raiz
+    pub async fn publish_root(&self) -> Result<WhitelistRoot, WhitelistError> {
+        let wallets = sqlx::query_scalar!("SELECT wallet_address FROM whitelisted_wallets ORDER BY wallet_address")
+            .fetch_all(&self.pool)
+            .await?;
+
+        let leaves = wallets
+            .iter()
+            .map(|w| wallet_to_field::<Scalar>(w).ok_or_else(|| WhitelistError::InvalidWallet(w.clone())))
+            .collect::<Result<Vec<_>, _>>()?;
+        let root = format_scalar(&whitelist_tree(&leaves)?.root());
+
+        let row = sqlx::query!(
+            "INSERT INTO whitelist_roots (root, wallets) VALUES ($1, $2) RETURNING id, published_at",
+            root,
+            &wallets
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        info!("Raiz da whitelist publicada: {} ({} carteiras)", root, wallets.len());
+
+        Ok(WhitelistRoot {
+            id: row.id,
+            root,
+            wallet_count: wallets.len(),
+            published_at: row.published_at,
+        })
+    }
+
+    /// Raiz publicada mais recente
+    pub async fn current_root(&self) -> Result<Option<WhitelistRoot>, WhitelistError> {
+        Ok(self.latest_snapshot().await?.map(|snapshot| snapshot.into()))
+    }
+
+    /// Prova que a carteira do usuário pertence à raiz publicada mais recente,
+    /// valendo uma única vez no contexto de aplicação `context`.
+    /// Entradas públicas: a raiz, o contexto e o nullifier.
+    pub async fn generate_proof(&self, user: &User, context: &str) -> Result<ZkpProof, WhitelistError> {
+        let definition = registry().for_proving(MEMBERSHIP_CIRCUIT, None).map_err(ProofError::from)?;
+        let snapshot = self.latest_snapshot().await?.ok_or(WhitelistError::NoPublishedRoot)?;
+        let wallet = normalize_wallet(&user.wallet_address)?;
+
+        let index = snapshot
+            .wallets
+            .iter()
+            .position(|w| *w == wallet)
+            .ok_or_else(|| WhitelistError::NotWhitelisted(wallet.clone()))?;
+
+        let leaves = snapshot
+            .wallets
+            .iter()
+            .map(|w| wallet_to_field::<Scalar>(w).ok_or_else(|| WhitelistError::InvalidWallet(w.clone())))
+            .collect::<Result<Vec<_>, _>>()?;
+        let tree = whitelist_tree(&leaves)?;
+
+        let circuit = MembershipCircuit {
+            wallet: Some(leaves[index]),
+            path: tree.path(index),
+        };
+
+        let (circuit, public_inputs, nullifier) = self
+            .zkp
+            .nullified(user.id, definition, context, circuit, MembershipCircuit::public_inputs(tree.root()))
+            .await?;
+
+        Ok(self
+            .zkp
+            .prove(user.id, definition, circuit, &public_inputs, &nullifier)
+            .await?)
+    }
+
+    async fn latest_snapshot(&self) -> Result<Option<RootSnapshot>, WhitelistError> {
+        let snapshot = sqlx::query_as!(
+            RootSnapshot,
+            "SELECT id, root, wallets, published_at FROM whitelist_roots ORDER BY published_at DESC LIMIT 1"
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(snapshot)
+    }
+}
+
+struct RootSnapshot {
+    id: Uuid,
+    root: String,
+    wallets: Vec<String>,
+    published_at: DateTime<Utc>,
+}
+
+impl From<RootSnapshot> for WhitelistRoot {
+    fn from(snapshot: RootSnapshot) -> Self {
+        Self {
+            id: snapshot.id,
+            root: snapshot.root,
+            wallet_count: snapshot.wallets.len(),
+            published_at: snapshot.published_at,
+        }
+    }
+}
+
+/// Endereços são comparados em minúsculas, sem o checksum EIP-55
+fn normalize_wallet(wallet: &str) -> Result<String, WhitelistError> {
+    let normalized = wallet.trim().to_lowercase();
+    match wallet_to_field::<Scalar>(&normalized) {
+        Some(_) => Ok(normalized),
+        None => Err(WhitelistError::InvalidWallet(wallet.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_wallet() {
+        assert_eq!(
+            normalize_wallet(" 0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed ").unwrap(),
+            "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"
+        );
+        assert!(matches!(normalize_wallet("0x1234"), Err(WhitelistError::InvalidWallet(_))));
+    }
+}
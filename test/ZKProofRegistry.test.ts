@@ -112,6 +112,73 @@ describe("ZKProofRegistry", function () {
         });
     });
 
+    describe("Verificação On-chain", function () {
+        const circuitId = ethers.utils.keccak256(ethers.utils.toUtf8Bytes("solvency-v1"));
+        const a: [number, number] = [1, 2];
+        const b: [[number, number], [number, number]] = [[3, 4], [5, 6]];
+        const c: [number, number] = [7, 8];
+
+        function proofId(input: number[]) {
+            return ethers.utils.keccak256(
+                ethers.utils.defaultAbiCoder.encode(
+                    ["uint256[2]", "uint256[2][2]", "uint256[2]", "uint256[]"],
+                    [a, b, c, input]
+                )
+            );
+        }
+
+        beforeEach(async function () {
+            const MockGroth16Verifier = await ethers.getContractFactory("MockGroth16Verifier");
+            const verifier = await MockGroth16Verifier.deploy();
+            await verifier.deployed();
+            await zkProofRegistry.connect(owner).setCircuitVerifier(circuitId, verifier.address);
+        });
+
+        it("Deve verificar uma prova válida sem o proprietário", async function () {
+            const id = proofId([1, 250]);
+            await zkProofRegistry.connect(subwallet1).submitProof(id);
+
+            await expect(zkProofRegistry.connect(user2).verifyProofOnChain(id, circuitId, a, b, c, [1, 250]))
+                .to.emit(zkProofRegistry, "ProofVerified")
+                .withArgs(id, user2.address);
+            expect((await zkProofRegistry.getProof(id)).verified).to.be.true;
+        });
+
+        it("Deve rejeitar prova inválida", async function () {
+            const id = proofId([0, 250]);
+            await zkProofRegistry.connect(subwallet1).submitProof(id);
+
+            await expect(
+                zkProofRegistry.connect(user2).verifyProofOnChain(id, circuitId, a, b, c, [0, 250])
+            ).to.be.revertedWith("Prova invalida");
+        });
+
+        it("Deve rejeitar argumentos que não correspondem ao registro", async function () {
+            const id = proofId([1, 250]);
+            await zkProofRegistry.connect(subwallet1).submitProof(id);
+
+            await expect(
+                zkProofRegistry.connect(user2).verifyProofOnChain(id, circuitId, a, b, c, [1, 251])
+            ).to.be.revertedWith("Prova nao corresponde ao registro");
+        });
+
+        it("Deve exigir verificador configurado para o circuito", async function () {
+            const id = proofId([1, 250]);
+            await zkProofRegistry.connect(subwallet1).submitProof(id);
+            const unknown = ethers.utils.keccak256(ethers.utils.toUtf8Bytes("membership-v1"));
+
+            await expect(
+                zkProofRegistry.connect(user2).verifyProofOnChain(id, unknown, a, b, c, [1, 250])
+            ).to.be.revertedWith("Circuito sem verificador");
+        });
+
+        it("Apenas o proprietário deve configurar verificadores", async function () {
+            await expect(
+                zkProofRegistry.connect(user1).setCircuitVerifier(circuitId, user1.address)
+            ).to.be.revertedWith("Ownable: caller is not the owner");
+        });
+    });
+
     describe("Recuperação de Emergência", function () {
         it("Não deve permitir recuperar tokens ACME", async function () {
             await expect(